@@ -166,6 +166,20 @@ pub use crate::vec::*;
 
 use anyhow::Error;
 
+/// Errors returned by matrix decompositions
+#[derive(Debug, Clone, thiserror::Error)]
+pub(crate) enum DecompositionError {
+    /// Matrix is (numerically) rank deficient
+    #[error("Rank deficient matrix")]
+    RankDeficient,
+    /// Matrix is not positive definite
+    #[error("Matrix is not positive definite")]
+    NotPositiveDefinite,
+    /// Dimensions of matrix and right hand side do not match
+    #[error("Dimension mismatch")]
+    DimensionMismatch,
+}
+
 /// Dot/scalar product of `T` and `self`
 pub trait ArgminDot<T, U> {
     /// Dot/scalar product of `T` and `self`
@@ -262,6 +276,58 @@ pub trait ArgminInv<T> {
     fn inv(&self) -> Result<T, Error>;
 }
 
+/// Solve the linear least squares problem `min_x ‖self x - b‖₂` via a QR decomposition of `self`
+///
+/// `self` must have at least as many rows as columns. An error is returned if `self` is
+/// (numerically) rank deficient.
+pub trait ArgminQrSolve<B, X> {
+    /// Solve the linear least squares problem `min_x ‖self x - b‖₂`
+    fn qr_solve(&self, b: &B) -> Result<X, Error>;
+}
+
+/// Cholesky decomposition `self = L Lᵀ` of a symmetric positive definite matrix
+///
+/// An error is returned if `self` is not positive definite.
+pub trait ArgminCholesky: Sized {
+    /// Returns the lower triangular factor `L`
+    fn cholesky(&self) -> Result<Self, Error>;
+}
+
+/// Solve `self x = b` via a Cholesky decomposition of the symmetric positive definite `self`
+///
+/// An error is returned if `self` is not positive definite.
+pub trait ArgminCholeskySolve<B, X> {
+    /// Solve `self x = b`
+    fn cholesky_solve(&self, b: &B) -> Result<X, Error>;
+}
+
+/// Apply a function to each element (`T`) of `self`
+pub trait ArgminMap<T> {
+    /// Apply `f` to each element of `self`
+    fn map<G: Fn(T) -> T>(&self, f: G) -> Self;
+}
+
+/// Scale the rows of a matrix by the corresponding elements of a vector `V`
+///
+/// This is equivalent to multiplying `self` from the left with a diagonal matrix with `V` on its
+/// diagonal.
+pub trait ArgminScaleRows<V> {
+    /// Multiply row `i` of `self` with element `i` of `v`
+    fn scale_rows(&self, v: &V) -> Self;
+}
+
+/// Convert into plain `Vec`s: vectors into `Vec<T>` and matrices into `Vec<Vec<T>>` (rows)
+pub trait ArgminToVec<V> {
+    /// Copy the elements of `self` into `Vec`s
+    fn to_vec(&self) -> V;
+}
+
+/// Construct from plain `Vec`s: vectors from `Vec<T>` and matrices from `Vec<Vec<T>>` (rows)
+pub trait ArgminFromVec<V> {
+    /// Construct from the elements in `v`
+    fn from_vec(v: V) -> Self;
+}
+
 /// Create a random number
 pub trait ArgminRandom {
     /// Get a random element between min and max,
@@ -0,0 +1,117 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminCholesky, ArgminCholeskySolve, DecompositionError, Error};
+use nalgebra::{
+    base::{allocator::Allocator, dimension::Dim, storage::Storage},
+    ComplexField, DefaultAllocator, Matrix, OMatrix, OVector, SquareMatrix, U1,
+};
+
+impl<N, D> ArgminCholesky for OMatrix<N, D, D>
+where
+    N: ComplexField,
+    D: Dim,
+    DefaultAllocator: Allocator<N, D, D>,
+{
+    #[inline]
+    fn cholesky(&self) -> Result<OMatrix<N, D, D>, Error> {
+        match self.clone_owned().cholesky() {
+            Some(c) => Ok(c.unpack()),
+            None => Err(DecompositionError::NotPositiveDefinite.into()),
+        }
+    }
+}
+
+impl<N, D, S, SB> ArgminCholeskySolve<Matrix<N, D, U1, SB>, OVector<N, D>> for SquareMatrix<N, D, S>
+where
+    N: ComplexField,
+    D: Dim,
+    S: Storage<N, D, D>,
+    SB: Storage<N, D, U1>,
+    DefaultAllocator: Allocator<N, D, D> + Allocator<N, D>,
+{
+    #[inline]
+    fn cholesky_solve(&self, b: &Matrix<N, D, U1, SB>) -> Result<OVector<N, D>, Error> {
+        if b.nrows() != self.nrows() {
+            return Err(DecompositionError::DimensionMismatch.into());
+        }
+        match self.clone_owned().cholesky() {
+            Some(c) => Ok(c.solve(b)),
+            None => Err(DecompositionError::NotPositiveDefinite.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{Matrix2, Vector2};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_cholesky_ $t>]() {
+                    let a = Matrix2::new(
+                        4 as $t, 2 as $t,
+                        2 as $t, 5 as $t,
+                    );
+                    let l = <Matrix2<$t> as ArgminCholesky>::cholesky(&a).unwrap();
+                    let target = Matrix2::new(2.0, 0.0, 1.0, 2.0);
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert!(((l[(i, j)] as f64) - target[(i, j)]).abs() < 1e-5);
+                        }
+                    }
+                    let a = Matrix2::new(
+                        1 as $t, 2 as $t,
+                        2 as $t, 1 as $t,
+                    );
+                    assert!(<Matrix2<$t> as ArgminCholesky>::cholesky(&a).is_err());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_solve_ $t>]() {
+                    let a = Matrix2::new(
+                        4 as $t, 2 as $t,
+                        2 as $t, 3 as $t,
+                    );
+                    let b = Vector2::new(2 as $t, 5 as $t);
+                    let res = <Matrix2<$t> as ArgminCholeskySolve<Vector2<$t>, Vector2<$t>>>::cholesky_solve(
+                        &a, &b,
+                    )
+                    .unwrap();
+                    let target = [-0.5, 2.0];
+                    for i in 0..2 {
+                        assert!(((res[i] as f64) - target[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_solve_not_positive_definite_ $t>]() {
+                    let a = Matrix2::new(
+                        1 as $t, 2 as $t,
+                        2 as $t, 1 as $t,
+                    );
+                    let b = Vector2::new(2 as $t, 5 as $t);
+                    let res = <Matrix2<$t> as ArgminCholeskySolve<Vector2<$t>, Vector2<$t>>>::cholesky_solve(
+                        &a, &b,
+                    );
+                    assert!(res.is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
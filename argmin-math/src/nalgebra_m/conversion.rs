@@ -0,0 +1,137 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminFromVec, ArgminToVec};
+use nalgebra::{
+    base::{allocator::Allocator, dimension::Dim, Scalar},
+    Const, DefaultAllocator, OMatrix, OVector, U1,
+};
+
+impl<N, R> ArgminToVec<Vec<N>> for OVector<N, R>
+where
+    N: Scalar + Copy,
+    R: Dim,
+    DefaultAllocator: Allocator<N, R, U1>,
+{
+    #[inline]
+    fn to_vec(&self) -> Vec<N> {
+        self.iter().copied().collect()
+    }
+}
+
+impl<N, R, C> ArgminToVec<Vec<Vec<N>>> for OMatrix<N, R, C>
+where
+    N: Scalar + Copy,
+    R: Dim,
+    C: Dim,
+    DefaultAllocator: Allocator<N, R, C>,
+{
+    #[inline]
+    fn to_vec(&self) -> Vec<Vec<N>> {
+        self.row_iter()
+            .map(|row| row.iter().copied().collect())
+            .collect()
+    }
+}
+
+impl<N, R> ArgminFromVec<Vec<N>> for OVector<N, R>
+where
+    N: Scalar + Copy,
+    R: Dim,
+    DefaultAllocator: Allocator<N, R, U1>,
+{
+    #[inline]
+    fn from_vec(v: Vec<N>) -> OVector<N, R> {
+        OVector::<N, R>::from_iterator_generic(R::from_usize(v.len()), Const::<1>, v)
+    }
+}
+
+impl<N, R, C> ArgminFromVec<Vec<Vec<N>>> for OMatrix<N, R, C>
+where
+    N: Scalar + Copy,
+    R: Dim,
+    C: Dim,
+    DefaultAllocator: Allocator<N, R, C>,
+{
+    #[inline]
+    fn from_vec(v: Vec<Vec<N>>) -> OMatrix<N, R, C> {
+        let nrows = v.len();
+        let ncols = v.first().map_or(0, |row| row.len());
+        assert!(v.iter().all(|row| row.len() == ncols));
+        OMatrix::<N, R, C>::from_fn_generic(R::from_usize(nrows), C::from_usize(ncols), |i, j| {
+            v[i][j]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{DMatrix, DVector, Matrix2x3, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_conversion_vec_ $t>]() {
+                    let a = Vector3::new(1 as $t, 4 as $t, 8 as $t);
+                    let res: Vec<$t> = ArgminToVec::to_vec(&a);
+                    assert_eq!(res, vec![1 as $t, 4 as $t, 8 as $t]);
+                    assert_eq!(<Vector3<$t> as ArgminFromVec<_>>::from_vec(res.clone()), a);
+                    assert_eq!(
+                        <DVector<$t> as ArgminFromVec<_>>::from_vec(res),
+                        DVector::from_vec(vec![1 as $t, 4 as $t, 8 as $t])
+                    );
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_conversion_mat_ $t>]() {
+                    let a = Matrix2x3::new(
+                        1 as $t, 4 as $t, 8 as $t,
+                        2 as $t, 5 as $t, 9 as $t
+                    );
+                    let res: Vec<Vec<$t>> = ArgminToVec::to_vec(&a);
+                    assert_eq!(
+                        res,
+                        vec![vec![1 as $t, 4 as $t, 8 as $t], vec![2 as $t, 5 as $t, 9 as $t]]
+                    );
+                    assert_eq!(<Matrix2x3<$t> as ArgminFromVec<_>>::from_vec(res.clone()), a);
+                    assert_eq!(
+                        <DMatrix<$t> as ArgminFromVec<_>>::from_vec(res),
+                        DMatrix::from_row_slice(2, 3, &[
+                            1 as $t, 4 as $t, 8 as $t,
+                            2 as $t, 5 as $t, 9 as $t
+                        ])
+                    );
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_conversion_vec_panic_ $t>]() {
+                    let v = vec![1 as $t, 4 as $t];
+                    <Vector3<$t> as ArgminFromVec<_>>::from_vec(v);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,154 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminQrSolve, DecompositionError, Error};
+use nalgebra::{
+    base::{
+        allocator::Allocator,
+        dimension::{Dim, DimMin},
+        storage::Storage,
+    },
+    ComplexField, Const, DefaultAllocator, Matrix, OMatrix, OVector, U1,
+};
+use num_traits::{Float, Zero};
+
+impl<N, R, C, S, SB> ArgminQrSolve<Matrix<N, R, U1, SB>, OVector<N, C>> for Matrix<N, R, C, S>
+where
+    N: ComplexField,
+    N::RealField: Float,
+    R: DimMin<C, Output = C>,
+    C: Dim,
+    S: Storage<N, R, C>,
+    SB: Storage<N, R, U1>,
+    DefaultAllocator: Allocator<N, R, C> + Allocator<N, R> + Allocator<N, C> + Allocator<N, C, C>,
+{
+    #[inline]
+    fn qr_solve(&self, b: &Matrix<N, R, U1, SB>) -> Result<OVector<N, C>, Error> {
+        let (nrows, ncols) = self.shape_generic();
+        if b.nrows() != nrows.value() {
+            return Err(DecompositionError::DimensionMismatch.into());
+        }
+        if nrows.value() < ncols.value() || ncols.value() == 0 {
+            return Err(DecompositionError::RankDeficient.into());
+        }
+
+        let qr = self.clone_owned().qr();
+        let r: OMatrix<N, C, C> = qr.r();
+
+        let max_diag = (0..ncols.value())
+            .map(|i| r[(i, i)].clone().modulus())
+            .fold(<N::RealField as Zero>::zero(), |acc, x| {
+                if x > acc {
+                    x
+                } else {
+                    acc
+                }
+            });
+        let tol = nalgebra::convert::<f64, N::RealField>(nrows.value() as f64)
+            * <N::RealField as Float>::epsilon()
+            * max_diag;
+        if (0..ncols.value()).any(|i| r[(i, i)].clone().modulus() <= tol) {
+            return Err(DecompositionError::RankDeficient.into());
+        }
+
+        let mut qtb = b.clone_owned();
+        qr.q_tr_mul(&mut qtb);
+        let qtb: OVector<N, C> = qtb.generic_slice((0, 0), (ncols, Const::<1>)).into_owned();
+
+        r.solve_upper_triangular(&qtb)
+            .ok_or_else(|| DecompositionError::RankDeficient.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{DMatrix, DVector, Matrix2, Matrix3x2, Vector2, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_qr_solve_square_ $t>]() {
+                    let a = Matrix2::new(
+                        2 as $t, 5 as $t,
+                        1 as $t, 3 as $t,
+                    );
+                    let b = Vector2::new(1 as $t, 2 as $t);
+                    let res = <Matrix2<$t> as ArgminQrSolve<Vector2<$t>, Vector2<$t>>>::qr_solve(
+                        &a, &b
+                    )
+                    .unwrap();
+                    let target = [-7 as $t, 3 as $t];
+                    for i in 0..2 {
+                        assert!(((res[i] - target[i]) as f64).abs() < 1e-4);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_solve_least_squares_ $t>]() {
+                    let a = Matrix3x2::new(
+                        1 as $t, 0 as $t,
+                        1 as $t, 1 as $t,
+                        1 as $t, 2 as $t,
+                    );
+                    let b = Vector3::new(1 as $t, 3 as $t, 4 as $t);
+                    let res = <Matrix3x2<$t> as ArgminQrSolve<Vector3<$t>, Vector2<$t>>>::qr_solve(
+                        &a, &b
+                    )
+                    .unwrap();
+                    let target = [7.0 / 6.0, 1.5];
+                    for i in 0..2 {
+                        assert!(((res[i] as f64) - target[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_solve_least_squares_dynamic_ $t>]() {
+                    let a = DMatrix::from_row_slice(3, 2, &[
+                        1 as $t, 0 as $t,
+                        1 as $t, 1 as $t,
+                        1 as $t, 2 as $t,
+                    ]);
+                    let b = DVector::from_vec(vec![1 as $t, 3 as $t, 4 as $t]);
+                    let res = <DMatrix<$t> as ArgminQrSolve<DVector<$t>, DVector<$t>>>::qr_solve(
+                        &a, &b
+                    )
+                    .unwrap();
+                    let target = [7.0 / 6.0, 1.5];
+                    for i in 0..2 {
+                        assert!(((res[i] as f64) - target[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_solve_rank_deficient_ $t>]() {
+                    let a = Matrix3x2::new(
+                        1 as $t, 2 as $t,
+                        2 as $t, 4 as $t,
+                        3 as $t, 6 as $t,
+                    );
+                    let b = Vector3::new(1 as $t, 2 as $t, 3 as $t);
+                    let res = <Matrix3x2<$t> as ArgminQrSolve<Vector3<$t>, Vector2<$t>>>::qr_solve(
+                        &a, &b
+                    );
+                    assert!(res.is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,87 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminScaleRows;
+use nalgebra::{
+    base::{allocator::Allocator, dimension::Dim, storage::Storage, Scalar},
+    ClosedMul, DefaultAllocator, Matrix, OMatrix, U1,
+};
+
+impl<N, R, C, S> ArgminScaleRows<Matrix<N, R, U1, S>> for OMatrix<N, R, C>
+where
+    N: Scalar + Copy + ClosedMul,
+    R: Dim,
+    C: Dim,
+    S: Storage<N, R, U1>,
+    DefaultAllocator: Allocator<N, R, C>,
+{
+    #[inline]
+    fn scale_rows(&self, v: &Matrix<N, R, U1, S>) -> OMatrix<N, R, C> {
+        assert_eq!(self.nrows(), v.nrows());
+        let mut out = self.clone();
+        for (i, mut row) in out.row_iter_mut().enumerate() {
+            row *= v[i];
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{DMatrix, DVector, Matrix2x3, Vector2};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scale_rows_ $t>]() {
+                    let a = Matrix2x3::new(
+                        1 as $t, 4 as $t, 8 as $t,
+                        2 as $t, 5 as $t, 9 as $t
+                    );
+                    let v = Vector2::new(2 as $t, 3 as $t);
+                    let target = Matrix2x3::new(
+                        2 as $t, 8 as $t, 16 as $t,
+                        6 as $t, 15 as $t, 27 as $t
+                    );
+                    let res = a.scale_rows(&v);
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            assert!((((res[(i, j)] - target[(i, j)]) as f64).abs()) < std::f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_scale_rows_panic_ $t>]() {
+                    let a = DMatrix::from_row_slice(2, 3, &[
+                        1 as $t, 4 as $t, 8 as $t,
+                        2 as $t, 5 as $t, 9 as $t
+                    ]);
+                    let v = DVector::from_vec(vec![2 as $t, 3 as $t, 4 as $t]);
+                    a.scale_rows(&v);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,111 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminCholesky, ArgminCholeskySolve, DecompositionError, Error};
+use ndarray::{Array1, Array2};
+use ndarray_linalg::{Cholesky, SolveC, UPLO};
+
+macro_rules! make_cholesky_solve {
+    ($t:ty) => {
+        impl ArgminCholesky for Array2<$t> {
+            #[inline]
+            fn cholesky(&self) -> Result<Array2<$t>, Error> {
+                let (m, n) = self.dim();
+                if m != n {
+                    return Err(DecompositionError::DimensionMismatch.into());
+                }
+                Cholesky::cholesky(self, UPLO::Lower)
+                    .map_err(|_| DecompositionError::NotPositiveDefinite.into())
+            }
+        }
+
+        impl ArgminCholeskySolve<Array1<$t>, Array1<$t>> for Array2<$t> {
+            #[inline]
+            fn cholesky_solve(&self, b: &Array1<$t>) -> Result<Array1<$t>, Error> {
+                let (m, n) = self.dim();
+                if m != n || b.len() != n {
+                    return Err(DecompositionError::DimensionMismatch.into());
+                }
+                self.solvec(b)
+                    .map_err(|_| DecompositionError::NotPositiveDefinite.into())
+            }
+        }
+    };
+}
+
+make_cholesky_solve!(f32);
+make_cholesky_solve!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_cholesky_ $t>]() {
+                    let a = array![
+                        [4 as $t, 2 as $t],
+                        [2 as $t, 5 as $t],
+                    ];
+                    let l = <Array2<$t> as ArgminCholesky>::cholesky(&a).unwrap();
+                    let target = [[2.0, 0.0], [1.0, 2.0]];
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert!(((l[(i, j)] as f64) - target[i][j]).abs() < 1e-5);
+                        }
+                    }
+                    let a = array![
+                        [1 as $t, 2 as $t],
+                        [2 as $t, 1 as $t],
+                    ];
+                    assert!(<Array2<$t> as ArgminCholesky>::cholesky(&a).is_err());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_solve_ $t>]() {
+                    let a = array![
+                        [4 as $t, 2 as $t],
+                        [2 as $t, 3 as $t],
+                    ];
+                    let b = array![2 as $t, 5 as $t];
+                    let res = <Array2<$t> as ArgminCholeskySolve<Array1<$t>, Array1<$t>>>::cholesky_solve(
+                        &a, &b,
+                    )
+                    .unwrap();
+                    let target = [-0.5, 2.0];
+                    for i in 0..2 {
+                        assert!(((res[i] as f64) - target[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_solve_not_positive_definite_ $t>]() {
+                    let a = array![
+                        [1 as $t, 2 as $t],
+                        [2 as $t, 1 as $t],
+                    ];
+                    let b = array![2 as $t, 5 as $t];
+                    let res = <Array2<$t> as ArgminCholeskySolve<Array1<$t>, Array1<$t>>>::cholesky_solve(
+                        &a, &b,
+                    );
+                    assert!(res.is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,114 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminFromVec, ArgminToVec};
+use ndarray::{Array1, Array2};
+
+macro_rules! make_conversion {
+    ($t:ty) => {
+        impl ArgminToVec<Vec<$t>> for Array1<$t> {
+            #[inline]
+            fn to_vec(&self) -> Vec<$t> {
+                self.iter().cloned().collect()
+            }
+        }
+
+        impl ArgminToVec<Vec<Vec<$t>>> for Array2<$t> {
+            #[inline]
+            fn to_vec(&self) -> Vec<Vec<$t>> {
+                self.outer_iter()
+                    .map(|row| row.iter().cloned().collect())
+                    .collect()
+            }
+        }
+
+        impl ArgminFromVec<Vec<$t>> for Array1<$t> {
+            #[inline]
+            fn from_vec(v: Vec<$t>) -> Array1<$t> {
+                Array1::from(v)
+            }
+        }
+
+        impl ArgminFromVec<Vec<Vec<$t>>> for Array2<$t> {
+            #[inline]
+            fn from_vec(v: Vec<Vec<$t>>) -> Array2<$t> {
+                let nrows = v.len();
+                let ncols = v.first().map_or(0, |row| row.len());
+                assert!(v.iter().all(|row| row.len() == ncols));
+                Array2::from_shape_vec((nrows, ncols), v.into_iter().flatten().collect()).unwrap()
+            }
+        }
+    };
+}
+
+make_conversion!(i8);
+make_conversion!(u8);
+make_conversion!(i16);
+make_conversion!(u16);
+make_conversion!(i32);
+make_conversion!(u32);
+make_conversion!(i64);
+make_conversion!(u64);
+make_conversion!(f32);
+make_conversion!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_conversion_vec_ $t>]() {
+                    let a = array![1 as $t, 4 as $t, 8 as $t];
+                    let res: Vec<$t> = ArgminToVec::to_vec(&a);
+                    assert_eq!(res, vec![1 as $t, 4 as $t, 8 as $t]);
+                    assert_eq!(<Array1<$t> as ArgminFromVec<_>>::from_vec(res), a);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_conversion_mat_ $t>]() {
+                    let a = array![
+                        [1 as $t, 4 as $t, 8 as $t],
+                        [2 as $t, 5 as $t, 9 as $t]
+                    ];
+                    let res: Vec<Vec<$t>> = ArgminToVec::to_vec(&a);
+                    assert_eq!(
+                        res,
+                        vec![vec![1 as $t, 4 as $t, 8 as $t], vec![2 as $t, 5 as $t, 9 as $t]]
+                    );
+                    assert_eq!(<Array2<$t> as ArgminFromVec<_>>::from_vec(res), a);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_conversion_mat_panic_ $t>]() {
+                    let v = vec![vec![1 as $t, 4 as $t, 8 as $t], vec![2 as $t, 5 as $t]];
+                    <Array2<$t> as ArgminFromVec<_>>::from_vec(v);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
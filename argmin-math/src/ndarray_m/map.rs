@@ -0,0 +1,92 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminMap;
+use ndarray::{Array1, Array2};
+
+macro_rules! make_map {
+    ($t:ty) => {
+        impl ArgminMap<$t> for Array1<$t> {
+            #[inline]
+            fn map<G: Fn($t) -> $t>(&self, f: G) -> Array1<$t> {
+                self.mapv(f)
+            }
+        }
+
+        impl ArgminMap<$t> for Array2<$t> {
+            #[inline]
+            fn map<G: Fn($t) -> $t>(&self, f: G) -> Array2<$t> {
+                self.mapv(f)
+            }
+        }
+    };
+}
+
+make_map!(i8);
+make_map!(u8);
+make_map!(i16);
+make_map!(u16);
+make_map!(i32);
+make_map!(u32);
+make_map!(i64);
+make_map!(u64);
+make_map!(f32);
+make_map!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, Array1, Array2};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_map_vec_ $t>]() {
+                    let a = array![1 as $t, 4 as $t, 8 as $t];
+                    let target = array![2 as $t, 8 as $t, 16 as $t];
+                    let res = <Array1<$t> as ArgminMap<$t>>::map(&a, |x| x * (2 as $t));
+                    for i in 0..3 {
+                        assert!((((res[i] - target[i]) as f64).abs()) < std::f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_map_mat_ $t>]() {
+                    let a = array![
+                        [1 as $t, 4 as $t, 8 as $t],
+                        [2 as $t, 5 as $t, 9 as $t]
+                    ];
+                    let target = array![
+                        [2 as $t, 8 as $t, 16 as $t],
+                        [4 as $t, 10 as $t, 18 as $t]
+                    ];
+                    let res = <Array2<$t> as ArgminMap<$t>>::map(&a, |x| x * (2 as $t));
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            assert!((((res[(i, j)] - target[(i, j)]) as f64).abs()) < std::f64::EPSILON);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -6,29 +6,39 @@
 // copied, modified, or distributed except according to those terms.
 
 mod add;
+mod cholesky;
 mod conj;
+mod conversion;
 mod div;
 mod dot;
 mod eye;
 mod inv;
+mod map;
 mod mul;
 mod norm;
+mod qr;
 mod scaledadd;
 mod scaledsub;
+mod scalerows;
 mod sub;
 mod transpose;
 mod zero;
 
 pub use add::*;
+pub use cholesky::*;
 pub use conj::*;
+pub use conversion::*;
 pub use div::*;
 pub use dot::*;
 pub use eye::*;
 pub use inv::*;
+pub use map::*;
 pub use mul::*;
 pub use norm::*;
+pub use qr::*;
 pub use scaledadd::*;
 pub use scaledsub::*;
+pub use scalerows::*;
 pub use sub::*;
 pub use transpose::*;
 pub use zero::*;
@@ -0,0 +1,102 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminQrSolve, DecompositionError, Error};
+use ndarray::{Array1, Array2};
+use ndarray_linalg::{Diag, SolveTriangular, QR, UPLO};
+
+macro_rules! make_qr_solve {
+    ($t:ty) => {
+        impl ArgminQrSolve<Array1<$t>, Array1<$t>> for Array2<$t> {
+            #[inline]
+            fn qr_solve(&self, b: &Array1<$t>) -> Result<Array1<$t>, Error> {
+                let (m, n) = self.dim();
+                if b.len() != m {
+                    return Err(DecompositionError::DimensionMismatch.into());
+                }
+                if m < n || n == 0 {
+                    return Err(DecompositionError::RankDeficient.into());
+                }
+                let (q, r) = self.qr()?;
+                let diag = r.diag();
+                let max_diag = diag.iter().map(|x| x.abs()).fold(0.0, <$t>::max);
+                let tol = (m as $t) * <$t>::EPSILON * max_diag;
+                if diag.iter().any(|x| x.abs() <= tol) {
+                    return Err(DecompositionError::RankDeficient.into());
+                }
+                let qtb = q.t().dot(b);
+                Ok(r.solve_triangular(UPLO::Upper, Diag::NonUnit, &qtb)?)
+            }
+        }
+    };
+}
+
+make_qr_solve!(f32);
+make_qr_solve!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_qr_solve_square_ $t>]() {
+                    let a = array![
+                        [2 as $t, 5 as $t],
+                        [1 as $t, 3 as $t],
+                    ];
+                    let b = array![1 as $t, 2 as $t];
+                    let res = <Array2<$t> as ArgminQrSolve<Array1<$t>, Array1<$t>>>::qr_solve(&a, &b)
+                        .unwrap();
+                    let target = [-7 as $t, 3 as $t];
+                    for i in 0..2 {
+                        assert!(((res[i] - target[i]) as f64).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_solve_least_squares_ $t>]() {
+                    let a = array![
+                        [1 as $t, 0 as $t],
+                        [1 as $t, 1 as $t],
+                        [1 as $t, 2 as $t],
+                    ];
+                    let b = array![1 as $t, 3 as $t, 4 as $t];
+                    let res = <Array2<$t> as ArgminQrSolve<Array1<$t>, Array1<$t>>>::qr_solve(&a, &b)
+                        .unwrap();
+                    let target = [7.0 / 6.0, 1.5];
+                    for i in 0..2 {
+                        assert!(((res[i] as f64) - target[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_solve_rank_deficient_ $t>]() {
+                    let a = array![
+                        [1 as $t, 2 as $t],
+                        [2 as $t, 4 as $t],
+                        [3 as $t, 6 as $t],
+                    ];
+                    let b = array![1 as $t, 2 as $t, 3 as $t];
+                    let res = <Array2<$t> as ArgminQrSolve<Array1<$t>, Array1<$t>>>::qr_solve(&a, &b);
+                    assert!(res.is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
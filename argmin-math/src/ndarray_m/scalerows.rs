@@ -0,0 +1,88 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminScaleRows;
+use ndarray::{Array1, Array2, Axis};
+
+macro_rules! make_scale_rows {
+    ($t:ty) => {
+        impl ArgminScaleRows<Array1<$t>> for Array2<$t> {
+            #[inline]
+            fn scale_rows(&self, v: &Array1<$t>) -> Array2<$t> {
+                assert_eq!(self.nrows(), v.len());
+                self * &v.view().insert_axis(Axis(1))
+            }
+        }
+    };
+}
+
+make_scale_rows!(i8);
+make_scale_rows!(u8);
+make_scale_rows!(i16);
+make_scale_rows!(u16);
+make_scale_rows!(i32);
+make_scale_rows!(u32);
+make_scale_rows!(i64);
+make_scale_rows!(u64);
+make_scale_rows!(f32);
+make_scale_rows!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scale_rows_ $t>]() {
+                    let a = array![
+                        [1 as $t, 4 as $t, 8 as $t],
+                        [2 as $t, 5 as $t, 9 as $t]
+                    ];
+                    let v = array![2 as $t, 3 as $t];
+                    let target = array![
+                        [2 as $t, 8 as $t, 16 as $t],
+                        [6 as $t, 15 as $t, 27 as $t]
+                    ];
+                    let res = a.scale_rows(&v);
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            assert!((((res[(i, j)] - target[(i, j)]) as f64).abs()) < std::f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_scale_rows_panic_ $t>]() {
+                    let a = array![
+                        [1 as $t, 4 as $t, 8 as $t],
+                        [2 as $t, 5 as $t, 9 as $t]
+                    ];
+                    let v = array![2 as $t, 3 as $t, 4 as $t];
+                    a.scale_rows(&v);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
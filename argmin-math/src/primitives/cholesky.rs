@@ -0,0 +1,60 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminCholeskySolve, DecompositionError, Error};
+
+macro_rules! make_cholesky_solve {
+    ($t:ty) => {
+        impl ArgminCholeskySolve<$t, $t> for $t {
+            #[inline]
+            fn cholesky_solve(&self, b: &$t) -> Result<$t, Error> {
+                if *self > 0.0 {
+                    Ok(b / self)
+                } else {
+                    Err(DecompositionError::NotPositiveDefinite.into())
+                }
+            }
+        }
+    };
+}
+
+make_cholesky_solve!(f32);
+make_cholesky_solve!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_cholesky_solve_ $t>]() {
+                    let a = 4 as $t;
+                    let b = 2 as $t;
+                    let res = <$t as ArgminCholeskySolve<$t, $t>>::cholesky_solve(&a, &b).unwrap();
+                    assert!(((res - 0.5 as $t) as f64).abs() < std::f64::EPSILON);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_solve_not_positive_definite_ $t>]() {
+                    for a in [0 as $t, -1 as $t] {
+                        let b = 2 as $t;
+                        let res = <$t as ArgminCholeskySolve<$t, $t>>::cholesky_solve(&a, &b);
+                        assert!(res.is_err());
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,60 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminMap;
+
+macro_rules! make_map {
+    ($t:ty) => {
+        impl ArgminMap<$t> for $t {
+            #[inline]
+            fn map<G: Fn($t) -> $t>(&self, f: G) -> $t {
+                f(*self)
+            }
+        }
+    };
+}
+
+make_map!(i8);
+make_map!(u8);
+make_map!(i16);
+make_map!(u16);
+make_map!(i32);
+make_map!(u32);
+make_map!(i64);
+make_map!(u64);
+make_map!(f32);
+make_map!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_map_ $t>]() {
+                    let a = 4 as $t;
+                    let res = <$t as ArgminMap<$t>>::map(&a, |x| x * (2 as $t));
+                    assert!((((res - 8 as $t) as f64).abs()) < std::f64::EPSILON);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
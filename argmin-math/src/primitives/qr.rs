@@ -0,0 +1,59 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminQrSolve, DecompositionError, Error};
+
+macro_rules! make_qr_solve {
+    ($t:ty) => {
+        impl ArgminQrSolve<$t, $t> for $t {
+            #[inline]
+            fn qr_solve(&self, b: &$t) -> Result<$t, Error> {
+                if self.abs() > 0.0 {
+                    Ok(b / self)
+                } else {
+                    Err(DecompositionError::RankDeficient.into())
+                }
+            }
+        }
+    };
+}
+
+make_qr_solve!(f32);
+make_qr_solve!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_qr_solve_ $t>]() {
+                    let a = 4 as $t;
+                    let b = 2 as $t;
+                    let res = <$t as ArgminQrSolve<$t, $t>>::qr_solve(&a, &b).unwrap();
+                    assert!(((res - 0.5 as $t) as f64).abs() < std::f64::EPSILON);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_solve_singular_ $t>]() {
+                    let a = 0 as $t;
+                    let b = 2 as $t;
+                    let res = <$t as ArgminQrSolve<$t, $t>>::qr_solve(&a, &b);
+                    assert!(res.is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
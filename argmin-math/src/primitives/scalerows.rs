@@ -0,0 +1,60 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminScaleRows;
+
+macro_rules! make_scale_rows {
+    ($t:ty) => {
+        impl ArgminScaleRows<$t> for $t {
+            #[inline]
+            fn scale_rows(&self, v: &$t) -> $t {
+                self * v
+            }
+        }
+    };
+}
+
+make_scale_rows!(i8);
+make_scale_rows!(u8);
+make_scale_rows!(i16);
+make_scale_rows!(u16);
+make_scale_rows!(i32);
+make_scale_rows!(u32);
+make_scale_rows!(i64);
+make_scale_rows!(u64);
+make_scale_rows!(f32);
+make_scale_rows!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scale_rows_ $t>]() {
+                    let a = 4 as $t;
+                    let res = a.scale_rows(&(2 as $t));
+                    assert!((((res - 8 as $t) as f64).abs()) < std::f64::EPSILON);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,199 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminCholesky, ArgminCholeskySolve, DecompositionError, Error};
+
+macro_rules! make_cholesky {
+    ($t:ty) => {
+        impl ArgminCholesky for Vec<Vec<$t>> {
+            /// Cholesky decomposition `self = L Lᵀ`
+            fn cholesky(&self) -> Result<Vec<Vec<$t>>, Error> {
+                let n = self.len();
+                if self.iter().any(|row| row.len() != n) {
+                    return Err(DecompositionError::DimensionMismatch.into());
+                }
+
+                let mut l = vec![vec![0.0 as $t; n]; n];
+                for i in 0..n {
+                    for j in 0..=i {
+                        let s: $t = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+                        if i == j {
+                            let d = self[i][i] - s;
+                            if d.is_nan() || d <= 0.0 {
+                                return Err(DecompositionError::NotPositiveDefinite.into());
+                            }
+                            l[i][i] = d.sqrt();
+                        } else {
+                            l[i][j] = (self[i][j] - s) / l[j][j];
+                        }
+                    }
+                }
+                Ok(l)
+            }
+        }
+
+        impl ArgminCholeskySolve<Vec<$t>, Vec<$t>> for Vec<Vec<$t>> {
+            /// Cholesky decomposition `self = L Lᵀ` followed by forward and back substitution
+            fn cholesky_solve(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                if b.len() != self.len() {
+                    return Err(DecompositionError::DimensionMismatch.into());
+                }
+                let l = self.cholesky()?;
+                let n = l.len();
+
+                // Solve L y = b
+                let mut y = vec![0.0 as $t; n];
+                for i in 0..n {
+                    let s: $t = (0..i).map(|k| l[i][k] * y[k]).sum();
+                    y[i] = (b[i] - s) / l[i][i];
+                }
+
+                // Solve Lᵀ x = y
+                let mut x = vec![0.0 as $t; n];
+                for i in (0..n).rev() {
+                    let s: $t = ((i + 1)..n).map(|k| l[k][i] * x[k]).sum();
+                    x[i] = (y[i] - s) / l[i][i];
+                }
+                Ok(x)
+            }
+        }
+
+        impl ArgminCholeskySolve<Vec<Vec<$t>>, Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            /// Solve `self X = B` for a matrix `B` (stored as a vector of rows) with a single
+            /// Cholesky decomposition `self = L Lᵀ`
+            fn cholesky_solve(&self, b: &Vec<Vec<$t>>) -> Result<Vec<Vec<$t>>, Error> {
+                let m = if b.is_empty() { 0 } else { b[0].len() };
+                if b.len() != self.len() || b.iter().any(|row| row.len() != m) {
+                    return Err(DecompositionError::DimensionMismatch.into());
+                }
+                let l = self.cholesky()?;
+                let n = l.len();
+
+                let mut x = b.clone();
+                for c in 0..m {
+                    // Solve L y = b
+                    for i in 0..n {
+                        let s: $t = (0..i).map(|k| l[i][k] * x[k][c]).sum();
+                        x[i][c] = (x[i][c] - s) / l[i][i];
+                    }
+                    // Solve Lᵀ x = y
+                    for i in (0..n).rev() {
+                        let s: $t = ((i + 1)..n).map(|k| l[k][i] * x[k][c]).sum();
+                        x[i][c] = (x[i][c] - s) / l[i][i];
+                    }
+                }
+                Ok(x)
+            }
+        }
+    };
+}
+
+make_cholesky!(f32);
+make_cholesky!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_cholesky_solve_ $t>]() {
+                    let a = vec![
+                        vec![4 as $t, 2 as $t],
+                        vec![2 as $t, 3 as $t],
+                    ];
+                    let b = vec![2 as $t, 5 as $t];
+                    let res = <Vec<Vec<$t>> as ArgminCholeskySolve<Vec<$t>, Vec<$t>>>::cholesky_solve(
+                        &a, &b,
+                    )
+                    .unwrap();
+                    let target = [-0.5, 2.0];
+                    for i in 0..2 {
+                        assert!(((res[i] as f64) - target[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_ $t>]() {
+                    let a = vec![
+                        vec![4 as $t, 2 as $t],
+                        vec![2 as $t, 5 as $t],
+                    ];
+                    let l = <Vec<Vec<$t>> as ArgminCholesky>::cholesky(&a).unwrap();
+                    let target = [[2.0, 0.0], [1.0, 2.0]];
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert!(((l[i][j] as f64) - target[i][j]).abs() < 1e-5);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_solve_matrix_ $t>]() {
+                    let a = vec![
+                        vec![4 as $t, 2 as $t],
+                        vec![2 as $t, 3 as $t],
+                    ];
+                    let b = vec![
+                        vec![2 as $t, 1 as $t],
+                        vec![5 as $t, 0 as $t],
+                    ];
+                    let res = <Vec<Vec<$t>> as ArgminCholeskySolve<Vec<Vec<$t>>, Vec<Vec<$t>>>>::cholesky_solve(
+                        &a, &b,
+                    )
+                    .unwrap();
+                    let target = [[-0.5, 0.375], [2.0, -0.25]];
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert!(((res[i][j] as f64) - target[i][j]).abs() < 1e-5);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_solve_not_positive_definite_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 2 as $t],
+                        vec![2 as $t, 1 as $t],
+                    ];
+                    let b = vec![2 as $t, 5 as $t];
+                    let res = <Vec<Vec<$t>> as ArgminCholeskySolve<Vec<$t>, Vec<$t>>>::cholesky_solve(
+                        &a, &b,
+                    );
+                    assert!(res.is_err());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_solve_dimension_mismatch_ $t>]() {
+                    let a = vec![
+                        vec![4 as $t, 2 as $t],
+                        vec![2 as $t, 3 as $t],
+                    ];
+                    let b = vec![2 as $t];
+                    let res = <Vec<Vec<$t>> as ArgminCholeskySolve<Vec<$t>, Vec<$t>>>::cholesky_solve(
+                        &a, &b,
+                    );
+                    assert!(res.is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,86 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminFromVec, ArgminToVec};
+
+macro_rules! make_conversion {
+    ($t:ty) => {
+        impl ArgminToVec<Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn to_vec(&self) -> Vec<$t> {
+                self.clone()
+            }
+        }
+
+        impl ArgminToVec<Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn to_vec(&self) -> Vec<Vec<$t>> {
+                self.clone()
+            }
+        }
+
+        impl ArgminFromVec<Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn from_vec(v: Vec<$t>) -> Vec<$t> {
+                v
+            }
+        }
+
+        impl ArgminFromVec<Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn from_vec(v: Vec<Vec<$t>>) -> Vec<Vec<$t>> {
+                v
+            }
+        }
+    };
+}
+
+make_conversion!(i8);
+make_conversion!(u8);
+make_conversion!(i16);
+make_conversion!(u16);
+make_conversion!(i32);
+make_conversion!(u32);
+make_conversion!(i64);
+make_conversion!(u64);
+make_conversion!(f32);
+make_conversion!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_conversion_ $t>]() {
+                    let a = vec![vec![1 as $t, 2 as $t], vec![3 as $t, 4 as $t]];
+                    let v = vec![5 as $t, 6 as $t];
+                    let a_vec: Vec<Vec<$t>> = ArgminToVec::to_vec(&a);
+                    let v_vec: Vec<$t> = ArgminToVec::to_vec(&v);
+                    assert_eq!(a_vec, a);
+                    assert_eq!(v_vec, v);
+                    assert_eq!(<Vec<Vec<$t>> as ArgminFromVec<_>>::from_vec(a_vec), a);
+                    assert_eq!(<Vec<$t> as ArgminFromVec<_>>::from_vec(v_vec), v);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -57,11 +57,10 @@ macro_rules! make_dot_vec {
                 assert!(sr > 0);
                 let sc = self[0].len();
                 assert!(sc > 0);
-                let or = other.len();
-                assert!(or > 0);
-                let oc = other[0].len();
-                assert_eq!(sc, or);
+                let oc = other.len();
                 assert!(oc > 0);
+                let or = other[0].len();
+                assert_eq!(sc, or);
                 let v = vec![<$t>::default(); oc];
                 let mut out = vec![v; sr];
                 for i in 0..sr {
@@ -225,6 +224,33 @@ mod tests {
                 }
             }
 
+            item! {
+                #[test]
+                fn [<test_mat_mat_non_square_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 2 as $t, 3 as $t],
+                        vec![4 as $t, 5 as $t, 6 as $t],
+                    ];
+                    let b = vec![
+                        vec![3 as $t, 2 as $t],
+                        vec![6 as $t, 5 as $t],
+                        vec![2 as $t, 4 as $t],
+                    ];
+                    let res = vec![
+                        vec![21 as $t, 24 as $t],
+                        vec![54 as $t, 57 as $t],
+                    ];
+                    let product = a.dot(&b);
+                    assert_eq!(product.len(), 2);
+                    for i in 0..2 {
+                        assert_eq!(product[i].len(), 2);
+                        for j in 0..2 {
+                            assert!((((res[i][j] - product[i][j]) as f64).abs()) < std::f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
             item! {
                 #[test]
                 #[should_panic]
@@ -281,9 +307,8 @@ mod tests {
                         vec![3 as $t, 2 as $t, 1 as $t]
                     ];
                     let b = vec![
-                        vec![3 as $t, 2 as $t],
-                        vec![6 as $t, 5 as $t],
-                        vec![3 as $t, 2 as $t]
+                        vec![3 as $t, 2 as $t, 1 as $t],
+                        vec![6 as $t, 5 as $t, 4 as $t]
                     ];
                     a.dot(&b);
                 }
@@ -0,0 +1,92 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminMap;
+
+macro_rules! make_map {
+    ($t:ty) => {
+        impl ArgminMap<$t> for Vec<$t> {
+            #[inline]
+            fn map<G: Fn($t) -> $t>(&self, f: G) -> Vec<$t> {
+                self.iter().map(|x| f(*x)).collect()
+            }
+        }
+
+        impl ArgminMap<$t> for Vec<Vec<$t>> {
+            #[inline]
+            fn map<G: Fn($t) -> $t>(&self, f: G) -> Vec<Vec<$t>> {
+                self.iter()
+                    .map(|row| row.iter().map(|x| f(*x)).collect())
+                    .collect()
+            }
+        }
+    };
+}
+
+make_map!(i8);
+make_map!(u8);
+make_map!(i16);
+make_map!(u16);
+make_map!(i32);
+make_map!(u32);
+make_map!(i64);
+make_map!(u64);
+make_map!(f32);
+make_map!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_map_vec_ $t>]() {
+                    let a = vec![1 as $t, 4 as $t, 8 as $t];
+                    let target = vec![2 as $t, 8 as $t, 16 as $t];
+                    let res = <Vec<$t> as ArgminMap<$t>>::map(&a, |x| x * (2 as $t));
+                    for i in 0..3 {
+                        assert!((((res[i] - target[i]) as f64).abs()) < std::f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_map_mat_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 4 as $t, 8 as $t],
+                        vec![2 as $t, 5 as $t, 9 as $t],
+                    ];
+                    let target = vec![
+                        vec![2 as $t, 8 as $t, 16 as $t],
+                        vec![4 as $t, 10 as $t, 18 as $t],
+                    ];
+                    let res = <Vec<Vec<$t>> as ArgminMap<$t>>::map(&a, |x| x * (2 as $t));
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            assert!((((res[i][j] - target[i][j]) as f64).abs()) < std::f64::EPSILON);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -6,31 +6,41 @@
 // copied, modified, or distributed except according to those terms.
 
 mod add;
+mod cholesky;
 mod conj;
+mod conversion;
 mod div;
 mod dot;
 mod eye;
+mod map;
 mod minmax;
 mod mul;
 mod norm;
+mod qr;
 mod random;
 mod scaledadd;
 mod scaledsub;
+mod scalerows;
 mod sub;
 mod transpose;
 mod zero;
 
 pub use add::*;
+pub use cholesky::*;
 pub use conj::*;
+pub use conversion::*;
 pub use div::*;
 pub use dot::*;
 pub use eye::*;
+pub use map::*;
 pub use minmax::*;
 pub use mul::*;
 pub use norm::*;
+pub use qr::*;
 pub use random::*;
 pub use scaledadd::*;
 pub use scaledsub::*;
+pub use scalerows::*;
 pub use sub::*;
 pub use transpose::*;
 pub use zero::*;
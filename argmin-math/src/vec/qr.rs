@@ -0,0 +1,148 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminQrSolve, DecompositionError, Error};
+
+macro_rules! make_qr_solve {
+    ($t:ty) => {
+        impl ArgminQrSolve<Vec<$t>, Vec<$t>> for Vec<Vec<$t>> {
+            /// Householder QR decomposition of `self` (stored as a vector of rows) followed by
+            /// back substitution
+            fn qr_solve(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                let m = self.len();
+                let n = if m > 0 { self[0].len() } else { 0 };
+                if b.len() != m || self.iter().any(|row| row.len() != n) {
+                    return Err(DecompositionError::DimensionMismatch.into());
+                }
+                if m < n || n == 0 {
+                    return Err(DecompositionError::RankDeficient.into());
+                }
+
+                let mut r = self.clone();
+                let mut qtb = b.clone();
+
+                for k in 0..n {
+                    let norm = (k..m).map(|i| r[i][k].powi(2)).sum::<$t>().sqrt();
+                    if norm <= 0.0 {
+                        continue;
+                    }
+                    let alpha = if r[k][k] > 0.0 { -norm } else { norm };
+                    let mut v: Vec<$t> = (k..m).map(|i| r[i][k]).collect();
+                    v[0] -= alpha;
+                    let vtv: $t = v.iter().map(|x| x.powi(2)).sum();
+                    if vtv <= 0.0 {
+                        continue;
+                    }
+                    for j in k..n {
+                        let s: $t = v.iter().enumerate().map(|(i, vi)| vi * r[k + i][j]).sum();
+                        let f = 2.0 * s / vtv;
+                        for (i, vi) in v.iter().enumerate() {
+                            r[k + i][j] -= f * vi;
+                        }
+                    }
+                    let s: $t = v.iter().enumerate().map(|(i, vi)| vi * qtb[k + i]).sum();
+                    let f = 2.0 * s / vtv;
+                    for (i, vi) in v.iter().enumerate() {
+                        qtb[k + i] -= f * vi;
+                    }
+                }
+
+                let max_diag = (0..n).map(|k| r[k][k].abs()).fold(0.0, <$t>::max);
+                let tol = (m as $t) * <$t>::EPSILON * max_diag;
+                if (0..n).any(|k| r[k][k].abs() <= tol) {
+                    return Err(DecompositionError::RankDeficient.into());
+                }
+
+                let mut x = vec![0.0; n];
+                for k in (0..n).rev() {
+                    let s: $t = ((k + 1)..n).map(|j| r[k][j] * x[j]).sum();
+                    x[k] = (qtb[k] - s) / r[k][k];
+                }
+                Ok(x)
+            }
+        }
+    };
+}
+
+make_qr_solve!(f32);
+make_qr_solve!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_qr_solve_square_ $t>]() {
+                    let a = vec![
+                        vec![2 as $t, 5 as $t],
+                        vec![1 as $t, 3 as $t],
+                    ];
+                    let b = vec![1 as $t, 2 as $t];
+                    let res = <Vec<Vec<$t>> as ArgminQrSolve<Vec<$t>, Vec<$t>>>::qr_solve(&a, &b)
+                        .unwrap();
+                    let target = [-7 as $t, 3 as $t];
+                    for i in 0..2 {
+                        assert!(((res[i] - target[i]) as f64).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_solve_least_squares_ $t>]() {
+                    // Fit a straight line through (0, 1), (1, 3), (2, 4)
+                    let a = vec![
+                        vec![1 as $t, 0 as $t],
+                        vec![1 as $t, 1 as $t],
+                        vec![1 as $t, 2 as $t],
+                    ];
+                    let b = vec![1 as $t, 3 as $t, 4 as $t];
+                    let res = <Vec<Vec<$t>> as ArgminQrSolve<Vec<$t>, Vec<$t>>>::qr_solve(&a, &b)
+                        .unwrap();
+                    let target = [7.0 / 6.0, 1.5];
+                    for i in 0..2 {
+                        assert!(((res[i] as f64) - target[i]).abs() < 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_solve_rank_deficient_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 2 as $t],
+                        vec![2 as $t, 4 as $t],
+                        vec![3 as $t, 6 as $t],
+                    ];
+                    let b = vec![1 as $t, 2 as $t, 3 as $t];
+                    let res = <Vec<Vec<$t>> as ArgminQrSolve<Vec<$t>, Vec<$t>>>::qr_solve(&a, &b);
+                    assert!(res.is_err());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_qr_solve_dimension_mismatch_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 2 as $t],
+                        vec![2 as $t, 5 as $t],
+                    ];
+                    let b = vec![1 as $t, 2 as $t, 3 as $t];
+                    let res = <Vec<Vec<$t>> as ArgminQrSolve<Vec<$t>, Vec<$t>>>::qr_solve(&a, &b);
+                    assert!(res.is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
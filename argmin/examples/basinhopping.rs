@@ -0,0 +1,73 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{CostFunction, Error, Executor, Gradient};
+use argmin::solver::basinhopping::BasinHopping;
+use argmin::solver::linesearch::MoreThuenteLineSearch;
+use argmin::solver::quasinewton::LBFGS;
+use argmin_testfunctions::rastrigin;
+use std::f64::consts::PI;
+
+struct Rastrigin {}
+
+impl CostFunction for Rastrigin {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rastrigin(p))
+    }
+}
+
+impl Gradient for Rastrigin {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(p.iter()
+            .map(|x| 2.0 * x + 20.0 * PI * (2.0 * PI * x).sin())
+            .collect())
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define cost function
+    let cost = Rastrigin {};
+
+    // Set up local solver
+    let linesearch = MoreThuenteLineSearch::new();
+    let lbfgs = LBFGS::new(linesearch, 7);
+
+    // Set up basin-hopping around the local solver
+    let lower_bound = vec![-5.12, -5.12, -5.12];
+    let upper_bound = vec![5.12, 5.12, 5.12];
+    let solver = BasinHopping::new(lbfgs, (lower_bound, upper_bound))
+        .with_step_size(0.1)?
+        .with_local_max_iters(100)
+        .with_stall_best(100);
+
+    // Run solver
+    let res = Executor::new(cost, solver)
+        .configure(|state| state.param(vec![4.1, -3.2, 2.3]).max_iters(200))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Wait a second (lets the logger flush everything before printing again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    // Print result
+    println!("{}", res);
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{CostFunction, Error, Executor};
+use argmin::solver::bayesianoptimization::{AcquisitionFunction, BayesianOptimization, Kernel};
+use argmin_testfunctions::himmelblau;
+
+struct Himmelblau {}
+
+impl CostFunction for Himmelblau {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(himmelblau(param))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    let cost_function = Himmelblau {};
+
+    let solver = BayesianOptimization::new((vec![-4.0, -4.0], vec![4.0, 4.0]))?
+        // Optional: covariance function of the Gaussian process (defaults to `Kernel::Matern52`)
+        .with_kernel(Kernel::Matern52)
+        // Optional: acquisition function (defaults to expected improvement with `xi = 0.01`)
+        .with_acquisition(AcquisitionFunction::ExpectedImprovement { xi: 0.01 })?
+        // Optional: number of samples of the initial design (defaults to `2 * n + 1`)
+        .with_initial_samples(10)?;
+
+    let res = Executor::new(cost_function, solver)
+        // Every iteration evaluates the cost function exactly once.
+        .configure(|state| state.max_iters(50))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Wait a second (lets the logger flush everything before printing again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    // Print Result
+    println!("{}", res);
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{Error, Executor, LinearProgram, MixedIntegerLinearProgram};
+use argmin::solver::branchandbound::{BranchAndBound, NodeSelection};
+
+/// 0-1 knapsack problem: maximize the total value of the packed items without exceeding the
+/// capacity.
+struct Knapsack {
+    values: Vec<f64>,
+    weights: Vec<f64>,
+    capacity: f64,
+}
+
+impl LinearProgram for Knapsack {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    fn c(&self) -> Result<Vec<f64>, Error> {
+        // Maximizing the value is minimizing its negative
+        Ok(self.values.iter().map(|v| -v).collect())
+    }
+
+    fn b(&self) -> Result<Vec<f64>, Error> {
+        let mut b = vec![self.capacity];
+        b.extend(vec![1.0; self.values.len()]);
+        Ok(b)
+    }
+
+    fn A(&self) -> Result<Vec<Vec<f64>>, Error> {
+        // Capacity constraint and x_i <= 1
+        let n = self.values.len();
+        let mut a = vec![self.weights.clone()];
+        for i in 0..n {
+            let mut row = vec![0.0; n];
+            row[i] = 1.0;
+            a.push(row);
+        }
+        Ok(a)
+    }
+}
+
+impl MixedIntegerLinearProgram for Knapsack {
+    fn integer_variables(&self) -> Result<Vec<usize>, Error> {
+        Ok((0..self.values.len()).collect())
+    }
+}
+
+fn run() -> Result<(), Error> {
+    let problem = Knapsack {
+        values: vec![12.0, 11.0, 9.0, 8.0, 7.0, 6.0, 5.0, 3.0],
+        weights: vec![7.0, 6.0, 5.0, 5.0, 4.0, 3.0, 3.0, 2.0],
+        capacity: 20.0,
+    };
+
+    // Set up solver
+    let solver = BranchAndBound::new()
+        .with_node_selection(NodeSelection::BestBound)
+        .with_gap_tolerance(1e-9)?;
+
+    // Run solver
+    let res = Executor::new(problem, solver)
+        .configure(|state| state.max_iters(1000))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Wait a second (lets the observer flush everything before printing again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    // Print result
+    println!("{}", res);
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
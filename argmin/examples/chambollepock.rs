@@ -0,0 +1,128 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{AdjointOperator, Error, Executor, Operator};
+use argmin::solver::primaldual::{ChambollePock, ProximalMap};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Forward differences `(Kx)_i = x_{i+1} - x_i`
+struct Differences {}
+
+impl Operator for Differences {
+    type Param = Vec<f64>;
+    type Output = Vec<f64>;
+
+    fn apply(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(x.windows(2).map(|w| w[1] - w[0]).collect())
+    }
+}
+
+impl AdjointOperator for Differences {
+    fn apply_adjoint(&self, y: &Self::Output) -> Result<Self::Param, Error> {
+        let mut x = vec![0.0; y.len() + 1];
+        for (i, y) in y.iter().enumerate() {
+            x[i] -= y;
+            x[i + 1] += y;
+        }
+        Ok(x)
+    }
+}
+
+/// Data fidelity term `G(x) = 1/2 ‖x - f‖²`
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct DataFidelity {
+    f: Vec<f64>,
+}
+
+impl ProximalMap<Vec<f64>, f64> for DataFidelity {
+    fn prox(&self, v: &Vec<f64>, step: f64) -> Result<Vec<f64>, Error> {
+        Ok(v.iter()
+            .zip(self.f.iter())
+            .map(|(v, f)| (v + step * f) / (1.0 + step))
+            .collect())
+    }
+
+    fn value(&self, x: &Vec<f64>) -> Result<Option<f64>, Error> {
+        let sq: f64 = x
+            .iter()
+            .zip(self.f.iter())
+            .map(|(x, f)| (x - f).powi(2))
+            .sum();
+        Ok(Some(0.5 * sq))
+    }
+
+    fn conjugate_value(&self, v: &Vec<f64>) -> Result<Option<f64>, Error> {
+        Ok(Some(
+            v.iter()
+                .zip(self.f.iter())
+                .map(|(v, f)| 0.5 * v * v + v * f)
+                .sum(),
+        ))
+    }
+}
+
+/// Conjugate of the total variation `F(z) = lambda ‖z‖₁`, which is the indicator function of
+/// `|y_i| <= lambda`
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct TotalVariationConjugate {
+    lambda: f64,
+}
+
+impl ProximalMap<Vec<f64>, f64> for TotalVariationConjugate {
+    fn prox(&self, v: &Vec<f64>, _step: f64) -> Result<Vec<f64>, Error> {
+        Ok(v.iter()
+            .map(|v| v.clamp(-self.lambda, self.lambda))
+            .collect())
+    }
+
+    fn value(&self, y: &Vec<f64>) -> Result<Option<f64>, Error> {
+        let inside = y.iter().all(|y| y.abs() <= self.lambda * (1.0 + 1e-12));
+        Ok(Some(if inside { 0.0 } else { f64::INFINITY }))
+    }
+
+    fn conjugate_value(&self, z: &Vec<f64>) -> Result<Option<f64>, Error> {
+        Ok(Some(self.lambda * z.iter().map(|z| z.abs()).sum::<f64>()))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Noisy piecewise constant signal
+    let f: Vec<f64> = (0..40)
+        .map(|i| {
+            let clean = if (10..25).contains(&i) { 1.0 } else { 0.0 };
+            clean + 0.1 * (i as f64 * 2.3).sin()
+        })
+        .collect();
+    let init_param = vec![0.0; f.len()];
+
+    // Total variation denoising `min_x 1/2 ‖x - f‖² + lambda ‖Kx‖₁`. The step sizes are derived
+    // from an estimate of the operator norm of `K`.
+    let solver = ChambollePock::new(DataFidelity { f }, TotalVariationConjugate { lambda: 0.2 })
+        .with_tolerance(1e-8)?;
+
+    // Run solver
+    let res = Executor::new(Differences {}, solver)
+        .configure(|state| state.param(init_param).max_iters(5000))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(100))
+        .run()?;
+
+    // Wait a second (lets the logger flush everything first)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    // print result
+    println!("{}", res);
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{CostFunction, Error, Executor, PartialDerivative};
+use argmin::solver::coordinatedescent::{
+    CoordinateDescent, CoordinateSelection, PartialDerivativeCoordinateDescent,
+};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+/// Ridge regression `1/2 ‖Xw - y‖² + lambda/2 ‖w‖²`
+struct RidgeRegression {
+    /// Design matrix (rows are samples)
+    features: Vec<Vec<f64>>,
+    /// Observations
+    targets: Vec<f64>,
+    /// Regularization parameter
+    lambda: f64,
+}
+
+impl RidgeRegression {
+    fn residuals(&self, w: &[f64]) -> Vec<f64> {
+        self.features
+            .iter()
+            .zip(self.targets.iter())
+            .map(|(x, y)| x.iter().zip(w.iter()).map(|(a, b)| a * b).sum::<f64>() - y)
+            .collect()
+    }
+}
+
+impl CostFunction for RidgeRegression {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, w: &Vec<f64>) -> Result<f64, Error> {
+        let residuals = self.residuals(w);
+        Ok(0.5 * residuals.iter().map(|r| r * r).sum::<f64>()
+            + 0.5 * self.lambda * w.iter().map(|v| v * v).sum::<f64>())
+    }
+}
+
+impl PartialDerivative for RidgeRegression {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    fn partial_derivative(&self, w: &Vec<f64>, index: usize) -> Result<f64, Error> {
+        let residuals = self.residuals(w);
+        Ok(self
+            .features
+            .iter()
+            .zip(residuals.iter())
+            .map(|(x, r)| x[index] * r)
+            .sum::<f64>()
+            + self.lambda * w[index])
+    }
+}
+
+fn problem() -> RidgeRegression {
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+    let true_weights = [1.5, -2.0, 0.5, 3.0, -1.0];
+    let features: Vec<Vec<f64>> = (0..50)
+        .map(|_| (0..5).map(|_| rng.gen_range(-1.0..1.0)).collect())
+        .collect();
+    let targets = features
+        .iter()
+        .map(|x| {
+            x.iter()
+                .zip(true_weights.iter())
+                .map(|(a, b)| a * b)
+                .sum::<f64>()
+                + 0.1 * rng.gen_range(-1.0..1.0)
+        })
+        .collect();
+    RidgeRegression {
+        features,
+        targets,
+        lambda: 0.1,
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Derivative-free coordinate descent with exact steps by Brent's method
+    let solver = CoordinateDescent::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(42))
+        .with_selection(CoordinateSelection::Cyclic)?
+        .with_tolerance(1e-12)?;
+
+    let res = Executor::new(problem(), solver)
+        .configure(|state| state.param(vec![0.0; 5]).max_iters(500))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(5))
+        .run()?;
+
+    // Wait a second (lets the logger flush everything before printing to screen again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    println!("{}", res);
+
+    // Gauss-Southwell rule using partial derivatives
+    let solver =
+        PartialDerivativeCoordinateDescent::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(42))
+            .with_selection(CoordinateSelection::GaussSouthwell)
+            .with_tolerance(1e-6)?;
+
+    let res = Executor::new(problem(), solver)
+        .configure(|state| state.param(vec![0.0; 5]).max_iters(500))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(5))
+        .run()?;
+
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    println!("{}", res);
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+    }
+}
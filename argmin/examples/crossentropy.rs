@@ -0,0 +1,92 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{CostFunction, Error, Executor, State};
+use argmin::solver::crossentropy::{Categorical, CrossEntropy, Gaussian};
+use argmin_testfunctions::rastrigin;
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+struct Rastrigin {}
+
+impl CostFunction for Rastrigin {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Vec<f64>) -> Result<f64, Error> {
+        Ok(rastrigin(p))
+    }
+}
+
+/// Number of positions in which a bit string differs from a hidden target
+struct HiddenBits {
+    target: Vec<usize>,
+}
+
+impl CostFunction for HiddenBits {
+    type Param = Vec<usize>;
+    type Output = f64;
+
+    fn cost(&self, p: &Vec<usize>) -> Result<f64, Error> {
+        Ok(p.iter()
+            .zip(self.target.iter())
+            .filter(|(a, b)| a != b)
+            .count() as f64)
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Continuous problem with many local minima: wide Gaussian search distribution
+    let distribution = Gaussian::new(vec![3.0, 3.0], vec![3.0, 3.0]);
+
+    let solver =
+        CrossEntropy::new_with_rng(distribution, 100, Xoshiro256PlusPlus::seed_from_u64(42))?
+            .with_elite_fraction(0.2)?
+            .with_smoothing(0.7)?
+            .with_tolerance(1e-8)?;
+
+    let res = Executor::new(Rastrigin {}, solver)
+        .configure(|state| state.max_iters(1000))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(20))
+        .run()?;
+
+    // Wait a second (lets the logger flush everything before printing to screen again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    println!("{}", res);
+    println!(
+        "Mean of final distribution: {:?}",
+        res.solver().distribution().mean()
+    );
+
+    // Discrete problem: independent categorical distributions over the values of each bit
+    let target = vec![1, 0, 1, 1, 0, 0, 1, 0, 1, 1, 1, 0, 0, 1, 0, 1, 0, 1, 1, 0];
+    let distribution = Categorical::uniform(target.len(), 2)?;
+
+    let solver =
+        CrossEntropy::new_with_rng(distribution, 200, Xoshiro256PlusPlus::seed_from_u64(42))?
+            .with_tolerance(1e-6)?;
+
+    let res = Executor::new(HiddenBits { target }, solver)
+        .configure(|state| state.max_iters(100))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    println!("{}", res);
+    println!(
+        "Best bit string: {:?}",
+        res.state().get_best_param().unwrap()
+    );
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{CostFunction, Error, Executor};
+use argmin::solver::direct::{Direct, DirectVariant};
+use argmin_testfunctions::goldsteinprice;
+
+struct GoldsteinPrice {}
+
+impl CostFunction for GoldsteinPrice {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(goldsteinprice(p))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define cost function
+    let cost = GoldsteinPrice {};
+
+    // Set up solver (use `DirectVariant::Original` for the original DIRECT algorithm)
+    let solver = Direct::new((vec![-2.0, -2.0], vec![2.0, 2.0]))?
+        .with_variant(DirectVariant::LocallyBiased)
+        .with_max_evals(300)
+        .with_min_volume(1e-10)?;
+
+    // Run solver
+    let res = Executor::new(cost, solver)
+        .configure(|state| state.max_iters(100))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Wait a second (lets the logger flush everything before printing again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    // Print result
+    println!("{}", res);
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
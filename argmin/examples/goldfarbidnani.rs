@@ -0,0 +1,92 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{Error, Executor, QuadraticProgram};
+use argmin::solver::quadraticprogramming::GoldfarbIdnani;
+
+/// min (x1 - 1)^2 + (x2 - 2.5)^2 subject to
+///
+///   -x1 + 2 x2 <= 2
+///    x1 + 2 x2 <= 6
+///    x1 - 2 x2 <= 2
+///   -x1 <= 0, -x2 <= 0
+///
+/// The upper bound of the second constraint can be changed.
+struct Qp {
+    b1: f64,
+}
+
+impl QuadraticProgram for Qp {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    fn H(&self) -> Result<Vec<Vec<f64>>, Error> {
+        Ok(vec![vec![2.0, 0.0], vec![0.0, 2.0]])
+    }
+
+    fn c(&self) -> Result<Vec<f64>, Error> {
+        Ok(vec![-2.0, -5.0])
+    }
+
+    fn A_ineq(&self) -> Result<Vec<Vec<f64>>, Error> {
+        Ok(vec![
+            vec![-1.0, 2.0],
+            vec![1.0, 2.0],
+            vec![1.0, -2.0],
+            vec![-1.0, 0.0],
+            vec![0.0, -1.0],
+        ])
+    }
+
+    fn b_ineq(&self) -> Result<Vec<f64>, Error> {
+        Ok(vec![2.0, self.b1, 2.0, 0.0, 0.0])
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Set up solver
+    let solver = GoldfarbIdnani::new();
+
+    // Run solver
+    let res = Executor::new(Qp { b1: 6.0 }, solver)
+        .configure(|state| state.max_iters(100))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Wait a second (lets the observer flush everything before printing again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    // Print result
+    println!("{}", res);
+    println!("Active set: {:?}", res.solver.active_set());
+    println!(
+        "Lagrange multipliers: {:?}",
+        res.solver.lagrange_multipliers_ineq()
+    );
+
+    // Solve a slightly modified problem, warm started from the previous active set
+    let solver = GoldfarbIdnani::new().with_active_set(res.solver.active_set().to_vec());
+    let res = Executor::new(Qp { b1: 4.0 }, solver)
+        .configure(|state| state.max_iters(100))
+        .run()?;
+
+    println!("{}", res);
+    println!("Active set: {:?}", res.solver.active_set());
+    println!(
+        "Lagrange multipliers: {:?}",
+        res.solver.lagrange_multipliers_ineq()
+    );
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
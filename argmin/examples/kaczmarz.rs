@@ -0,0 +1,139 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{Error, Executor, RowAccess, State};
+use argmin::solver::kaczmarz::{BlockKaczmarz, RandomizedKaczmarz, SART};
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+/// Parallel beam tomography of an `n x n` image.
+///
+/// Each row of the system corresponds to one ray (angle and detector bin). The system matrix is
+/// never stored, the rows are computed on the fly by sampling points along the ray.
+struct Tomography {
+    /// Image size
+    n: usize,
+    /// Number of projection angles
+    num_angles: usize,
+    /// Measured projections
+    sinogram: Vec<f64>,
+}
+
+impl Tomography {
+    fn new(n: usize, num_angles: usize, image: &[f64]) -> Self {
+        let mut tomo = Tomography {
+            n,
+            num_angles,
+            sinogram: vec![],
+        };
+        tomo.sinogram = (0..tomo.num_rows())
+            .map(|i| dot(&tomo.ray(i), image))
+            .collect();
+        tomo
+    }
+
+    /// Weights of all pixels along ray `index`
+    fn ray(&self, index: usize) -> Vec<f64> {
+        let n = self.n as f64;
+        let angle = std::f64::consts::PI * (index / self.n) as f64 / self.num_angles as f64;
+        let offset = (index % self.n) as f64 + 0.5 - n / 2.0;
+        let (sin, cos) = angle.sin_cos();
+        let step = 0.25;
+        let mut weights = vec![0.0; self.n * self.n];
+        let mut t = -n;
+        while t < n {
+            let x = offset * cos - t * sin + n / 2.0;
+            let y = offset * sin + t * cos + n / 2.0;
+            if x >= 0.0 && x < n && y >= 0.0 && y < n {
+                weights[y as usize * self.n + x as usize] += step;
+            }
+            t += step;
+        }
+        weights
+    }
+}
+
+impl RowAccess for Tomography {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    fn num_rows(&self) -> usize {
+        self.num_angles * self.n
+    }
+
+    fn row(&self, index: usize) -> Result<(Vec<f64>, f64), Error> {
+        Ok((self.ray(index), self.sinogram[index]))
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(a, b)| a * b).sum()
+}
+
+/// Relative reconstruction error
+fn error(x: &[f64], image: &[f64]) -> f64 {
+    let diff: Vec<f64> = x.iter().zip(image.iter()).map(|(x, y)| x - y).collect();
+    (dot(&diff, &diff) / dot(image, image)).sqrt()
+}
+
+fn run() -> Result<(), Error> {
+    // Phantom: a bright square containing a darker disc
+    let n = 16;
+    let image: Vec<f64> = (0..n * n)
+        .map(|i| {
+            let (x, y) = ((i % n) as f64 - 7.5, (i / n) as f64 - 7.5);
+            if x * x + y * y < 9.0 {
+                0.5
+            } else if x.abs() < 6.0 && y.abs() < 6.0 {
+                1.0
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let init_param = vec![0.0; n * n];
+    let iters = 50;
+
+    // Randomized Kaczmarz method with rows drawn proportional to their squared norms
+    let solver = RandomizedKaczmarz::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(42));
+    let res = Executor::new(Tomography::new(n, 24, &image), solver)
+        .configure(|state| state.param(init_param.clone()).max_iters(iters))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(10))
+        .run()?;
+
+    // Wait a second (lets the logger flush everything before printing to screen again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    println!("{}", res);
+    let kaczmarz_error = error(res.state().get_best_param().unwrap(), &image);
+
+    // Block Kaczmarz method with random blocks of 16 rays
+    let solver = BlockKaczmarz::new_with_rng(16, Xoshiro256PlusPlus::seed_from_u64(42))?;
+    let res = Executor::new(Tomography::new(n, 24, &image), solver)
+        .configure(|state| state.param(init_param.clone()).max_iters(iters))
+        .run()?;
+    let block_error = error(res.state().get_best_param().unwrap(), &image);
+
+    // SART with one block per projection angle
+    let solver = SART::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(42)).with_block_size(n)?;
+    let res = Executor::new(Tomography::new(n, 24, &image), solver)
+        .configure(|state| state.param(init_param).max_iters(iters))
+        .run()?;
+    let sart_error = error(res.state().get_best_param().unwrap(), &image);
+
+    println!("Relative reconstruction errors after {} sweeps:", iters);
+    println!("  Randomized Kaczmarz: {:.4}", kaczmarz_error);
+    println!("  Block Kaczmarz:      {:.4}", block_error);
+    println!("  SART:                {:.4}", sart_error);
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{CostFunction, Error, Executor, Gradient};
+use argmin::solver::quasinewton::LSR1TrustRegion;
+use argmin::solver::trustregion::Steihaug;
+use argmin_testfunctions::rosenbrock;
+use finitediff::FiniteDiff;
+use ndarray::Array1;
+
+struct Rosenbrock {
+    a: f64,
+    b: f64,
+}
+
+impl CostFunction for Rosenbrock {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rosenbrock(&p.to_vec(), self.a, self.b))
+    }
+}
+
+impl Gradient for Rosenbrock {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok((*p).central_diff(&|x| rosenbrock(&x.to_vec(), self.a, self.b)))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define cost function
+    let cost = Rosenbrock { a: 1.0, b: 100.0 };
+
+    // Define initial parameter vector
+    let init_param: Array1<f64> = Array1::from_iter((0..50).map(|i| (i % 3) as f64 - 1.5));
+
+    // Set up the subproblem (only Hessian-vector products are needed)
+    let subproblem = Steihaug::new().with_max_iters(50);
+
+    // Set up solver with a history size of 7
+    let solver = LSR1TrustRegion::new(subproblem, 7).with_tolerance_grad(1e-6)?;
+
+    // Run solver
+    let res = Executor::new(cost, solver)
+        .configure(|state| state.param(init_param).max_iters(5000))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(100))
+        .run()?;
+
+    // Wait a second (lets the observer flush everything before printing again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    // Print result
+    println!("{}", res);
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{CostFunction, Error, Executor};
+use argmin::solver::nes::SeparableNES;
+use argmin_testfunctions::rosenbrock_2d;
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+struct Rosenbrock {
+    a: f64,
+    b: f64,
+}
+
+impl CostFunction for Rosenbrock {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Vec<f64>) -> Result<f64, Error> {
+        Ok(rosenbrock_2d(p, self.a, self.b))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // define cost function
+    let cost = Rosenbrock { a: 1.0, b: 100.0 };
+
+    // initial mean and standard deviations of the search distribution
+    let mean: Vec<f64> = vec![-1.2, 1.0];
+    let std_dev: Vec<f64> = vec![0.5, 0.5];
+
+    let solver = SeparableNES::new_with_rng(mean, std_dev, Xoshiro256PlusPlus::seed_from_u64(42))
+        .with_population_size(20)?
+        .with_tolerance(1e-8)?;
+
+    let res = Executor::new(cost, solver)
+        .configure(|state| state.max_iters(20_000))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(500))
+        .run()?;
+
+    // Wait a second (lets the logger flush everything before printing to screen again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    println!("{}", res);
+    println!("Mean of final distribution: {:?}", res.solver().mean());
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{Error, Executor, Jacobian, Operator};
+use argmin::solver::nnls::NNLS;
+
+use nalgebra::{DMatrix, DVector};
+
+// Linear spectral unmixing: a measured spectrum is modelled as a nonnegative combination of the
+// spectra of a few pure components (columns of `spectra`).
+struct Unmixing {
+    spectra: DMatrix<f64>,
+    measured: DVector<f64>,
+}
+
+impl Operator for Unmixing {
+    type Param = DVector<f64>;
+    type Output = DVector<f64>;
+
+    fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(&self.spectra * p - &self.measured)
+    }
+}
+
+impl Jacobian for Unmixing {
+    type Param = DVector<f64>;
+    type Jacobian = DMatrix<f64>;
+
+    fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+        Ok(self.spectra.clone())
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Gaussian shaped spectra of four components, sampled at 12 wavelengths
+    let centers = [2.0, 4.5, 7.0, 9.0];
+    let spectra = DMatrix::from_fn(12, 4, |i, j| (-(i as f64 - centers[j]).powi(2) / 4.0).exp());
+
+    // Mixture of the first and third component with a small disturbance
+    let abundances = DVector::from_vec(vec![0.6, 0.0, 0.4, 0.0]);
+    let disturbance = DVector::from_fn(12, |i, _| 0.01 * (i as f64 * 1.7).sin());
+    let measured = &spectra * abundances - disturbance;
+
+    let problem = Unmixing { spectra, measured };
+
+    // Define initial parameter vector
+    let init_param: DVector<f64> = DVector::zeros(4);
+
+    // Set up solver
+    let solver: NNLS<f64> = NNLS::new();
+
+    // Run solver
+    let res = Executor::new(problem, solver)
+        .configure(|state| state.param(init_param).max_iters(20))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Wait a second (lets the logger flush everything before printing again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    // Print result
+    println!("{}", res);
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{CostFunction, Error, Executor, Gradient};
+use argmin::solver::nonsmooth::{ProximalBundle, SubgradientMethod, SubgradientStep};
+
+/// Least absolute deviations fit of a line: `sum_i |x_0 + x_1 * t_i - y_i|`
+struct LeastAbsoluteDeviations {
+    t: Vec<f64>,
+    y: Vec<f64>,
+}
+
+impl LeastAbsoluteDeviations {
+    fn residuals(&self, p: &[f64]) -> impl Iterator<Item = (f64, f64)> + '_ {
+        let (a, b) = (p[0], p[1]);
+        self.t
+            .iter()
+            .zip(self.y.iter())
+            .map(move |(t, y)| (*t, a + b * t - y))
+    }
+}
+
+impl CostFunction for LeastAbsoluteDeviations {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(self.residuals(p).map(|(_, r)| r.abs()).sum())
+    }
+}
+
+impl Gradient for LeastAbsoluteDeviations {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        // `signum` returns a valid subgradient of `|r|`, except for `r == 0`, where any value in
+        // `[-1, 1]` is valid
+        Ok(self.residuals(p).fold(vec![0.0, 0.0], |g, (t, r)| {
+            let s = if r == 0.0 { 0.0 } else { r.signum() };
+            vec![g[0] + s, g[1] + s * t]
+        }))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Data on the line `1 + 2t` with two outliers
+    let t: Vec<f64> = (0..10).map(f64::from).collect();
+    let mut y: Vec<f64> = t.iter().map(|t| 1.0 + 2.0 * t).collect();
+    y[3] += 10.0;
+    y[7] -= 15.0;
+    let init_param = vec![0.0, 0.0];
+
+    // Subgradient method with diminishing step lengths
+    let solver = SubgradientMethod::new().with_step(SubgradientStep::Diminishing(1.0))?;
+    let res = Executor::new(
+        LeastAbsoluteDeviations {
+            t: t.clone(),
+            y: y.clone(),
+        },
+        solver,
+    )
+    .configure(|state| state.param(init_param.clone()).max_iters(2000))
+    .run()?;
+    println!("{}", res);
+
+    // Proximal bundle method
+    let solver = ProximalBundle::new().with_proximal_parameter(10.0)?;
+    let res = Executor::new(LeastAbsoluteDeviations { t, y }, solver)
+        .configure(|state| state.param(init_param).max_iters(100))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Wait a second (lets the logger flush everything first)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    // print result
+    println!("{}", res);
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
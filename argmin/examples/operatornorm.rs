@@ -0,0 +1,129 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{AdjointOperator, CostFunction, Error, Executor, Gradient, Operator, Problem};
+use argmin::solver::landweber::Landweber;
+use argmin::solver::operatornorm::{operator_norm, Lanczos, NormalOperator};
+
+/// Dense matrix `A` (row-major)
+#[derive(Clone)]
+struct Matrix {
+    rows: Vec<Vec<f64>>,
+}
+
+impl Operator for Matrix {
+    type Param = Vec<f64>;
+    type Output = Vec<f64>;
+
+    fn apply(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(self
+            .rows
+            .iter()
+            .map(|row| row.iter().zip(x.iter()).map(|(a, x)| a * x).sum())
+            .collect())
+    }
+}
+
+impl AdjointOperator for Matrix {
+    fn apply_adjoint(&self, y: &Self::Output) -> Result<Self::Param, Error> {
+        let mut x = vec![0.0; self.rows[0].len()];
+        for (row, y) in self.rows.iter().zip(y.iter()) {
+            for (x, a) in x.iter_mut().zip(row.iter()) {
+                *x += a * y;
+            }
+        }
+        Ok(x)
+    }
+}
+
+/// Linear least squares problem `f(x) = 1/2 ‖Ax - b‖²`
+struct LeastSquares {
+    a: Matrix,
+    b: Vec<f64>,
+}
+
+impl LeastSquares {
+    fn residual(&self, x: &[f64]) -> Result<Vec<f64>, Error> {
+        Ok(self
+            .a
+            .apply(&x.to_vec())?
+            .iter()
+            .zip(self.b.iter())
+            .map(|(ax, b)| ax - b)
+            .collect())
+    }
+}
+
+impl CostFunction for LeastSquares {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(0.5 * self.residual(x)?.iter().map(|r| r * r).sum::<f64>())
+    }
+}
+
+impl Gradient for LeastSquares {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, x: &Self::Param) -> Result<Self::Gradient, Error> {
+        self.a.apply_adjoint(&self.residual(x)?)
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Smooth kernel, as arising from the discretization of an integral operator
+    let n = 30;
+    let a = Matrix {
+        rows: (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        let d = (i as f64 - j as f64) / 4.0;
+                        (-d * d).exp() / n as f64
+                    })
+                    .collect()
+            })
+            .collect(),
+    };
+
+    // Quick estimate with the utility function
+    let norm: f64 = operator_norm(&mut Problem::new(a.clone()), &vec![0.0; n], 100)?;
+    println!("Power iteration estimate of ‖A‖: {norm}");
+
+    // Lanczos method on `AᵀA` as solver
+    let res = Executor::new(NormalOperator::new(a.clone()), Lanczos::new())
+        .configure(|state| state.param(vec![1.0; n]).max_iters(n as u64))
+        .run()?;
+    println!(
+        "Lanczos estimate of ‖A‖:         {} ({} iterations)",
+        res.solver().max_eigenvalue().sqrt(),
+        res.state().iter
+    );
+
+    // Landweber iteration with automatically chosen step length
+    let x_true: Vec<f64> = (0..n).map(|i| (i as f64 / 5.0).sin()).collect();
+    let b = a.apply(&x_true)?;
+    let res = Executor::new(LeastSquares { a, b }, Landweber::automatic())
+        .configure(|state| state.param(vec![0.0; n]).max_iters(50))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(10))
+        .run()?;
+
+    // Wait a second (lets the logger flush everything before printing to screen again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    println!("{}", res);
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{CostFunction, Error, Executor, Gradient};
+use argmin::solver::linesearch::MoreThuenteLineSearch;
+use argmin::solver::riemannian::{RiemannianConjugateGradient, Stiefel};
+
+// Brockett cost function `tr(XᵀAXN)` with `N = diag(p, ..., 1)` on the Stiefel manifold of
+// `n x p` matrices with orthonormal columns. Its minimizer consists of the eigenvectors of the `p`
+// smallest eigenvalues of the symmetric matrix `A`, ordered by increasing eigenvalue. Matrices
+// are stored in row-major order.
+struct Brockett {
+    a: Vec<f64>,
+    n: usize,
+    p: usize,
+}
+
+impl Brockett {
+    fn ax(&self, x: &[f64]) -> Vec<f64> {
+        let (n, p) = (self.n, self.p);
+        let mut ax = vec![0.0; n * p];
+        for i in 0..n {
+            for k in 0..n {
+                for j in 0..p {
+                    ax[i * p + j] += self.a[i * n + k] * x[k * p + j];
+                }
+            }
+        }
+        ax
+    }
+}
+
+impl CostFunction for Brockett {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(self
+            .ax(x)
+            .iter()
+            .zip(x.iter())
+            .enumerate()
+            .map(|(idx, (ax, x))| (self.p - idx % self.p) as f64 * ax * x)
+            .sum())
+    }
+}
+
+impl Gradient for Brockett {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, x: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(self
+            .ax(x)
+            .iter()
+            .enumerate()
+            .map(|(idx, ax)| 2.0 * (self.p - idx % self.p) as f64 * ax)
+            .collect())
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Discrete 1D Laplacian, eigenvalues `2 - 2 cos(k pi / 6)`
+    let n = 5;
+    let a: Vec<f64> = (0..n * n)
+        .map(|idx| match (idx / n) as i64 - (idx % n) as i64 {
+            0 => 2.0,
+            1 | -1 => -1.0,
+            _ => 0.0,
+        })
+        .collect();
+    let problem = Brockett { a, n, p: 2 };
+
+    // Initial orthonormal frame: first two unit vectors
+    let init_param = vec![1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+
+    // Set up line search and solver
+    let linesearch = MoreThuenteLineSearch::new();
+    let solver = RiemannianConjugateGradient::new(Stiefel::new(n, 2)?, linesearch)
+        .with_restart_iters(10)
+        .with_tolerance(1e-8)?;
+
+    // Run solver
+    let res = Executor::new(problem, solver)
+        .configure(|state| state.param(init_param).max_iters(200))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Wait a second (lets the logger flush everything before printing again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    // Print result
+    println!("{}", res);
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::observers::{ObserverMode, SlogLogger};
+use argmin::core::{CostFunction, Error, Executor};
+use argmin::solver::spsa::{NoiseAwareTermination, SPSA};
+use argmin_testfunctions::rosenbrock_2d;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use std::sync::Mutex;
+
+/// Rosenbrock function observed through a noisy Monte Carlo estimate
+struct NoisyRosenbrock {
+    /// Number of samples per evaluation
+    num_samples: usize,
+    /// Random number generator of the simulation
+    rng: Mutex<Xoshiro256PlusPlus>,
+}
+
+impl CostFunction for NoisyRosenbrock {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Vec<f64>) -> Result<f64, Error> {
+        let mut rng = self.rng.lock().unwrap();
+        // Average of noisy samples which are unbiased for the true cost function value
+        let noise: f64 = (0..self.num_samples)
+            .map(|_| rng.gen_range(-1.0..1.0))
+            .sum::<f64>()
+            / self.num_samples as f64;
+        Ok(rosenbrock_2d(p, 1.0, 100.0) + noise)
+    }
+}
+
+fn run() -> Result<(), Error> {
+    let cost = NoisyRosenbrock {
+        num_samples: 10,
+        rng: Mutex::new(Xoshiro256PlusPlus::seed_from_u64(1)),
+    };
+
+    // define inital parameter vector
+    let init_param: Vec<f64> = vec![-1.2, 1.0];
+
+    // Stop once the average of 200 repeated evaluations, checked every 500 iterations, does not
+    // decrease significantly anymore.
+    let termination = NoiseAwareTermination::new(200)?.with_interval(500)?;
+
+    let solver = SPSA::new_with_rng(0.05, 0.1, Xoshiro256PlusPlus::seed_from_u64(42))?
+        .with_stability_constant(100.0)?
+        .with_gradient_averaging(2)?
+        .with_noise_aware_termination(termination);
+
+    let res = Executor::new(cost, solver)
+        .configure(|state| state.param(init_param).max_iters(100_000))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(500))
+        .run()?;
+
+    // Wait a second (lets the logger flush everything before printing to screen again)
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    println!("{}", res);
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{}", e);
+    }
+}
@@ -0,0 +1,657 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error, Jacobian, Operator, OptimizationResult, State};
+use argmin_math::{ArgminQrSolve, ArgminToVec};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Statistics of the parameters of a least squares fit
+///
+/// Obtained via [`OptimizationResult::fit_statistics`] after solving a least squares problem
+/// (for instance with [`GaussNewton`](`crate::solver::gaussnewton::GaussNewton`)). With `m`
+/// residuals `r`, `n` parameters and the Jacobian `J` evaluated at the best parameter vector, the
+/// covariance matrix of the parameters is estimated as `s^2 (JᵀJ)^-1` with the residual variance
+/// `s^2 = ||r||^2 / (m - n)`. `JᵀJ` is never formed explicitly; the covariance matrix is instead
+/// computed from QR decompositions of `J` (see
+/// [`ArgminQrSolve`](`argmin_math::ArgminQrSolve`)), which avoids squaring the condition number
+/// of `J`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct FitStatistics<F> {
+    /// Parameter vector at which the statistics were computed
+    pub param: Vec<F>,
+    /// Degrees of freedom `m - n`
+    pub dof: usize,
+    /// Residual variance `s^2`
+    pub residual_variance: F,
+    /// Covariance matrix `s^2 (JᵀJ)^-1`
+    pub covariance: Vec<Vec<F>>,
+    /// Standard errors of the parameters (square roots of the diagonal of the covariance matrix)
+    pub standard_errors: Vec<F>,
+    /// Correlation matrix of the parameters
+    pub correlation: Vec<Vec<F>>,
+}
+
+impl<F: ArgminFloat> FitStatistics<F> {
+    /// Compute the statistics from the residuals and the Jacobian at `param`.
+    ///
+    /// If `absolute_sigma` is `true`, the residuals are assumed to be scaled by their known
+    /// standard deviations and the covariance matrix is not scaled by the residual variance.
+    pub(crate) fn from_residuals(
+        param: Vec<F>,
+        residuals: &[F],
+        jacobian: &Vec<Vec<F>>,
+        absolute_sigma: bool,
+    ) -> Result<Self, Error>
+    where
+        Vec<Vec<F>>: ArgminQrSolve<Vec<F>, Vec<F>>,
+    {
+        let m = residuals.len();
+        let n = param.len();
+        if jacobian.len() != m || jacobian.iter().any(|row| row.len() != n) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`fit_statistics`: Jacobian does not match the number of residuals and parameters."
+            ));
+        }
+        if m <= n {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`fit_statistics`: More residuals than parameters required."
+            ));
+        }
+        let dof = m - n;
+        let residual_variance = residuals
+            .iter()
+            .fold(float!(0.0), |acc: F, r| acc + *r * *r)
+            / float!(dof as f64);
+        let scale = if absolute_sigma {
+            float!(1.0)
+        } else {
+            residual_variance
+        };
+
+        let rank_deficient = || -> Error {
+            argmin_error!(
+                InvalidParameter,
+                "`fit_statistics`: Jacobian is rank deficient, covariance is undefined."
+            )
+        };
+        // Fails if `J` does not have full column rank
+        jacobian
+            .qr_solve(&residuals.to_vec())
+            .map_err(|_| rank_deficient())?;
+
+        // Regressing column `k` of `J` on the previous columns, `J_k = J_{<k} c_k + d_k q_k` with
+        // orthonormal `q_k`, yields `J U = Q D` with the unit upper triangular matrix `U` (column
+        // `k` is `(-c_k, 1, 0, ...)`) and `D = diag(d_k)`. Therefore `(JᵀJ)^-1 = U D^-2 Uᵀ`.
+        let mut u: Vec<Vec<F>> = vec![vec![float!(0.0); n]; n];
+        let mut d2: Vec<F> = Vec::with_capacity(n);
+        for k in 0..n {
+            let column: Vec<F> = jacobian.iter().map(|row| row[k]).collect();
+            let remainder: Vec<F> = if k == 0 {
+                column
+            } else {
+                let previous: Vec<Vec<F>> = jacobian.iter().map(|row| row[..k].to_vec()).collect();
+                let c = previous.qr_solve(&column).map_err(|_| rank_deficient())?;
+                for i in 0..k {
+                    u[i][k] = -c[i];
+                }
+                jacobian
+                    .iter()
+                    .zip(column.iter())
+                    .map(|(row, y)| {
+                        *y - row[..k]
+                            .iter()
+                            .zip(c.iter())
+                            .fold(float!(0.0), |acc: F, (a, b)| acc + *a * *b)
+                    })
+                    .collect()
+            };
+            u[k][k] = float!(1.0);
+            let d = remainder
+                .iter()
+                .fold(float!(0.0), |acc: F, r| acc + *r * *r);
+            if d <= float!(0.0) {
+                return Err(rank_deficient());
+            }
+            d2.push(d);
+        }
+        let covariance: Vec<Vec<F>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        (i.max(j)..n).fold(float!(0.0), |acc: F, k| acc + u[i][k] * u[j][k] / d2[k])
+                            * scale
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let standard_errors: Vec<F> = (0..n).map(|i| covariance[i][i].sqrt()).collect();
+        let correlation = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| covariance[i][j] / (standard_errors[i] * standard_errors[j]))
+                    .collect()
+            })
+            .collect();
+
+        Ok(FitStatistics {
+            param,
+            dof,
+            residual_variance,
+            covariance,
+            standard_errors,
+            correlation,
+        })
+    }
+
+    /// Confidence intervals `param_i ± t * standard_error_i` for each parameter, where `t` is the
+    /// quantile of Student's t-distribution with `dof` degrees of freedom for the given
+    /// confidence `level` (for instance `0.95`).
+    ///
+    /// The confidence level must be in `(0, 1)`.
+    pub fn confidence_intervals(&self, level: F) -> Result<Vec<(F, F)>, Error> {
+        if level <= float!(0.0) || level >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`FitStatistics`: confidence level must be in (0, 1)."
+            ));
+        }
+        let p = 0.5 * (1.0 + level.to_f64().unwrap());
+        let t: F = float!(student_t_quantile(p, self.dof as f64));
+        Ok(self
+            .param
+            .iter()
+            .zip(self.standard_errors.iter())
+            .map(|(&p, &se)| (p - t * se, p + t * se))
+            .collect())
+    }
+}
+
+impl<O, S, I, P, R, J, F> OptimizationResult<O, S, I>
+where
+    O: Operator<Param = P, Output = R> + Jacobian<Param = P, Jacobian = J>,
+    I: State<Param = P, Float = F>,
+    P: ArgminToVec<Vec<F>>,
+    R: ArgminToVec<Vec<F>>,
+    J: ArgminToVec<Vec<Vec<F>>>,
+    F: ArgminFloat,
+    Vec<Vec<F>>: ArgminQrSolve<Vec<F>, Vec<F>>,
+{
+    /// Compute covariance matrix, standard errors and correlation matrix of the parameters of a
+    /// least squares fit at the best parameter vector (see [`FitStatistics`]).
+    ///
+    /// The problem must implement [`Operator`] returning the residuals and [`Jacobian`]. Both are
+    /// evaluated once more at the best parameter vector. These evaluations bypass the function
+    /// evaluation counts, which therefore still refer to the solver run. Parameter vector,
+    /// residuals and Jacobian of any math backend are supported; they are converted via
+    /// [`ArgminToVec`](`argmin_math::ArgminToVec`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, Jacobian, Operator};
+    /// # use argmin::solver::gaussnewton::GaussNewton;
+    /// # struct LineFit { x: Vec<f64>, y: Vec<f64> }
+    /// # impl Operator for LineFit {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = Vec<f64>;
+    /// #     fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(self.x.iter().zip(self.y.iter()).map(|(x, y)| p[0] + p[1] * x - y).collect())
+    /// #     }
+    /// # }
+    /// # impl Jacobian for LineFit {
+    /// #     type Param = Vec<f64>;
+    /// #     type Jacobian = Vec<Vec<f64>>;
+    /// #     fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+    /// #         Ok(self.x.iter().map(|x| vec![1.0, *x]).collect())
+    /// #     }
+    /// # }
+    /// # fn main() -> Result<(), Error> {
+    /// # let problem = LineFit {
+    /// #     x: vec![0.0, 1.0, 2.0, 3.0, 4.0],
+    /// #     y: vec![1.1, 2.9, 5.2, 6.8, 9.1],
+    /// # };
+    /// let res = Executor::new(problem, GaussNewton::new())
+    ///     .configure(|state| state.param(vec![0.0, 0.0]).max_iters(10))
+    ///     .run()?;
+    ///
+    /// let stats = res.fit_statistics()?;
+    /// let standard_errors = &stats.standard_errors;
+    /// let intervals = stats.confidence_intervals(0.95)?;
+    /// # assert!(intervals[1].0 < 2.0 && 2.0 < intervals[1].1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fit_statistics(&self) -> Result<FitStatistics<F>, Error> {
+        self.compute_fit_statistics(false)
+    }
+
+    /// Same as [`fit_statistics`](`OptimizationResult::fit_statistics`), but assumes that the
+    /// residuals are already scaled by known standard deviations of the observations. The
+    /// covariance matrix is therefore not scaled by the residual variance.
+    pub fn fit_statistics_absolute_sigma(&self) -> Result<FitStatistics<F>, Error> {
+        self.compute_fit_statistics(true)
+    }
+
+    fn compute_fit_statistics(&self, absolute_sigma: bool) -> Result<FitStatistics<F>, Error> {
+        let param = self
+            .state
+            .get_best_param()
+            .ok_or_else(argmin_error_closure!(
+                NotInitialized,
+                "`fit_statistics`: No best parameter vector available."
+            ))?;
+        let problem = self
+            .problem
+            .problem
+            .as_ref()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`fit_statistics`: Problem not available."
+            ))?;
+        let residuals = problem.apply(param)?.to_vec();
+        let jacobian = problem.jacobian(param)?.to_vec();
+        FitStatistics::from_residuals(param.to_vec(), &residuals, &jacobian, absolute_sigma)
+    }
+}
+
+/// Natural logarithm of the gamma function (Lanczos approximation)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection formula
+        std::f64::consts::PI.ln() - (std::f64::consts::PI * x).sin().ln() - ln_gamma(1.0 - x)
+    } else {
+        let x = x - 1.0;
+        let t = x + 7.5;
+        let a = COEFFS[1..]
+            .iter()
+            .enumerate()
+            .fold(COEFFS[0], |acc, (i, c)| acc + c / (x + i as f64 + 1.0));
+        0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+    }
+}
+
+/// Continued fraction for the regularized incomplete beta function
+fn beta_continued_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..500 {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        let aa = m * (b - m) * x / ((a + m2 - 1.0) * (a + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+        let aa = -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + 1.0));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let del = d * c;
+        h *= del;
+        if (del - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    h
+}
+
+/// Regularized incomplete beta function `I_x(a, b)`
+fn incomplete_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let ln_front = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        ln_front.exp() * beta_continued_fraction(x, a, b) / a
+    } else {
+        1.0 - ln_front.exp() * beta_continued_fraction(1.0 - x, b, a) / b
+    }
+}
+
+/// Cumulative distribution function of Student's t-distribution with `dof` degrees of freedom
+fn student_t_cdf(t: f64, dof: f64) -> f64 {
+    let tail = 0.5 * incomplete_beta(dof / (dof + t * t), 0.5 * dof, 0.5);
+    if t >= 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+/// Quantile of Student's t-distribution with `dof` degrees of freedom for `0.5 <= p < 1`
+fn student_t_quantile(p: f64, dof: f64) -> f64 {
+    let mut lo = 0.0;
+    let mut hi = 1.0;
+    while student_t_cdf(hi, dof) < p {
+        lo = hi;
+        hi *= 2.0;
+    }
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if student_t_cdf(mid, dof) < p {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo <= f64::EPSILON * hi {
+            break;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::gaussnewton::GaussNewton;
+    use approx::assert_relative_eq;
+
+    send_sync_test!(fit_statistics, FitStatistics<f64>);
+
+    struct LineFit {
+        x: Vec<f64>,
+        y: Vec<f64>,
+    }
+
+    impl Operator for LineFit {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self
+                .x
+                .iter()
+                .zip(self.y.iter())
+                .map(|(x, y)| p[0] + p[1] * x - y)
+                .collect())
+        }
+    }
+
+    impl Jacobian for LineFit {
+        type Param = Vec<f64>;
+        type Jacobian = Vec<Vec<f64>>;
+
+        fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(self.x.iter().map(|x| vec![1.0, *x]).collect())
+        }
+    }
+
+    #[test]
+    fn test_student_t_quantile() {
+        // Cauchy distribution
+        assert_relative_eq!(
+            student_t_quantile(0.975, 1.0),
+            (std::f64::consts::PI * 0.475).tan(),
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(
+            student_t_quantile(0.975, 10.0),
+            2.228138851986,
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(
+            student_t_quantile(0.95, 3.0),
+            2.353363434802,
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(student_t_quantile(0.5, 5.0), 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_line_fit() {
+        let x = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let y = vec![1.1, 2.9, 5.2, 6.8, 9.1];
+        let res = Executor::new(
+            LineFit {
+                x: x.clone(),
+                y: y.clone(),
+            },
+            GaussNewton::new(),
+        )
+        .configure(|state| state.param(vec![0.0, 0.0]).max_iters(10))
+        .run()
+        .unwrap();
+        let counts = res.problem.counts.clone();
+        let stats = res.fit_statistics().unwrap();
+
+        // Closed form solution of simple linear regression
+        let n = x.len() as f64;
+        let x_mean = x.iter().sum::<f64>() / n;
+        let y_mean = y.iter().sum::<f64>() / n;
+        let sxx: f64 = x.iter().map(|x| (x - x_mean).powi(2)).sum();
+        let sxy: f64 = x
+            .iter()
+            .zip(y.iter())
+            .map(|(x, y)| (x - x_mean) * (y - y_mean))
+            .sum();
+        let b = sxy / sxx;
+        let a = y_mean - b * x_mean;
+        let s2 = x
+            .iter()
+            .zip(y.iter())
+            .map(|(x, y)| (y - a - b * x).powi(2))
+            .sum::<f64>()
+            / (n - 2.0);
+
+        assert_eq!(stats.dof, 3);
+        assert_relative_eq!(stats.param[0], a, epsilon = 1e-10);
+        assert_relative_eq!(stats.param[1], b, epsilon = 1e-10);
+        assert_relative_eq!(stats.residual_variance, s2, epsilon = 1e-10);
+        assert_relative_eq!(
+            stats.covariance[0][0],
+            s2 * (1.0 / n + x_mean * x_mean / sxx),
+            epsilon = 1e-10
+        );
+        assert_relative_eq!(stats.covariance[1][1], s2 / sxx, epsilon = 1e-10);
+        assert_relative_eq!(stats.covariance[0][1], -x_mean * s2 / sxx, epsilon = 1e-10);
+        assert_eq!(
+            stats.covariance[0][1].to_ne_bytes(),
+            stats.covariance[1][0].to_ne_bytes()
+        );
+        assert_relative_eq!(stats.standard_errors[1], (s2 / sxx).sqrt(), epsilon = 1e-10);
+        assert_relative_eq!(stats.correlation[0][0], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(stats.correlation[1][1], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(
+            stats.correlation[0][1],
+            -x_mean / (1.0 / n + x_mean * x_mean / sxx).sqrt() / sxx.sqrt(),
+            epsilon = 1e-10
+        );
+
+        let t = student_t_quantile(0.975, 3.0);
+        let intervals = stats.confidence_intervals(0.95).unwrap();
+        assert_relative_eq!(intervals[1].0, b - t * (s2 / sxx).sqrt(), epsilon = 1e-10);
+        assert_relative_eq!(intervals[1].1, b + t * (s2 / sxx).sqrt(), epsilon = 1e-10);
+
+        let abs = res.fit_statistics_absolute_sigma().unwrap();
+        assert_relative_eq!(abs.covariance[1][1], 1.0 / sxx, epsilon = 1e-10);
+
+        // Function evaluation counts are not affected
+        assert_eq!(res.problem.counts, counts);
+    }
+
+    #[test]
+    fn test_ill_conditioned() {
+        // Line fit far away from the origin: the condition number of `J` is about `1e6`, the one
+        // of `JᵀJ` about `1e12`.
+        let x: Vec<f64> = (0..5).map(|i| 1e6 + i as f64).collect();
+        let jacobian: Vec<Vec<f64>> = x.iter().map(|x| vec![1.0, *x]).collect();
+        let stats =
+            FitStatistics::from_residuals(vec![1.0, 2.0], &[0.1; 5], &jacobian, true).unwrap();
+        let x_mean = 1e6 + 2.0;
+        let sxx = 10.0;
+        assert_relative_eq!(stats.covariance[1][1], 1.0 / sxx, max_relative = 1e-8);
+        assert_relative_eq!(stats.covariance[0][1], -x_mean / sxx, max_relative = 1e-8);
+        assert_relative_eq!(
+            stats.covariance[0][0],
+            1.0 / 5.0 + x_mean * x_mean / sxx,
+            max_relative = 1e-8
+        );
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    #[test]
+    fn test_nalgebra() {
+        use nalgebra::{DMatrix, DVector};
+
+        struct DenseLineFit {
+            x: DVector<f64>,
+            y: DVector<f64>,
+        }
+
+        impl Operator for DenseLineFit {
+            type Param = DVector<f64>;
+            type Output = DVector<f64>;
+
+            fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(self.x.map(|x| p[0] + p[1] * x) - &self.y)
+            }
+        }
+
+        impl Jacobian for DenseLineFit {
+            type Param = DVector<f64>;
+            type Jacobian = DMatrix<f64>;
+
+            fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+                Ok(DMatrix::from_fn(self.x.len(), 2, |i, j| {
+                    if j == 0 {
+                        1.0
+                    } else {
+                        self.x[i]
+                    }
+                }))
+            }
+        }
+
+        let x = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let y = vec![1.1, 2.9, 5.2, 6.8, 9.1];
+        let expected = Executor::new(
+            LineFit {
+                x: x.clone(),
+                y: y.clone(),
+            },
+            GaussNewton::new(),
+        )
+        .configure(|state| state.param(vec![0.0, 0.0]).max_iters(10))
+        .run()
+        .unwrap()
+        .fit_statistics()
+        .unwrap();
+        let stats = Executor::new(
+            DenseLineFit {
+                x: DVector::from_vec(x),
+                y: DVector::from_vec(y),
+            },
+            GaussNewton::new(),
+        )
+        .configure(|state| state.param(DVector::zeros(2)).max_iters(10))
+        .run()
+        .unwrap()
+        .fit_statistics()
+        .unwrap();
+
+        assert_eq!(stats.dof, expected.dof);
+        for i in 0..2 {
+            assert_relative_eq!(stats.param[i], expected.param[i], epsilon = 1e-10);
+            for j in 0..2 {
+                assert_relative_eq!(
+                    stats.covariance[i][j],
+                    expected.covariance[i][j],
+                    epsilon = 1e-10
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        // Not enough residuals
+        let res = Executor::new(
+            LineFit {
+                x: vec![0.0, 1.0],
+                y: vec![1.0, 2.0],
+            },
+            GaussNewton::new(),
+        )
+        .configure(|state| state.param(vec![0.0, 0.0]).max_iters(1))
+        .run()
+        .unwrap();
+        assert_error!(
+            res.fit_statistics(),
+            ArgminError,
+            "Invalid parameter: \"`fit_statistics`: More residuals than parameters required.\""
+        );
+
+        // Rank deficient Jacobian
+        let stats = FitStatistics::from_residuals(
+            vec![1.0, 1.0],
+            &[1.0, 2.0, 3.0],
+            &vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]],
+            false,
+        );
+        assert_error!(
+            stats,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`fit_statistics`: Jacobian is rank deficient, covariance ",
+                "is undefined.\""
+            )
+        );
+
+        let stats = FitStatistics::from_residuals(
+            vec![1.0, 1.0],
+            &[1.0, 2.0, 3.0],
+            &vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]],
+            false,
+        )
+        .unwrap();
+        for level in [0.0, 1.0, 1.5] {
+            assert_error!(
+                stats.confidence_intervals(level),
+                ArgminError,
+                "Invalid parameter: \"`FitStatistics`: confidence level must be in (0, 1).\""
+            );
+        }
+    }
+}
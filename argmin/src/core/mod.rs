@@ -18,6 +18,8 @@ pub mod checkpointing;
 mod errors;
 /// Executor
 mod executor;
+/// Statistics of least squares fits
+mod fitstatistics;
 /// Trait alias for float types
 mod float;
 /// Key value datastructure
@@ -46,10 +48,15 @@ pub use crate::solver::trustregion::TrustRegionRadius;
 pub use anyhow::Error;
 pub use errors::ArgminError;
 pub use executor::Executor;
+pub use fitstatistics::FitStatistics;
 pub use float::ArgminFloat;
 pub use kv::KV;
 pub use parallelization::{SendAlias, SyncAlias};
-pub use problem::{CostFunction, Gradient, Hessian, Jacobian, LinearProgram, Operator, Problem};
+pub use problem::{
+    AdjointOperator, CostFunction, Gradient, Hessian, HessianVectorProduct, Jacobian,
+    LinearProgram, MixedIntegerLinearProgram, Operator, PartialDerivative, Problem,
+    QuadraticProgram, RowAccess,
+};
 pub use result::OptimizationResult;
 pub use serialization::{DeserializeOwnedAlias, SerializeAlias};
 pub use solver::Solver;
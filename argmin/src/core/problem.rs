@@ -13,8 +13,8 @@ use std::collections::HashMap;
 /// Wrapper around problems defined by users.
 ///
 /// Keeps track of how many times methods such as `apply`, `cost`, `gradient`, `jacobian`,
-/// `hessian`, `anneal` and so on are called. It is used to pass the problem from one iteration of
-/// a solver to the next.
+/// `hessian`, `hessian_vector_product`, `anneal` and so on are called. It is used to pass the
+/// problem from one iteration of a solver to the next.
 #[derive(Clone, Debug, Default)]
 pub struct Problem<O> {
     /// Problem defined by user
@@ -349,6 +349,94 @@ pub trait Operator {
     bulk!(apply, Self::Param, Self::Output);
 }
 
+/// Defines the application of the adjoint of an [`Operator`].
+///
+/// For a linear operator `K`, `apply_adjoint` computes `Kᵀy`, mapping from the output space of
+/// the operator back to its parameter space.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{AdjointOperator, Error, Operator};
+///
+/// struct Model {
+///     matrix: Vec<Vec<f64>>,
+/// }
+///
+/// impl Operator for Model {
+///     type Param = Vec<f64>;
+///     type Output = Vec<f64>;
+///
+///     fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(self
+///             .matrix
+///             .iter()
+///             .map(|row| row.iter().zip(param.iter()).map(|(a, x)| a * x).sum())
+///             .collect())
+///     }
+/// }
+///
+/// impl AdjointOperator for Model {
+///     /// Multiply the transpose of `self.matrix` with `param`
+///     fn apply_adjoint(&self, param: &Self::Output) -> Result<Self::Param, Error> {
+///         let mut out = vec![0.0; self.matrix[0].len()];
+///         for (row, y) in self.matrix.iter().zip(param.iter()) {
+///             for (o, a) in out.iter_mut().zip(row.iter()) {
+///                 *o += a * y;
+///             }
+///         }
+///         Ok(out)
+///     }
+/// }
+/// ```
+pub trait AdjointOperator: Operator {
+    /// Applies the adjoint of the operator to an element of its output space
+    fn apply_adjoint(&self, param: &Self::Output) -> Result<Self::Param, Error>;
+}
+
+/// Gives access to individual rows of a linear system `Ax = b`
+///
+/// Row-action methods such as the Kaczmarz method only ever need a single row `a_i` of `A` and
+/// the corresponding entry `b_i` of the right hand side at a time. This allows solving systems
+/// for which `A` is too large to be formed explicitly, for instance in tomography, where rows can
+/// be computed on the fly. The row `a_i` is returned as the same type as the parameter vector.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{RowAccess, Error};
+///
+/// struct System {
+///     matrix: Vec<Vec<f64>>,
+///     rhs: Vec<f64>,
+/// }
+///
+/// impl RowAccess for System {
+///     type Param = Vec<f64>;
+///     type Float = f64;
+///
+///     fn num_rows(&self) -> usize {
+///         self.rhs.len()
+///     }
+///
+///     fn row(&self, index: usize) -> Result<(Self::Param, Self::Float), Error> {
+///         Ok((self.matrix[index].clone(), self.rhs[index]))
+///     }
+/// }
+/// ```
+pub trait RowAccess {
+    /// Type of the parameter vector
+    type Param;
+    /// Precision of floats
+    type Float: ArgminFloat;
+
+    /// Number of rows of the linear system
+    fn num_rows(&self) -> usize;
+
+    /// Row `a_i` of the system matrix and the corresponding entry `b_i` of the right hand side
+    fn row(&self, index: usize) -> Result<(Self::Param, Self::Float), Error>;
+}
+
 /// Defines computation of a cost function value
 ///
 /// # Example
@@ -413,6 +501,38 @@ pub trait Gradient {
     bulk!(gradient, Self::Param, Self::Gradient);
 }
 
+/// Defines the computation of individual partial derivatives
+///
+/// Coordinate descent methods only need the partial derivative `∂f/∂x_i` with respect to a single
+/// coordinate at a time, which for many problems (for instance regression problems with a
+/// residual vector that can be updated cheaply) is much cheaper than computing the full gradient.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{PartialDerivative, Error};
+///
+/// struct Paraboloid {}
+///
+/// impl PartialDerivative for Paraboloid {
+///     type Param = Vec<f64>;
+///     type Float = f64;
+///
+///     fn partial_derivative(&self, param: &Self::Param, index: usize) -> Result<f64, Error> {
+///         Ok(2.0 * (index as f64 + 1.0) * param[index])
+///     }
+/// }
+/// ```
+pub trait PartialDerivative {
+    /// Type of the parameter vector
+    type Param;
+    /// Precision of floats
+    type Float: ArgminFloat;
+
+    /// Compute the partial derivative with respect to the coordinate `index`
+    fn partial_derivative(&self, param: &Self::Param, index: usize) -> Result<Self::Float, Error>;
+}
+
 /// Defines the computation of the Hessian.
 ///
 /// # Example
@@ -444,6 +564,48 @@ pub trait Hessian {
     bulk!(hessian, Self::Param, Self::Hessian);
 }
 
+/// Defines the computation of Hessian-vector products.
+///
+/// This allows Newton-type methods which only need the action of the Hessian on a vector to be
+/// used without ever building the full Hessian matrix.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{HessianVectorProduct, Error};
+/// use argmin_testfunctions::rosenbrock_2d_hessian;
+///
+/// struct Rosenbrock {}
+///
+/// impl HessianVectorProduct for Rosenbrock {
+///     type Param = Vec<f64>;
+///
+///     /// Compute the product of the Hessian of the rosenbrock function and `vec`
+///     fn hessian_vector_product(
+///         &self,
+///         param: &Self::Param,
+///         vec: &Self::Param,
+///     ) -> Result<Self::Param, Error> {
+///         let h = rosenbrock_2d_hessian(param, 1.0, 100.0);
+///         Ok(vec![
+///             h[0] * vec[0] + h[1] * vec[1],
+///             h[2] * vec[0] + h[3] * vec[1],
+///         ])
+///     }
+/// }
+/// ```
+pub trait HessianVectorProduct {
+    /// Type of the parameter vector
+    type Param;
+
+    /// Compute the product of the Hessian at `param` with `vec`
+    fn hessian_vector_product(
+        &self,
+        param: &Self::Param,
+        vec: &Self::Param,
+    ) -> Result<Self::Param, Error>;
+}
+
 /// Defines the computation of the Jacobian.
 ///
 /// # Example
@@ -480,6 +642,8 @@ pub trait Jacobian {
 
 /// Defines a linear Program
 ///
+/// `min_x cᵀx` subject to `Ax <= b` and `x >= 0`.
+///
 /// # Example
 ///
 /// ```
@@ -537,6 +701,131 @@ pub trait LinearProgram {
     }
 }
 
+/// Defines a mixed integer linear program by marking variables of a [`LinearProgram`] which are
+/// required to take integer values.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{LinearProgram, MixedIntegerLinearProgram, Error};
+///
+/// struct Problem {}
+///
+/// impl LinearProgram for Problem {
+///     type Param = Vec<f64>;
+///     type Float = f64;
+///
+///     fn c(&self) -> Result<Vec<Self::Float>, Error> {
+///         Ok(vec![-1.0, -2.0])
+///     }
+///
+///     fn b(&self) -> Result<Vec<Self::Float>, Error> {
+///         Ok(vec![3.5, 4.0])
+///     }
+///
+///     fn A(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
+///         Ok(vec![vec![1.0, 1.0], vec![0.0, 1.0]])
+///     }
+/// }
+///
+/// impl MixedIntegerLinearProgram for Problem {
+///     fn integer_variables(&self) -> Result<Vec<usize>, Error> {
+///         Ok(vec![0])
+///     }
+/// }
+/// ```
+pub trait MixedIntegerLinearProgram: LinearProgram {
+    /// Indices of the variables which are required to be integer
+    fn integer_variables(&self) -> Result<Vec<usize>, Error> {
+        Err(argmin_error!(
+            NotImplemented,
+            "Method `integer_variables` of MixedIntegerLinearProgram trait not implemented!"
+        ))
+    }
+}
+
+/// Defines a convex quadratic program
+///
+/// `min_x 1/2 xᵀHx + cᵀx` subject to `A_eq x = b_eq` and `A_ineq x <= b_ineq`.
+///
+/// Matrices are given as vectors of rows. `H` and `c` need to be implemented, the constraints
+/// default to none.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{QuadraticProgram, Error};
+///
+/// struct Problem {}
+///
+/// impl QuadraticProgram for Problem {
+///     type Param = Vec<f64>;
+///     type Float = f64;
+///
+///     fn H(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
+///         Ok(vec![vec![2.0, 0.0], vec![0.0, 2.0]])
+///     }
+///
+///     fn c(&self) -> Result<Vec<Self::Float>, Error> {
+///         Ok(vec![-2.0, -5.0])
+///     }
+///
+///     fn A_ineq(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
+///         Ok(vec![vec![1.0, 2.0]])
+///     }
+///
+///     fn b_ineq(&self) -> Result<Vec<Self::Float>, Error> {
+///         Ok(vec![6.0])
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub trait QuadraticProgram {
+    /// Type of the parameter vector
+    type Param;
+    /// Precision of floats
+    type Float: ArgminFloat;
+
+    /// Symmetric matrix `H` of the quadratic term
+    fn H(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
+        Err(argmin_error!(
+            NotImplemented,
+            "Method `H` of QuadraticProgram trait not implemented!"
+        ))
+    }
+
+    /// Vector `c` of the linear term
+    fn c(&self) -> Result<Vec<Self::Float>, Error> {
+        Err(argmin_error!(
+            NotImplemented,
+            "Method `c` of QuadraticProgram trait not implemented!"
+        ))
+    }
+
+    /// Matrix `A_eq` of the equality constraints `A_eq x = b_eq`. Defaults to no constraints.
+    fn A_eq(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
+        Ok(vec![])
+    }
+
+    /// Right hand side `b_eq` of the equality constraints `A_eq x = b_eq`. Defaults to no
+    /// constraints.
+    fn b_eq(&self) -> Result<Vec<Self::Float>, Error> {
+        Ok(vec![])
+    }
+
+    /// Matrix `A_ineq` of the inequality constraints `A_ineq x <= b_ineq`. Defaults to no
+    /// constraints.
+    fn A_ineq(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
+        Ok(vec![])
+    }
+
+    /// Right hand side `b_ineq` of the inequality constraints `A_ineq x <= b_ineq`. Defaults to
+    /// no constraints.
+    fn b_ineq(&self) -> Result<Vec<Self::Float>, Error> {
+        Ok(vec![])
+    }
+}
+
 /// Wraps a call to `apply` defined in the `Operator` trait and as such allows to call `apply` on
 /// an instance of `Problem`. Internally, the number of evaluations of `apply` is counted.
 impl<O: Operator> Problem<O> {
@@ -617,6 +906,120 @@ impl<O: Operator> Problem<O> {
     }
 }
 
+/// Wraps a call to `apply_adjoint` defined in the `AdjointOperator` trait and as such allows to call
+/// `apply_adjoint` on an instance of `Problem`. Internally, the number of evaluations of
+/// `apply_adjoint` is counted.
+impl<O: AdjointOperator> Problem<O> {
+    /// Calls `apply_adjoint` defined in the `AdjointOperator` trait and keeps track of the number
+    /// of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{AdjointOperator, Problem, Operator, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Operator for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = Vec<f64>;
+    /// #
+    /// #     fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(vec![1.0f64, 1.0f64])
+    /// #     }
+    /// # }
+    /// #
+    /// # impl AdjointOperator for UserDefinedProblem {
+    /// #     fn apply_adjoint(&self, param: &Self::Output) -> Result<Self::Param, Error> {
+    /// #         Ok(vec![2.0f64, 2.0f64])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `AdjointOperator`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.apply_adjoint(&param);
+    ///
+    /// assert_eq!(problem1.counts["adjoint_operator_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![2.0f64, 2.0f64]);
+    /// ```
+    pub fn apply_adjoint(&mut self, param: &O::Output) -> Result<O::Param, Error> {
+        self.problem("adjoint_operator_count", |problem| {
+            problem.apply_adjoint(param)
+        })
+    }
+}
+
+/// Wraps calls to `num_rows` and `row` defined in the `RowAccess` trait and as such allows to
+/// call them on an instance of `Problem`. Internally, the number of evaluations of `row` is
+/// counted.
+impl<O: RowAccess> Problem<O> {
+    /// Calls `num_rows` defined in the `RowAccess` trait. This call is not counted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, RowAccess, Error};
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl RowAccess for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn num_rows(&self) -> usize {
+    /// #         3
+    /// #     }
+    /// #
+    /// #     fn row(&self, index: usize) -> Result<(Self::Param, Self::Float), Error> {
+    /// #         Ok((vec![1.0f64, 1.0f64], index as f64))
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `RowAccess`.
+    /// let problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// assert_eq!(problem1.num_rows(), 3);
+    /// ```
+    pub fn num_rows(&self) -> usize {
+        self.problem.as_ref().unwrap().num_rows()
+    }
+
+    /// Calls `row` defined in the `RowAccess` trait and keeps track of the number of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, RowAccess, Error};
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl RowAccess for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn num_rows(&self) -> usize {
+    /// #         3
+    /// #     }
+    /// #
+    /// #     fn row(&self, index: usize) -> Result<(Self::Param, Self::Float), Error> {
+    /// #         Ok((vec![1.0f64, 1.0f64], index as f64))
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `RowAccess`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let res = problem1.row(2);
+    ///
+    /// assert_eq!(problem1.counts["row_count"], 1);
+    /// # assert_eq!(res.unwrap(), (vec![1.0f64, 1.0f64], 2.0f64));
+    /// ```
+    pub fn row(&mut self, index: usize) -> Result<(O::Param, O::Float), Error> {
+        self.problem("row_count", |problem| problem.row(index))
+    }
+}
+
 /// Wraps a call to `cost` defined in the `CostFunction` trait and as such allows to call `cost` on
 /// an instance of `Problem`. Internally, the number of evaluations of `cost` is counted.
 impl<O: CostFunction> Problem<O> {
@@ -779,6 +1182,49 @@ impl<O: Gradient> Problem<O> {
     }
 }
 
+/// Wraps a call to `partial_derivative` defined in the `PartialDerivative` trait and as such
+/// allows to call `partial_derivative` on an instance of `Problem`. Internally, the number of
+/// evaluations of `partial_derivative` is counted.
+impl<O: PartialDerivative> Problem<O> {
+    /// Calls `partial_derivative` defined in the `PartialDerivative` trait and keeps track of the
+    /// number of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, PartialDerivative, Error};
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl PartialDerivative for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn partial_derivative(&self, param: &Self::Param, index: usize) -> Result<f64, Error> {
+    /// #         Ok(2.0 * param[index])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `PartialDerivative`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.partial_derivative(&param, 1);
+    ///
+    /// assert_eq!(problem1.counts["partial_derivative_count"], 1);
+    /// # assert_eq!(res.unwrap().to_ne_bytes(), 2.0f64.to_ne_bytes());
+    /// ```
+    pub fn partial_derivative(
+        &mut self,
+        param: &O::Param,
+        index: usize,
+    ) -> Result<O::Float, Error> {
+        self.problem("partial_derivative_count", |problem| {
+            problem.partial_derivative(param, index)
+        })
+    }
+}
+
 /// Wraps a call to `hessian` defined in the `Hessian` trait and as such allows to call `hessian` on
 /// an instance of `Problem`. Internally, the number of evaluations of `hessian` is counted.
 impl<O: Hessian> Problem<O> {
@@ -859,6 +1305,54 @@ impl<O: Hessian> Problem<O> {
     }
 }
 
+/// Wraps a call to `hessian_vector_product` defined in the `HessianVectorProduct` trait and as such
+/// allows to call `hessian_vector_product` on an instance of `Problem`. Internally, the number of
+/// evaluations of `hessian_vector_product` is counted.
+impl<O: HessianVectorProduct> Problem<O> {
+    /// Calls `hessian_vector_product` defined in the `HessianVectorProduct` trait and keeps track
+    /// of the number of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, HessianVectorProduct, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl HessianVectorProduct for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #
+    /// #     fn hessian_vector_product(
+    /// #         &self,
+    /// #         param: &Self::Param,
+    /// #         vec: &Self::Param,
+    /// #     ) -> Result<Self::Param, Error> {
+    /// #         Ok(vec.clone())
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `HessianVectorProduct`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    /// let vec = vec![1.0f64, 3.0f64];
+    ///
+    /// let res = problem1.hessian_vector_product(&param, &vec);
+    ///
+    /// assert_eq!(problem1.counts["hessian_vector_product_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![1.0f64, 3.0f64]);
+    /// ```
+    pub fn hessian_vector_product(
+        &mut self,
+        param: &O::Param,
+        vec: &O::Param,
+    ) -> Result<O::Param, Error> {
+        self.problem("hessian_vector_product_count", |problem| {
+            problem.hessian_vector_product(param, vec)
+        })
+    }
+}
+
 /// Wraps a call to `jacobian` defined in the `Jacobian` trait and as such allows to call `jacobian`
 /// on an instance of `Problem`. Internally, the number of evaluations of `jacobian` is counted.
 impl<O: Jacobian> Problem<O> {
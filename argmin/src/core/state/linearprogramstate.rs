@@ -266,12 +266,13 @@ where
                 && self.best_cost.is_infinite()
                 && self.cost.is_sign_positive() == self.best_cost.is_sign_positive())
         {
-            let param = (*self.param.as_ref().unwrap()).clone();
-            let cost = self.cost;
-            std::mem::swap(&mut self.prev_best_param, &mut self.best_param);
-            self.best_param = Some(param);
+            // If there is no parameter vector, then also don't set the best param.
+            if let Some(param) = self.param.as_ref().cloned() {
+                std::mem::swap(&mut self.prev_best_param, &mut self.best_param);
+                self.best_param = Some(param);
+            }
             std::mem::swap(&mut self.prev_best_cost, &mut self.best_cost);
-            self.best_cost = cost;
+            self.best_cost = self.cost;
             self.last_best_iter = self.iter;
         }
     }
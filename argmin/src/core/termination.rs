@@ -30,6 +30,8 @@ pub enum TerminationReason {
     LineSearchConditionMet,
     /// Reached target tolerance
     TargetToleranceReached,
+    /// Reached maximum number of function evaluations
+    MaxFuncEvalsReached,
     /// Algorithm aborted
     Aborted,
 }
@@ -50,6 +52,7 @@ impl TerminationReason {
     /// assert!(TerminationReason::BestStallIterExceeded.terminated());
     /// assert!(TerminationReason::LineSearchConditionMet.terminated());
     /// assert!(TerminationReason::TargetToleranceReached.terminated());
+    /// assert!(TerminationReason::MaxFuncEvalsReached.terminated());
     /// assert!(TerminationReason::Aborted.terminated());
     /// assert!(!TerminationReason::NotTerminated.terminated());
     /// ```
@@ -97,6 +100,10 @@ impl TerminationReason {
     ///     "Target tolerance reached"
     /// );
     /// assert_eq!(
+    ///     TerminationReason::MaxFuncEvalsReached.text(),
+    ///     "Maximum number of function evaluations reached"
+    /// );
+    /// assert_eq!(
     ///     TerminationReason::Aborted.text(),
     ///     "Optimization aborted"
     /// );
@@ -116,6 +123,9 @@ impl TerminationReason {
             TerminationReason::BestStallIterExceeded => "Best stall iterations exceeded",
             TerminationReason::LineSearchConditionMet => "Line search condition met",
             TerminationReason::TargetToleranceReached => "Target tolerance reached",
+            TerminationReason::MaxFuncEvalsReached => {
+                "Maximum number of function evaluations reached"
+            }
             TerminationReason::Aborted => "Optimization aborted",
         }
     }
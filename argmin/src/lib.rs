@@ -37,6 +37,7 @@
 //!   - [Backtracking line search](`crate::solver::linesearch::BacktrackingLineSearch`)
 //!   - [More-Thuente line search](`crate::solver::linesearch::MoreThuenteLineSearch`)
 //!   - [Hager-Zhang line search](`crate::solver::linesearch::HagerZhangLineSearch`)
+//!   - [Nonmonotone line search](`crate::solver::linesearch::NonmonotoneLineSearch`)
 //!
 //! - [Trust region method](`crate::solver::trustregion::TrustRegion`)
 //!   - [Cauchy point method](`crate::solver::trustregion::CauchyPoint`)
@@ -45,6 +46,8 @@
 //!   
 //! - [Steepest descent](`crate::solver::gradientdescent::SteepestDescent`)
 //!
+//! - [Spectral projected gradient](`crate::solver::projectedgradient::SpectralProjectedGradient`)
+//!
 //! - [Conjugate gradient methods](`crate::solver::conjugategradient`)
 //!   - [Conjugate gradient method](`crate::solver::conjugategradient::ConjugateGradient`)
 //!   - [Nonlinear conjugate gradient method](`crate::solver::conjugategradient::NonlinearConjugateGradient`)
@@ -59,6 +62,7 @@
 //!   - [DFP](`crate::solver::quasinewton::DFP`)
 //!   - [SR1](`crate::solver::quasinewton::SR1`)
 //!   - [SR1-TrustRegion](`crate::solver::quasinewton::SR1TrustRegion`)
+//!   - [L-SR1-TrustRegion](`crate::solver::quasinewton::LSR1TrustRegion`)
 //!
 //! - [Gauss-Newton methods](`crate::solver::gaussnewton`)
 //!   - [Gauss-Newton method](`crate::solver::gaussnewton::GaussNewton`)
@@ -66,8 +70,34 @@
 //!
 //! - [Golden-section search](`crate::solver::goldensectionsearch::GoldenSectionSearch`)
 //!
+//! - [Coordinate descent](`crate::solver::coordinatedescent`)
+//!   - [Derivative-free coordinate descent](`crate::solver::coordinatedescent::CoordinateDescent`)
+//!   - [Coordinate descent with partial derivatives](`crate::solver::coordinatedescent::PartialDerivativeCoordinateDescent`)
+//!
 //! - [Landweber iteration](`crate::solver::landweber::Landweber`)
 //!
+//! - [Row-action methods for linear systems](`crate::solver::kaczmarz`)
+//!   - [Randomized Kaczmarz method (ART)](`crate::solver::kaczmarz::RandomizedKaczmarz`)
+//!   - [Block Kaczmarz method](`crate::solver::kaczmarz::BlockKaczmarz`)
+//!   - [Simultaneous algebraic reconstruction technique (SART)](`crate::solver::kaczmarz::SART`)
+//!
+//! - [Operator norm and eigenvalue estimation](`crate::solver::operatornorm`)
+//!   - [Power iteration](`crate::solver::operatornorm::PowerIteration`)
+//!   - [Lanczos method](`crate::solver::operatornorm::Lanczos`)
+//!
+//! - [Nonnegative least squares (Lawson-Hanson, FNNLS)](`crate::solver::nnls::NNLS`)
+//!
+//! - [Nonsmooth convex optimization](`crate::solver::nonsmooth`)
+//!   - [Subgradient method](`crate::solver::nonsmooth::SubgradientMethod`)
+//!   - [Proximal bundle method](`crate::solver::nonsmooth::ProximalBundle`)
+//!
+//! - [Primal-dual methods](`crate::solver::primaldual`)
+//!   - [Chambolle-Pock primal-dual hybrid gradient](`crate::solver::primaldual::ChambollePock`)
+//!
+//! - [Riemannian optimization](`crate::solver::riemannian`)
+//!   - [Riemannian steepest descent](`crate::solver::riemannian::RiemannianSteepestDescent`)
+//!   - [Riemannian conjugate gradient](`crate::solver::riemannian::RiemannianConjugateGradient`)
+//!
 //! - [Brent's methods](`crate::solver::brent`)
 //!   - [Brent's minimization method](`crate::solver::brent::BrentOpt`)
 //!   - [Brent's root finding method](`crate::solver::brent::BrentRoot`)
@@ -76,8 +106,25 @@
 //!
 //! - [Simulated Annealing](`crate::solver::simulatedannealing::SimulatedAnnealing`)
 //!
+//! - [Simultaneous perturbation stochastic approximation (SPSA)](`crate::solver::spsa::SPSA`)
+//!
+//! - [Cross-entropy method](`crate::solver::crossentropy::CrossEntropy`)
+//!
+//! - [Separable natural evolution strategy (SNES)](`crate::solver::nes::SeparableNES`)
+//!
 //! - [Particle Swarm Optimization](`crate::solver::particleswarm::ParticleSwarm`)
 //!
+//! - [Bayesian Optimization](`crate::solver::bayesianoptimization::BayesianOptimization`)
+//!
+//! - [Basin-hopping and multi-start](`crate::solver::basinhopping::BasinHopping`)
+//!
+//! - [DIRECT and DIRECT-L](`crate::solver::direct::Direct`)
+//!
+//! - [Quadratic programming](`crate::solver::quadraticprogramming`)
+//!   - [Goldfarb-Idnani dual active set method](`crate::solver::quadraticprogramming::GoldfarbIdnani`)
+//!
+//! - [Branch and bound for mixed integer linear programs](`crate::solver::branchandbound::BranchAndBound`)
+//!
 //! # License
 //!
 //! Licensed under either of
@@ -0,0 +1,605 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Basin-hopping and multi-start
+//!
+//! Global optimization by repeatedly running a local solver from different starting points.
+//!
+//! For details see [`BasinHopping`].
+//!
+//! ## References
+//!
+//! \[0\] David J. Wales and Jonathan P. K. Doye (1997). Global Optimization by Basin-Hopping and
+//! the Lowest Energy Structures of Lennard-Jones Clusters Containing up to 110 Atoms.
+//! J. Phys. Chem. A 1997, 101, 28, 5111–5116. <https://doi.org/10.1021/jp970984n>
+//!
+//! \[1\] Robert H. Leary (2000). Global Optimization on Funneling Landscapes.
+//! Journal of Global Optimization 18, 367–383. <https://doi.org/10.1023/A:1026500301312>
+
+use crate::core::{
+    ArgminFloat, CostFunction, DeserializeOwnedAlias, Error, Executor, IterState,
+    OptimizationResult, Problem, SerializeAlias, Solver, State, TerminationReason, KV,
+};
+use argmin_math::{ArgminAdd, ArgminMinMax, ArgminMul, ArgminRandom, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Strategy used by [`BasinHopping`] to choose the starting point of the next local search
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum RestartStrategy {
+    /// Randomly perturb the best parameter vector found so far (monotonic basin-hopping)
+    #[default]
+    Perturb,
+    /// Sample a new starting point uniformly within the bounds (multi-start)
+    MultiStart,
+}
+
+/// # Basin-hopping and multi-start
+///
+/// Meta-solver which repeatedly runs a local solver (such as [`BFGS`](`crate::solver::quasinewton::BFGS`),
+/// [`LBFGS`](`crate::solver::quasinewton::LBFGS`) or
+/// [`NelderMead`](`crate::solver::neldermead::NelderMead`)) to convergence from different starting
+/// points and keeps track of the best local minimum found.
+///
+/// Each iteration consists of a complete run of the local solver via a nested
+/// [`Executor`](`crate::core::Executor`). The starting point of each local search is chosen
+/// according to the [`RestartStrategy`] (set via
+/// [`with_strategy`](`BasinHopping::with_strategy`)):
+///
+/// * [`RestartStrategy::Perturb`] (default): Each component of the best parameter vector found so
+///   far is perturbed by a uniformly distributed random number in `[-s, s]`, where `s` is the
+///   step size (set via [`with_step_size`](`BasinHopping::with_step_size`)) times the width of
+///   the bounds. The result is clamped to the bounds.
+/// * [`RestartStrategy::MultiStart`]: The starting point is sampled uniformly within the bounds.
+///
+/// The initial parameter vector can be provided via the [`configure`](`crate::core::Executor::configure`)
+/// method of the [`Executor`](`crate::core::Executor`). If it is not provided, it is sampled
+/// uniformly within the bounds. Any initial inverse Hessian or Hessian provided this way is passed
+/// on to every run of the local solver, which is needed for instance for
+/// [`BFGS`](`crate::solver::quasinewton::BFGS`). The number of iterations of each local run can be
+/// limited via [`with_local_max_iters`](`BasinHopping::with_local_max_iters`). Local solvers which
+/// do not use the starting point by default, such as
+/// [`NelderMead`](`crate::solver::neldermead::NelderMead`), have to be configured accordingly
+/// (see [`NelderMead::with_simplex_translation`](`crate::solver::neldermead::NelderMead::with_simplex_translation`)).
+///
+/// The parameter vector in the state corresponds to the local minimum found in the current
+/// iteration, whereas the best parameter vector corresponds to the best local minimum found so
+/// far. The function evaluation counts of all local runs are accumulated in the outer problem.
+///
+/// The algorithm stops after the maximum number of iterations is reached or if no improvement
+/// was found for a given number of consecutive iterations (set via
+/// [`with_stall_best`](`BasinHopping::with_stall_best`)).
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] as well as everything the
+/// local solver requires.
+///
+/// ## References
+///
+/// \[0\] David J. Wales and Jonathan P. K. Doye (1997). Global Optimization by Basin-Hopping and
+/// the Lowest Energy Structures of Lennard-Jones Clusters Containing up to 110 Atoms.
+/// J. Phys. Chem. A 1997, 101, 28, 5111–5116. <https://doi.org/10.1021/jp970984n>
+///
+/// \[1\] Robert H. Leary (2000). Global Optimization on Funneling Landscapes.
+/// Journal of Global Optimization 18, 367–383. <https://doi.org/10.1023/A:1026500301312>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BasinHopping<S, P, F> {
+    /// Local solver
+    solver: S,
+    /// Bounds on parameter space
+    bounds: (P, P),
+    /// Strategy for choosing the starting point of the next local search
+    strategy: RestartStrategy,
+    /// Step size relative to the width of the bounds
+    step_size: F,
+    /// Maximum number of iterations of each local search
+    local_max_iters: u64,
+    /// Number of iterations since the last best solution was found
+    stall_iter_best: u64,
+    /// Stop if `stall_iter_best` exceeds this number
+    stall_iter_best_limit: u64,
+}
+
+impl<S, P, F> BasinHopping<S, P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `BasinHopping`
+    ///
+    /// Takes the local solver and bounds on the search space as inputs. `bounds` is a tuple
+    /// `(lower_bound, upper_bound)`, where `lower_bound` and `upper_bound` are of the same type as
+    /// the parameter vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::basinhopping::BasinHopping;
+    /// # use argmin::solver::neldermead::NelderMead;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// # let simplex = vec![vec![0.0, 0.0], vec![0.1, 0.0], vec![0.0, 0.1]];
+    /// let local_solver: NelderMead<Vec<f64>, f64> =
+    ///     NelderMead::new(simplex).with_simplex_translation(true);
+    /// let solver: BasinHopping<_, _, f64> =
+    ///     BasinHopping::new(local_solver, (lower_bound, upper_bound));
+    /// ```
+    pub fn new(solver: S, bounds: (P, P)) -> Self {
+        BasinHopping {
+            solver,
+            bounds,
+            strategy: RestartStrategy::Perturb,
+            step_size: float!(0.1),
+            local_max_iters: u64::MAX,
+            stall_iter_best: 0,
+            stall_iter_best_limit: u64::MAX,
+        }
+    }
+
+    /// Set the strategy for choosing the starting point of the next local search
+    ///
+    /// Defaults to [`RestartStrategy::Perturb`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::basinhopping::{BasinHopping, RestartStrategy};
+    /// # use argmin::solver::neldermead::NelderMead;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// # let simplex = vec![vec![0.0, 0.0], vec![0.1, 0.0], vec![0.0, 0.1]];
+    /// # let local_solver: NelderMead<Vec<f64>, f64> = NelderMead::new(simplex);
+    /// let solver: BasinHopping<_, _, f64> =
+    ///     BasinHopping::new(local_solver, (lower_bound, upper_bound))
+    ///         .with_strategy(RestartStrategy::MultiStart);
+    /// ```
+    #[must_use]
+    pub fn with_strategy(mut self, strategy: RestartStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Set the step size of the perturbation relative to the width of the bounds
+    ///
+    /// Must be in `(0, 1]` and defaults to `0.1`. Only used with [`RestartStrategy::Perturb`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::basinhopping::BasinHopping;
+    /// # use argmin::solver::neldermead::NelderMead;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// # let simplex = vec![vec![0.0, 0.0], vec![0.1, 0.0], vec![0.0, 0.1]];
+    /// # let local_solver: NelderMead<Vec<f64>, f64> = NelderMead::new(simplex);
+    /// let solver =
+    ///     BasinHopping::new(local_solver, (lower_bound, upper_bound)).with_step_size(0.2)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step_size(mut self, step_size: F) -> Result<Self, Error> {
+        if step_size <= float!(0.0) || step_size > float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BasinHopping`: step size must be in (0, 1]."
+            ));
+        }
+        self.step_size = step_size;
+        Ok(self)
+    }
+
+    /// Set the maximum number of iterations of each run of the local solver
+    ///
+    /// Defaults to `u64::MAX`, which means that the local solver runs until its own stopping
+    /// criteria are met.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::basinhopping::BasinHopping;
+    /// # use argmin::solver::neldermead::NelderMead;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// # let simplex = vec![vec![0.0, 0.0], vec![0.1, 0.0], vec![0.0, 0.1]];
+    /// # let local_solver: NelderMead<Vec<f64>, f64> = NelderMead::new(simplex);
+    /// let solver: BasinHopping<_, _, f64> =
+    ///     BasinHopping::new(local_solver, (lower_bound, upper_bound)).with_local_max_iters(100);
+    /// ```
+    #[must_use]
+    pub fn with_local_max_iters(mut self, iters: u64) -> Self {
+        self.local_max_iters = iters;
+        self
+    }
+
+    /// Set the number of consecutive iterations without improvement of the best cost function
+    /// value after which the algorithm stops
+    ///
+    /// Defaults to `u64::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::basinhopping::BasinHopping;
+    /// # use argmin::solver::neldermead::NelderMead;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// # let simplex = vec![vec![0.0, 0.0], vec![0.1, 0.0], vec![0.0, 0.1]];
+    /// # let local_solver: NelderMead<Vec<f64>, f64> = NelderMead::new(simplex);
+    /// let solver: BasinHopping<_, _, f64> =
+    ///     BasinHopping::new(local_solver, (lower_bound, upper_bound)).with_stall_best(20);
+    /// ```
+    #[must_use]
+    pub fn with_stall_best(mut self, iter: u64) -> Self {
+        self.stall_iter_best_limit = iter;
+        self
+    }
+}
+
+impl<S, P, F> BasinHopping<S, P, F>
+where
+    P: Clone + ArgminAdd<P, P> + ArgminSub<P, P> + ArgminMul<F, P> + ArgminRandom + ArgminMinMax,
+    F: ArgminFloat,
+{
+    /// Sample a parameter vector uniformly within the bounds
+    fn sample(&self) -> P {
+        P::rand_from_range(&self.bounds.0, &self.bounds.1)
+    }
+
+    /// Randomly perturb `param` and clamp the result to the bounds
+    fn perturb(&self, param: &P) -> P {
+        let (lower, upper) = &self.bounds;
+        let step = upper.sub(lower).mul(&self.step_size);
+        let step_neg = step.mul(&float!(-1.0));
+        let new_param = param.add(&P::rand_from_range(&step_neg, &step));
+        P::max(&P::min(&new_param, upper), lower)
+    }
+
+    /// Run the local solver from `start` and return the local minimum and its cost function value
+    /// as well as the number of iterations and the termination reason of the local solver.
+    fn local_search<O, G, J, H>(
+        &self,
+        problem: &mut Problem<O>,
+        start: P,
+        state: &IterState<P, G, J, H, F>,
+    ) -> Result<(P, F, u64, TerminationReason), Error>
+    where
+        O: CostFunction<Param = P, Output = F>,
+        S: Solver<O, IterState<P, G, J, H, F>> + Clone,
+        P: SerializeAlias + DeserializeOwnedAlias,
+        G: Clone + SerializeAlias + DeserializeOwnedAlias,
+        J: Clone + SerializeAlias + DeserializeOwnedAlias,
+        H: Clone + SerializeAlias + DeserializeOwnedAlias,
+    {
+        let hessian = state.get_hessian().cloned();
+        let inv_hessian = state.get_inv_hessian().cloned();
+        let local_max_iters = self.local_max_iters;
+
+        let OptimizationResult {
+            problem: mut local_problem,
+            state: mut local_state,
+            ..
+        } = Executor::new(
+            problem.take_problem().ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`BasinHopping`: Failed to take `problem` for local search"
+            ))?,
+            self.solver.clone(),
+        )
+        .configure(|config| {
+            let config = config.param(start).max_iters(local_max_iters);
+            let config = match hessian {
+                Some(hessian) => config.hessian(hessian),
+                None => config,
+            };
+            match inv_hessian {
+                Some(inv_hessian) => config.inv_hessian(inv_hessian),
+                None => config,
+            }
+        })
+        .ctrlc(false)
+        .run()?;
+
+        // Hand the problem back and accumulate the function evaluation counts of the local run
+        problem.problem = Some(
+            local_problem
+                .take_problem()
+                .ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`BasinHopping`: Failed to take `problem` from local search"
+                ))?,
+        );
+        problem.consume_func_counts(local_problem);
+
+        let local_iters = local_state.get_iter();
+        let local_termination = local_state.get_termination_reason();
+        let cost = local_state.get_best_cost();
+        let param = local_state
+            .take_best_param()
+            .or_else(|| local_state.take_param())
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`BasinHopping`: No parameters returned by local search."
+            ))?;
+
+        // Some solvers (such as Newton's method) do not compute the cost function value.
+        let cost = if cost.is_finite() {
+            cost
+        } else {
+            problem.cost(&param)?
+        };
+
+        Ok((param, cost, local_iters, local_termination))
+    }
+}
+
+impl<O, S, P, G, J, H, F> Solver<O, IterState<P, G, J, H, F>> for BasinHopping<S, P, F>
+where
+    O: CostFunction<Param = P, Output = F>,
+    S: Solver<O, IterState<P, G, J, H, F>> + Clone + SerializeAlias,
+    P: Clone
+        + SerializeAlias
+        + DeserializeOwnedAlias
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminRandom
+        + ArgminMinMax,
+    G: Clone + SerializeAlias + DeserializeOwnedAlias,
+    J: Clone + SerializeAlias + DeserializeOwnedAlias,
+    H: Clone + SerializeAlias + DeserializeOwnedAlias,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Basin-hopping";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, J, H, F>,
+    ) -> Result<(IterState<P, G, J, H, F>, Option<KV>), Error> {
+        let start = state.take_param().unwrap_or_else(|| self.sample());
+
+        let (param, cost, local_iters, local_termination) =
+            self.local_search(problem, start, &state)?;
+
+        Ok((
+            state.param(param).cost(cost),
+            Some(make_kv!(
+                "strategy" => format!("{:?}", self.strategy);
+                "step_size" => self.step_size;
+                "local_max_iters" => self.local_max_iters;
+                "stall_iter_best_limit" => self.stall_iter_best_limit;
+                "local_iters" => local_iters;
+                "local_termination" => local_termination;
+            )),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, J, H, F>,
+    ) -> Result<(IterState<P, G, J, H, F>, Option<KV>), Error> {
+        let start = match self.strategy {
+            RestartStrategy::Perturb => {
+                let best_param = state.get_best_param().ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`BasinHopping`: Best parameter vector in state not set."
+                ))?;
+                self.perturb(best_param)
+            }
+            RestartStrategy::MultiStart => self.sample(),
+        };
+
+        let (param, cost, local_iters, local_termination) =
+            self.local_search(problem, start, &state)?;
+
+        let new_best_found = cost < state.get_best_cost();
+        if new_best_found {
+            self.stall_iter_best = 0;
+        } else {
+            self.stall_iter_best += 1;
+        }
+
+        state = state.param(param).cost(cost);
+
+        Ok((
+            state,
+            Some(make_kv!(
+                "new_best" => new_best_found;
+                "stall_iter_best" => self.stall_iter_best;
+                "local_iters" => local_iters;
+                "local_termination" => local_termination;
+            )),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, G, J, H, F>) -> TerminationReason {
+        if self.stall_iter_best >= self.stall_iter_best_limit {
+            return TerminationReason::BestStallIterExceeded;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Gradient, State};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::neldermead::NelderMead;
+    use crate::solver::quasinewton::LBFGS;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        basin_hopping,
+        BasinHopping<NelderMead<Vec<f64>, f64>, Vec<f64>, f64>
+    );
+
+    #[derive(Clone)]
+    struct Rastrigin {}
+
+    impl CostFunction for Rastrigin {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter()
+                .map(|x| x.powi(2) + 10.0 * (1.0 - (2.0 * std::f64::consts::PI * x).cos()))
+                .sum())
+        }
+    }
+
+    impl Gradient for Rastrigin {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(p.iter()
+                .map(|x| {
+                    2.0 * x + 20.0 * std::f64::consts::PI * (2.0 * std::f64::consts::PI * x).sin()
+                })
+                .collect())
+        }
+    }
+
+    fn bounds() -> (Vec<f64>, Vec<f64>) {
+        (vec![-5.12, -5.12], vec![5.12, 5.12])
+    }
+
+    fn simplex() -> Vec<Vec<f64>> {
+        vec![vec![0.0, 0.0], vec![0.1, 0.0], vec![0.0, 0.1]]
+    }
+
+    #[test]
+    fn test_new() {
+        let solver: BasinHopping<NelderMead<Vec<f64>, f64>, Vec<f64>, f64> =
+            BasinHopping::new(NelderMead::new(simplex()), bounds());
+        let BasinHopping {
+            solver: _,
+            bounds: b,
+            strategy,
+            step_size,
+            local_max_iters,
+            stall_iter_best,
+            stall_iter_best_limit,
+        } = solver;
+        assert_eq!(b, bounds());
+        assert_eq!(strategy, RestartStrategy::Perturb);
+        assert_eq!(step_size.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(local_max_iters, u64::MAX);
+        assert_eq!(stall_iter_best, 0);
+        assert_eq!(stall_iter_best_limit, u64::MAX);
+    }
+
+    #[test]
+    fn test_with_step_size() {
+        for step_size in [f64::EPSILON, 0.5, 1.0] {
+            let solver: BasinHopping<NelderMead<Vec<f64>, f64>, Vec<f64>, f64> =
+                BasinHopping::new(NelderMead::new(simplex()), bounds());
+            let solver = solver.with_step_size(step_size).unwrap();
+            assert_eq!(solver.step_size.to_ne_bytes(), step_size.to_ne_bytes());
+        }
+
+        for step_size in [0.0, -1.0, 1.0 + f64::EPSILON] {
+            let solver: BasinHopping<NelderMead<Vec<f64>, f64>, Vec<f64>, f64> =
+                BasinHopping::new(NelderMead::new(simplex()), bounds());
+            assert_error!(
+                solver.with_step_size(step_size),
+                ArgminError,
+                "Invalid parameter: \"`BasinHopping`: step size must be in (0, 1].\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_perturb_stays_within_bounds() {
+        let solver: BasinHopping<NelderMead<Vec<f64>, f64>, Vec<f64>, f64> =
+            BasinHopping::new(NelderMead::new(simplex()), bounds())
+                .with_step_size(1.0)
+                .unwrap();
+        for _ in 0..100 {
+            let p = solver.perturb(&vec![5.0, -5.0]);
+            assert!(p.iter().all(|x| (-5.12..=5.12).contains(x)));
+        }
+    }
+
+    #[test]
+    fn test_init_accumulates_func_counts() {
+        let nm = NelderMead::new(simplex()).with_simplex_translation(true);
+        let mut solver = BasinHopping::new(nm, bounds());
+        let mut problem = Problem::new(Rastrigin {});
+        let state: IterState<Vec<f64>, (), (), (), f64> = IterState::new().param(vec![2.9, 0.1]);
+        let (state, _) = solver.init(&mut problem, state).unwrap();
+
+        assert!(problem.problem.is_some());
+        assert!(problem.counts["cost_count"] > 0);
+        let param = state.get_param().unwrap();
+        assert_relative_eq!(param[0], 2.98, epsilon = 1e-2);
+        assert_relative_eq!(param[1], 0.0, epsilon = 1e-2);
+        assert_relative_eq!(
+            state.get_cost(),
+            Rastrigin {}.cost(param).unwrap(),
+            epsilon = f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_basin_hopping_lbfgs() {
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let lbfgs = LBFGS::new(linesearch, 7);
+        let solver = BasinHopping::new(lbfgs, bounds()).with_local_max_iters(100);
+
+        let res = Executor::new(Rastrigin {}, solver)
+            .configure(|state| state.param(vec![3.1, -2.9]).max_iters(100))
+            .run()
+            .unwrap();
+
+        let best = res.state.get_best_param().unwrap();
+        assert_relative_eq!(best[0], 0.0, epsilon = 1e-4);
+        assert_relative_eq!(best[1], 0.0, epsilon = 1e-4);
+        assert_relative_eq!(res.state.get_best_cost(), 0.0, epsilon = 1e-6);
+
+        // Counts of the local runs end up in the outer problem and state
+        let counts = res.state.get_func_counts();
+        assert_eq!(counts["cost_count"], res.problem.counts["cost_count"]);
+        assert_eq!(
+            counts["gradient_count"],
+            res.problem.counts["gradient_count"]
+        );
+        assert!(counts["gradient_count"] > 100);
+    }
+
+    #[test]
+    fn test_multi_start_stall() {
+        let nm = NelderMead::new(simplex())
+            .with_sd_tolerance(1e-10)
+            .unwrap()
+            .with_simplex_translation(true);
+        let solver = BasinHopping::new(nm, bounds())
+            .with_strategy(RestartStrategy::MultiStart)
+            .with_local_max_iters(1000)
+            .with_stall_best(3);
+
+        let res = Executor::new(Rastrigin {}, solver)
+            .configure(|state| state.max_iters(1000))
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::BestStallIterExceeded
+        );
+        assert_eq!(res.solver.stall_iter_best, 3);
+        assert!(res.state.get_best_cost() <= res.state.get_cost());
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::ArgminFloat;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Acquisition functions for [`BayesianOptimization`](`super::BayesianOptimization`)
+///
+/// Given the predicted mean `mu` and standard deviation `sigma` of the surrogate model at a
+/// candidate point and the lowest cost function value `f_best` observed so far, the acquisition
+/// functions are defined as follows (the candidate with the highest value is evaluated next):
+///
+/// * `ExpectedImprovement { xi }`: `E[max(f_best - f - xi, 0)]`, where `f ~ N(mu, sigma^2)`
+/// * `UpperConfidenceBound { kappa }`: `-(mu - kappa * sigma)`, the upper confidence bound of
+///   the negated cost function
+///
+/// Larger values of `xi` and `kappa` favor exploration over exploitation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum AcquisitionFunction<F> {
+    /// Expected improvement with exploration parameter `xi`
+    ExpectedImprovement {
+        /// Exploration parameter (>= 0)
+        xi: F,
+    },
+    /// Upper confidence bound with exploration parameter `kappa`
+    UpperConfidenceBound {
+        /// Exploration parameter (>= 0)
+        kappa: F,
+    },
+}
+
+impl<F: ArgminFloat> Default for AcquisitionFunction<F> {
+    fn default() -> Self {
+        AcquisitionFunction::ExpectedImprovement { xi: float!(0.01) }
+    }
+}
+
+impl<F: ArgminFloat> AcquisitionFunction<F> {
+    /// Value of the acquisition function for predicted `mean` and standard deviation `std` and
+    /// the lowest observed cost function value `best`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesianoptimization::AcquisitionFunction;
+    /// let ucb = AcquisitionFunction::UpperConfidenceBound { kappa: 2.0f64 };
+    /// assert_eq!(ucb.value(1.0, 0.5, 0.0), 0.0);
+    /// ```
+    pub fn value(&self, mean: F, std: F, best: F) -> F {
+        match *self {
+            AcquisitionFunction::ExpectedImprovement { xi } => {
+                let improvement = best - mean - xi;
+                if std <= F::epsilon() {
+                    return improvement.max(float!(0.0));
+                }
+                let z = improvement / std;
+                improvement * normal_cdf(z) + std * normal_pdf(z)
+            }
+            AcquisitionFunction::UpperConfidenceBound { kappa } => -(mean - kappa * std),
+        }
+    }
+
+    /// Returns `true` if the exploration parameter is non-negative
+    pub(super) fn is_valid(&self) -> bool {
+        match *self {
+            AcquisitionFunction::ExpectedImprovement { xi } => xi >= float!(0.0),
+            AcquisitionFunction::UpperConfidenceBound { kappa } => kappa >= float!(0.0),
+        }
+    }
+}
+
+/// Probability density function of the standard normal distribution
+fn normal_pdf<F: ArgminFloat>(z: F) -> F {
+    (-z * z / float!(2.0)).exp() / (float!(2.0) * F::PI()).sqrt()
+}
+
+/// Cumulative distribution function of the standard normal distribution
+fn normal_cdf<F: ArgminFloat>(z: F) -> F {
+    float!(0.5) * erfc(-z / F::SQRT_2())
+}
+
+/// Complementary error function with a fractional error below `1.2e-7` (Chebyshev fit from
+/// Numerical Recipes, 2nd ed., section 6.2)
+fn erfc<F: ArgminFloat>(x: F) -> F {
+    let z = x.abs();
+    let t = float!(1.0) / (float!(1.0) + float!(0.5) * z);
+    let coeffs = [
+        -1.26551223,
+        1.00002368,
+        0.37409196,
+        0.09678418,
+        -0.18628806,
+        0.27886807,
+        -1.13520398,
+        1.48851587,
+        -0.82215223,
+        0.17087277,
+    ];
+    let poly = coeffs
+        .iter()
+        .rev()
+        .fold(float!(0.0), |acc, &c| acc * t + float!(c));
+    let ans = t * (-z * z + poly).exp();
+    if x >= float!(0.0) {
+        ans
+    } else {
+        float!(2.0) - ans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_normal_cdf() {
+        assert_relative_eq!(normal_cdf(0.0f64), 0.5, epsilon = 1e-7);
+        assert_relative_eq!(normal_cdf(1.0f64), 0.841344746, epsilon = 1e-7);
+        assert_relative_eq!(normal_cdf(-1.96f64), 0.024997895, epsilon = 1e-7);
+        assert_relative_eq!(normal_cdf(3.0f64), 0.998650102, epsilon = 1e-7);
+    }
+
+    #[test]
+    fn test_expected_improvement() {
+        let ei = AcquisitionFunction::ExpectedImprovement { xi: 0.0f64 };
+        // Without uncertainty, EI is the plain improvement
+        assert_relative_eq!(ei.value(1.0, 0.0, 3.0), 2.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(ei.value(4.0, 0.0, 3.0), 0.0, epsilon = f64::EPSILON);
+        // At mean == best, EI = sigma * phi(0)
+        assert_relative_eq!(
+            ei.value(1.0, 2.0, 1.0),
+            2.0 / (2.0 * std::f64::consts::PI).sqrt(),
+            epsilon = 1e-7
+        );
+        // More uncertainty means more expected improvement
+        assert!(ei.value(1.0, 2.0, 0.0) > ei.value(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_upper_confidence_bound() {
+        let ucb = AcquisitionFunction::UpperConfidenceBound { kappa: 2.0f64 };
+        assert_relative_eq!(ucb.value(1.0, 0.25, 100.0), -0.5, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(AcquisitionFunction::ExpectedImprovement { xi: 0.0f64 }.is_valid());
+        assert!(!AcquisitionFunction::ExpectedImprovement { xi: -1.0f64 }.is_valid());
+        assert!(AcquisitionFunction::UpperConfidenceBound { kappa: 1.0f64 }.is_valid());
+        assert!(!AcquisitionFunction::UpperConfidenceBound { kappa: -1.0f64 }.is_valid());
+    }
+}
@@ -0,0 +1,682 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, OptimizationResult, State,
+};
+use crate::solver::linesearch::MoreThuenteLineSearch;
+use crate::solver::quasinewton::LBFGS;
+use argmin_math::{
+    ArgminAdd, ArgminCholesky, ArgminCholeskySolve, ArgminDot, ArgminMul, ArgminNorm, ArgminSub,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Covariance functions available for the [`GaussianProcess`] surrogate.
+///
+/// All kernels use one lengthscale per dimension (automatic relevance determination). With the
+/// scaled distance `r^2 = sum_d (x_d - x'_d)^2 / l_d^2` and the signal variance `s^2`, the
+/// covariances are:
+///
+/// * `Kernel::RBF`: `s^2 * exp(-r^2 / 2)`
+/// * `Kernel::Matern32`: `s^2 * (1 + sqrt(3) r) * exp(-sqrt(3) r)`
+/// * `Kernel::Matern52`: `s^2 * (1 + sqrt(5) r + 5/3 r^2) * exp(-sqrt(5) r)`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum Kernel {
+    /// Squared exponential (radial basis function) kernel
+    RBF,
+    /// Matérn kernel with smoothness `nu = 3/2`
+    Matern32,
+    /// Matérn kernel with smoothness `nu = 5/2`
+    #[default]
+    Matern52,
+}
+
+impl Kernel {
+    /// Kernel value for unit signal variance as a function of the squared scaled distance `r2`
+    fn eval<F: ArgminFloat>(&self, r2: F) -> F {
+        match self {
+            Kernel::RBF => (-r2 / float!(2.0)).exp(),
+            Kernel::Matern32 => {
+                let u = (float!(3.0) * r2).sqrt();
+                (float!(1.0) + u) * (-u).exp()
+            }
+            Kernel::Matern52 => {
+                let u = (float!(5.0) * r2).sqrt();
+                (float!(1.0) + u + u * u / float!(3.0)) * (-u).exp()
+            }
+        }
+    }
+
+    /// Factor `g(r2)` such that the derivative of the kernel (for unit signal variance) with
+    /// respect to the log of the lengthscale `l_d` is `g(r2) * (x_d - x'_d)^2 / l_d^2`.
+    fn lengthscale_factor<F: ArgminFloat>(&self, r2: F) -> F {
+        match self {
+            Kernel::RBF => (-r2 / float!(2.0)).exp(),
+            Kernel::Matern32 => {
+                let u = (float!(3.0) * r2).sqrt();
+                float!(3.0) * (-u).exp()
+            }
+            Kernel::Matern52 => {
+                let u = (float!(5.0) * r2).sqrt();
+                float!(5.0) / float!(3.0) * (float!(1.0) + u) * (-u).exp()
+            }
+        }
+    }
+}
+
+/// Gaussian process regression model
+///
+/// Used as surrogate of the cost function in
+/// [`BayesianOptimization`](`crate::solver::bayesianoptimization::BayesianOptimization`), but can
+/// also be used on its own.
+///
+/// The observations are standardized internally (zero mean, unit variance) and the model has a
+/// constant zero prior mean in the standardized space. Hyperparameters are the lengthscales (one
+/// per dimension), the signal variance and the noise variance. They can be fitted by maximizing
+/// the log marginal likelihood with [`GaussianProcess::fit_hyperparameters`], which uses
+/// [`LBFGS`] with a [`MoreThuenteLineSearch`] on the logarithms of the hyperparameters.
+///
+/// ## Reference
+///
+/// Carl Edward Rasmussen and Christopher K. I. Williams (2006). Gaussian Processes for Machine
+/// Learning. MIT Press. ISBN 0-262-18253-X.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct GaussianProcess<F> {
+    /// Covariance function
+    kernel: Kernel,
+    /// Lengthscales (one per dimension)
+    lengthscales: Vec<F>,
+    /// Signal variance
+    signal_variance: F,
+    /// Noise variance
+    noise_variance: F,
+    /// Training inputs
+    x: Vec<Vec<F>>,
+    /// Mean of training outputs
+    y_mean: F,
+    /// Standard deviation of training outputs
+    y_std: F,
+    /// Inverse of the covariance matrix of the training inputs
+    k_inv: Vec<Vec<F>>,
+    /// `K^-1 y` of standardized training outputs
+    alpha: Vec<F>,
+}
+
+impl<F> GaussianProcess<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`GaussianProcess`] for inputs of dimension `dim`
+    ///
+    /// The lengthscales default to `0.5`, the signal variance to `1.0` and the noise variance to
+    /// `1e-6`. These can be changed with [`GaussianProcess::with_hyperparameters`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesianoptimization::{GaussianProcess, Kernel};
+    /// let gp: GaussianProcess<f64> = GaussianProcess::new(Kernel::Matern52, 2);
+    /// ```
+    pub fn new(kernel: Kernel, dim: usize) -> Self {
+        GaussianProcess {
+            kernel,
+            lengthscales: vec![float!(0.5); dim],
+            signal_variance: float!(1.0),
+            noise_variance: float!(1e-6),
+            x: vec![],
+            y_mean: float!(0.0),
+            y_std: float!(1.0),
+            k_inv: vec![],
+            alpha: vec![],
+        }
+    }
+
+    /// Set the hyperparameters of the model
+    ///
+    /// All lengthscales as well as the signal variance must be positive, the noise variance must
+    /// be non-negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesianoptimization::{GaussianProcess, Kernel};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gp: GaussianProcess<f64> =
+    ///     GaussianProcess::new(Kernel::RBF, 2).with_hyperparameters(vec![0.1, 0.3], 1.0, 1e-4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_hyperparameters(
+        mut self,
+        lengthscales: Vec<F>,
+        signal_variance: F,
+        noise_variance: F,
+    ) -> Result<Self, Error> {
+        if lengthscales.len() != self.lengthscales.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GaussianProcess`: number of lengthscales must match the input dimension."
+            ));
+        }
+        if lengthscales.iter().any(|&l| l <= float!(0.0)) || signal_variance <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GaussianProcess`: lengthscales and signal variance must be > 0."
+            ));
+        }
+        if noise_variance < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GaussianProcess`: noise variance must be >= 0."
+            ));
+        }
+        self.lengthscales = lengthscales;
+        self.signal_variance = signal_variance;
+        self.noise_variance = noise_variance;
+        Ok(self)
+    }
+
+    /// Returns the lengthscales
+    pub fn lengthscales(&self) -> &[F] {
+        &self.lengthscales
+    }
+
+    /// Returns the signal variance
+    pub fn signal_variance(&self) -> F {
+        self.signal_variance
+    }
+
+    /// Returns the noise variance
+    pub fn noise_variance(&self) -> F {
+        self.noise_variance
+    }
+
+    /// Conditions the model on the observations `y` at the inputs `x`
+    ///
+    /// The current hyperparameters are kept. Fails if there are no observations, if `x` and `y`
+    /// differ in length or if the covariance matrix is not numerically positive definite.
+    pub fn fit(&mut self, x: &[Vec<F>], y: &[F]) -> Result<(), Error>
+    where
+        Vec<Vec<F>>: ArgminCholesky
+            + ArgminCholeskySolve<Vec<F>, Vec<F>>
+            + ArgminCholeskySolve<Vec<Vec<F>>, Vec<Vec<F>>>,
+    {
+        let ys = self.standardize(x, y)?;
+        let theta = self.theta();
+        let (k, _) = jittered_cholesky(covariance(self.kernel, x, &theta))?;
+        self.alpha = k.cholesky_solve(&ys)?;
+        self.k_inv = k.cholesky_solve(&identity(x.len()))?;
+        self.x = x.to_vec();
+        Ok(())
+    }
+
+    /// Fits the hyperparameters to the observations `y` at the inputs `x` by maximizing the log
+    /// marginal likelihood and conditions the model on the observations afterwards.
+    ///
+    /// The optimization is started from the current hyperparameters and runs at most `max_iters`
+    /// iterations of [`LBFGS`], which stops once the norm of the gradient of the negative log
+    /// marginal likelihood drops below `1e-6`. If the optimization fails, the current
+    /// hyperparameters are kept.
+    pub fn fit_hyperparameters(
+        &mut self,
+        x: &[Vec<F>],
+        y: &[F],
+        max_iters: u64,
+    ) -> Result<(), Error>
+    where
+        Vec<F>: ArgminSub<Vec<F>, Vec<F>>
+            + ArgminAdd<Vec<F>, Vec<F>>
+            + ArgminDot<Vec<F>, F>
+            + ArgminMul<F, Vec<F>>
+            + ArgminNorm<F>,
+        Vec<Vec<F>>: ArgminCholesky
+            + ArgminCholeskySolve<Vec<F>, Vec<F>>
+            + ArgminCholeskySolve<Vec<Vec<F>>, Vec<Vec<F>>>,
+        F: ArgminMul<Vec<F>, Vec<F>>,
+    {
+        let ys = self.standardize(x, y)?;
+        let problem = NegLogMarginalLikelihood {
+            kernel: self.kernel,
+            x: x.to_vec(),
+            y: ys,
+        };
+        let theta = self.theta();
+        let init_cost = problem.cost(&theta)?;
+
+        let linesearch = MoreThuenteLineSearch::new();
+        let solver = LBFGS::new(linesearch, 7).with_tolerance_grad(float!(1e-6))?;
+        let res = Executor::new(problem, solver)
+            .configure(|state: IterState<Vec<F>, Vec<F>, (), (), F>| {
+                state.param(theta).max_iters(max_iters)
+            })
+            .ctrlc(false)
+            .timer(false)
+            .run();
+
+        if let Ok(OptimizationResult { state, .. }) = res {
+            if let Some(theta) = state.get_best_param() {
+                if state.get_best_cost().is_finite() && state.get_best_cost() <= init_cost {
+                    self.set_theta(&clamp_theta(theta));
+                }
+            }
+        }
+        self.fit(x, y)
+    }
+
+    /// Predicts mean and standard deviation of the latent function at `x`
+    ///
+    /// Requires the model to be conditioned on observations via [`GaussianProcess::fit`] or
+    /// [`GaussianProcess::fit_hyperparameters`].
+    pub fn predict(&self, x: &[F]) -> Result<(F, F), Error> {
+        if self.x.is_empty() {
+            return Err(argmin_error!(
+                NotInitialized,
+                "`GaussianProcess`: model must be fitted before predicting."
+            ));
+        }
+        let kstar: Vec<F> = self
+            .x
+            .iter()
+            .map(|xi| self.signal_variance * self.kernel.eval(self.scaled_dist2(x, xi)))
+            .collect();
+        let mean = kstar
+            .iter()
+            .zip(self.alpha.iter())
+            .fold(float!(0.0), |acc, (&k, &a)| acc + k * a);
+        // k*ᵀ K^-1 k*
+        let explained = self
+            .k_inv
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(kstar.iter())
+                    .fold(float!(0.0), |acc, (&kij, &kj)| acc + kij * kj)
+            })
+            .zip(kstar.iter())
+            .fold(float!(0.0), |acc, (v, &k)| acc + v * k);
+        let var = self.signal_variance - explained;
+        let std = var.max(float!(0.0)).sqrt();
+        Ok((mean * self.y_std + self.y_mean, std * self.y_std))
+    }
+
+    /// Standardizes `y` and stores mean and standard deviation
+    fn standardize(&mut self, x: &[Vec<F>], y: &[F]) -> Result<Vec<F>, Error> {
+        if x.is_empty() || x.len() != y.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GaussianProcess`: requires the same non-zero number of inputs and outputs."
+            ));
+        }
+        let n = F::from_usize(y.len()).unwrap();
+        let mean = y.iter().fold(float!(0.0), |acc, &yi| acc + yi) / n;
+        let var = y
+            .iter()
+            .fold(float!(0.0), |acc, &yi| acc + (yi - mean).powi(2))
+            / n;
+        let std = if var.sqrt() > F::epsilon() {
+            var.sqrt()
+        } else {
+            float!(1.0)
+        };
+        self.y_mean = mean;
+        self.y_std = std;
+        Ok(y.iter().map(|&yi| (yi - mean) / std).collect())
+    }
+
+    /// Squared distance of `a` and `b` scaled by the lengthscales
+    fn scaled_dist2(&self, a: &[F], b: &[F]) -> F {
+        a.iter()
+            .zip(b.iter())
+            .zip(self.lengthscales.iter())
+            .fold(float!(0.0), |acc, ((&ai, &bi), &l)| {
+                acc + ((ai - bi) / l).powi(2)
+            })
+    }
+
+    /// Hyperparameters in log space: `[ln l_1, ..., ln l_d, ln s^2, ln sigma_n^2]`
+    fn theta(&self) -> Vec<F> {
+        let mut theta: Vec<F> = self.lengthscales.iter().map(|l| l.ln()).collect();
+        theta.push(self.signal_variance.ln());
+        theta.push(self.noise_variance.max(min_noise()).ln());
+        theta
+    }
+
+    /// Sets hyperparameters from log space
+    fn set_theta(&mut self, theta: &[F]) {
+        let d = theta.len() - 2;
+        self.lengthscales = theta[..d].iter().map(|t| t.exp()).collect();
+        self.signal_variance = theta[d].exp();
+        self.noise_variance = theta[d + 1].exp();
+    }
+}
+
+/// Smallest noise variance used during hyperparameter fitting
+fn min_noise<F: ArgminFloat>() -> F {
+    float!(1e-8)
+}
+
+/// Box constraints on the log hyperparameters (inputs are assumed to be scaled to the unit
+/// hypercube and outputs to be standardized)
+fn theta_bounds<F: ArgminFloat>(idx: usize, len: usize) -> (F, F) {
+    if idx < len - 2 {
+        (float!(1e-3).ln(), float!(1e2).ln())
+    } else if idx == len - 2 {
+        (float!(1e-2).ln(), float!(1e2).ln())
+    } else {
+        (min_noise::<F>().ln(), float!(1.0).ln())
+    }
+}
+
+/// Projects log hyperparameters onto their bounds
+fn clamp_theta<F: ArgminFloat>(theta: &[F]) -> Vec<F> {
+    let len = theta.len();
+    theta
+        .iter()
+        .enumerate()
+        .map(|(i, &t)| {
+            let (lo, hi) = theta_bounds::<F>(i, len);
+            t.max(lo).min(hi)
+        })
+        .collect()
+}
+
+/// Covariance matrix of the inputs `x` including noise for log hyperparameters `theta`
+fn covariance<F: ArgminFloat>(kernel: Kernel, x: &[Vec<F>], theta: &[F]) -> Vec<Vec<F>> {
+    let d = theta.len() - 2;
+    let signal_variance = theta[d].exp();
+    let noise_variance = theta[d + 1].exp();
+    let n = x.len();
+    let mut k = vec![vec![float!(0.0); n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let r2 = scaled_dist2(&x[i], &x[j], &theta[..d]);
+            let kij = signal_variance * kernel.eval(r2);
+            k[i][j] = kij;
+            k[j][i] = kij;
+        }
+        k[i][i] = k[i][i] + noise_variance;
+    }
+    k
+}
+
+/// Squared distance of `a` and `b` scaled by the lengthscales given in log space
+fn scaled_dist2<F: ArgminFloat>(a: &[F], b: &[F], log_lengthscales: &[F]) -> F {
+    a.iter()
+        .zip(b.iter())
+        .zip(log_lengthscales.iter())
+        .fold(float!(0.0), |acc, ((&ai, &bi), &ll)| {
+            acc + ((ai - bi) / ll.exp()).powi(2)
+        })
+}
+
+/// Cholesky factor `L` of the symmetric matrix `k = L Lᵀ` together with `k` itself. If `k` is
+/// not numerically positive definite, increasing amounts of jitter are added to its diagonal
+/// (and are included in the returned matrix).
+fn jittered_cholesky<F>(mut k: Vec<Vec<F>>) -> Result<(Vec<Vec<F>>, Vec<Vec<F>>), Error>
+where
+    F: ArgminFloat,
+    Vec<Vec<F>>: ArgminCholesky,
+{
+    let mut jitter = float!(0.0);
+    for _ in 0..6 {
+        if let Ok(l) = k.cholesky() {
+            if l.iter().enumerate().all(|(i, row)| row[i].is_finite()) {
+                return Ok((k, l));
+            }
+        }
+        let new_jitter = if jitter > float!(0.0) {
+            jitter * float!(10.0)
+        } else {
+            float!(1e-10)
+        };
+        for (i, row) in k.iter_mut().enumerate() {
+            row[i] = row[i] + new_jitter - jitter;
+        }
+        jitter = new_jitter;
+    }
+    Err(argmin_error!(
+        ConditionViolated,
+        "`GaussianProcess`: covariance matrix is not positive definite."
+    ))
+}
+
+/// `n x n` identity matrix
+fn identity<F: ArgminFloat>(n: usize) -> Vec<Vec<F>> {
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| if i == j { float!(1.0) } else { float!(0.0) })
+                .collect()
+        })
+        .collect()
+}
+
+/// Negative log marginal likelihood of a Gaussian process as a function of the log
+/// hyperparameters. Used for fitting the hyperparameters with [`LBFGS`].
+#[derive(Clone)]
+struct NegLogMarginalLikelihood<F> {
+    kernel: Kernel,
+    x: Vec<Vec<F>>,
+    y: Vec<F>,
+}
+
+impl<F> NegLogMarginalLikelihood<F>
+where
+    F: ArgminFloat,
+    Vec<Vec<F>>: ArgminCholesky + ArgminCholeskySolve<Vec<F>, Vec<F>>,
+{
+    /// Covariance matrix, its Cholesky factor and `K^-1 y`
+    fn factorize(&self, theta: &[F]) -> Result<(Vec<Vec<F>>, Vec<Vec<F>>, Vec<F>), Error> {
+        let (k, l) = jittered_cholesky(covariance(self.kernel, &self.x, theta))?;
+        let alpha = k.cholesky_solve(&self.y)?;
+        Ok((k, l, alpha))
+    }
+}
+
+impl<F> CostFunction for NegLogMarginalLikelihood<F>
+where
+    F: ArgminFloat,
+    Vec<Vec<F>>: ArgminCholesky + ArgminCholeskySolve<Vec<F>, Vec<F>>,
+{
+    type Param = Vec<F>;
+    type Output = F;
+
+    fn cost(&self, theta: &Self::Param) -> Result<Self::Output, Error> {
+        let theta = clamp_theta(theta);
+        let (_, chol, alpha) = self.factorize(&theta)?;
+        let n = F::from_usize(self.y.len()).unwrap();
+        let data_fit = self
+            .y
+            .iter()
+            .zip(alpha.iter())
+            .fold(float!(0.0), |acc, (&y, &a)| acc + y * a);
+        let log_det = chol
+            .iter()
+            .enumerate()
+            .fold(float!(0.0), |acc, (i, row)| acc + row[i].ln());
+        Ok(float!(0.5) * data_fit + log_det + float!(0.5) * n * (float!(2.0) * F::PI()).ln())
+    }
+}
+
+impl<F> Gradient for NegLogMarginalLikelihood<F>
+where
+    F: ArgminFloat,
+    Vec<Vec<F>>: ArgminCholesky
+        + ArgminCholeskySolve<Vec<F>, Vec<F>>
+        + ArgminCholeskySolve<Vec<Vec<F>>, Vec<Vec<F>>>,
+{
+    type Param = Vec<F>;
+    type Gradient = Vec<F>;
+
+    fn gradient(&self, theta_in: &Self::Param) -> Result<Self::Gradient, Error> {
+        let theta = clamp_theta(theta_in);
+        let (k, _, alpha) = self.factorize(&theta)?;
+        let n = self.x.len();
+        let d = theta.len() - 2;
+        let signal_variance = theta[d].exp();
+        let noise_variance = theta[d + 1].exp();
+
+        // W = K^-1 - alpha alpha^T
+        let mut w: Vec<Vec<F>> = k.cholesky_solve(&identity(n))?;
+        for (wi, &ai) in w.iter_mut().zip(alpha.iter()) {
+            for (wij, &aj) in wi.iter_mut().zip(alpha.iter()) {
+                *wij = *wij - ai * aj;
+            }
+        }
+
+        // dNLML/dtheta = 0.5 * tr(W dK/dtheta)
+        let mut grad = vec![float!(0.0); theta.len()];
+        for (i, (xi, wi)) in self.x.iter().zip(w.iter()).enumerate() {
+            for (j, (xj, &wij)) in self.x.iter().zip(wi.iter()).enumerate() {
+                let r2 = scaled_dist2(xi, xj, &theta[..d]);
+                grad[d] = grad[d] + wij * signal_variance * self.kernel.eval(r2);
+                if i != j {
+                    let g = wij * signal_variance * self.kernel.lengthscale_factor(r2);
+                    for (k, gk) in grad.iter_mut().enumerate().take(d) {
+                        let s = (xi[k] - xj[k]) / theta[k].exp();
+                        *gk = *gk + g * s * s;
+                    }
+                }
+            }
+            grad[d + 1] = grad[d + 1] + wi[i] * noise_variance;
+        }
+
+        // Outside of the bounds the cost is constant, therefore the gradient vanishes there.
+        let len = theta.len();
+        Ok(grad
+            .into_iter()
+            .zip(theta_in.iter())
+            .enumerate()
+            .map(|(i, (g, &t))| {
+                let (lo, hi) = theta_bounds::<F>(i, len);
+                if t < lo || t > hi {
+                    float!(0.0)
+                } else {
+                    float!(0.5) * g
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    fn data() -> (Vec<Vec<f64>>, Vec<f64>) {
+        let x: Vec<Vec<f64>> = (0..8).map(|i| vec![i as f64 / 7.0]).collect();
+        let y = x.iter().map(|x| (6.0 * x[0]).sin()).collect();
+        (x, y)
+    }
+
+    #[test]
+    fn test_with_hyperparameters() {
+        let gp: GaussianProcess<f64> = GaussianProcess::new(Kernel::RBF, 2);
+        let gp = gp.with_hyperparameters(vec![0.1, 0.2], 2.0, 0.0).unwrap();
+        assert_eq!(gp.lengthscales(), &[0.1, 0.2]);
+        assert_eq!(gp.signal_variance().to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(gp.noise_variance().to_ne_bytes(), 0.0f64.to_ne_bytes());
+
+        let res = GaussianProcess::new(Kernel::RBF, 2).with_hyperparameters(vec![0.1], 1.0, 0.0);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`GaussianProcess`: ",
+                "number of lengthscales must match the input dimension.\""
+            )
+        );
+
+        let res = GaussianProcess::new(Kernel::RBF, 1).with_hyperparameters(vec![0.1], 1.0, -1e-3);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`GaussianProcess`: noise variance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_predict_not_fitted() {
+        let gp: GaussianProcess<f64> = GaussianProcess::new(Kernel::RBF, 1);
+        assert_error!(
+            gp.predict(&[0.5]),
+            ArgminError,
+            "Not initialized: \"`GaussianProcess`: model must be fitted before predicting.\""
+        );
+    }
+
+    #[test]
+    fn test_interpolates_data() {
+        let (x, y) = data();
+        for kernel in [Kernel::RBF, Kernel::Matern32, Kernel::Matern52] {
+            let mut gp = GaussianProcess::new(kernel, 1)
+                .with_hyperparameters(vec![0.3], 1.0, 1e-8)
+                .unwrap();
+            gp.fit(&x, &y).unwrap();
+            for (xi, yi) in x.iter().zip(y.iter()) {
+                let (mean, std) = gp.predict(xi).unwrap();
+                assert_relative_eq!(mean, *yi, epsilon = 1e-4);
+                assert!(std < 1e-2);
+            }
+            // far away from the data the uncertainty grows
+            let (_, std) = gp.predict(&[3.0]).unwrap();
+            assert!(std > 0.5);
+        }
+    }
+
+    #[test]
+    fn test_gradient() {
+        let (x, y) = data();
+        for kernel in [Kernel::RBF, Kernel::Matern32, Kernel::Matern52] {
+            let problem = NegLogMarginalLikelihood {
+                kernel,
+                x: x.clone(),
+                y: y.clone(),
+            };
+            let theta = vec![0.2f64.ln(), 0.8f64.ln(), 1e-3f64.ln()];
+            let grad = problem.gradient(&theta).unwrap();
+            for i in 0..theta.len() {
+                let h = 1e-6;
+                let mut tp = theta.clone();
+                tp[i] += h;
+                let mut tm = theta.clone();
+                tm[i] -= h;
+                let fd = (problem.cost(&tp).unwrap() - problem.cost(&tm).unwrap()) / (2.0 * h);
+                assert_relative_eq!(grad[i], fd, epsilon = 1e-4, max_relative = 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fit_hyperparameters() {
+        let (x, y) = data();
+        let mut gp = GaussianProcess::new(Kernel::Matern52, 1);
+        let before = NegLogMarginalLikelihood {
+            kernel: Kernel::Matern52,
+            x: x.clone(),
+            y: gp.standardize(&x, &y).unwrap(),
+        };
+        let nlml_before = before.cost(&gp.theta()).unwrap();
+        eprintln!(
+            "DEBUG {:?} {:?} {:?}",
+            gp.theta(),
+            nlml_before,
+            before.gradient(&gp.theta())
+        );
+        gp.fit_hyperparameters(&x, &y, 100).unwrap();
+        let nlml_after = before.cost(&gp.theta()).unwrap();
+        assert!(nlml_after < nlml_before);
+        let (mean, _) = gp.predict(&[0.5]).unwrap();
+        assert_relative_eq!(mean, 3.0f64.sin(), epsilon = 1e-1);
+    }
+}
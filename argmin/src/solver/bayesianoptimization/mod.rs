@@ -0,0 +1,751 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Bayesian optimization
+//!
+//! Global optimization of expensive black-box cost functions on a box using a Gaussian process
+//! surrogate model.
+//!
+//! See [`BayesianOptimization`] for details.
+//!
+//! ## References
+//!
+//! Jones, D. R., Schonlau, M. and Welch, W. J. (1998): Efficient Global Optimization of Expensive
+//! Black-Box Functions. Journal of Global Optimization 13, 455–492.
+//! <https://doi.org/10.1023/A:1008306431147>
+//!
+//! Shahriari, B. et.al. (2016): Taking the Human Out of the Loop: A Review of Bayesian
+//! Optimization. Proceedings of the IEEE 104(1), 148–175.
+//! <https://doi.org/10.1109/JPROC.2015.2494218>
+
+mod acquisition;
+mod gp;
+
+pub use self::acquisition::AcquisitionFunction;
+pub use self::gp::{GaussianProcess, Kernel};
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, SerializeAlias, Solver, KV,
+};
+use argmin_math::{
+    ArgminAdd, ArgminCholesky, ArgminCholeskySolve, ArgminDot, ArgminMul, ArgminNorm, ArgminSub,
+};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// # Bayesian optimization
+///
+/// Sequential model-based global optimization for expensive cost functions on a box. Every
+/// iteration evaluates the cost function exactly once, therefore the maximum number of
+/// iterations set via [`IterState::max_iters`] is the evaluation budget.
+///
+/// The first iterations evaluate an initial design which is sampled by Latin hypercube sampling
+/// within the bounds (the number of samples defaults to `2 * n + 1` for `n` dimensions and can be
+/// set with [`with_initial_samples`](`BayesianOptimization::with_initial_samples`)). An initial
+/// parameter vector provided via [`configure`](`crate::core::Executor::configure`) of
+/// [`Executor`](`crate::core::Executor`) is evaluated in addition.
+///
+/// Afterwards, a [`GaussianProcess`] is fitted to all observations in each iteration. Its
+/// hyperparameters are obtained by maximizing the marginal likelihood with
+/// [`LBFGS`](`crate::solver::quasinewton::LBFGS`) (see
+/// [`with_max_fit_iters`](`BayesianOptimization::with_max_fit_iters`)). The next parameter vector
+/// to be evaluated is the maximizer of the [`AcquisitionFunction`] over a set of random candidates
+/// (see [`with_acquisition_samples`](`BayesianOptimization::with_acquisition_samples`)), half of
+/// which are sampled uniformly within the bounds and half close to the best parameter vector
+/// found so far.
+///
+/// The entire state of the solver, including the observations, the surrogate model and the random
+/// number generator, is stored in the solver itself. Therefore runs can be resumed from
+/// checkpoints without losing any cost function evaluations.
+///
+/// The optimization problem must use `Vec<F>` as parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## References
+///
+/// Jones, D. R., Schonlau, M. and Welch, W. J. (1998): Efficient Global Optimization of Expensive
+/// Black-Box Functions. Journal of Global Optimization 13, 455–492.
+/// <https://doi.org/10.1023/A:1008306431147>
+///
+/// Shahriari, B. et.al. (2016): Taking the Human Out of the Loop: A Review of Bayesian
+/// Optimization. Proceedings of the IEEE 104(1), 148–175.
+/// <https://doi.org/10.1109/JPROC.2015.2494218>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BayesianOptimization<F, R> {
+    /// Bounds on parameter space
+    bounds: (Vec<F>, Vec<F>),
+    /// Surrogate model
+    gp: GaussianProcess<F>,
+    /// Acquisition function
+    acquisition: AcquisitionFunction<F>,
+    /// Number of samples of the initial design
+    initial_samples: Option<usize>,
+    /// Number of random candidates when maximizing the acquisition function
+    acquisition_samples: usize,
+    /// Maximum number of iterations of hyperparameter fitting
+    max_fit_iters: u64,
+    /// Evaluated parameter vectors (scaled to the unit hypercube)
+    x: Vec<Vec<F>>,
+    /// Observed cost function values
+    y: Vec<F>,
+    /// Parameter vectors of the initial design which are yet to be evaluated (scaled to the unit
+    /// hypercube)
+    pending: VecDeque<Vec<F>>,
+    /// Random number generator
+    rng: R,
+}
+
+impl<F> BayesianOptimization<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`BayesianOptimization`]
+    ///
+    /// Takes the bounds on the search space as input. `bounds` is a tuple
+    /// `(lower_bound, upper_bound)` and each lower bound must be smaller than the corresponding
+    /// upper bound.
+    ///
+    /// Uses the `Xoshiro256PlusPlus` RNG internally. For use of another RNG, consider using
+    /// [`BayesianOptimization::new_with_rng`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesianoptimization::BayesianOptimization;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bo = BayesianOptimization::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(bounds: (Vec<F>, Vec<F>)) -> Result<Self, Error> {
+        BayesianOptimization::new_with_rng(bounds, Xoshiro256PlusPlus::from_entropy())
+    }
+}
+
+impl<F, R> BayesianOptimization<F, R>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`BayesianOptimization`]
+    ///
+    /// Takes the bounds on the search space as input (see [`BayesianOptimization::new`]).
+    /// Requires a RNG which must implement `rand::Rng` (and `serde::Serialize` if the `serde1`
+    /// feature is enabled).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesianoptimization::BayesianOptimization;
+    /// # use argmin::core::Error;
+    /// # use rand::SeedableRng;
+    /// # use rand_xoshiro::Xoshiro256PlusPlus;
+    /// # fn main() -> Result<(), Error> {
+    /// let rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    /// let bo = BayesianOptimization::new_with_rng((vec![-1.0f64, -1.0], vec![1.0, 1.0]), rng)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_rng(bounds: (Vec<F>, Vec<F>), rng: R) -> Result<Self, Error> {
+        if bounds.0.is_empty() || bounds.0.len() != bounds.1.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesianOptimization`: lower and upper bound must be of the same non-zero length."
+            ));
+        }
+        if bounds.0.iter().zip(bounds.1.iter()).any(|(l, u)| l >= u) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesianOptimization`: lower bound must be smaller than upper bound."
+            ));
+        }
+        let dim = bounds.0.len();
+        Ok(BayesianOptimization {
+            bounds,
+            gp: GaussianProcess::new(Kernel::default(), dim),
+            acquisition: AcquisitionFunction::default(),
+            initial_samples: None,
+            acquisition_samples: 1000,
+            max_fit_iters: 50,
+            x: vec![],
+            y: vec![],
+            pending: VecDeque::new(),
+            rng,
+        })
+    }
+
+    /// Set the covariance function of the surrogate model
+    ///
+    /// Defaults to [`Kernel::Matern52`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesianoptimization::{BayesianOptimization, Kernel};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bo = BayesianOptimization::new((vec![-1.0f64], vec![1.0]))?.with_kernel(Kernel::RBF);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_kernel(mut self, kernel: Kernel) -> Self {
+        self.gp = GaussianProcess::new(kernel, self.bounds.0.len());
+        self
+    }
+
+    /// Set the acquisition function
+    ///
+    /// Defaults to [`AcquisitionFunction::ExpectedImprovement`] with `xi = 0.01`. The exploration
+    /// parameter must be non-negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesianoptimization::{BayesianOptimization, AcquisitionFunction};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bo = BayesianOptimization::new((vec![-1.0f64], vec![1.0]))?
+    ///     .with_acquisition(AcquisitionFunction::UpperConfidenceBound { kappa: 2.0 })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_acquisition(mut self, acquisition: AcquisitionFunction<F>) -> Result<Self, Error> {
+        if !acquisition.is_valid() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesianOptimization`: exploration parameter of acquisition function must be >= 0."
+            ));
+        }
+        self.acquisition = acquisition;
+        Ok(self)
+    }
+
+    /// Set the number of samples of the initial design
+    ///
+    /// Must be at least 2. Defaults to `2 * n + 1`, where `n` is the number of dimensions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesianoptimization::BayesianOptimization;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bo = BayesianOptimization::new((vec![-1.0f64], vec![1.0]))?.with_initial_samples(10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_initial_samples(mut self, samples: usize) -> Result<Self, Error> {
+        if samples < 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesianOptimization`: number of initial samples must be >= 2."
+            ));
+        }
+        self.initial_samples = Some(samples);
+        Ok(self)
+    }
+
+    /// Set the number of random candidates used for maximizing the acquisition function
+    ///
+    /// Must be at least 1. Defaults to `1000`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesianoptimization::BayesianOptimization;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bo = BayesianOptimization::new((vec![-1.0f64], vec![1.0]))?
+    ///     .with_acquisition_samples(5000)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_acquisition_samples(mut self, samples: usize) -> Result<Self, Error> {
+        if samples < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesianOptimization`: number of acquisition samples must be >= 1."
+            ));
+        }
+        self.acquisition_samples = samples;
+        Ok(self)
+    }
+
+    /// Set the maximum number of L-BFGS iterations for fitting the hyperparameters of the
+    /// surrogate model in each iteration
+    ///
+    /// Setting this to `0` disables hyperparameter fitting. Defaults to `50`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesianoptimization::BayesianOptimization;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bo = BayesianOptimization::new((vec![-1.0f64], vec![1.0]))?.with_max_fit_iters(100);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_max_fit_iters(mut self, iters: u64) -> Self {
+        self.max_fit_iters = iters;
+        self
+    }
+
+    /// Set the surrogate model
+    ///
+    /// This allows to provide initial hyperparameters. The dimension of the model must match the
+    /// dimension of the bounds. Note that the model operates on parameter vectors which are
+    /// scaled to the unit hypercube.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesianoptimization::{BayesianOptimization, GaussianProcess, Kernel};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gp = GaussianProcess::new(Kernel::RBF, 1).with_hyperparameters(vec![0.2], 1.0, 1e-4)?;
+    /// let bo = BayesianOptimization::new((vec![-1.0f64], vec![1.0]))?.with_surrogate(gp)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_surrogate(mut self, gp: GaussianProcess<F>) -> Result<Self, Error> {
+        if gp.lengthscales().len() != self.bounds.0.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesianOptimization`: dimension of surrogate model must match bounds."
+            ));
+        }
+        self.gp = gp;
+        Ok(self)
+    }
+
+    /// Returns the surrogate model
+    pub fn surrogate(&self) -> &GaussianProcess<F> {
+        &self.gp
+    }
+
+    /// Maps a parameter vector to the unit hypercube
+    fn scale_to_unit(&self, param: &[F]) -> Vec<F> {
+        param
+            .iter()
+            .zip(self.bounds.0.iter().zip(self.bounds.1.iter()))
+            .map(|(&p, (&l, &u))| (p - l) / (u - l))
+            .collect()
+    }
+
+    /// Maps a vector from the unit hypercube to the parameter space
+    fn scale_from_unit(&self, z: &[F]) -> Vec<F> {
+        z.iter()
+            .zip(self.bounds.0.iter().zip(self.bounds.1.iter()))
+            .map(|(&z, (&l, &u))| l + z * (u - l))
+            .collect()
+    }
+}
+
+impl<F, R> BayesianOptimization<F, R>
+where
+    F: ArgminFloat,
+    R: Rng,
+{
+    /// Uniformly distributed random number in `[0, 1)`
+    fn uniform(&mut self) -> F {
+        let u: f64 = self.rng.gen();
+        float!(u)
+    }
+
+    /// Latin hypercube sample of `n` points in the unit hypercube
+    fn latin_hypercube(&mut self, n: usize) -> Vec<Vec<F>> {
+        let dim = self.bounds.0.len();
+        let mut samples = vec![vec![float!(0.0); dim]; n];
+        for d in 0..dim {
+            let mut strata: Vec<usize> = (0..n).collect();
+            strata.shuffle(&mut self.rng);
+            for (sample, stratum) in samples.iter_mut().zip(strata) {
+                let offset = self.uniform();
+                sample[d] = (F::from_usize(stratum).unwrap() + offset) / F::from_usize(n).unwrap();
+            }
+        }
+        samples
+    }
+
+    /// Returns the random candidate with the highest acquisition function value together with
+    /// that value
+    fn maximize_acquisition(&mut self) -> Result<(Vec<F>, F), Error> {
+        let dim = self.bounds.0.len();
+        let best = self
+            .y
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, &y)| (self.x[i].clone(), y))
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`BayesianOptimization`: No observations available."
+            ))?;
+
+        let radius = float!(0.05);
+        let mut best_candidate = (best.0.clone(), F::neg_infinity());
+        for i in 0..self.acquisition_samples {
+            let candidate: Vec<F> = if i % 2 == 0 {
+                (0..dim).map(|_| self.uniform()).collect()
+            } else {
+                best.0
+                    .iter()
+                    .map(|&x| {
+                        let step = (float!(2.0) * self.uniform() - float!(1.0)) * radius;
+                        (x + step).max(float!(0.0)).min(float!(1.0))
+                    })
+                    .collect()
+            };
+            let (mean, std) = self.gp.predict(&candidate)?;
+            let value = self.acquisition.value(mean, std, best.1);
+            if value > best_candidate.1 {
+                best_candidate = (candidate, value);
+            }
+        }
+        Ok(best_candidate)
+    }
+}
+
+impl<O, F, R> Solver<O, IterState<Vec<F>, (), (), (), F>> for BayesianOptimization<F, R>
+where
+    O: CostFunction<Param = Vec<F>, Output = F>,
+    F: ArgminFloat + ArgminMul<Vec<F>, Vec<F>>,
+    Vec<F>: ArgminSub<Vec<F>, Vec<F>>
+        + ArgminAdd<Vec<F>, Vec<F>>
+        + ArgminDot<Vec<F>, F>
+        + ArgminMul<F, Vec<F>>
+        + ArgminNorm<F>,
+    Vec<Vec<F>>: ArgminCholesky
+        + ArgminCholeskySolve<Vec<F>, Vec<F>>
+        + ArgminCholeskySolve<Vec<Vec<F>>, Vec<Vec<F>>>,
+    R: Rng + SerializeAlias,
+{
+    const NAME: &'static str = "Bayesian Optimization";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<Vec<F>, (), (), (), F>,
+    ) -> Result<(IterState<Vec<F>, (), (), (), F>, Option<KV>), Error> {
+        let dim = self.bounds.0.len();
+        let initial_samples = self.initial_samples.unwrap_or(2 * dim + 1);
+        self.pending = self.latin_hypercube(initial_samples).into();
+
+        if let Some(param) = state.take_param() {
+            if param.len() != dim {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`BayesianOptimization`: initial parameter vector must match bounds."
+                ));
+            }
+            let cost = state.get_cost();
+            let cost = if cost.is_infinite() {
+                problem.cost(&param)?
+            } else {
+                cost
+            };
+            self.x.push(self.scale_to_unit(&param));
+            self.y.push(cost);
+            state = state.param(param).cost(cost);
+        }
+
+        Ok((
+            state,
+            Some(make_kv!(
+                "initial_samples" => initial_samples;
+                "acquisition_samples" => self.acquisition_samples;
+            )),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<Vec<F>, (), (), (), F>,
+    ) -> Result<(IterState<Vec<F>, (), (), (), F>, Option<KV>), Error> {
+        let (z, kv) = if let Some(z) = self.pending.pop_front() {
+            (z, make_kv!("observations" => self.y.len();))
+        } else {
+            if self.max_fit_iters > 0 {
+                self.gp
+                    .fit_hyperparameters(&self.x, &self.y, self.max_fit_iters)?;
+            } else {
+                self.gp.fit(&self.x, &self.y)?;
+            }
+            let (z, acquisition) = self.maximize_acquisition()?;
+            (
+                z,
+                make_kv!(
+                    "observations" => self.y.len();
+                    "acquisition" => acquisition;
+                    "signal_variance" => self.gp.signal_variance();
+                    "noise_variance" => self.gp.noise_variance();
+                ),
+            )
+        };
+
+        let param = self.scale_from_unit(&z);
+        let cost = problem.cost(&param)?;
+        self.x.push(z);
+        self.y.push(cost);
+
+        Ok((state.param(param).cost(cost), Some(kv)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor, State};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        bayesianoptimization,
+        BayesianOptimization<f64, Xoshiro256PlusPlus>
+    );
+
+    struct Parabola {}
+
+    impl CostFunction for Parabola {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((p[0] - 0.3).powi(2) + (p[1] + 0.5).powi(2))
+        }
+    }
+
+    fn bounds() -> (Vec<f64>, Vec<f64>) {
+        (vec![-1.0, -1.0], vec![1.0, 1.0])
+    }
+
+    #[test]
+    fn test_new() {
+        let bo = BayesianOptimization::new(bounds()).unwrap();
+        let BayesianOptimization {
+            bounds: b,
+            gp: _,
+            acquisition,
+            initial_samples,
+            acquisition_samples,
+            max_fit_iters,
+            x,
+            y,
+            pending,
+            rng: _,
+        } = bo;
+        assert_eq!(b, bounds());
+        assert_eq!(
+            acquisition,
+            AcquisitionFunction::ExpectedImprovement { xi: 0.01 }
+        );
+        assert!(initial_samples.is_none());
+        assert_eq!(acquisition_samples, 1000);
+        assert_eq!(max_fit_iters, 50);
+        assert!(x.is_empty());
+        assert!(y.is_empty());
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_new_invalid_bounds() {
+        let res = BayesianOptimization::new((vec![-1.0f64], vec![1.0, 1.0]));
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`BayesianOptimization`: ",
+                "lower and upper bound must be of the same non-zero length.\""
+            )
+        );
+
+        let res = BayesianOptimization::new((vec![-1.0f64, 1.0], vec![1.0, 1.0]));
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`BayesianOptimization`: ",
+                "lower bound must be smaller than upper bound.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_with_acquisition() {
+        let res = BayesianOptimization::new(bounds())
+            .unwrap()
+            .with_acquisition(AcquisitionFunction::UpperConfidenceBound { kappa: 1.5 });
+        assert_eq!(
+            res.unwrap().acquisition,
+            AcquisitionFunction::UpperConfidenceBound { kappa: 1.5 }
+        );
+
+        let res = BayesianOptimization::new(bounds())
+            .unwrap()
+            .with_acquisition(AcquisitionFunction::ExpectedImprovement { xi: -0.1 });
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`BayesianOptimization`: ",
+                "exploration parameter of acquisition function must be >= 0.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_with_initial_samples() {
+        let bo = BayesianOptimization::new(bounds())
+            .unwrap()
+            .with_initial_samples(7)
+            .unwrap();
+        assert_eq!(bo.initial_samples, Some(7));
+
+        for samples in [0, 1] {
+            let res = BayesianOptimization::new(bounds())
+                .unwrap()
+                .with_initial_samples(samples);
+            assert_error!(
+                res,
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`BayesianOptimization`: ",
+                    "number of initial samples must be >= 2.\""
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_acquisition_samples() {
+        let bo = BayesianOptimization::new(bounds())
+            .unwrap()
+            .with_acquisition_samples(10)
+            .unwrap();
+        assert_eq!(bo.acquisition_samples, 10);
+
+        let res = BayesianOptimization::new(bounds())
+            .unwrap()
+            .with_acquisition_samples(0);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`BayesianOptimization`: ",
+                "number of acquisition samples must be >= 1.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_with_surrogate() {
+        let gp = GaussianProcess::new(Kernel::RBF, 3);
+        let res = BayesianOptimization::new(bounds())
+            .unwrap()
+            .with_surrogate(gp);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`BayesianOptimization`: ",
+                "dimension of surrogate model must match bounds.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_latin_hypercube() {
+        let mut bo =
+            BayesianOptimization::new_with_rng(bounds(), Xoshiro256PlusPlus::seed_from_u64(1))
+                .unwrap();
+        let samples = bo.latin_hypercube(10);
+        assert_eq!(samples.len(), 10);
+        for d in 0..2 {
+            // every stratum is hit exactly once
+            let mut strata: Vec<usize> = samples.iter().map(|s| (s[d] * 10.0) as usize).collect();
+            strata.sort_unstable();
+            assert_eq!(strata, (0..10).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_init() {
+        let mut bo =
+            BayesianOptimization::new_with_rng(bounds(), Xoshiro256PlusPlus::seed_from_u64(1))
+                .unwrap();
+        let state: IterState<Vec<f64>, (), (), (), f64> = IterState::new().param(vec![0.0, 0.0]);
+        let mut problem = Problem::new(TestProblem::new());
+        let (state, _) = bo.init(&mut problem, state).unwrap();
+        assert_eq!(bo.pending.len(), 5);
+        assert_eq!(bo.x, vec![vec![0.5, 0.5]]);
+        assert_eq!(bo.y, vec![1.0]);
+        assert_eq!(state.get_cost().to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(problem.counts["cost_count"], 1);
+
+        let state: IterState<Vec<f64>, (), (), (), f64> = IterState::new().param(vec![0.0]);
+        let res = bo.init(&mut problem, state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`BayesianOptimization`: ",
+                "initial parameter vector must match bounds.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_next_iter_stays_within_bounds() {
+        let mut bo =
+            BayesianOptimization::new_with_rng(bounds(), Xoshiro256PlusPlus::seed_from_u64(2))
+                .unwrap()
+                .with_acquisition_samples(100)
+                .unwrap();
+        let mut problem = Problem::new(Parabola {});
+        let (mut state, _) = bo.init(&mut problem, IterState::new()).unwrap();
+        for _ in 0..8 {
+            (state, _) = bo.next_iter(&mut problem, state).unwrap();
+            let param = state.get_param().unwrap();
+            assert!(param.iter().all(|&p| (-1.0..=1.0).contains(&p)));
+        }
+        assert_eq!(bo.y.len(), 8);
+        assert_eq!(problem.counts["cost_count"], 8);
+    }
+
+    #[test]
+    fn test_optimize() {
+        for acquisition in [
+            AcquisitionFunction::ExpectedImprovement { xi: 0.01 },
+            AcquisitionFunction::UpperConfidenceBound { kappa: 2.0 },
+        ] {
+            let bo =
+                BayesianOptimization::new_with_rng(bounds(), Xoshiro256PlusPlus::seed_from_u64(3))
+                    .unwrap()
+                    .with_acquisition(acquisition)
+                    .unwrap();
+            let res = Executor::new(Parabola {}, bo)
+                .configure(|state| state.max_iters(30))
+                .run()
+                .unwrap();
+            let best = res.state().get_best_param().unwrap();
+            assert_relative_eq!(best[0], 0.3, epsilon = 5e-2);
+            assert_relative_eq!(best[1], -0.5, epsilon = 5e-2);
+            assert_eq!(res.problem().counts["cost_count"], 30);
+        }
+    }
+}
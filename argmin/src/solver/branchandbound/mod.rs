@@ -0,0 +1,624 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Branch and bound
+//!
+//! Branch and bound method for mixed integer linear programs.
+//!
+//! See [`BranchAndBound`] for details.
+//!
+//! ## Reference
+//!
+//! A. H. Land and A. G. Doig (1960). An automatic method of solving discrete programming
+//! problems. Econometrica 28(3), pp. 497-520.
+
+mod simplex;
+
+use self::simplex::LpStatus;
+use crate::core::{
+    ArgminFloat, Error, LinearProgramState, MixedIntegerLinearProgram, Problem, Solver,
+    TerminationReason, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Order in which the nodes of the branch and bound tree are processed
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum NodeSelection {
+    /// Node with the lowest bound (objective function value of the LP relaxation of its parent)
+    #[default]
+    BestBound,
+    /// Most recently created node
+    DepthFirst,
+}
+
+/// Node of the branch and bound tree: a subproblem with tightened variable bounds
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct Node<F> {
+    /// Lower bounds of the variables
+    lower: Vec<F>,
+    /// Upper bounds of the variables (infinite if unbounded)
+    upper: Vec<F>,
+    /// Lower bound of the objective function value in this subproblem
+    bound: F,
+    /// Depth in the tree
+    depth: usize,
+}
+
+/// # Branch and bound
+///
+/// Solves mixed integer linear programs
+///
+/// `min_x cᵀx` subject to `Ax <= b`, `x >= 0` and `x_i` integer for `i` in a given set of
+/// indices
+///
+/// (see [`MixedIntegerLinearProgram`]).
+///
+/// Each node of the branch and bound tree is a subproblem with tightened bounds on the integer
+/// variables. In each iteration, one node is selected according to the [`NodeSelection`]
+/// strategy and its LP relaxation is solved with a dense two-phase simplex method. If the
+/// relaxation is infeasible or its objective function value is not better than the current best
+/// integer solution (incumbent), the node is pruned. If the solution of the relaxation satisfies
+/// all integrality constraints (up to the tolerance set with
+/// [`with_integrality_tolerance`](`BranchAndBound::with_integrality_tolerance`)), it becomes the
+/// new incumbent. Otherwise, the node is split into two on the most fractional integer variable
+/// `x_j`, one with `x_j <= floor(x_j)` and one with `x_j >= ceil(x_j)`.
+///
+/// The incumbent is stored as parameter vector in the state. The algorithm terminates once the
+/// relative gap
+///
+/// `|incumbent - best_bound| / (1e-10 + |incumbent|)`
+///
+/// between the incumbent and the lowest bound of all open nodes falls below the tolerance set
+/// with [`with_gap_tolerance`](`BranchAndBound::with_gap_tolerance`) (default: `1e-6`). Once all
+/// nodes are processed, the gap is zero and the incumbent is optimal. Number of open nodes,
+/// incumbent, best bound and gap are reported to observers in each iteration.
+///
+/// An error is returned if the problem is infeasible or its LP relaxation is unbounded.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`MixedIntegerLinearProgram`].
+///
+/// ## Reference
+///
+/// A. H. Land and A. G. Doig (1960). An automatic method of solving discrete programming
+/// problems. Econometrica 28(3), pp. 497-520.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BranchAndBound<F> {
+    /// Node selection strategy
+    node_selection: NodeSelection,
+    /// Tolerance on the relative gap
+    gap_tol: F,
+    /// Tolerance on the integrality of integer variables
+    integrality_tol: F,
+    /// Cost vector
+    c: Vec<F>,
+    /// Constraint matrix
+    a: Vec<Vec<F>>,
+    /// Right hand side of the constraints
+    b: Vec<F>,
+    /// Indices of integer variables
+    integer_variables: Vec<usize>,
+    /// Open nodes
+    nodes: Vec<Node<F>>,
+    /// Objective function value of the incumbent
+    incumbent: F,
+    /// Current relative gap
+    gap: F,
+}
+
+impl<F> BranchAndBound<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`BranchAndBound`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::branchandbound::BranchAndBound;
+    /// let solver: BranchAndBound<f64> = BranchAndBound::new();
+    /// ```
+    pub fn new() -> Self {
+        BranchAndBound {
+            node_selection: NodeSelection::BestBound,
+            gap_tol: float!(1e-6),
+            integrality_tol: float!(1e-6),
+            c: vec![],
+            a: vec![],
+            b: vec![],
+            integer_variables: vec![],
+            nodes: vec![],
+            incumbent: F::infinity(),
+            gap: F::infinity(),
+        }
+    }
+
+    /// Set the node selection strategy. Defaults to [`NodeSelection::BestBound`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::branchandbound::{BranchAndBound, NodeSelection};
+    /// let solver: BranchAndBound<f64> =
+    ///     BranchAndBound::new().with_node_selection(NodeSelection::DepthFirst);
+    /// ```
+    #[must_use]
+    pub fn with_node_selection(mut self, node_selection: NodeSelection) -> Self {
+        self.node_selection = node_selection;
+        self
+    }
+
+    /// Set the tolerance on the relative gap between incumbent and best bound.
+    ///
+    /// Must be non-negative and defaults to `1e-6`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::branchandbound::BranchAndBound;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: BranchAndBound<f64> = BranchAndBound::new().with_gap_tolerance(1e-3)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_gap_tolerance(mut self, gap_tol: F) -> Result<Self, Error> {
+        if gap_tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BranchAndBound`: gap tolerance must be >= 0."
+            ));
+        }
+        self.gap_tol = gap_tol;
+        Ok(self)
+    }
+
+    /// Set the tolerance on the integrality of integer variables.
+    ///
+    /// Must be in `[0, 0.5)` and defaults to `1e-6`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::branchandbound::BranchAndBound;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: BranchAndBound<f64> = BranchAndBound::new().with_integrality_tolerance(1e-8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_integrality_tolerance(mut self, integrality_tol: F) -> Result<Self, Error> {
+        if integrality_tol < float!(0.0) || integrality_tol >= float!(0.5) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BranchAndBound`: integrality tolerance must be in [0, 0.5)."
+            ));
+        }
+        self.integrality_tol = integrality_tol;
+        Ok(self)
+    }
+
+    /// Lowest bound of all open nodes (the incumbent if there are none)
+    fn best_bound(&self) -> F {
+        self.nodes
+            .iter()
+            .fold(self.incumbent, |acc, node| acc.min(node.bound))
+    }
+
+    /// Relative gap between incumbent and best bound
+    fn compute_gap(&self) -> F {
+        if self.incumbent.is_infinite() {
+            return F::infinity();
+        }
+        (self.incumbent - self.best_bound()).abs() / (float!(1e-10) + self.incumbent.abs())
+    }
+
+    /// Take the next node according to the node selection strategy
+    fn pop_node(&mut self) -> Option<Node<F>> {
+        match self.node_selection {
+            NodeSelection::DepthFirst => self.nodes.pop(),
+            NodeSelection::BestBound => {
+                let idx = self
+                    .nodes
+                    .iter()
+                    .enumerate()
+                    .fold(None, |best: Option<(usize, F)>, (i, node)| match best {
+                        Some((_, bound)) if bound <= node.bound => best,
+                        _ => Some((i, node.bound)),
+                    })?
+                    .0;
+                Some(self.nodes.remove(idx))
+            }
+        }
+    }
+
+    /// Whether a node with the given bound cannot improve on the incumbent
+    fn prunable(&self, bound: F) -> bool {
+        !self.incumbent.is_infinite()
+            && self.incumbent - bound <= self.gap_tol * (float!(1e-10) + self.incumbent.abs())
+    }
+
+    /// Solve the LP relaxation of a node
+    fn relaxation(&self, node: &Node<F>) -> LpStatus<F> {
+        let n = self.c.len();
+        let mut a = self.a.clone();
+        let mut b = self.b.clone();
+        for j in 0..n {
+            if node.upper[j].is_finite() {
+                let mut row = vec![float!(0.0); n];
+                row[j] = float!(1.0);
+                a.push(row);
+                b.push(node.upper[j]);
+            }
+            if node.lower[j] > float!(0.0) {
+                let mut row = vec![float!(0.0); n];
+                row[j] = float!(-1.0);
+                a.push(row);
+                b.push(-node.lower[j]);
+            }
+        }
+        simplex::solve(&self.c, &a, &b, F::epsilon().sqrt())
+    }
+}
+
+impl<F> Default for BranchAndBound<F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        BranchAndBound::new()
+    }
+}
+
+impl<O, F> Solver<O, LinearProgramState<Vec<F>, F>> for BranchAndBound<F>
+where
+    O: MixedIntegerLinearProgram<Param = Vec<F>, Float = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Branch and bound";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: LinearProgramState<Vec<F>, F>,
+    ) -> Result<(LinearProgramState<Vec<F>, F>, Option<KV>), Error> {
+        let (c, a, b, mut integer_variables) = problem.problem("lp_count", |lp| {
+            Ok((lp.c()?, lp.A()?, lp.b()?, lp.integer_variables()?))
+        })?;
+        let n = c.len();
+        if n == 0 || a.len() != b.len() || a.iter().any(|row| row.len() != n) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BranchAndBound`: dimensions of `c`, `A` and `b` do not match."
+            ));
+        }
+        if integer_variables.iter().any(|&i| i >= n) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BranchAndBound`: invalid index of integer variable."
+            ));
+        }
+        integer_variables.sort_unstable();
+        integer_variables.dedup();
+
+        self.c = c;
+        self.a = a;
+        self.b = b;
+        self.integer_variables = integer_variables;
+        self.nodes = vec![Node {
+            lower: vec![float!(0.0); n],
+            upper: vec![F::infinity(); n],
+            bound: F::neg_infinity(),
+            depth: 0,
+        }];
+        self.incumbent = F::infinity();
+        self.gap = F::infinity();
+
+        Ok((state, None))
+    }
+
+    fn next_iter(
+        &mut self,
+        _problem: &mut Problem<O>,
+        mut state: LinearProgramState<Vec<F>, F>,
+    ) -> Result<(LinearProgramState<Vec<F>, F>, Option<KV>), Error> {
+        let node = self.pop_node().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`BranchAndBound`: No open nodes left."
+        ))?;
+        let depth = node.depth;
+
+        let mut relaxation = F::infinity();
+        if !self.prunable(node.bound) {
+            match self.relaxation(&node) {
+                LpStatus::Unbounded => {
+                    return Err(argmin_error!(
+                        ConditionViolated,
+                        "`BranchAndBound`: LP relaxation is unbounded."
+                    ))
+                }
+                LpStatus::Infeasible => {}
+                LpStatus::Optimal(mut x, objective) => {
+                    relaxation = objective;
+                    if !self.prunable(objective) {
+                        // Most fractional integer variable
+                        let branch = self
+                            .integer_variables
+                            .iter()
+                            .map(|&j| (j, (x[j] - x[j].floor() - float!(0.5)).abs()))
+                            .filter(|(j, _)| (x[*j] - x[*j].round()).abs() > self.integrality_tol)
+                            .fold(None, |best: Option<(usize, F)>, (j, dist)| match best {
+                                Some((_, d)) if d <= dist => best,
+                                _ => Some((j, dist)),
+                            });
+                        match branch {
+                            Some((j, _)) => {
+                                let mut down = node.clone();
+                                down.upper[j] = x[j].floor();
+                                down.bound = objective;
+                                down.depth += 1;
+                                let mut up = node;
+                                up.lower[j] = x[j].ceil();
+                                up.bound = objective;
+                                up.depth += 1;
+                                // With depth first search, the branch closer to the relaxed
+                                // solution is explored first.
+                                if x[j] - x[j].floor() > float!(0.5) {
+                                    self.nodes.push(down);
+                                    self.nodes.push(up);
+                                } else {
+                                    self.nodes.push(up);
+                                    self.nodes.push(down);
+                                }
+                            }
+                            None => {
+                                for &j in self.integer_variables.iter() {
+                                    x[j] = x[j].round();
+                                }
+                                self.incumbent = objective;
+                                state = state.param(x).cost(objective);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.nodes.is_empty() && self.incumbent.is_infinite() {
+            return Err(argmin_error!(
+                ConditionViolated,
+                "`BranchAndBound`: mixed integer linear program is infeasible."
+            ));
+        }
+        self.gap = self.compute_gap();
+
+        Ok((
+            state,
+            Some(make_kv![
+                "depth" => depth;
+                "relaxation" => relaxation;
+                "open_nodes" => self.nodes.len();
+                "incumbent" => self.incumbent;
+                "best_bound" => self.best_bound();
+                "gap" => self.gap;
+            ]),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &LinearProgramState<Vec<F>, F>) -> TerminationReason {
+        if self.gap <= self.gap_tol {
+            return TerminationReason::TargetToleranceReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, LinearProgram, State};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(branch_and_bound, BranchAndBound<f64>);
+
+    #[derive(Clone)]
+    struct Milp {
+        c: Vec<f64>,
+        a: Vec<Vec<f64>>,
+        b: Vec<f64>,
+        integer: Vec<usize>,
+    }
+
+    impl LinearProgram for Milp {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn c(&self) -> Result<Vec<f64>, Error> {
+            Ok(self.c.clone())
+        }
+
+        fn b(&self) -> Result<Vec<f64>, Error> {
+            Ok(self.b.clone())
+        }
+
+        fn A(&self) -> Result<Vec<Vec<f64>>, Error> {
+            Ok(self.a.clone())
+        }
+    }
+
+    impl MixedIntegerLinearProgram for Milp {
+        fn integer_variables(&self) -> Result<Vec<usize>, Error> {
+            Ok(self.integer.clone())
+        }
+    }
+
+    /// 0-1 knapsack: values 10, 13, 7, 8, weights 5, 7, 4, 3, capacity 14.
+    /// Optimal: items 1, 2 and 3 with value 28.
+    fn knapsack() -> Milp {
+        let mut a = vec![vec![5.0, 7.0, 4.0, 3.0]];
+        let mut b = vec![14.0];
+        for j in 0..4 {
+            let mut row = vec![0.0; 4];
+            row[j] = 1.0;
+            a.push(row);
+            b.push(1.0);
+        }
+        Milp {
+            c: vec![-10.0, -13.0, -7.0, -8.0],
+            a,
+            b,
+            integer: vec![0, 1, 2, 3],
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let solver: BranchAndBound<f64> = BranchAndBound::new();
+        assert_eq!(solver.node_selection, NodeSelection::BestBound);
+        assert_eq!(solver.gap_tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(solver.integrality_tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert!(solver.nodes.is_empty());
+    }
+
+    #[test]
+    fn test_knapsack() {
+        for node_selection in [NodeSelection::BestBound, NodeSelection::DepthFirst] {
+            let res = Executor::new(
+                knapsack(),
+                BranchAndBound::new().with_node_selection(node_selection),
+            )
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+
+            let param = res.state.get_best_param().unwrap();
+            for (x, expected) in param.iter().zip([0.0f64, 1.0, 1.0, 1.0].iter()) {
+                assert_eq!(x.to_ne_bytes(), expected.to_ne_bytes());
+            }
+            assert_relative_eq!(res.state.get_best_cost(), -28.0, epsilon = 1e-10);
+            assert!(res.solver.nodes.is_empty());
+            assert_eq!(
+                res.state.get_termination_reason(),
+                TerminationReason::TargetToleranceReached
+            );
+        }
+    }
+
+    #[test]
+    fn test_mixed_integer() {
+        // max x + y s.t. -x + y <= 1, 3x + 2y <= 12, 2x + 3y <= 12 with y integer.
+        // The LP relaxation is solved by (2.4, 2.4), the MILP by (8/3, 2).
+        let milp = Milp {
+            c: vec![-1.0, -1.0],
+            a: vec![vec![-1.0, 1.0], vec![3.0, 2.0], vec![2.0, 3.0]],
+            b: vec![1.0, 12.0, 12.0],
+            integer: vec![1],
+        };
+        let res = Executor::new(milp, BranchAndBound::new())
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 8.0 / 3.0, epsilon = 1e-10);
+        assert_relative_eq!(param[1], 2.0, epsilon = 1e-10);
+        assert_relative_eq!(res.state.get_best_cost(), -14.0 / 3.0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_gap_tolerance() {
+        // With a huge gap tolerance, the first integer solution is accepted
+        let res = Executor::new(
+            knapsack(),
+            BranchAndBound::new()
+                .with_node_selection(NodeSelection::DepthFirst)
+                .with_gap_tolerance(1.0)
+                .unwrap(),
+        )
+        .configure(|state| state.max_iters(100))
+        .run()
+        .unwrap();
+        assert!(res.state.get_best_cost() <= -20.0);
+        assert!(res.solver.gap <= 1.0);
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetToleranceReached
+        );
+    }
+
+    #[test]
+    fn test_infeasible() {
+        // 2x = 1 with x integer
+        let milp = Milp {
+            c: vec![1.0],
+            a: vec![vec![2.0], vec![-2.0]],
+            b: vec![1.0, -1.0],
+            integer: vec![0],
+        };
+        let res = Executor::new(milp, BranchAndBound::new())
+            .configure(|state| state.max_iters(100))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            "Condition violated: \"`BranchAndBound`: mixed integer linear program is infeasible.\""
+        );
+    }
+
+    #[test]
+    fn test_unbounded() {
+        let milp = Milp {
+            c: vec![-1.0, 0.0],
+            a: vec![vec![0.0, 1.0]],
+            b: vec![1.0],
+            integer: vec![0],
+        };
+        let res = Executor::new(milp, BranchAndBound::new())
+            .configure(|state| state.max_iters(100))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            "Condition violated: \"`BranchAndBound`: LP relaxation is unbounded.\""
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_error!(
+            BranchAndBound::new().with_gap_tolerance(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`BranchAndBound`: gap tolerance must be >= 0.\""
+        );
+        assert_error!(
+            BranchAndBound::new().with_integrality_tolerance(0.5),
+            ArgminError,
+            "Invalid parameter: \"`BranchAndBound`: integrality tolerance must be in [0, 0.5).\""
+        );
+
+        let mut milp = knapsack();
+        milp.integer = vec![4];
+        assert_error!(
+            Executor::new(milp, BranchAndBound::new()).run(),
+            ArgminError,
+            "Invalid parameter: \"`BranchAndBound`: invalid index of integer variable.\""
+        );
+
+        let mut milp = knapsack();
+        milp.b.pop();
+        assert_error!(
+            Executor::new(milp, BranchAndBound::new()).run(),
+            ArgminError,
+            "Invalid parameter: \"`BranchAndBound`: dimensions of `c`, `A` and `b` do not match.\""
+        );
+    }
+}
@@ -0,0 +1,232 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Dense two-phase simplex method for the LP relaxations solved in branch and bound.
+
+use crate::core::ArgminFloat;
+
+/// Outcome of solving a linear program
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum LpStatus<F> {
+    /// Optimal solution and objective function value
+    Optimal(Vec<F>, F),
+    /// No feasible point exists
+    Infeasible,
+    /// Objective function is unbounded from below
+    Unbounded,
+}
+
+/// Solves `min_x cᵀx` subject to `Ax <= b` and `x >= 0` with the two-phase simplex method on a
+/// dense tableau. Bland's rule is used to prevent cycling.
+pub(super) fn solve<F: ArgminFloat>(c: &[F], a: &[Vec<F>], b: &[F], tol: F) -> LpStatus<F> {
+    let n = c.len();
+    let m = b.len();
+    let n_art = b.iter().filter(|b| **b < float!(0.0)).count();
+    let width = n + m + n_art;
+
+    // Tableau `[A I (artificials) | b]` with rows of negative `b` negated
+    let mut tableau = Vec::with_capacity(m);
+    let mut basis = Vec::with_capacity(m);
+    let mut art = n + m;
+    for (i, (a_i, b_i)) in a.iter().zip(b.iter()).enumerate() {
+        let mut row = vec![float!(0.0); width + 1];
+        if *b_i >= float!(0.0) {
+            row[..n].copy_from_slice(a_i);
+            row[n + i] = float!(1.0);
+            row[width] = *b_i;
+            basis.push(n + i);
+        } else {
+            for (r, a) in row.iter_mut().zip(a_i.iter()) {
+                *r = -*a;
+            }
+            row[n + i] = float!(-1.0);
+            row[art] = float!(1.0);
+            row[width] = -*b_i;
+            basis.push(art);
+            art += 1;
+        }
+        tableau.push(row);
+    }
+
+    // Phase 1: minimize the sum of the artificial variables
+    if n_art > 0 {
+        let mut cost = vec![float!(0.0); width];
+        for c in cost[(n + m)..].iter_mut() {
+            *c = float!(1.0);
+        }
+        run(&mut tableau, &mut basis, &cost, width, width, tol);
+        let infeasibility = objective(&tableau, &basis, &cost, width);
+        let scale = b.iter().fold(float!(1.0), |acc, b| acc.max(b.abs()));
+        if infeasibility > tol * scale {
+            return LpStatus::Infeasible;
+        }
+        // Drive remaining (zero) artificial variables out of the basis where possible
+        for i in 0..m {
+            if basis[i] >= n + m {
+                if let Some(j) = (0..(n + m)).find(|&j| tableau[i][j].abs() > tol) {
+                    pivot(&mut tableau, i, j);
+                    basis[i] = j;
+                }
+            }
+        }
+    }
+
+    // Phase 2: minimize `cᵀx`, artificial variables may not enter the basis
+    let mut cost = vec![float!(0.0); width];
+    cost[..n].copy_from_slice(c);
+    if !run(&mut tableau, &mut basis, &cost, n + m, width, tol) {
+        return LpStatus::Unbounded;
+    }
+
+    let mut x = vec![float!(0.0); n];
+    for (row, &j) in tableau.iter().zip(basis.iter()) {
+        if j < n {
+            x[j] = row[width];
+        }
+    }
+    let objective = c
+        .iter()
+        .zip(x.iter())
+        .fold(float!(0.0), |acc, (c, x)| acc + *c * *x);
+    LpStatus::Optimal(x, objective)
+}
+
+/// Objective function value of the current basic solution
+fn objective<F: ArgminFloat>(tableau: &[Vec<F>], basis: &[usize], cost: &[F], width: usize) -> F {
+    tableau
+        .iter()
+        .zip(basis.iter())
+        .fold(float!(0.0), |acc, (row, &j)| acc + cost[j] * row[width])
+}
+
+/// Pivot on element `(r, col)`
+fn pivot<F: ArgminFloat>(tableau: &mut [Vec<F>], r: usize, col: usize) {
+    let p = tableau[r][col];
+    for v in tableau[r].iter_mut() {
+        *v = *v / p;
+    }
+    let pivot_row = tableau[r].clone();
+    for (i, row) in tableau.iter_mut().enumerate() {
+        if i == r {
+            continue;
+        }
+        let f = row[col];
+        if f != float!(0.0) {
+            for (v, p) in row.iter_mut().zip(pivot_row.iter()) {
+                *v = *v - f * *p;
+            }
+        }
+    }
+}
+
+/// Simplex iterations with Bland's rule. Only the first `n_enter` columns may enter the basis.
+///
+/// Returns `false` if the objective function is unbounded.
+fn run<F: ArgminFloat>(
+    tableau: &mut [Vec<F>],
+    basis: &mut [usize],
+    cost: &[F],
+    n_enter: usize,
+    width: usize,
+    tol: F,
+) -> bool {
+    loop {
+        // Entering variable: smallest index with negative reduced cost
+        let entering = (0..n_enter).find(|&j| {
+            let reduced = tableau
+                .iter()
+                .zip(basis.iter())
+                .fold(cost[j], |acc, (row, &bi)| acc - cost[bi] * row[j]);
+            reduced < -tol
+        });
+        let j = match entering {
+            Some(j) => j,
+            None => return true,
+        };
+
+        // Leaving variable: minimum ratio, ties broken by smallest basis index
+        let mut leaving: Option<(usize, F)> = None;
+        for (i, row) in tableau.iter().enumerate() {
+            if row[j] > tol {
+                let ratio = row[width] / row[j];
+                leaving = match leaving {
+                    Some((l, best))
+                        if ratio > best + tol
+                            || ((ratio - best).abs() <= tol && basis[l] < basis[i]) =>
+                    {
+                        Some((l, best))
+                    }
+                    _ => Some((i, ratio)),
+                };
+            }
+        }
+        let r = match leaving {
+            Some((r, _)) => r,
+            None => return false,
+        };
+
+        pivot(tableau, r, j);
+        basis[r] = j;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn optimal(status: LpStatus<f64>) -> (Vec<f64>, f64) {
+        match status {
+            LpStatus::Optimal(x, obj) => (x, obj),
+            s => panic!("expected optimal solution, got {:?}", s),
+        }
+    }
+
+    #[test]
+    fn test_lp() {
+        // max 3x + 5y s.t. x <= 4, 2y <= 12, 3x + 2y <= 18
+        let (x, obj) = optimal(solve(
+            &[-3.0, -5.0],
+            &[vec![1.0, 0.0], vec![0.0, 2.0], vec![3.0, 2.0]],
+            &[4.0, 12.0, 18.0],
+            1e-9,
+        ));
+        assert_relative_eq!(x[0], 2.0, epsilon = 1e-10);
+        assert_relative_eq!(x[1], 6.0, epsilon = 1e-10);
+        assert_relative_eq!(obj, -36.0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_lp_phase_one() {
+        // min x + 2y s.t. x + y >= 2, x - y <= 1, x >= 0.5
+        let (x, obj) = optimal(solve(
+            &[1.0, 2.0],
+            &[vec![-1.0, -1.0], vec![1.0, -1.0], vec![-1.0, 0.0]],
+            &[-2.0, 1.0, -0.5],
+            1e-9,
+        ));
+        assert_relative_eq!(x[0], 1.5, epsilon = 1e-10);
+        assert_relative_eq!(x[1], 0.5, epsilon = 1e-10);
+        assert_relative_eq!(obj, 2.5, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_lp_infeasible() {
+        // x <= 1 and x >= 2
+        let status = solve(&[1.0], &[vec![1.0], vec![-1.0]], &[1.0, -2.0], 1e-9);
+        assert_eq!(status, LpStatus::Infeasible);
+    }
+
+    #[test]
+    fn test_lp_unbounded() {
+        // min -x s.t. y <= 1
+        let status = solve(&[-1.0, 0.0], &[vec![0.0, 1.0]], &[1.0], 1e-9);
+        assert_eq!(status, LpStatus::Unbounded);
+        let status = solve(&[-1.0], &[], &[], 1e-9);
+        assert_eq!(status, LpStatus::Unbounded);
+    }
+}
@@ -16,7 +16,15 @@
 //!
 //! \[0\] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
+//!
+//! \[1\] William W. Hager and Hongchao Zhang (2006). A survey of nonlinear conjugate gradient
+//! methods. Pacific Journal of Optimization 2(1), pp. 35-58.
+//!
+//! \[2\] William W. Hager and Hongchao Zhang (2005). A new conjugate gradient method with
+//! guaranteed descent and an efficient line search. SIAM Journal on Optimization 16(1),
+//! pp. 170-192.
 
+use crate::core::Error;
 use crate::core::{ArgminFloat, SerializeAlias};
 use argmin_math::{ArgminDot, ArgminNorm, ArgminSub};
 #[cfg(feature = "serde1")]
@@ -252,13 +260,462 @@ where
     }
 }
 
+/// Dai and Yuan (DY) method
+///
+/// Formula: `||\nabla f_{k+1}||^2 / <(\nabla f_{k+1} - \nabla f_k), p_k>`
+///
+/// See \[1\].
+#[derive(Default, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct DaiYuan {}
+
+impl DaiYuan {
+    /// Construct a new instance of `DaiYuan`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::conjugategradient::beta::DaiYuan;
+    /// let beta_method = DaiYuan::new();
+    /// ```
+    pub fn new() -> Self {
+        DaiYuan {}
+    }
+}
+
+impl<G, P, F> NLCGBetaUpdate<G, P, F> for DaiYuan
+where
+    G: ArgminDot<G, F> + ArgminDot<P, F> + ArgminSub<G, G>,
+    F: ArgminFloat,
+{
+    /// Update beta using the Dai-Yuan method.
+    ///
+    /// Formula: `||\nabla f_{k+1}||^2 / <(\nabla f_{k+1} - \nabla f_k), p_k>`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate approx;
+    /// # use approx::assert_relative_eq;
+    /// # use argmin::solver::conjugategradient::beta::{NLCGBetaUpdate, DaiYuan};
+    /// # let dfk = vec![1f64, 2.0];
+    /// # let dfk1 = vec![3f64, 4.0];
+    /// # let pk = vec![5f64, 6.0];
+    /// let beta_method = DaiYuan::new();
+    /// let beta: f64 = beta_method.update(&dfk, &dfk1, &pk);
+    /// # assert_relative_eq!(beta, 25.0/22.0, epsilon = f64::EPSILON);
+    /// ```
+    fn update(&self, dfk: &G, dfk1: &G, pk: &P) -> F {
+        dfk1.dot(dfk1) / dfk1.sub(dfk).dot(pk)
+    }
+}
+
+/// Liu and Storey (LS) method
+///
+/// Formula: `-<\nabla f_{k+1}, (\nabla f_{k+1} - \nabla f_k)> / <\nabla f_k, p_k>`
+///
+/// See \[1\].
+#[derive(Default, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LiuStorey {}
+
+impl LiuStorey {
+    /// Construct a new instance of `LiuStorey`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::conjugategradient::beta::LiuStorey;
+    /// let beta_method = LiuStorey::new();
+    /// ```
+    pub fn new() -> Self {
+        LiuStorey {}
+    }
+}
+
+impl<G, P, F> NLCGBetaUpdate<G, P, F> for LiuStorey
+where
+    G: ArgminDot<G, F> + ArgminDot<P, F> + ArgminSub<G, G>,
+    F: ArgminFloat,
+{
+    /// Update beta using the Liu-Storey method.
+    ///
+    /// Formula: `-<\nabla f_{k+1}, (\nabla f_{k+1} - \nabla f_k)> / <\nabla f_k, p_k>`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate approx;
+    /// # use approx::assert_relative_eq;
+    /// # use argmin::solver::conjugategradient::beta::{NLCGBetaUpdate, LiuStorey};
+    /// # let dfk = vec![1f64, 2.0];
+    /// # let dfk1 = vec![3f64, 4.0];
+    /// # let pk = vec![5f64, 6.0];
+    /// let beta_method = LiuStorey::new();
+    /// let beta: f64 = beta_method.update(&dfk, &dfk1, &pk);
+    /// # assert_relative_eq!(beta, -14.0/17.0, epsilon = f64::EPSILON);
+    /// ```
+    fn update(&self, dfk: &G, dfk1: &G, pk: &P) -> F {
+        -dfk1.dot(&dfk1.sub(dfk)) / dfk.dot(pk)
+    }
+}
+
+/// Conjugate Descent (CD) method by Fletcher
+///
+/// Formula: `-||\nabla f_{k+1}||^2 / <\nabla f_k, p_k>`
+///
+/// See \[1\].
+#[derive(Default, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ConjugateDescent {}
+
+impl ConjugateDescent {
+    /// Construct a new instance of `ConjugateDescent`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::conjugategradient::beta::ConjugateDescent;
+    /// let beta_method = ConjugateDescent::new();
+    /// ```
+    pub fn new() -> Self {
+        ConjugateDescent {}
+    }
+}
+
+impl<G, P, F> NLCGBetaUpdate<G, P, F> for ConjugateDescent
+where
+    G: ArgminDot<G, F> + ArgminDot<P, F>,
+    F: ArgminFloat,
+{
+    /// Update beta using the Conjugate Descent method.
+    ///
+    /// Formula: `-||\nabla f_{k+1}||^2 / <\nabla f_k, p_k>`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate approx;
+    /// # use approx::assert_relative_eq;
+    /// # use argmin::solver::conjugategradient::beta::{NLCGBetaUpdate, ConjugateDescent};
+    /// # let dfk = vec![1f64, 2.0];
+    /// # let dfk1 = vec![3f64, 4.0];
+    /// # let pk = vec![5f64, 6.0];
+    /// let beta_method = ConjugateDescent::new();
+    /// let beta: f64 = beta_method.update(&dfk, &dfk1, &pk);
+    /// # assert_relative_eq!(beta, -25.0/17.0, epsilon = f64::EPSILON);
+    /// ```
+    fn update(&self, dfk: &G, dfk1: &G, pk: &P) -> F {
+        -dfk1.dot(dfk1) / dfk.dot(pk)
+    }
+}
+
+/// Hager and Zhang (HZ) method as used in CG_DESCENT
+///
+/// With `y_k = \nabla f_{k+1} - \nabla f_k`:
+///
+/// Formula: `max(beta_HZ, eta_k)` where
+/// `beta_HZ = <y_k - 2 p_k ||y_k||^2 / <y_k, p_k>, \nabla f_{k+1}> / <y_k, p_k>`
+/// and `eta_k = -1 / (||p_k|| min(eta, ||\nabla f_k||))`.
+///
+/// The truncation with `eta_k` ensures global convergence for general nonlinear functions. The
+/// parameter `eta` defaults to `0.01` and can be set via [`with_eta`](`HagerZhang::with_eta`).
+///
+/// See \[2\].
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct HagerZhang<F> {
+    /// Truncation parameter
+    eta: F,
+}
+
+impl<F> HagerZhang<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `HagerZhang`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::conjugategradient::beta::HagerZhang;
+    /// let beta_method: HagerZhang<f64> = HagerZhang::new();
+    /// ```
+    pub fn new() -> Self {
+        HagerZhang { eta: float!(0.01) }
+    }
+
+    /// Set the truncation parameter `eta`.
+    ///
+    /// Must be larger than 0 and defaults to `0.01`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::conjugategradient::beta::HagerZhang;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let beta_method: HagerZhang<f64> = HagerZhang::new().with_eta(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_eta(mut self, eta: F) -> Result<Self, Error> {
+        if eta <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`HagerZhang`: eta must be > 0."
+            ));
+        }
+        self.eta = eta;
+        Ok(self)
+    }
+}
+
+impl<F> Default for HagerZhang<F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        HagerZhang::new()
+    }
+}
+
+impl<G, P, F> NLCGBetaUpdate<G, P, F> for HagerZhang<F>
+where
+    G: ArgminDot<G, F> + ArgminDot<P, F> + ArgminSub<G, G> + ArgminNorm<F>,
+    P: ArgminNorm<F>,
+    F: ArgminFloat,
+{
+    /// Update beta using the Hager-Zhang method.
+    ///
+    /// Formula: `max(beta_HZ, eta_k)` (see [`HagerZhang`])
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate approx;
+    /// # use approx::assert_relative_eq;
+    /// # use argmin::solver::conjugategradient::beta::{NLCGBetaUpdate, HagerZhang};
+    /// # let dfk = vec![1f64, 2.0];
+    /// # let dfk1 = vec![3f64, 4.0];
+    /// # let pk = vec![5f64, 6.0];
+    /// let beta_method = HagerZhang::new();
+    /// let beta: f64 = beta_method.update(&dfk, &dfk1, &pk);
+    /// # assert_relative_eq!(beta, 14.0/22.0 - 624.0/484.0, epsilon = f64::EPSILON);
+    /// ```
+    fn update(&self, dfk: &G, dfk1: &G, pk: &P) -> F {
+        let yk = dfk1.sub(dfk);
+        let ykpk: F = yk.dot(pk);
+        let ykyk: F = yk.dot(&yk);
+        let beta = (yk.dot(dfk1) - float!(2.0) * ykyk * dfk1.dot(pk) / ykpk) / ykpk;
+        let eta_k = float!(-1.0) / (pk.norm() * self.eta.min(dfk.norm()));
+        beta.max(eta_k)
+    }
+}
+
+/// Hybrid Hestenes-Stiefel/Dai-Yuan (HS-DY) method
+///
+/// Formula: `max(0, min(beta_HS, beta_DY))`
+///
+/// See [`HestenesStiefel`], [`DaiYuan`] and \[1\].
+#[derive(Default, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct HestenesStiefelDaiYuan {}
+
+impl HestenesStiefelDaiYuan {
+    /// Construct a new instance of `HestenesStiefelDaiYuan`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::conjugategradient::beta::HestenesStiefelDaiYuan;
+    /// let beta_method = HestenesStiefelDaiYuan::new();
+    /// ```
+    pub fn new() -> Self {
+        HestenesStiefelDaiYuan {}
+    }
+}
+
+impl<G, P, F> NLCGBetaUpdate<G, P, F> for HestenesStiefelDaiYuan
+where
+    G: ArgminDot<G, F> + ArgminDot<P, F> + ArgminSub<G, G>,
+    F: ArgminFloat,
+{
+    /// Update beta using the hybrid Hestenes-Stiefel/Dai-Yuan method.
+    ///
+    /// Formula: `max(0, min(beta_HS, beta_DY))`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate approx;
+    /// # use approx::assert_relative_eq;
+    /// # use argmin::solver::conjugategradient::beta::{NLCGBetaUpdate, HestenesStiefelDaiYuan};
+    /// # let dfk = vec![1f64, 2.0];
+    /// # let dfk1 = vec![3f64, 4.0];
+    /// # let pk = vec![5f64, 6.0];
+    /// let beta_method = HestenesStiefelDaiYuan::new();
+    /// let beta: f64 = beta_method.update(&dfk, &dfk1, &pk);
+    /// # assert_relative_eq!(beta, 14.0/22.0, epsilon = f64::EPSILON);
+    /// ```
+    fn update(&self, dfk: &G, dfk1: &G, pk: &P) -> F {
+        let beta_hs: F = HestenesStiefel::new().update(dfk, dfk1, pk);
+        let beta_dy: F = DaiYuan::new().update(dfk, dfk1, pk);
+        float!(0.0).max(beta_hs.min(beta_dy))
+    }
+}
+
+/// Hybrid Fletcher-Reeves/Polak-Ribiere (FR-PR) method
+///
+/// Formula: `max(-beta_FR, min(beta_PR, beta_FR))`
+///
+/// See [`FletcherReeves`], [`PolakRibiere`] and \[0\].
+#[derive(Default, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct FletcherReevesPolakRibiere {}
+
+impl FletcherReevesPolakRibiere {
+    /// Construct a new instance of `FletcherReevesPolakRibiere`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::conjugategradient::beta::FletcherReevesPolakRibiere;
+    /// let beta_method = FletcherReevesPolakRibiere::new();
+    /// ```
+    pub fn new() -> Self {
+        FletcherReevesPolakRibiere {}
+    }
+}
+
+impl<G, P, F> NLCGBetaUpdate<G, P, F> for FletcherReevesPolakRibiere
+where
+    G: ArgminDot<G, F> + ArgminSub<G, G> + ArgminNorm<F>,
+    F: ArgminFloat,
+{
+    /// Update beta using the hybrid Fletcher-Reeves/Polak-Ribiere method.
+    ///
+    /// Formula: `max(-beta_FR, min(beta_PR, beta_FR))`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate approx;
+    /// # use approx::assert_relative_eq;
+    /// # use argmin::solver::conjugategradient::beta::{NLCGBetaUpdate, FletcherReevesPolakRibiere};
+    /// # let dfk = vec![1f64, 2.0];
+    /// # let dfk1 = vec![3f64, 4.0];
+    /// let beta_method = FletcherReevesPolakRibiere::new();
+    /// let beta: f64 = beta_method.update(&dfk, &dfk1, &());
+    /// # assert_relative_eq!(beta, 14.0/5.0, epsilon = f64::EPSILON);
+    /// ```
+    fn update(&self, dfk: &G, dfk1: &G, pk: &P) -> F {
+        let beta_fr: F = FletcherReeves::new().update(dfk, dfk1, pk);
+        let beta_pr: F = PolakRibiere::new().update(dfk, dfk1, pk);
+        (-beta_fr).max(beta_pr.min(beta_fr))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ArgminError;
     use crate::test_trait_impl;
+    use approx::assert_relative_eq;
 
     test_trait_impl!(fletcher_reeves, FletcherReeves);
     test_trait_impl!(polak_ribiere, PolakRibiere);
     test_trait_impl!(polak_ribiere_plus, PolakRibierePlus);
     test_trait_impl!(hestenes_stiefel, HestenesStiefel);
+    test_trait_impl!(dai_yuan, DaiYuan);
+    test_trait_impl!(liu_storey, LiuStorey);
+    test_trait_impl!(conjugate_descent, ConjugateDescent);
+    test_trait_impl!(hager_zhang, HagerZhang<f64>);
+    test_trait_impl!(hestenes_stiefel_dai_yuan, HestenesStiefelDaiYuan);
+    test_trait_impl!(fletcher_reeves_polak_ribiere, FletcherReevesPolakRibiere);
+
+    #[test]
+    fn test_hager_zhang_with_eta() {
+        let beta_method: HagerZhang<f64> = HagerZhang::new();
+        assert_eq!(beta_method.eta.to_ne_bytes(), 0.01f64.to_ne_bytes());
+
+        for eta in [f64::EPSILON, 0.1, 1.0, 10.0] {
+            let res = HagerZhang::new().with_eta(eta);
+            assert_eq!(res.unwrap().eta.to_ne_bytes(), eta.to_ne_bytes());
+        }
+
+        for eta in [0.0, -f64::EPSILON, -1.0] {
+            let res = HagerZhang::new().with_eta(eta);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`HagerZhang`: eta must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_hager_zhang_truncation() {
+        let dfk = vec![1f64, 2.0];
+        let dfk1 = vec![3f64, 4.0];
+        let pk = vec![5f64, 6.0];
+
+        // eta_k = -1 / (||p_k|| * min(10, ||dfk||)) is larger than beta_HZ
+        let beta_method = HagerZhang::new().with_eta(10.0).unwrap();
+        let beta: f64 = beta_method.update(&dfk, &dfk1, &pk);
+        let eta_k = -1.0 / (61.0f64.sqrt() * 5.0f64.sqrt());
+        assert_relative_eq!(beta, eta_k, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_nonlinear_cg_rosenbrock() {
+        use crate::core::{CostFunction, Error, Executor, Gradient, State};
+        use crate::solver::conjugategradient::NonlinearConjugateGradient;
+        use crate::solver::linesearch::MoreThuenteLineSearch;
+        use argmin_testfunctions::{rosenbrock_2d, rosenbrock_2d_derivative};
+
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(rosenbrock_2d(p, 1.0, 100.0))
+            }
+        }
+
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(rosenbrock_2d_derivative(p, 1.0, 100.0))
+            }
+        }
+
+        fn run<B: NLCGBetaUpdate<Vec<f64>, Vec<f64>, f64>>(beta_method: B) -> Vec<f64> {
+            let linesearch = MoreThuenteLineSearch::new().with_c(1e-4, 0.1).unwrap();
+            let solver = NonlinearConjugateGradient::new(linesearch, beta_method)
+                .restart_iters(10)
+                .restart_orthogonality(0.1);
+            let res = Executor::new(Rosenbrock {}, solver)
+                .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(2000))
+                .run()
+                .unwrap();
+            res.state.get_best_param().unwrap().clone()
+        }
+
+        for param in [
+            run(DaiYuan::new()),
+            run(LiuStorey::new()),
+            run(ConjugateDescent::new()),
+            run(HagerZhang::new()),
+            run(HestenesStiefelDaiYuan::new()),
+            run(FletcherReevesPolakRibiere::new()),
+        ] {
+            assert_relative_eq!(param[0], 1.0, epsilon = 1e-3);
+            assert_relative_eq!(param[1], 1.0, epsilon = 1e-3);
+        }
+    }
 }
@@ -179,7 +179,7 @@ where
         let OptimizationResult {
             problem: line_problem,
             state: mut line_state,
-            ..
+            solver: line_solver,
         } = Executor::new(
             problem.take_problem().ok_or_else(argmin_error_closure!(
                 PotentialBug,
@@ -191,6 +191,8 @@ where
         .ctrlc(false)
         .run()?;
 
+        self.linesearch.consume_line_search(line_solver);
+
         // takes care of the counts of function evaluations
         problem.consume_problem(line_problem);
 
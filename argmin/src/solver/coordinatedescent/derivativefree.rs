@@ -0,0 +1,513 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, SerializeAlias, Solver, State,
+    TerminationReason, KV,
+};
+use crate::solver::coordinatedescent::{
+    minimize_coordinate, next_bracket_width, CoordinateMinimizer, CoordinateSelection,
+};
+use argmin_math::{ArgminFromVec, ArgminToVec};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Coordinate descent
+///
+/// Derivative-free coordinate descent: In each iteration, a single coordinate `i` is chosen and
+/// the cost function is minimized along this coordinate within the bracket
+/// `[x_i - w_i, x_i + w_i]` by a one-dimensional solver (Brent's method by default, see
+/// [`with_minimizer`](`CoordinateDescent::with_minimizer`)). The step is only accepted if it
+/// decreases the cost function value. The bracket widths `w_i` are initialized with `1` (see
+/// [`with_bracket_width`](`CoordinateDescent::with_bracket_width`)) and doubled whenever a step
+/// ends close to the boundary of the bracket.
+///
+/// Coordinates are visited cyclically by default, see
+/// [`with_selection`](`CoordinateDescent::with_selection`). The Gauss-Southwell rule requires
+/// partial derivatives, see
+/// [`PartialDerivativeCoordinateDescent`](`super::PartialDerivativeCoordinateDescent`).
+///
+/// The solver terminates once the cost function value decreased by no more than a tolerance
+/// (`sqrt(EPSILON)` by default, see [`with_tolerance`](`CoordinateDescent::with_tolerance`))
+/// during a round of iterations in which each coordinate was visited at least once.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`]. The parameter vector must
+/// be convertible from and to a `Vec` of floats.
+///
+/// ## Reference
+///
+/// Stephen J. Wright (2015). Coordinate descent algorithms. Mathematical Programming 151, 3–34.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct CoordinateDescent<F, R> {
+    /// Coordinate selection rule
+    selection: CoordinateSelection,
+    /// One-dimensional solver
+    minimizer: CoordinateMinimizer,
+    /// Initial bracket width
+    bracket_width: F,
+    /// Current bracket width of each coordinate
+    bracket_widths: Vec<F>,
+    /// Tolerance of the one-dimensional minimizations
+    line_tol: F,
+    /// Tolerance on the decrease of the cost function value
+    tol: F,
+    /// Next coordinate in cyclic order
+    next_coordinate: usize,
+    /// Cost function value at the beginning of the current round of updates
+    reference_cost: F,
+    /// Coordinates visited in the current round
+    visited: Vec<bool>,
+    /// Whether the cost function value stagnated
+    stagnated: bool,
+    /// Random number generator
+    rng: R,
+}
+
+impl<F> CoordinateDescent<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`CoordinateDescent`]
+    ///
+    /// Uses the `Xoshiro256PlusPlus` RNG internally. For use of another RNG or a seeded RNG,
+    /// consider using [`CoordinateDescent::new_with_rng`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::CoordinateDescent;
+    /// let solver: CoordinateDescent<f64, _> = CoordinateDescent::new();
+    /// ```
+    pub fn new() -> Self {
+        CoordinateDescent::new_with_rng(Xoshiro256PlusPlus::from_entropy())
+    }
+}
+
+impl<F> Default for CoordinateDescent<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        CoordinateDescent::new()
+    }
+}
+
+impl<F, R> CoordinateDescent<F, R>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`CoordinateDescent`]
+    ///
+    /// Requires a RNG which must implement `rand::Rng` (and `serde::Serialize` if the `serde1`
+    /// feature is enabled).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::CoordinateDescent;
+    /// use rand::SeedableRng;
+    /// use rand_xoshiro::Xoshiro256PlusPlus;
+    ///
+    /// let rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    /// let solver: CoordinateDescent<f64, _> = CoordinateDescent::new_with_rng(rng);
+    /// ```
+    pub fn new_with_rng(rng: R) -> Self {
+        CoordinateDescent {
+            selection: CoordinateSelection::Cyclic,
+            minimizer: CoordinateMinimizer::Brent,
+            bracket_width: float!(1.0),
+            bracket_widths: vec![],
+            line_tol: F::epsilon().sqrt(),
+            tol: F::epsilon().sqrt(),
+            next_coordinate: 0,
+            reference_cost: F::infinity(),
+            visited: vec![],
+            stagnated: false,
+            rng,
+        }
+    }
+
+    /// Set the coordinate selection rule.
+    ///
+    /// Defaults to [`CoordinateSelection::Cyclic`]. [`CoordinateSelection::GaussSouthwell`]
+    /// requires partial derivatives and is therefore rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::{CoordinateDescent, CoordinateSelection};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: CoordinateDescent<f64, _> =
+    ///     CoordinateDescent::new().with_selection(CoordinateSelection::Randomized)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_selection(mut self, selection: CoordinateSelection) -> Result<Self, Error> {
+        if selection == CoordinateSelection::GaussSouthwell {
+            return Err(argmin_error!(
+                InvalidParameter,
+                concat!(
+                    "`CoordinateDescent`: Gauss-Southwell selection requires partial derivatives. ",
+                    "Use `PartialDerivativeCoordinateDescent` instead."
+                )
+            ));
+        }
+        self.selection = selection;
+        Ok(self)
+    }
+
+    /// Set the one-dimensional solver.
+    ///
+    /// Defaults to [`CoordinateMinimizer::Brent`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::{CoordinateDescent, CoordinateMinimizer};
+    /// let solver: CoordinateDescent<f64, _> =
+    ///     CoordinateDescent::new().with_minimizer(CoordinateMinimizer::GoldenSectionSearch);
+    /// ```
+    #[must_use]
+    pub fn with_minimizer(mut self, minimizer: CoordinateMinimizer) -> Self {
+        self.minimizer = minimizer;
+        self
+    }
+
+    /// Set the initial width of the brackets in which the minimum along a coordinate is searched.
+    ///
+    /// Must be larger than `0` and defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::CoordinateDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: CoordinateDescent<f64, _> = CoordinateDescent::new().with_bracket_width(10.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_bracket_width(mut self, width: F) -> Result<Self, Error> {
+        if width <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CoordinateDescent`: bracket width must be larger than 0."
+            ));
+        }
+        self.bracket_width = width;
+        Ok(self)
+    }
+
+    /// Set the tolerance of the one-dimensional minimizations.
+    ///
+    /// Must be larger than `0` and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::CoordinateDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: CoordinateDescent<f64, _> = CoordinateDescent::new().with_line_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_line_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CoordinateDescent`: line tolerance must be larger than 0."
+            ));
+        }
+        self.line_tol = tol;
+        Ok(self)
+    }
+
+    /// Set the tolerance on the decrease of the cost function value.
+    ///
+    /// Must be non-negative and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::CoordinateDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: CoordinateDescent<f64, _> = CoordinateDescent::new().with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CoordinateDescent`: tolerance must be non-negative."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), F>> for CoordinateDescent<F, R>
+where
+    O: CostFunction<Param = P, Output = F>,
+    P: Clone + SerializeAlias + ArgminToVec<Vec<F>> + ArgminFromVec<Vec<F>>,
+    F: ArgminFloat,
+    R: Rng + SerializeAlias,
+{
+    const NAME: &'static str = "Coordinate descent";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`CoordinateDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let num_coordinates = param.to_vec().len();
+        if num_coordinates == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CoordinateDescent`: parameter vector must not be empty."
+            ));
+        }
+        let cost = problem.cost(param)?;
+        self.bracket_widths = vec![self.bracket_width; num_coordinates];
+        self.next_coordinate = 0;
+        self.reference_cost = cost;
+        self.visited = vec![false; num_coordinates];
+        self.stagnated = false;
+        Ok((state.cost(cost), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`CoordinateDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let cost = state.get_cost();
+        let num_coordinates = self.bracket_widths.len();
+        let index = match self.selection {
+            CoordinateSelection::Randomized => self.rng.gen_range(0..num_coordinates),
+            _ => {
+                let index = self.next_coordinate;
+                self.next_coordinate = (index + 1) % num_coordinates;
+                index
+            }
+        };
+
+        let mut x = param.to_vec();
+        let x_i = x[index];
+        let width = self.bracket_widths[index];
+        let (t, new_cost) = minimize_coordinate(
+            problem,
+            x.clone(),
+            index,
+            (x_i - width, x_i + width),
+            self.minimizer,
+            self.line_tol,
+        )?;
+        let (param, cost) = if new_cost < cost {
+            x[index] = t;
+            self.bracket_widths[index] = next_bracket_width(width, t - x_i);
+            (P::from_vec(x), new_cost)
+        } else {
+            (param, cost)
+        };
+
+        // A round ends once each coordinate was visited at least once
+        self.visited[index] = true;
+        if self.visited.iter().all(|&visited| visited) {
+            self.stagnated = self.reference_cost - cost <= self.tol;
+            self.reference_cost = cost;
+            self.visited.iter_mut().for_each(|visited| *visited = false);
+        }
+
+        Ok((
+            state.param(param).cost(cost),
+            Some(make_kv!(
+                "coordinate" => index;
+                "bracket_width" => self.bracket_widths[index];
+            )),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, (), (), (), F>) -> TerminationReason {
+        if self.stagnated {
+            return TerminationReason::NoChangeInCost;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::coordinatedescent::tests::Quadratic;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(coordinate_descent, CoordinateDescent<f64, Xoshiro256PlusPlus>);
+
+    #[test]
+    fn test_new() {
+        let solver: CoordinateDescent<f64, _> = CoordinateDescent::new();
+        assert_eq!(solver.selection, CoordinateSelection::Cyclic);
+        assert_eq!(solver.minimizer, CoordinateMinimizer::Brent);
+        assert_eq!(solver.bracket_width.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(solver.bracket_widths.is_empty());
+        assert_eq!(
+            solver.line_tol.to_ne_bytes(),
+            f64::EPSILON.sqrt().to_ne_bytes()
+        );
+        assert_eq!(solver.tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(!solver.stagnated);
+    }
+
+    #[test]
+    fn test_builders() {
+        let solver: CoordinateDescent<f64, _> = CoordinateDescent::new();
+        assert_error!(
+            solver
+                .clone()
+                .with_selection(CoordinateSelection::GaussSouthwell),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`CoordinateDescent`: Gauss-Southwell selection requires ",
+                "partial derivatives. Use `PartialDerivativeCoordinateDescent` instead.\""
+            )
+        );
+        assert_error!(
+            solver.clone().with_bracket_width(0.0),
+            ArgminError,
+            "Invalid parameter: \"`CoordinateDescent`: bracket width must be larger than 0.\""
+        );
+        assert_error!(
+            solver.clone().with_line_tolerance(0.0),
+            ArgminError,
+            "Invalid parameter: \"`CoordinateDescent`: line tolerance must be larger than 0.\""
+        );
+        assert_error!(
+            solver.clone().with_tolerance(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`CoordinateDescent`: tolerance must be non-negative.\""
+        );
+        let solver = solver
+            .with_selection(CoordinateSelection::Randomized)
+            .unwrap()
+            .with_minimizer(CoordinateMinimizer::GoldenSectionSearch)
+            .with_bracket_width(2.0)
+            .unwrap()
+            .with_line_tolerance(1e-6)
+            .unwrap()
+            .with_tolerance(0.0)
+            .unwrap();
+        assert_eq!(solver.selection, CoordinateSelection::Randomized);
+        assert_eq!(solver.minimizer, CoordinateMinimizer::GoldenSectionSearch);
+        assert_eq!(solver.bracket_width.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(solver.line_tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(solver.tol.to_ne_bytes(), 0.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut solver: CoordinateDescent<f64, _> = CoordinateDescent::new();
+        let res = solver.init(&mut Problem::new(Quadratic {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`CoordinateDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        for selection in [CoordinateSelection::Cyclic, CoordinateSelection::Randomized] {
+            for minimizer in [
+                CoordinateMinimizer::Brent,
+                CoordinateMinimizer::GoldenSectionSearch,
+            ] {
+                let solver = CoordinateDescent::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(42))
+                    .with_selection(selection)
+                    .unwrap()
+                    .with_minimizer(minimizer)
+                    .with_tolerance(1e-14)
+                    .unwrap();
+                let res = Executor::new(Quadratic {}, solver)
+                    .configure(|state| state.param(vec![0.0; 3]).max_iters(1000))
+                    .run()
+                    .unwrap();
+                assert_eq!(
+                    res.state().get_termination_reason(),
+                    TerminationReason::NoChangeInCost
+                );
+                let param = res.state().get_best_param().unwrap();
+                assert_relative_eq!(param[0], 1.0, epsilon = 1e-4);
+                assert_relative_eq!(param[1], -2.0, epsilon = 1e-4);
+                assert_relative_eq!(param[2], 3.0, epsilon = 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bracket_expansion() {
+        // The minimum lies far outside of the initial brackets
+        let solver = CoordinateDescent::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(42))
+            .with_bracket_width(0.01)
+            .unwrap();
+        let res = Executor::new(Quadratic {}, solver)
+            .configure(|state| state.param(vec![0.0; 3]).max_iters(1000))
+            .run()
+            .unwrap();
+        let param = res.state().get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-4);
+        assert_relative_eq!(param[1], -2.0, epsilon = 1e-4);
+        assert_relative_eq!(param[2], 3.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_seeded_runs_reproducible() {
+        let run = |seed: u64| {
+            let solver = CoordinateDescent::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(seed))
+                .with_selection(CoordinateSelection::Randomized)
+                .unwrap();
+            Executor::new(Quadratic {}, solver)
+                .configure(|state| state.param(vec![0.0; 3]).max_iters(4))
+                .run()
+                .unwrap()
+                .state()
+                .get_param()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(run(1), run(1));
+        assert_ne!(run(1), run(2));
+    }
+}
@@ -0,0 +1,254 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Coordinate descent
+//!
+//! Coordinate descent methods minimize a function along a single coordinate at a time while
+//! keeping all other coordinates fixed. Each step is an (approximately) exact one-dimensional
+//! minimization, performed by either [`BrentOpt`](`crate::solver::brent::BrentOpt`) or
+//! [`GoldenSectionSearch`](`crate::solver::goldensectionsearch::GoldenSectionSearch`) (see
+//! [`CoordinateMinimizer`]). This is particularly efficient for (nearly) separable problems such
+//! as many regression problems. This module provides
+//!
+//! * [`CoordinateDescent`], which only requires the cost function, and
+//! * [`PartialDerivativeCoordinateDescent`], which additionally uses partial derivatives
+//!   provided via the [`PartialDerivative`](`crate::core::PartialDerivative`) trait to choose the
+//!   direction in which the minimum along a coordinate is searched, to skip stationary
+//!   coordinates and to enable the Gauss-Southwell rule.
+//!
+//! One iteration of both solvers updates a single coordinate. The coordinate is chosen according
+//! to [`CoordinateSelection`]. The random number generator is part of the solver, hence seeded
+//! runs are reproducible and can be checkpointed.
+//!
+//! ## References
+//!
+//! Stephen J. Wright (2015). Coordinate descent algorithms. Mathematical Programming 151, 3–34.
+//!
+//! Julie Nutini, Mark Schmidt, Issam H. Laradji, Michael Friedlander and Hoyt Koepke (2015).
+//! Coordinate descent converges faster with the Gauss-Southwell rule than random selection.
+//! Proceedings of the 32nd International Conference on Machine Learning (ICML), 1632–1641.
+
+mod derivativefree;
+mod partialderivative;
+
+pub use self::derivativefree::CoordinateDescent;
+pub use self::partialderivative::PartialDerivativeCoordinateDescent;
+
+use crate::core::{ArgminFloat, CostFunction, Error, Executor, OptimizationResult, Problem};
+use crate::solver::brent::BrentOpt;
+use crate::solver::goldensectionsearch::GoldenSectionSearch;
+use argmin_math::ArgminFromVec;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of iterations of a one-dimensional minimization
+const LINE_MAX_ITERS: u64 = 100;
+
+/// Rule by which the coordinate updated in an iteration is chosen
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum CoordinateSelection {
+    /// Visit all coordinates in their natural order
+    Cyclic,
+    /// Draw coordinates uniformly at random with replacement
+    Randomized,
+    /// Choose the coordinate with the largest absolute partial derivative (greedy selection).
+    /// Requires all partial derivatives in each iteration and is therefore only available in
+    /// [`PartialDerivativeCoordinateDescent`].
+    GaussSouthwell,
+}
+
+/// One-dimensional solver used to minimize along a single coordinate
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum CoordinateMinimizer {
+    /// Brent's method (see [`BrentOpt`](`crate::solver::brent::BrentOpt`))
+    Brent,
+    /// Golden-section search (see
+    /// [`GoldenSectionSearch`](`crate::solver::goldensectionsearch::GoldenSectionSearch`))
+    GoldenSectionSearch,
+}
+
+/// Restriction of a cost function to a single coordinate
+struct CoordinateLine<O, F> {
+    /// Original problem
+    problem: O,
+    /// Point whose coordinate `index` is varied
+    base: Vec<F>,
+    /// Coordinate
+    index: usize,
+}
+
+impl<O, P, F> CostFunction for CoordinateLine<O, F>
+where
+    O: CostFunction<Param = P, Output = F>,
+    P: ArgminFromVec<Vec<F>>,
+    F: ArgminFloat,
+{
+    type Param = F;
+    type Output = F;
+
+    fn cost(&self, t: &F) -> Result<F, Error> {
+        let mut x = self.base.clone();
+        x[self.index] = *t;
+        self.problem.cost(&P::from_vec(x))
+    }
+}
+
+/// Minimizes the cost function along coordinate `index` of `base` within `bounds`.
+///
+/// Returns the best value of the coordinate found and the corresponding cost function value. All
+/// cost function evaluations are counted in `problem`.
+fn minimize_coordinate<O, P, F>(
+    problem: &mut Problem<O>,
+    base: Vec<F>,
+    index: usize,
+    bounds: (F, F),
+    minimizer: CoordinateMinimizer,
+    tol: F,
+) -> Result<(F, F), Error>
+where
+    O: CostFunction<Param = P, Output = F>,
+    P: ArgminFromVec<Vec<F>>,
+    F: ArgminFloat,
+{
+    // Golden-section search requires an initial estimate strictly inside of the bracket
+    let start = (bounds.0 + bounds.1) / float!(2.0);
+    let line = CoordinateLine {
+        problem: problem.take_problem().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "Coordinate descent: Failed to take `problem` for one-dimensional minimization"
+        ))?,
+        base,
+        index,
+    };
+    let (mut line_problem, mut line_state) = match minimizer {
+        CoordinateMinimizer::Brent => {
+            let OptimizationResult { problem, state, .. } = Executor::new(
+                line,
+                BrentOpt::new(bounds.0, bounds.1).set_tolerance(tol, tol),
+            )
+            .configure(|state| state.param(start).max_iters(LINE_MAX_ITERS))
+            .ctrlc(false)
+            .run()?;
+            (problem, state)
+        }
+        CoordinateMinimizer::GoldenSectionSearch => {
+            let OptimizationResult { problem, state, .. } = Executor::new(
+                line,
+                GoldenSectionSearch::new(bounds.0, bounds.1)?.with_tolerance(tol)?,
+            )
+            .configure(|state| state.param(start).max_iters(LINE_MAX_ITERS))
+            .ctrlc(false)
+            .run()?;
+            (problem, state)
+        }
+    };
+
+    // Hand the problem back and accumulate the function evaluation counts
+    problem.problem = Some(
+        line_problem
+            .take_problem()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "Coordinate descent: Failed to take `problem` from one-dimensional minimization"
+            ))?
+            .problem,
+    );
+    problem.consume_func_counts(line_problem);
+
+    let cost = line_state.get_best_cost();
+    let t = line_state
+        .take_best_param()
+        .ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "Coordinate descent: No parameter returned by one-dimensional minimization."
+        ))?;
+    Ok((t, cost))
+}
+
+/// Returns the bracket width for the next minimization along a coordinate. The width is doubled
+/// whenever a step ended close to the boundary of the bracket, which indicates that the minimum
+/// along the coordinate may lie outside of the bracket.
+fn next_bracket_width<F: ArgminFloat>(width: F, step: F) -> F {
+    if step.abs() >= float!(0.9) * width {
+        float!(2.0) * width
+    } else {
+        width
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::core::PartialDerivative;
+    use approx::assert_relative_eq;
+
+    /// Quadratic `f(x) = 1/2 xᵀAx - bᵀx` with coupled coordinates and minimum `[1, -2, 3]`
+    pub(crate) struct Quadratic {}
+
+    const MATRIX: [[f64; 3]; 3] = [[3.0, 1.0, 0.0], [1.0, 4.0, 1.0], [0.0, 1.0, 2.0]];
+    const RHS: [f64; 3] = [1.0, -4.0, 4.0];
+
+    impl CostFunction for Quadratic {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Vec<f64>) -> Result<f64, Error> {
+            let mut cost = 0.0;
+            for i in 0..3 {
+                let ax: f64 = (0..3).map(|j| MATRIX[i][j] * p[j]).sum();
+                cost += 0.5 * p[i] * ax - RHS[i] * p[i];
+            }
+            Ok(cost)
+        }
+    }
+
+    impl PartialDerivative for Quadratic {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn partial_derivative(&self, p: &Vec<f64>, index: usize) -> Result<f64, Error> {
+            let ax: f64 = (0..3).map(|j| MATRIX[index][j] * p[j]).sum();
+            Ok(ax - RHS[index])
+        }
+    }
+
+    #[test]
+    fn test_minimize_coordinate() {
+        for minimizer in [
+            CoordinateMinimizer::Brent,
+            CoordinateMinimizer::GoldenSectionSearch,
+        ] {
+            // Symmetric and one-sided brackets
+            for bounds in [(-2.0, 2.0), (-2.0, 0.0)] {
+                let mut problem = Problem::new(Quadratic {});
+                // Along coordinate 1 at [0, 0, 0], the minimum is at -4 / 4 = -1
+                let (t, cost) = minimize_coordinate(
+                    &mut problem,
+                    vec![0.0, 0.0, 0.0],
+                    1,
+                    bounds,
+                    minimizer,
+                    1e-8,
+                )
+                .unwrap();
+                assert_relative_eq!(t, -1.0, epsilon = 1e-6);
+                assert_relative_eq!(cost, -2.0, epsilon = 1e-10);
+                assert!(problem.problem.is_some());
+                assert!(problem.counts["cost_count"] > 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_next_bracket_width() {
+        assert_relative_eq!(next_bracket_width(1.0f64, 0.5), 1.0);
+        assert_relative_eq!(next_bracket_width(1.0f64, -0.95), 2.0);
+        assert_relative_eq!(next_bracket_width(1.0f64, 1.0), 2.0);
+    }
+}
@@ -0,0 +1,621 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, PartialDerivative, Problem, SerializeAlias,
+    Solver, State, TerminationReason, KV,
+};
+use crate::solver::coordinatedescent::{
+    minimize_coordinate, next_bracket_width, CoordinateMinimizer, CoordinateSelection,
+};
+use argmin_math::{ArgminFromVec, ArgminToVec};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Coordinate descent with partial derivatives
+///
+/// In each iteration, a single coordinate `i` is chosen and the partial derivative `g_i = ∂f/∂x_i`
+/// is computed. If `|g_i|` exceeds a tolerance, the cost function is minimized along this
+/// coordinate within the bracket `[x_i - w_i, x_i]` if `g_i > 0` or `[x_i, x_i + w_i]` if
+/// `g_i < 0` by a one-dimensional solver (Brent's method by default, see
+/// [`with_minimizer`](`PartialDerivativeCoordinateDescent::with_minimizer`)). The step is only
+/// accepted if it decreases the cost function value. The bracket widths `w_i` are initialized with
+/// `1` (see [`with_bracket_width`](`PartialDerivativeCoordinateDescent::with_bracket_width`)) and
+/// doubled whenever a step ends close to the boundary of the bracket.
+///
+/// Coordinates are visited cyclically by default. With the Gauss-Southwell rule, all partial
+/// derivatives are computed in each iteration and the coordinate with the largest absolute partial
+/// derivative is chosen, which often requires far fewer iterations. See
+/// [`with_selection`](`PartialDerivativeCoordinateDescent::with_selection`).
+///
+/// The solver terminates once `|g_i|` was found below a tolerance (`sqrt(EPSILON)` by default, see
+/// [`with_tolerance`](`PartialDerivativeCoordinateDescent::with_tolerance`)) for all coordinates
+/// without any step in between, or, with the Gauss-Southwell rule, as soon as the largest absolute
+/// partial derivative is below the tolerance. If, in between, the minimization along a coordinate
+/// does not decrease the cost function value anymore (the accuracy of the one-dimensional solver
+/// is exhausted), this coordinate is treated as stationary and the solver terminates with
+/// [`TerminationReason::NoChangeInCost`] instead.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and
+/// [`PartialDerivative`]. The parameter vector must be convertible from and to a `Vec` of
+/// floats.
+///
+/// ## References
+///
+/// Stephen J. Wright (2015). Coordinate descent algorithms. Mathematical Programming 151, 3–34.
+///
+/// Julie Nutini, Mark Schmidt, Issam H. Laradji, Michael Friedlander and Hoyt Koepke (2015).
+/// Coordinate descent converges faster with the Gauss-Southwell rule than random selection.
+/// Proceedings of the 32nd International Conference on Machine Learning (ICML), 1632–1641.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct PartialDerivativeCoordinateDescent<F, R> {
+    /// Coordinate selection rule
+    selection: CoordinateSelection,
+    /// One-dimensional solver
+    minimizer: CoordinateMinimizer,
+    /// Initial bracket width
+    bracket_width: F,
+    /// Current bracket width of each coordinate
+    bracket_widths: Vec<F>,
+    /// Tolerance of the one-dimensional minimizations
+    line_tol: F,
+    /// Tolerance on the absolute partial derivatives
+    tol: F,
+    /// Next coordinate in cyclic order
+    next_coordinate: usize,
+    /// Coordinates with absolute partial derivative below the tolerance or along which the cost
+    /// function value could not be decreased since the last step
+    stationary: Vec<bool>,
+    /// Whether a step along a coordinate did not decrease the cost function value since the last
+    /// step
+    stalled: bool,
+    /// Random number generator
+    rng: R,
+}
+
+impl<F> PartialDerivativeCoordinateDescent<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`PartialDerivativeCoordinateDescent`]
+    ///
+    /// Uses the `Xoshiro256PlusPlus` RNG internally. For use of another RNG or a seeded RNG,
+    /// consider using [`PartialDerivativeCoordinateDescent::new_with_rng`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::PartialDerivativeCoordinateDescent;
+    /// let solver: PartialDerivativeCoordinateDescent<f64, _> =
+    ///     PartialDerivativeCoordinateDescent::new();
+    /// ```
+    pub fn new() -> Self {
+        PartialDerivativeCoordinateDescent::new_with_rng(Xoshiro256PlusPlus::from_entropy())
+    }
+}
+
+impl<F> Default for PartialDerivativeCoordinateDescent<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        PartialDerivativeCoordinateDescent::new()
+    }
+}
+
+impl<F, R> PartialDerivativeCoordinateDescent<F, R>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`PartialDerivativeCoordinateDescent`]
+    ///
+    /// Requires a RNG which must implement `rand::Rng` (and `serde::Serialize` if the `serde1`
+    /// feature is enabled).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::PartialDerivativeCoordinateDescent;
+    /// use rand::SeedableRng;
+    /// use rand_xoshiro::Xoshiro256PlusPlus;
+    ///
+    /// let rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    /// let solver: PartialDerivativeCoordinateDescent<f64, _> =
+    ///     PartialDerivativeCoordinateDescent::new_with_rng(rng);
+    /// ```
+    pub fn new_with_rng(rng: R) -> Self {
+        PartialDerivativeCoordinateDescent {
+            selection: CoordinateSelection::Cyclic,
+            minimizer: CoordinateMinimizer::Brent,
+            bracket_width: float!(1.0),
+            bracket_widths: vec![],
+            line_tol: F::epsilon().sqrt(),
+            tol: F::epsilon().sqrt(),
+            next_coordinate: 0,
+            stationary: vec![],
+            stalled: false,
+            rng,
+        }
+    }
+
+    /// Set the coordinate selection rule.
+    ///
+    /// Defaults to [`CoordinateSelection::Cyclic`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::{
+    /// #     CoordinateSelection, PartialDerivativeCoordinateDescent,
+    /// # };
+    /// let solver: PartialDerivativeCoordinateDescent<f64, _> =
+    ///     PartialDerivativeCoordinateDescent::new()
+    ///         .with_selection(CoordinateSelection::GaussSouthwell);
+    /// ```
+    #[must_use]
+    pub fn with_selection(mut self, selection: CoordinateSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Set the one-dimensional solver.
+    ///
+    /// Defaults to [`CoordinateMinimizer::Brent`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::{
+    /// #     CoordinateMinimizer, PartialDerivativeCoordinateDescent,
+    /// # };
+    /// let solver: PartialDerivativeCoordinateDescent<f64, _> =
+    ///     PartialDerivativeCoordinateDescent::new()
+    ///         .with_minimizer(CoordinateMinimizer::GoldenSectionSearch);
+    /// ```
+    #[must_use]
+    pub fn with_minimizer(mut self, minimizer: CoordinateMinimizer) -> Self {
+        self.minimizer = minimizer;
+        self
+    }
+
+    /// Set the initial width of the brackets in which the minimum along a coordinate is searched.
+    ///
+    /// Must be larger than `0` and defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::PartialDerivativeCoordinateDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: PartialDerivativeCoordinateDescent<f64, _> =
+    ///     PartialDerivativeCoordinateDescent::new().with_bracket_width(10.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_bracket_width(mut self, width: F) -> Result<Self, Error> {
+        if width <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`PartialDerivativeCoordinateDescent`: bracket width must be larger than 0."
+            ));
+        }
+        self.bracket_width = width;
+        Ok(self)
+    }
+
+    /// Set the tolerance of the one-dimensional minimizations.
+    ///
+    /// Must be larger than `0` and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::PartialDerivativeCoordinateDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: PartialDerivativeCoordinateDescent<f64, _> =
+    ///     PartialDerivativeCoordinateDescent::new().with_line_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_line_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`PartialDerivativeCoordinateDescent`: line tolerance must be larger than 0."
+            ));
+        }
+        self.line_tol = tol;
+        Ok(self)
+    }
+
+    /// Set the tolerance on the absolute partial derivatives.
+    ///
+    /// Must be non-negative and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::PartialDerivativeCoordinateDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: PartialDerivativeCoordinateDescent<f64, _> =
+    ///     PartialDerivativeCoordinateDescent::new().with_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`PartialDerivativeCoordinateDescent`: tolerance must be non-negative."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), F>> for PartialDerivativeCoordinateDescent<F, R>
+where
+    O: CostFunction<Param = P, Output = F> + PartialDerivative<Param = P, Float = F>,
+    P: Clone + SerializeAlias + ArgminToVec<Vec<F>> + ArgminFromVec<Vec<F>>,
+    F: ArgminFloat,
+    R: Rng + SerializeAlias,
+{
+    const NAME: &'static str = "Coordinate descent with partial derivatives";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`PartialDerivativeCoordinateDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let num_coordinates = param.to_vec().len();
+        if num_coordinates == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`PartialDerivativeCoordinateDescent`: parameter vector must not be empty."
+            ));
+        }
+        let cost = problem.cost(param)?;
+        self.bracket_widths = vec![self.bracket_width; num_coordinates];
+        self.next_coordinate = 0;
+        self.stationary = vec![false; num_coordinates];
+        self.stalled = false;
+        Ok((state.cost(cost), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`PartialDerivativeCoordinateDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let cost = state.get_cost();
+        let num_coordinates = self.bracket_widths.len();
+        let (index, derivative) = match self.selection {
+            CoordinateSelection::Cyclic => {
+                let index = self.next_coordinate;
+                self.next_coordinate = (index + 1) % num_coordinates;
+                (index, problem.partial_derivative(&param, index)?)
+            }
+            CoordinateSelection::Randomized => {
+                let index = self.rng.gen_range(0..num_coordinates);
+                (index, problem.partial_derivative(&param, index)?)
+            }
+            CoordinateSelection::GaussSouthwell => {
+                // Coordinates known to be stationary are excluded
+                let mut best: Option<(usize, F)> = None;
+                for index in 0..num_coordinates {
+                    let derivative = problem.partial_derivative(&param, index)?;
+                    let larger = match best {
+                        Some((_, d)) => derivative.abs() > d.abs(),
+                        None => true,
+                    };
+                    if !self.stationary[index] && larger {
+                        best = Some((index, derivative));
+                    }
+                }
+                best.ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`PartialDerivativeCoordinateDescent`: all coordinates are stationary."
+                ))?
+            }
+        };
+
+        if derivative.abs() <= self.tol {
+            if self.selection == CoordinateSelection::GaussSouthwell {
+                // The largest absolute partial derivative is below the tolerance
+                self.stationary
+                    .iter_mut()
+                    .for_each(|stationary| *stationary = true);
+                self.stalled = false;
+            } else {
+                self.stationary[index] = true;
+            }
+            return Ok((
+                state.param(param).cost(cost),
+                Some(make_kv!(
+                    "coordinate" => index;
+                    "partial_derivative" => derivative;
+                )),
+            ));
+        }
+
+        let mut x = param.to_vec();
+        let x_i = x[index];
+        let width = self.bracket_widths[index];
+        let bounds = if derivative > float!(0.0) {
+            (x_i - width, x_i)
+        } else {
+            (x_i, x_i + width)
+        };
+        let (t, new_cost) = minimize_coordinate(
+            problem,
+            x.clone(),
+            index,
+            bounds,
+            self.minimizer,
+            self.line_tol,
+        )?;
+        let (param, cost) = if new_cost < cost {
+            x[index] = t;
+            self.bracket_widths[index] = next_bracket_width(width, t - x_i);
+            self.stationary
+                .iter_mut()
+                .for_each(|stationary| *stationary = false);
+            self.stalled = false;
+            (P::from_vec(x), new_cost)
+        } else {
+            // The accuracy of the one-dimensional minimization is exhausted along this coordinate
+            self.stationary[index] = true;
+            self.stalled = true;
+            (param, cost)
+        };
+
+        Ok((
+            state.param(param).cost(cost),
+            Some(make_kv!(
+                "coordinate" => index;
+                "partial_derivative" => derivative;
+            )),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, (), (), (), F>) -> TerminationReason {
+        if !self.stationary.is_empty() && self.stationary.iter().all(|&stationary| stationary) {
+            if self.stalled {
+                return TerminationReason::NoChangeInCost;
+            }
+            return TerminationReason::TargetToleranceReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::coordinatedescent::tests::Quadratic;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        partial_derivative_coordinate_descent,
+        PartialDerivativeCoordinateDescent<f64, Xoshiro256PlusPlus>
+    );
+
+    #[test]
+    fn test_new() {
+        let solver: PartialDerivativeCoordinateDescent<f64, _> =
+            PartialDerivativeCoordinateDescent::new();
+        assert_eq!(solver.selection, CoordinateSelection::Cyclic);
+        assert_eq!(solver.minimizer, CoordinateMinimizer::Brent);
+        assert_eq!(solver.bracket_width.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(solver.bracket_widths.is_empty());
+        assert_eq!(
+            solver.line_tol.to_ne_bytes(),
+            f64::EPSILON.sqrt().to_ne_bytes()
+        );
+        assert_eq!(solver.tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(solver.stationary.is_empty());
+        assert!(!solver.stalled);
+    }
+
+    #[test]
+    fn test_builders() {
+        let solver: PartialDerivativeCoordinateDescent<f64, _> =
+            PartialDerivativeCoordinateDescent::new();
+        assert_error!(
+            solver.clone().with_bracket_width(-1.0),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`PartialDerivativeCoordinateDescent`: ",
+                "bracket width must be larger than 0.\""
+            )
+        );
+        assert_error!(
+            solver.clone().with_line_tolerance(0.0),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`PartialDerivativeCoordinateDescent`: ",
+                "line tolerance must be larger than 0.\""
+            )
+        );
+        assert_error!(
+            solver.clone().with_tolerance(-1.0),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`PartialDerivativeCoordinateDescent`: ",
+                "tolerance must be non-negative.\""
+            )
+        );
+        let solver = solver
+            .with_selection(CoordinateSelection::GaussSouthwell)
+            .with_minimizer(CoordinateMinimizer::GoldenSectionSearch)
+            .with_bracket_width(2.0)
+            .unwrap()
+            .with_line_tolerance(1e-6)
+            .unwrap()
+            .with_tolerance(1e-4)
+            .unwrap();
+        assert_eq!(solver.selection, CoordinateSelection::GaussSouthwell);
+        assert_eq!(solver.minimizer, CoordinateMinimizer::GoldenSectionSearch);
+        assert_eq!(solver.bracket_width.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(solver.line_tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(solver.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut solver: PartialDerivativeCoordinateDescent<f64, _> =
+            PartialDerivativeCoordinateDescent::new();
+        let res = solver.init(&mut Problem::new(Quadratic {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`PartialDerivativeCoordinateDescent` requires an initial ",
+                "parameter vector. Please provide an initial guess via `Executor`s `configure` ",
+                "method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        for selection in [
+            CoordinateSelection::Cyclic,
+            CoordinateSelection::Randomized,
+            CoordinateSelection::GaussSouthwell,
+        ] {
+            for minimizer in [
+                CoordinateMinimizer::Brent,
+                CoordinateMinimizer::GoldenSectionSearch,
+            ] {
+                let solver = PartialDerivativeCoordinateDescent::new_with_rng(
+                    Xoshiro256PlusPlus::seed_from_u64(42),
+                )
+                .with_selection(selection)
+                .with_minimizer(minimizer)
+                .with_tolerance(1e-5)
+                .unwrap();
+                let res = Executor::new(Quadratic {}, solver)
+                    .configure(|state| state.param(vec![0.0; 3]).max_iters(1000))
+                    .run()
+                    .unwrap();
+                assert_eq!(
+                    res.state().get_termination_reason(),
+                    TerminationReason::TargetToleranceReached
+                );
+                let param = res.state().get_best_param().unwrap();
+                assert_relative_eq!(param[0], 1.0, epsilon = 1e-4);
+                assert_relative_eq!(param[1], -2.0, epsilon = 1e-4);
+                assert_relative_eq!(param[2], 3.0, epsilon = 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gauss_southwell_counts() {
+        let solver = PartialDerivativeCoordinateDescent::new()
+            .with_selection(CoordinateSelection::GaussSouthwell);
+        let res = Executor::new(Quadratic {}, solver)
+            .configure(|state| state.param(vec![0.0; 3]).max_iters(5))
+            .run()
+            .unwrap();
+        // All partial derivatives are computed in each iteration
+        assert_eq!(
+            res.problem().counts["partial_derivative_count"],
+            3 * res.state().get_iter()
+        );
+    }
+
+    #[test]
+    fn test_stalled() {
+        // With a tolerance of 0, the accuracy of the one-dimensional minimizations is exhausted
+        // before the tolerance is reached
+        for selection in [
+            CoordinateSelection::Cyclic,
+            CoordinateSelection::GaussSouthwell,
+        ] {
+            let solver: PartialDerivativeCoordinateDescent<f64, _> =
+                PartialDerivativeCoordinateDescent::new()
+                    .with_selection(selection)
+                    .with_tolerance(0.0)
+                    .unwrap();
+            let res = Executor::new(Quadratic {}, solver)
+                .configure(|state| state.param(vec![0.0; 3]).max_iters(10000))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state().get_termination_reason(),
+                TerminationReason::NoChangeInCost
+            );
+            let param = res.state().get_best_param().unwrap();
+            assert_relative_eq!(param[0], 1.0, epsilon = 1e-6);
+            assert_relative_eq!(param[1], -2.0, epsilon = 1e-6);
+            assert_relative_eq!(param[2], 3.0, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_stationary_start() {
+        let solver: PartialDerivativeCoordinateDescent<f64, _> =
+            PartialDerivativeCoordinateDescent::new();
+        let res = Executor::new(Quadratic {}, solver)
+            .configure(|state| state.param(vec![1.0, -2.0, 3.0]).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            TerminationReason::TargetToleranceReached
+        );
+        assert_eq!(res.state().get_iter(), 3);
+        assert_eq!(res.problem().counts["cost_count"], 1);
+    }
+
+    #[test]
+    fn test_seeded_runs_reproducible() {
+        let run = |seed: u64| {
+            let solver = PartialDerivativeCoordinateDescent::new_with_rng(
+                Xoshiro256PlusPlus::seed_from_u64(seed),
+            )
+            .with_selection(CoordinateSelection::Randomized);
+            Executor::new(Quadratic {}, solver)
+                .configure(|state| state.param(vec![0.0; 3]).max_iters(4))
+                .run()
+                .unwrap()
+                .state()
+                .get_param()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(run(1), run(1));
+        assert_ne!(run(1), run(2));
+    }
+}
@@ -0,0 +1,391 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use argmin_math::{ArgminAdd, ArgminMap, ArgminMul, ArgminNorm, ArgminSub, ArgminZeroLike};
+use rand::prelude::*;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+/// Parametric family of distributions over parameter vectors `P` used by
+/// [`CrossEntropy`](`crate::solver::crossentropy::CrossEntropy`)
+pub trait SamplingDistribution<P, F> {
+    /// Draw a sample from the distribution
+    fn sample<R: Rng>(&self, rng: &mut R) -> P;
+
+    /// Fit the distribution to the elite samples by maximum likelihood and blend the result with
+    /// the current parameters of the distribution: `new = smoothing * fit + (1 - smoothing) * old`
+    fn fit(&mut self, elites: &[&P], smoothing: F) -> Result<(), Error>;
+
+    /// Most likely parameter vector
+    fn mode(&self) -> P;
+
+    /// Measure of the spread of the distribution, which is zero if and only if the distribution
+    /// is degenerate
+    fn spread(&self) -> F;
+}
+
+/// Sample from a standard normal distribution (Box-Muller transform)
+pub(crate) fn standard_normal<F, R>(rng: &mut R) -> F
+where
+    F: ArgminFloat,
+    R: Rng,
+{
+    // `1 - u` is in `(0, 1]`, which avoids `ln(0)`
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    float!((-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos())
+}
+
+/// Parameter vector of the same shape as `param` with independent standard normal entries
+pub(crate) fn standard_normal_like<P, F, R>(param: &P, rng: &mut R) -> P
+where
+    P: ArgminMap<F>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    let rng = RefCell::new(rng);
+    param.map(|_| standard_normal(&mut **rng.borrow_mut()))
+}
+
+/// Normal distribution with diagonal covariance matrix
+///
+/// The spread is the norm of the vector of standard deviations.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::crossentropy::Gaussian;
+/// let gaussian: Gaussian<Vec<f64>> = Gaussian::new(vec![0.0, 0.0], vec![1.0, 2.0]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Gaussian<P> {
+    /// Mean
+    mean: P,
+    /// Standard deviations
+    std_dev: P,
+}
+
+impl<P> Gaussian<P> {
+    /// Construct a new instance of [`Gaussian`] from the mean and the vector of standard
+    /// deviations of the components
+    pub fn new(mean: P, std_dev: P) -> Self {
+        Gaussian { mean, std_dev }
+    }
+
+    /// Returns the mean
+    pub fn mean(&self) -> &P {
+        &self.mean
+    }
+
+    /// Returns the standard deviations of the components
+    pub fn std_dev(&self) -> &P {
+        &self.std_dev
+    }
+}
+
+impl<P, F> SamplingDistribution<P, F> for Gaussian<P>
+where
+    P: Clone
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminMul<P, P>
+        + ArgminMul<F, P>
+        + ArgminMap<F>
+        + ArgminNorm<F>
+        + ArgminZeroLike,
+    F: ArgminFloat,
+{
+    fn sample<R: Rng>(&self, rng: &mut R) -> P {
+        self.mean
+            .add(&self.std_dev.mul(&standard_normal_like(&self.mean, rng)))
+    }
+
+    fn fit(&mut self, elites: &[&P], smoothing: F) -> Result<(), Error> {
+        if elites.is_empty() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Gaussian`: cannot fit distribution to zero samples."
+            ));
+        }
+        let n_inv = float!(1.0) / F::from_usize(elites.len()).unwrap();
+        let mean = elites
+            .iter()
+            .fold(self.mean.zero_like(), |acc, e| acc.add(*e))
+            .mul(&n_inv);
+        let variance = elites
+            .iter()
+            .fold(self.mean.zero_like(), |acc, e| {
+                let diff = e.sub(&mean);
+                acc.add(&diff.mul(&diff))
+            })
+            .mul(&n_inv);
+        let std_dev = variance.map(|v| v.sqrt());
+        let keep = float!(1.0) - smoothing;
+        self.mean = mean.mul(&smoothing).add(&self.mean.mul(&keep));
+        self.std_dev = std_dev.mul(&smoothing).add(&self.std_dev.mul(&keep));
+        Ok(())
+    }
+
+    fn mode(&self) -> P {
+        self.mean.clone()
+    }
+
+    fn spread(&self) -> F {
+        self.std_dev.norm()
+    }
+}
+
+/// Independent categorical distributions over integer-valued parameter vectors
+///
+/// Variable `i` of a sample takes the value `k` with probability `probabilities[i][k]`. The
+/// spread is the sum over all variables of one minus the largest probability.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::crossentropy::Categorical;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// // Two binary variables and one variable with three categories
+/// let categorical: Categorical<f64> = Categorical::new(vec![
+///     vec![0.5, 0.5],
+///     vec![0.9, 0.1],
+///     vec![1.0, 1.0, 1.0],
+/// ])?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Categorical<F> {
+    /// Probabilities of all categories of all variables
+    probabilities: Vec<Vec<F>>,
+}
+
+impl<F> Categorical<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`Categorical`]
+    ///
+    /// Takes the (unnormalized) probabilities of the categories of each variable as input. These
+    /// must be non-negative and must not all be zero for a variable.
+    pub fn new(probabilities: Vec<Vec<F>>) -> Result<Self, Error> {
+        let probabilities = probabilities
+            .into_iter()
+            .map(|p| {
+                let total = p.iter().fold(float!(0.0), |acc, &q| acc + q);
+                if p.iter().any(|&q| q < float!(0.0)) || total <= float!(0.0) {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        concat!(
+                            "`Categorical`: probabilities must be non-negative ",
+                            "and not all zero for each variable."
+                        )
+                    ));
+                }
+                Ok(p.into_iter().map(|q| q / total).collect())
+            })
+            .collect::<Result<Vec<Vec<F>>, Error>>()?;
+        Ok(Categorical { probabilities })
+    }
+
+    /// Construct a new instance of [`Categorical`] where each of `num_variables` variables takes
+    /// one of `num_categories` values with equal probability.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::crossentropy::Categorical;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// // 20 binary variables
+    /// let categorical: Categorical<f64> = Categorical::uniform(20, 2)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn uniform(num_variables: usize, num_categories: usize) -> Result<Self, Error> {
+        Categorical::new(vec![vec![float!(1.0); num_categories]; num_variables])
+    }
+
+    /// Returns the probabilities of all categories of all variables
+    pub fn probabilities(&self) -> &Vec<Vec<F>> {
+        &self.probabilities
+    }
+}
+
+impl<F> SamplingDistribution<Vec<usize>, F> for Categorical<F>
+where
+    F: ArgminFloat,
+{
+    fn sample<R: Rng>(&self, rng: &mut R) -> Vec<usize> {
+        self.probabilities
+            .iter()
+            .map(|p| {
+                let u: F = float!(rng.gen::<f64>());
+                let mut cumulative = float!(0.0);
+                p.iter()
+                    .position(|&q| {
+                        cumulative = cumulative + q;
+                        u < cumulative
+                    })
+                    .unwrap_or(p.len() - 1)
+            })
+            .collect()
+    }
+
+    fn fit(&mut self, elites: &[&Vec<usize>], smoothing: F) -> Result<(), Error> {
+        if elites.is_empty() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Categorical`: cannot fit distribution to zero samples."
+            ));
+        }
+        let weight = smoothing / F::from_usize(elites.len()).unwrap();
+        for (i, p) in self.probabilities.iter_mut().enumerate() {
+            for q in p.iter_mut() {
+                *q = *q * (float!(1.0) - smoothing);
+            }
+            for e in elites {
+                let k = e[i];
+                if k >= p.len() {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        format!(
+                            "`Categorical`: value {} of variable {} is not a valid category.",
+                            k, i
+                        )
+                    ));
+                }
+                p[k] = p[k] + weight;
+            }
+        }
+        Ok(())
+    }
+
+    fn mode(&self) -> Vec<usize> {
+        self.probabilities
+            .iter()
+            .map(|p| {
+                p.iter()
+                    .enumerate()
+                    .fold((0, float!(-1.0)), |(best, best_q), (k, &q)| {
+                        if q > best_q {
+                            (k, q)
+                        } else {
+                            (best, best_q)
+                        }
+                    })
+                    .0
+            })
+            .collect()
+    }
+
+    fn spread(&self) -> F {
+        self.probabilities.iter().fold(float!(0.0), |acc, p| {
+            acc + float!(1.0) - p.iter().fold(float!(0.0), |m, &q| m.max(q))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    #[test]
+    fn test_standard_normal() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+        let samples: Vec<f64> = (0..20000).map(|_| standard_normal(&mut rng)).collect();
+        let mean = samples.iter().sum::<f64>() / 20000.0;
+        let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 20000.0;
+        assert_relative_eq!(mean, 0.0, epsilon = 0.03);
+        assert_relative_eq!(var, 1.0, epsilon = 0.03);
+    }
+
+    #[test]
+    fn test_gaussian_fit() {
+        let mut gaussian: Gaussian<Vec<f64>> = Gaussian::new(vec![0.0, 0.0], vec![1.0, 1.0]);
+        let elites = [vec![1.0, 2.0], vec![3.0, 2.0]];
+        let elites: Vec<&Vec<f64>> = elites.iter().collect();
+        gaussian.fit(&elites, 1.0).unwrap();
+        assert_eq!(gaussian.mean(), &vec![2.0, 2.0]);
+        assert_eq!(gaussian.std_dev(), &vec![1.0, 0.0]);
+        assert_relative_eq!(gaussian.spread(), 1.0);
+
+        gaussian.fit(&elites, 0.5).unwrap();
+        assert_eq!(gaussian.mean(), &vec![2.0, 2.0]);
+        assert_eq!(gaussian.std_dev(), &vec![1.0, 0.0]);
+
+        let mut gaussian: Gaussian<Vec<f64>> = Gaussian::new(vec![0.0, 0.0], vec![1.0, 1.0]);
+        gaussian.fit(&elites, 0.5).unwrap();
+        assert_eq!(gaussian.mode(), vec![1.0, 1.0]);
+        assert_eq!(gaussian.std_dev(), &vec![1.0, 0.5]);
+
+        assert_error!(
+            gaussian.fit(&[], 0.5),
+            ArgminError,
+            "Invalid parameter: \"`Gaussian`: cannot fit distribution to zero samples.\""
+        );
+    }
+
+    #[test]
+    fn test_categorical() {
+        let mut categorical: Categorical<f64> =
+            Categorical::new(vec![vec![1.0, 3.0], vec![1.0, 1.0, 2.0]]).unwrap();
+        assert_eq!(
+            categorical.probabilities(),
+            &vec![vec![0.25, 0.75], vec![0.25, 0.25, 0.5]]
+        );
+        assert_eq!(categorical.mode(), vec![1, 2]);
+        assert_relative_eq!(categorical.spread(), 0.75);
+
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+        let samples: Vec<Vec<usize>> = (0..1000).map(|_| categorical.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|s| s[0] < 2 && s[1] < 3));
+        let ones = samples.iter().filter(|s| s[0] == 1).count();
+        assert!(ones > 700 && ones < 800);
+
+        let elites = [vec![0, 0], vec![0, 1]];
+        let elites: Vec<&Vec<usize>> = elites.iter().collect();
+        categorical.fit(&elites, 1.0).unwrap();
+        assert_eq!(
+            categorical.probabilities(),
+            &vec![vec![1.0, 0.0], vec![0.5, 0.5, 0.0]]
+        );
+        assert_eq!(categorical.mode(), vec![0, 0]);
+
+        let elites = [vec![0, 3]];
+        let elites: Vec<&Vec<usize>> = elites.iter().collect();
+        assert_error!(
+            categorical.fit(&elites, 1.0),
+            ArgminError,
+            "Invalid parameter: \"`Categorical`: value 3 of variable 1 is not a valid category.\""
+        );
+    }
+
+    #[test]
+    fn test_categorical_invalid() {
+        for p in [vec![vec![0.0, 0.0]], vec![vec![1.0, -0.5]], vec![vec![]]] {
+            assert_error!(
+                Categorical::<f64>::new(p),
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`Categorical`: probabilities must be non-negative ",
+                    "and not all zero for each variable.\""
+                )
+            );
+        }
+        let categorical: Categorical<f64> = Categorical::uniform(3, 4).unwrap();
+        assert_eq!(categorical.probabilities(), &vec![vec![0.25; 4]; 3]);
+    }
+}
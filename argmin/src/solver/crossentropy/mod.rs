@@ -0,0 +1,471 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Cross-entropy method
+//!
+//! Sampling-based optimization which iteratively fits a parametric distribution to the best
+//! samples drawn from it. See [`CrossEntropy`] for details.
+//!
+//! ## Reference
+//!
+//! Reuven Y. Rubinstein and Dirk P. Kroese (2004). The Cross-Entropy Method: A Unified Approach
+//! to Combinatorial Optimization, Monte-Carlo Simulation and Machine Learning. Springer.
+//!
+//! Pieter-Tjerk de Boer, Dirk P. Kroese, Shie Mannor and Reuven Y. Rubinstein (2005). A tutorial
+//! on the cross-entropy method. Annals of Operations Research 134, 19–67.
+
+mod distribution;
+
+pub(crate) use self::distribution::standard_normal_like;
+pub use self::distribution::{Categorical, Gaussian, SamplingDistribution};
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, PopulationState, Problem, SerializeAlias, Solver, SyncAlias,
+    TerminationReason, KV,
+};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Cross-entropy method
+///
+/// In each iteration (generation), `num_samples` parameter vectors are drawn from a parametric
+/// distribution and scored via [`bulk_cost`](`crate::core::Problem::bulk_cost`), which computes
+/// the cost function values in parallel if the `rayon` feature is enabled. The distribution is
+/// then fitted to the elite samples, the fraction `elite_fraction` of samples with the lowest
+/// cost (`0.1` by default, see [`with_elite_fraction`](`CrossEntropy::with_elite_fraction`)). To
+/// avoid premature convergence, the fitted parameters are blended with the previous ones:
+/// `new = smoothing * fit + (1 - smoothing) * old`, with `smoothing = 0.7` by default (see
+/// [`with_smoothing`](`CrossEntropy::with_smoothing`)).
+///
+/// The distribution is any [`SamplingDistribution`], for instance a [`Gaussian`] with diagonal
+/// covariance matrix for continuous problems or [`Categorical`] for discrete problems. The
+/// fitted distribution can be obtained via [`distribution`](`CrossEntropy::distribution`).
+///
+/// The state holds the best sample of the current generation as individual and all samples of
+/// the current generation as population. The solver terminates once the spread of the
+/// distribution falls below a tolerance (`0` by default, see
+/// [`with_tolerance`](`CrossEntropy::with_tolerance`)). The random number generator is part of
+/// the solver, hence seeded runs are reproducible and can be checkpointed.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## References
+///
+/// Reuven Y. Rubinstein and Dirk P. Kroese (2004). The Cross-Entropy Method: A Unified Approach
+/// to Combinatorial Optimization, Monte-Carlo Simulation and Machine Learning. Springer.
+///
+/// Pieter-Tjerk de Boer, Dirk P. Kroese, Shie Mannor and Reuven Y. Rubinstein (2005). A tutorial
+/// on the cross-entropy method. Annals of Operations Research 134, 19–67.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct CrossEntropy<D, F, R> {
+    /// Sampling distribution
+    distribution: D,
+    /// Number of samples per generation
+    num_samples: usize,
+    /// Fraction of elite samples
+    elite_fraction: F,
+    /// Smoothing parameter
+    smoothing: F,
+    /// Tolerance on the spread of the distribution
+    tol: F,
+    /// Random number generator
+    rng: R,
+}
+
+impl<D, F> CrossEntropy<D, F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`CrossEntropy`]
+    ///
+    /// Takes the initial distribution and the number of samples per generation, which must be at
+    /// least 1, as input.
+    ///
+    /// Uses the `Xoshiro256PlusPlus` RNG internally. For use of another RNG or a seeded RNG,
+    /// consider using [`CrossEntropy::new_with_rng`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::crossentropy::{CrossEntropy, Gaussian};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let distribution = Gaussian::new(vec![0.0f64, 0.0], vec![1.0, 1.0]);
+    /// let cem: CrossEntropy<_, f64, _> = CrossEntropy::new(distribution, 100)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(distribution: D, num_samples: usize) -> Result<Self, Error> {
+        CrossEntropy::new_with_rng(
+            distribution,
+            num_samples,
+            Xoshiro256PlusPlus::from_entropy(),
+        )
+    }
+}
+
+impl<D, F, R> CrossEntropy<D, F, R>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`CrossEntropy`]
+    ///
+    /// Takes the initial distribution and the number of samples per generation, which must be at
+    /// least 1, as input.
+    /// Requires a RNG which must implement `rand::Rng` (and `serde::Serialize` if the `serde1`
+    /// feature is enabled).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::crossentropy::{Categorical, CrossEntropy};
+    /// # use argmin::core::Error;
+    /// use rand::SeedableRng;
+    /// use rand_xoshiro::Xoshiro256PlusPlus;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    /// let distribution: Categorical<f64> = Categorical::uniform(20, 2)?;
+    /// let cem: CrossEntropy<_, f64, _> = CrossEntropy::new_with_rng(distribution, 100, rng)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_rng(distribution: D, num_samples: usize, rng: R) -> Result<Self, Error> {
+        if num_samples == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CrossEntropy`: number of samples must be > 0."
+            ));
+        }
+        Ok(CrossEntropy {
+            distribution,
+            num_samples,
+            elite_fraction: float!(0.1),
+            smoothing: float!(0.7),
+            tol: float!(0.0),
+            rng,
+        })
+    }
+
+    /// Set the fraction of samples which are used to fit the distribution.
+    ///
+    /// Must be in `(0, 1]` and defaults to `0.1`. At least one sample is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::crossentropy::{CrossEntropy, Gaussian};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let distribution = Gaussian::new(vec![0.0f64, 0.0], vec![1.0, 1.0]);
+    /// let cem: CrossEntropy<_, f64, _> =
+    ///     CrossEntropy::new(distribution, 100)?.with_elite_fraction(0.2)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_elite_fraction(mut self, elite_fraction: F) -> Result<Self, Error> {
+        if elite_fraction <= float!(0.0) || elite_fraction > float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CrossEntropy`: elite fraction must be in (0, 1]."
+            ));
+        }
+        self.elite_fraction = elite_fraction;
+        Ok(self)
+    }
+
+    /// Set the smoothing parameter.
+    ///
+    /// Must be in `(0, 1]` and defaults to `0.7`. A value of `1` disables smoothing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::crossentropy::{CrossEntropy, Gaussian};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let distribution = Gaussian::new(vec![0.0f64, 0.0], vec![1.0, 1.0]);
+    /// let cem: CrossEntropy<_, f64, _> = CrossEntropy::new(distribution, 100)?.with_smoothing(0.5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_smoothing(mut self, smoothing: F) -> Result<Self, Error> {
+        if smoothing <= float!(0.0) || smoothing > float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CrossEntropy`: smoothing parameter must be in (0, 1]."
+            ));
+        }
+        self.smoothing = smoothing;
+        Ok(self)
+    }
+
+    /// Set the tolerance on the spread of the distribution.
+    ///
+    /// Must be non-negative and defaults to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::crossentropy::{CrossEntropy, Gaussian};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let distribution = Gaussian::new(vec![0.0f64, 0.0], vec![1.0, 1.0]);
+    /// let cem: CrossEntropy<_, f64, _> = CrossEntropy::new(distribution, 100)?.with_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CrossEntropy`: tolerance must be non-negative."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Returns the current sampling distribution
+    pub fn distribution(&self) -> &D {
+        &self.distribution
+    }
+}
+
+impl<O, D, P, F, R> Solver<O, PopulationState<P, F>> for CrossEntropy<D, F, R>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    D: SamplingDistribution<P, F> + SerializeAlias,
+    P: Clone + SyncAlias,
+    F: ArgminFloat,
+    R: Rng + SerializeAlias,
+{
+    const NAME: &'static str = "Cross-entropy method";
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: PopulationState<P, F>,
+    ) -> Result<(PopulationState<P, F>, Option<KV>), Error> {
+        let samples: Vec<P> = (0..self.num_samples)
+            .map(|_| self.distribution.sample(&mut self.rng))
+            .collect();
+        let costs = problem.bulk_cost(&samples)?;
+
+        let mut order: Vec<usize> = (0..self.num_samples).collect();
+        order.sort_by(|&a, &b| {
+            costs[a]
+                .partial_cmp(&costs[b])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let num_elites = (self.elite_fraction * F::from_usize(self.num_samples).unwrap())
+            .ceil()
+            .to_usize()
+            .unwrap()
+            .clamp(1, self.num_samples);
+        let elites: Vec<&P> = order[..num_elites].iter().map(|&i| &samples[i]).collect();
+        self.distribution.fit(&elites, self.smoothing)?;
+
+        let best = order[0];
+        let kv = make_kv!(
+            "elite_threshold" => costs[order[num_elites - 1]];
+            "spread" => self.distribution.spread();
+        );
+        Ok((
+            state
+                .individual(samples[best].clone())
+                .cost(costs[best])
+                .population(samples),
+            Some(kv),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &PopulationState<P, F>) -> TerminationReason {
+        if self.distribution.spread() <= self.tol {
+            return TerminationReason::TargetToleranceReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        crossentropy,
+        CrossEntropy<Gaussian<Vec<f64>>, f64, Xoshiro256PlusPlus>
+    );
+
+    struct Sphere {}
+
+    impl CostFunction for Sphere {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Vec<f64>) -> Result<f64, Error> {
+            Ok(p.iter().map(|x| (x - 1.0).powi(2)).sum())
+        }
+    }
+
+    /// Number of mismatches to a target bit string
+    struct Bits {
+        target: Vec<usize>,
+    }
+
+    impl CostFunction for Bits {
+        type Param = Vec<usize>;
+        type Output = f64;
+
+        fn cost(&self, p: &Vec<usize>) -> Result<f64, Error> {
+            Ok(p.iter()
+                .zip(self.target.iter())
+                .filter(|(a, b)| a != b)
+                .count() as f64)
+        }
+    }
+
+    fn gaussian() -> Gaussian<Vec<f64>> {
+        Gaussian::new(vec![-2.0, 3.0], vec![2.0, 2.0])
+    }
+
+    #[test]
+    fn test_new() {
+        let CrossEntropy {
+            distribution,
+            num_samples,
+            elite_fraction,
+            smoothing,
+            tol,
+            rng: _,
+        }: CrossEntropy<_, f64, _> = CrossEntropy::new(gaussian(), 50).unwrap();
+        assert_eq!(distribution, gaussian());
+        assert_eq!(num_samples, 50);
+        assert_eq!(elite_fraction.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(smoothing.to_ne_bytes(), 0.7f64.to_ne_bytes());
+        assert_eq!(tol.to_ne_bytes(), 0.0f64.to_ne_bytes());
+
+        let res: Result<CrossEntropy<_, f64, _>, _> = CrossEntropy::new(gaussian(), 0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`CrossEntropy`: number of samples must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_builders() {
+        let cem: CrossEntropy<_, f64, _> = CrossEntropy::new(gaussian(), 50)
+            .unwrap()
+            .with_elite_fraction(0.2)
+            .unwrap()
+            .with_smoothing(1.0)
+            .unwrap()
+            .with_tolerance(1e-3)
+            .unwrap();
+        assert_eq!(cem.elite_fraction.to_ne_bytes(), 0.2f64.to_ne_bytes());
+        assert_eq!(cem.smoothing.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(cem.tol.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+
+        for fraction in [0.0, -0.1, 1.1] {
+            assert_error!(
+                cem.clone().with_elite_fraction(fraction),
+                ArgminError,
+                "Invalid parameter: \"`CrossEntropy`: elite fraction must be in (0, 1].\""
+            );
+            assert_error!(
+                cem.clone().with_smoothing(fraction),
+                ArgminError,
+                "Invalid parameter: \"`CrossEntropy`: smoothing parameter must be in (0, 1].\""
+            );
+        }
+        assert_error!(
+            cem.with_tolerance(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`CrossEntropy`: tolerance must be non-negative.\""
+        );
+    }
+
+    #[test]
+    fn test_gaussian() {
+        let cem = CrossEntropy::new_with_rng(gaussian(), 100, Xoshiro256PlusPlus::seed_from_u64(1))
+            .unwrap()
+            .with_tolerance(1e-6)
+            .unwrap();
+        let res = Executor::new(Sphere {}, cem)
+            .configure(|state| state.max_iters(500))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            TerminationReason::TargetToleranceReached
+        );
+        let mean = res.solver().distribution().mean();
+        assert_relative_eq!(mean[0], 1.0, epsilon = 1e-4);
+        assert_relative_eq!(mean[1], 1.0, epsilon = 1e-4);
+        assert!(res.state().get_best_cost() < 1e-8);
+        assert_eq!(
+            res.problem().counts["cost_count"],
+            100 * res.state().get_iter()
+        );
+        assert_eq!(res.state().get_population().unwrap().len(), 100);
+    }
+
+    #[test]
+    fn test_categorical() {
+        let target = vec![1, 0, 1, 1, 0, 0, 1, 0, 1, 1, 1, 0, 0, 1, 0, 1, 0, 1, 1, 0];
+        let cem = CrossEntropy::new_with_rng(
+            Categorical::uniform(20, 2).unwrap(),
+            100,
+            Xoshiro256PlusPlus::seed_from_u64(1),
+        )
+        .unwrap()
+        .with_tolerance(1e-6)
+        .unwrap();
+        let res = Executor::new(
+            Bits {
+                target: target.clone(),
+            },
+            cem,
+        )
+        .configure(|state| state.max_iters(100))
+        .run()
+        .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            TerminationReason::TargetToleranceReached
+        );
+        assert_eq!(res.solver().distribution().mode(), target);
+        assert_eq!(res.state().get_best_param().unwrap(), &target);
+    }
+
+    #[test]
+    fn test_seeded_runs_reproducible() {
+        let run = |seed: u64| {
+            let cem =
+                CrossEntropy::new_with_rng(gaussian(), 20, Xoshiro256PlusPlus::seed_from_u64(seed))
+                    .unwrap();
+            Executor::new(Sphere {}, cem)
+                .configure(|state| state.max_iters(5))
+                .run()
+                .unwrap()
+                .solver()
+                .distribution()
+                .clone()
+        };
+        assert_eq!(run(3), run(3));
+        assert_ne!(run(3), run(4));
+    }
+}
@@ -0,0 +1,787 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # DIRECT (DIviding RECTangles)
+//!
+//! Deterministic, derivative-free global optimization of Lipschitz continuous cost functions on a
+//! box.
+//!
+//! See [`Direct`] for details.
+//!
+//! ## References
+//!
+//! \[0\] D. R. Jones, C. D. Perttunen and B. E. Stuckman (1993). Lipschitzian optimization without
+//! the Lipschitz constant. Journal of Optimization Theory and Applications 79, 157–181.
+//! <https://doi.org/10.1007/BF00941892>
+//!
+//! \[1\] J. M. Gablonsky and C. T. Kelley (2001). A Locally-Biased form of the DIRECT Algorithm.
+//! Journal of Global Optimization 21, 27–37. <https://doi.org/10.1023/A:1017930332101>
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, SyncAlias,
+    TerminationReason, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Variants of the [`Direct`] algorithm
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum DirectVariant {
+    /// Original DIRECT algorithm by Jones et al. \[0\]
+    #[default]
+    Original,
+    /// Locally-biased DIRECT-L algorithm by Gablonsky and Kelley \[1\]
+    LocallyBiased,
+}
+
+/// Hyperrectangle in the unit cube
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct Rectangle<F> {
+    /// Center of the hyperrectangle
+    center: Vec<F>,
+    /// Cost function value at the center
+    cost: F,
+    /// Side length in dimension `i` is `3^(-levels[i])`
+    levels: Vec<i32>,
+}
+
+/// # DIRECT (DIviding RECTangles)
+///
+/// Deterministic, derivative-free global optimization algorithm for Lipschitz continuous cost
+/// functions on a box, which does not require knowledge of the Lipschitz constant.
+///
+/// The box (set via the constructor) is normalized to the unit hypercube, whose center is
+/// evaluated during initialization. In each iteration the *potentially optimal* hyperrectangles
+/// are selected, which are the hyperrectangles which could contain the global minimum for some
+/// Lipschitz constant `K > 0` (those on the lower right convex hull of the points
+/// (size, cost function value at center)) and which would improve on the best cost function
+/// value `f_min` by at least `epsilon * |f_min|` (see
+/// [`with_epsilon`](`Direct::with_epsilon`)). Each selected hyperrectangle is trisected along
+/// its longest sides, which requires two cost function evaluations per longest side. All cost
+/// function evaluations of an iteration are computed via
+/// [`bulk_cost`](`crate::core::Problem::bulk_cost`) and are therefore computed in parallel if the
+/// `rayon` feature is enabled.
+///
+/// Two variants are available (see [`with_variant`](`Direct::with_variant`)):
+///
+/// * [`DirectVariant::Original`] (default): The size of a hyperrectangle is the distance from
+///   its center to its vertices and all hyperrectangles with the lowest cost function value of
+///   their size are candidates for selection.
+/// * [`DirectVariant::LocallyBiased`] (DIRECT-L): The size of a hyperrectangle is half the length
+///   of its longest side and only one hyperrectangle per size is a candidate for selection. This
+///   leads to fewer distinct sizes and fewer divisions per iteration, which biases the search
+///   towards local refinement and works well for problems with few local minima.
+///
+/// The number of hyperrectangles and the size of the smallest hyperrectangle (in normalized
+/// coordinates) are reported to the observers. Next to the maximum number of iterations and the
+/// target cost function value set via [`configure`](`crate::core::Executor::configure`) of
+/// [`Executor`](`crate::core::Executor`), the algorithm stops if the number of cost function
+/// evaluations reaches a budget (see [`with_max_evals`](`Direct::with_max_evals`)) or if the
+/// volume of the smallest hyperrectangle relative to the box falls below a given threshold (see
+/// [`with_min_volume`](`Direct::with_min_volume`)). Both conditions are checked after each
+/// iteration, therefore the evaluation budget may be exceeded by the evaluations of the last
+/// iteration.
+///
+/// An initial parameter vector is not needed and will be ignored if provided. Cost function values
+/// which are `NaN` (for instance outside of the domain of the cost function) are treated as `+∞`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## References
+///
+/// \[0\] D. R. Jones, C. D. Perttunen and B. E. Stuckman (1993). Lipschitzian optimization without
+/// the Lipschitz constant. Journal of Optimization Theory and Applications 79, 157–181.
+/// <https://doi.org/10.1007/BF00941892>
+///
+/// \[1\] J. M. Gablonsky and C. T. Kelley (2001). A Locally-Biased form of the DIRECT Algorithm.
+/// Journal of Global Optimization 21, 27–37. <https://doi.org/10.1023/A:1017930332101>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Direct<F> {
+    /// Lower and upper bound
+    bounds: (Vec<F>, Vec<F>),
+    /// Variant of the algorithm
+    variant: DirectVariant,
+    /// Required relative improvement of potentially optimal hyperrectangles
+    epsilon: F,
+    /// Maximum number of cost function evaluations
+    max_evals: u64,
+    /// Stop if the relative volume of the smallest hyperrectangle falls below this value
+    min_volume: F,
+    /// All hyperrectangles
+    rectangles: Vec<Rectangle<F>>,
+}
+
+impl<F> Direct<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `Direct`
+    ///
+    /// Takes the bounds of the search space as a tuple `(lower_bound, upper_bound)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::direct::Direct;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let direct = Direct::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(bounds: (Vec<F>, Vec<F>)) -> Result<Self, Error> {
+        if bounds.0.is_empty() || bounds.0.len() != bounds.1.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Direct`: lower and upper bound must be of the same non-zero length."
+            ));
+        }
+        if bounds.0.iter().zip(bounds.1.iter()).any(|(l, u)| l >= u) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Direct`: lower bound must be smaller than upper bound."
+            ));
+        }
+        Ok(Direct {
+            bounds,
+            variant: DirectVariant::Original,
+            epsilon: float!(1e-4),
+            max_evals: u64::MAX,
+            min_volume: float!(0.0),
+            rectangles: vec![],
+        })
+    }
+
+    /// Set the variant of the algorithm
+    ///
+    /// Defaults to [`DirectVariant::Original`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::direct::{Direct, DirectVariant};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let direct = Direct::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]))?
+    ///     .with_variant(DirectVariant::LocallyBiased);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_variant(mut self, variant: DirectVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Set the required relative improvement of potentially optimal hyperrectangles
+    ///
+    /// Must be non-negative and defaults to `1e-4`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::direct::Direct;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let direct = Direct::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]))?.with_epsilon(1e-3)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_epsilon(mut self, epsilon: F) -> Result<Self, Error> {
+        if epsilon < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Direct`: epsilon must be >= 0."
+            ));
+        }
+        self.epsilon = epsilon;
+        Ok(self)
+    }
+
+    /// Set the maximum number of cost function evaluations
+    ///
+    /// Defaults to `u64::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::direct::Direct;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let direct = Direct::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]))?.with_max_evals(1000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_max_evals(mut self, max_evals: u64) -> Self {
+        self.max_evals = max_evals;
+        self
+    }
+
+    /// Set the volume of the smallest hyperrectangle relative to the volume of the box below which
+    /// the algorithm stops
+    ///
+    /// Must be in `[0, 1)` and defaults to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::direct::Direct;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let direct = Direct::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]))?.with_min_volume(1e-8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_min_volume(mut self, min_volume: F) -> Result<Self, Error> {
+        if min_volume < float!(0.0) || min_volume >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Direct`: minimum volume must be in [0, 1)."
+            ));
+        }
+        self.min_volume = min_volume;
+        Ok(self)
+    }
+
+    /// Map a point of the unit hypercube to the box
+    fn scale_from_unit(&self, x: &[F]) -> Vec<F> {
+        x.iter()
+            .zip(self.bounds.0.iter().zip(self.bounds.1.iter()))
+            .map(|(&xi, (&l, &u))| l + xi * (u - l))
+            .collect()
+    }
+
+    /// Key which is equal for all hyperrectangles of the same size
+    fn size_key(&self, levels: &[i32]) -> Vec<i32> {
+        match self.variant {
+            DirectVariant::Original => {
+                let mut key = levels.to_vec();
+                key.sort_unstable();
+                key
+            }
+            DirectVariant::LocallyBiased => vec![*levels.iter().min().unwrap()],
+        }
+    }
+
+    /// Size of a hyperrectangle with the given size key
+    fn size(&self, key: &[i32]) -> F {
+        let third: F = float!(1.0 / 3.0);
+        match self.variant {
+            DirectVariant::Original => {
+                float!(0.5)
+                    * key
+                        .iter()
+                        .fold(float!(0.0), |acc, &k| acc + third.powi(2 * k))
+                        .sqrt()
+            }
+            DirectVariant::LocallyBiased => float!(0.5) * third.powi(key[0]),
+        }
+    }
+
+    /// Index of the hyperrectangle with the lowest cost function value
+    fn best(&self) -> usize {
+        self.rectangles.iter().enumerate().fold(0, |best, (i, r)| {
+            if r.cost < self.rectangles[best].cost {
+                i
+            } else {
+                best
+            }
+        })
+    }
+
+    /// Size of the smallest hyperrectangle
+    fn min_size(&self) -> F {
+        self.rectangles
+            .iter()
+            .map(|r| self.size(&self.size_key(&r.levels)))
+            .fold(F::infinity(), |acc, s| acc.min(s))
+    }
+
+    /// Volume of the smallest hyperrectangle relative to the box
+    fn min_rel_volume(&self) -> F {
+        let max_level_sum = self
+            .rectangles
+            .iter()
+            .map(|r| r.levels.iter().sum::<i32>())
+            .max()
+            .unwrap_or(0);
+        float!(1.0 / 3.0).powi(max_level_sum)
+    }
+
+    /// Indices of the potentially optimal hyperrectangles
+    fn potentially_optimal(&self) -> Vec<usize> {
+        // For each size, collect the hyperrectangles with the lowest cost function value
+        let mut groups: BTreeMap<Vec<i32>, (F, Vec<usize>)> = BTreeMap::new();
+        for (i, r) in self.rectangles.iter().enumerate() {
+            let entry = groups
+                .entry(self.size_key(&r.levels))
+                .or_insert((F::infinity(), vec![]));
+            if r.cost < entry.0 {
+                *entry = (r.cost, vec![i]);
+            } else if r.cost == entry.0
+                && (self.variant == DirectVariant::Original || entry.1.is_empty())
+            {
+                entry.1.push(i);
+            }
+        }
+
+        let mut points: Vec<(F, F, Vec<usize>)> = groups
+            .into_iter()
+            .filter(|(_, (_, idxs))| !idxs.is_empty())
+            .map(|(key, (cost, idxs))| (self.size(&key), cost, idxs))
+            .collect();
+        points.sort_by(|a, b| {
+            a.0.partial_cmp(&b.0)
+                .unwrap()
+                .then(a.1.partial_cmp(&b.1).unwrap())
+        });
+        points.dedup_by(|b, a| a.0 == b.0);
+
+        // The convex hull starts at the largest hyperrectangle with the lowest cost
+        let (start, f_min) =
+            points
+                .iter()
+                .enumerate()
+                .fold((0, F::infinity()), |(start, f_min), (i, p)| {
+                    if p.1 <= f_min {
+                        (i, p.1)
+                    } else {
+                        (start, f_min)
+                    }
+                });
+
+        // Lower right convex hull
+        let mut hull: Vec<usize> = vec![];
+        for j in start..points.len() {
+            while hull.len() >= 2 {
+                let a = &points[hull[hull.len() - 2]];
+                let b = &points[hull[hull.len() - 1]];
+                let c = &points[j];
+                if (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0) <= float!(0.0) {
+                    hull.pop();
+                } else {
+                    break;
+                }
+            }
+            hull.push(j);
+        }
+
+        // Require a sufficient improvement over the best cost function value
+        let threshold = f_min - self.epsilon * f_min.abs();
+        let mut selected = vec![];
+        for (h, &j) in hull.iter().enumerate() {
+            let (d, f, idxs) = &points[j];
+            if let Some(&k) = hull.get(h + 1) {
+                let slope = (points[k].1 - *f) / (points[k].0 - *d);
+                if *f - slope * *d > threshold {
+                    continue;
+                }
+            }
+            selected.extend(idxs.iter().cloned());
+        }
+        selected
+    }
+}
+
+/// Replace `NaN` with `+∞` such that cost function values can be ordered
+fn nan_to_inf<F: ArgminFloat>(cost: F) -> F {
+    if cost.is_nan() {
+        F::infinity()
+    } else {
+        cost
+    }
+}
+
+impl<O, F> Solver<O, IterState<Vec<F>, (), (), (), F>> for Direct<F>
+where
+    O: CostFunction<Param = Vec<F>, Output = F> + SyncAlias,
+    F: ArgminFloat + SyncAlias,
+{
+    const NAME: &'static str = "DIRECT";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<Vec<F>, (), (), (), F>,
+    ) -> Result<(IterState<Vec<F>, (), (), (), F>, Option<KV>), Error> {
+        let n = self.bounds.0.len();
+        let center = vec![float!(0.5); n];
+        let param = self.scale_from_unit(&center);
+        let cost = nan_to_inf(problem.cost(&param)?);
+        self.rectangles = vec![Rectangle {
+            center,
+            cost,
+            levels: vec![0; n],
+        }];
+
+        Ok((
+            state.param(param).cost(cost),
+            Some(make_kv!(
+                "variant" => format!("{:?}", self.variant);
+                "epsilon" => self.epsilon;
+                "max_evals" => self.max_evals;
+                "min_volume" => self.min_volume;
+            )),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<Vec<F>, (), (), (), F>,
+    ) -> Result<(IterState<Vec<F>, (), (), (), F>, Option<KV>), Error> {
+        let selected = self.potentially_optimal();
+
+        // Sample the points at a distance of a third of the longest side from the center along
+        // all longest sides of all selected hyperrectangles
+        let mut divisions: Vec<(usize, Vec<usize>)> = Vec::with_capacity(selected.len());
+        let mut points: Vec<Vec<F>> = vec![];
+        for &idx in selected.iter() {
+            let rect = &self.rectangles[idx];
+            let min_level = *rect.levels.iter().min().unwrap();
+            let dims: Vec<usize> = (0..rect.levels.len())
+                .filter(|&i| rect.levels[i] == min_level)
+                .collect();
+            let delta = float!(1.0 / 3.0).powi(min_level + 1);
+            for &i in dims.iter() {
+                for sign in [float!(1.0), float!(-1.0)] {
+                    let mut point = rect.center.clone();
+                    point[i] = point[i] + sign * delta;
+                    points.push(point);
+                }
+            }
+            divisions.push((idx, dims));
+        }
+
+        let params: Vec<Vec<F>> = points.iter().map(|p| self.scale_from_unit(p)).collect();
+        let costs = problem.bulk_cost(&params)?;
+
+        // Trisect the selected hyperrectangles. Dimensions are divided in order of the lowest cost
+        // function value of their two new points, such that the best points end up in the largest
+        // hyperrectangles.
+        let mut children = points
+            .into_iter()
+            .zip(costs)
+            .map(|(center, cost)| Rectangle {
+                center,
+                cost: nan_to_inf(cost),
+                levels: vec![],
+            });
+        for (idx, dims) in divisions {
+            let mut new: Vec<(usize, Rectangle<F>, Rectangle<F>)> = dims
+                .into_iter()
+                .map(|i| (i, children.next().unwrap(), children.next().unwrap()))
+                .collect();
+            new.sort_by(|a, b| {
+                a.1.cost
+                    .min(a.2.cost)
+                    .partial_cmp(&b.1.cost.min(b.2.cost))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            for (i, mut plus, mut minus) in new {
+                self.rectangles[idx].levels[i] += 1;
+                plus.levels = self.rectangles[idx].levels.clone();
+                minus.levels = self.rectangles[idx].levels.clone();
+                self.rectangles.push(plus);
+                self.rectangles.push(minus);
+            }
+        }
+
+        let best = &self.rectangles[self.best()];
+        let param = self.scale_from_unit(&best.center);
+        let cost = best.cost;
+
+        Ok((
+            state.param(param).cost(cost),
+            Some(make_kv!(
+                "rectangles" => self.rectangles.len();
+                "potentially_optimal" => selected.len();
+                "min_rect_size" => self.min_size();
+            )),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<Vec<F>, (), (), (), F>) -> TerminationReason {
+        let evals = state
+            .get_func_counts()
+            .get("cost_count")
+            .cloned()
+            .unwrap_or(0);
+        if evals >= self.max_evals {
+            return TerminationReason::MaxFuncEvalsReached;
+        }
+        if self.min_rel_volume() <= self.min_volume {
+            return TerminationReason::TargetToleranceReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(direct, Direct<f64>);
+
+    /// Branin function (global minimum of `0.397887` at `(-pi, 12.275)`, `(pi, 2.275)` and
+    /// `(9.42478, 2.475)`)
+    struct Branin {}
+
+    impl CostFunction for Branin {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            use std::f64::consts::PI;
+            let (x, y) = (p[0], p[1]);
+            let b = 5.1 / (4.0 * PI.powi(2));
+            let c = 5.0 / PI;
+            let t = 1.0 / (8.0 * PI);
+            Ok((y - b * x.powi(2) + c * x - 6.0).powi(2) + 10.0 * (1.0 - t) * x.cos() + 10.0)
+        }
+    }
+
+    fn branin_bounds() -> (Vec<f64>, Vec<f64>) {
+        (vec![-5.0, 0.0], vec![10.0, 15.0])
+    }
+
+    #[test]
+    fn test_new() {
+        let direct = Direct::new(branin_bounds()).unwrap();
+        let Direct {
+            bounds,
+            variant,
+            epsilon,
+            max_evals,
+            min_volume,
+            rectangles,
+        } = direct;
+        assert_eq!(bounds, branin_bounds());
+        assert_eq!(variant, DirectVariant::Original);
+        assert_eq!(epsilon.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(max_evals, u64::MAX);
+        assert_eq!(min_volume.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert!(rectangles.is_empty());
+    }
+
+    #[test]
+    fn test_new_invalid_bounds() {
+        assert_error!(
+            Direct::new((vec![0.0f64], vec![1.0, 1.0])),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`Direct`: lower and upper bound must be of the same ",
+                "non-zero length.\""
+            )
+        );
+        assert_error!(
+            Direct::new((vec![], vec![] as Vec<f64>)),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`Direct`: lower and upper bound must be of the same ",
+                "non-zero length.\""
+            )
+        );
+        assert_error!(
+            Direct::new((vec![0.0f64, 1.0], vec![1.0, 1.0])),
+            ArgminError,
+            "Invalid parameter: \"`Direct`: lower bound must be smaller than upper bound.\""
+        );
+    }
+
+    #[test]
+    fn test_with_epsilon() {
+        let direct = Direct::new(branin_bounds()).unwrap();
+        assert_error!(
+            direct.with_epsilon(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`Direct`: epsilon must be >= 0.\""
+        );
+        let direct = Direct::new(branin_bounds()).unwrap();
+        let direct = direct.with_epsilon(0.0).unwrap();
+        assert_eq!(direct.epsilon.to_ne_bytes(), 0.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_with_min_volume() {
+        for v in [-1.0, 1.0] {
+            let direct = Direct::new(branin_bounds()).unwrap();
+            assert_error!(
+                direct.with_min_volume(v),
+                ArgminError,
+                "Invalid parameter: \"`Direct`: minimum volume must be in [0, 1).\""
+            );
+        }
+        let direct = Direct::new(branin_bounds()).unwrap();
+        let direct = direct.with_min_volume(1e-6).unwrap();
+        assert_eq!(direct.min_volume.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_init() {
+        let mut direct = Direct::new(branin_bounds()).unwrap();
+        let mut problem = Problem::new(Branin {});
+        let (state, _) = direct
+            .init(&mut problem, IterState::new().param(vec![100.0, 100.0]))
+            .unwrap();
+        assert_eq!(state.get_param().unwrap(), &vec![2.5, 7.5]);
+        assert_relative_eq!(
+            state.get_cost(),
+            Branin {}.cost(&vec![2.5, 7.5]).unwrap(),
+            epsilon = f64::EPSILON
+        );
+        assert_eq!(direct.rectangles.len(), 1);
+        assert_eq!(problem.counts["cost_count"], 1);
+    }
+
+    #[test]
+    fn test_first_iteration() {
+        let mut direct = Direct::new(branin_bounds()).unwrap();
+        let mut problem = Problem::new(Branin {});
+        let (state, _) = direct.init(&mut problem, IterState::new()).unwrap();
+        let (_, kv) = direct.next_iter(&mut problem, state).unwrap();
+
+        // The unit cube is trisected along both dimensions
+        assert_eq!(direct.rectangles.len(), 5);
+        assert_eq!(problem.counts["cost_count"], 5);
+        assert_eq!(direct.rectangles[0].levels, vec![1, 1]);
+        let mut level_sums: Vec<i32> = direct.rectangles[1..]
+            .iter()
+            .map(|r| r.levels.iter().sum())
+            .collect();
+        level_sums.sort_unstable();
+        assert_eq!(level_sums, vec![1, 1, 2, 2]);
+        assert_relative_eq!(direct.min_size(), 0.5 * (2.0f64 / 9.0).sqrt());
+        assert_relative_eq!(direct.min_rel_volume(), 1.0 / 9.0);
+        let kv = format!("{:?}", kv.unwrap());
+        assert!(kv.contains("rectangles"));
+        assert!(kv.contains("min_rect_size"));
+    }
+
+    #[test]
+    fn test_potentially_optimal() {
+        let mut direct = Direct::new((vec![0.0], vec![1.0])).unwrap();
+        direct.rectangles = vec![
+            // Largest rectangle, always selected
+            Rectangle {
+                center: vec![0.5],
+                cost: 3.0,
+                levels: vec![0],
+            },
+            // Lowest cost
+            Rectangle {
+                center: vec![0.1],
+                cost: 0.0,
+                levels: vec![2],
+            },
+            // Above the hull
+            Rectangle {
+                center: vec![0.2],
+                cost: 2.5,
+                levels: vec![1],
+            },
+            // Same size, but worse than the one above
+            Rectangle {
+                center: vec![0.3],
+                cost: 2.8,
+                levels: vec![1],
+            },
+        ];
+        let mut selected = direct.potentially_optimal();
+        selected.sort_unstable();
+        assert_eq!(selected, vec![0, 1]);
+
+        // A large epsilon discards the rectangle with the lowest cost
+        direct.epsilon = 1.0;
+        direct.rectangles[1].cost = 1.0;
+        assert_eq!(direct.potentially_optimal(), vec![0]);
+    }
+
+    #[test]
+    fn test_direct() {
+        for variant in [DirectVariant::Original, DirectVariant::LocallyBiased] {
+            let direct = Direct::new(branin_bounds())
+                .unwrap()
+                .with_variant(variant)
+                .with_max_evals(500);
+            let res = Executor::new(Branin {}, direct)
+                .configure(|state| state.max_iters(1000))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                TerminationReason::MaxFuncEvalsReached
+            );
+            assert!(res.state.get_func_counts()["cost_count"] >= 500);
+            assert_relative_eq!(res.state.get_best_cost(), 0.397887, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_nan_cost() {
+        // Undefined for `x > 2`, which includes the center of the box and two of the three
+        // global minima
+        struct PartialBranin {}
+
+        impl CostFunction for PartialBranin {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                if p[0] > 2.0 {
+                    Ok(f64::NAN)
+                } else {
+                    Branin {}.cost(p)
+                }
+            }
+        }
+
+        for variant in [DirectVariant::Original, DirectVariant::LocallyBiased] {
+            let mut direct = Direct::new(branin_bounds()).unwrap().with_variant(variant);
+            let mut problem = Problem::new(PartialBranin {});
+            let (state, _) = direct.init(&mut problem, IterState::new()).unwrap();
+            assert!(state.get_cost().is_infinite() && state.get_cost() > 0.0);
+            assert_eq!(direct.potentially_optimal(), vec![0]);
+
+            let direct = direct.with_max_evals(1000);
+            let res = Executor::new(PartialBranin {}, direct)
+                .configure(|state| state.max_iters(1000))
+                .run()
+                .unwrap();
+            let best = res.state.get_best_param().unwrap();
+            assert_relative_eq!(res.state.get_best_cost(), 0.397887, epsilon = 1e-3);
+            assert_relative_eq!(best[0], -std::f64::consts::PI, epsilon = 1e-1);
+        }
+    }
+
+    #[test]
+    fn test_min_volume() {
+        let direct = Direct::new(branin_bounds())
+            .unwrap()
+            .with_min_volume(1e-6)
+            .unwrap();
+        let res = Executor::new(Branin {}, direct)
+            .configure(|state| state.max_iters(1000))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetToleranceReached
+        );
+        assert!(res.solver.min_rel_volume() <= 1e-6);
+    }
+}
@@ -0,0 +1,573 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, Executor, FitStatistics, Jacobian, Operator, State, TerminationReason,
+};
+use crate::solver::gaussnewton::GaussNewtonLS;
+use crate::solver::linesearch::{condition::ArmijoCondition, BacktrackingLineSearch};
+use argmin_math::{
+    ArgminCholeskySolve, ArgminDot, ArgminMul, ArgminNorm, ArgminQrSolve, ArgminScaledAdd,
+    ArgminToVec, ArgminTranspose,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Result of a curve fit (see [`CurveFit`] and [`curve_fit`])
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct CurveFitResult<F> {
+    /// Fitted parameters
+    pub param: Vec<F>,
+    /// Covariance matrix, standard errors and correlation matrix of the fitted parameters
+    pub statistics: FitStatistics<F>,
+    /// Sum of squared (scaled) residuals at the fitted parameters
+    pub chi_squared: F,
+    /// Number of iterations of the solver
+    pub iterations: u64,
+    /// Reason why the solver terminated
+    pub termination_reason: TerminationReason,
+}
+
+impl<F: ArgminFloat> CurveFitResult<F> {
+    /// Standard errors of the fitted parameters
+    pub fn standard_errors(&self) -> &[F] {
+        &self.statistics.standard_errors
+    }
+
+    /// Confidence intervals of the fitted parameters for the given confidence `level` (see
+    /// [`FitStatistics::confidence_intervals`]).
+    pub fn confidence_intervals(&self, level: F) -> Result<Vec<(F, F)>, Error> {
+        self.statistics.confidence_intervals(level)
+    }
+}
+
+/// Least squares problem of fitting a model to data, with residuals
+/// `(model(x_i, p) - y_i) / sigma_i` and a Jacobian computed via central differences.
+struct CurveFitProblem<'a, M, X, F> {
+    model: &'a M,
+    x: &'a [X],
+    y: &'a [F],
+    sigma: Option<&'a [F]>,
+}
+
+impl<'a, M, X, F> CurveFitProblem<'a, M, X, F>
+where
+    M: Fn(&X, &[F]) -> F,
+    F: ArgminFloat,
+{
+    fn residual(&self, i: usize, param: &[F]) -> F {
+        let r = (self.model)(&self.x[i], param) - self.y[i];
+        match self.sigma {
+            Some(sigma) => r / sigma[i],
+            None => r,
+        }
+    }
+}
+
+impl<'a, M, X, F> Operator for CurveFitProblem<'a, M, X, F>
+where
+    M: Fn(&X, &[F]) -> F,
+    F: ArgminFloat,
+{
+    type Param = Vec<F>;
+    type Output = Vec<F>;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok((0..self.x.len()).map(|i| self.residual(i, param)).collect())
+    }
+}
+
+impl<'a, M, X, F> Jacobian for CurveFitProblem<'a, M, X, F>
+where
+    M: Fn(&X, &[F]) -> F,
+    F: ArgminFloat,
+{
+    type Param = Vec<F>;
+    type Jacobian = Vec<Vec<F>>;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        let n = param.len();
+        let eps_cbrt = F::epsilon().cbrt();
+        let mut jacobian = vec![vec![float!(0.0); n]; self.x.len()];
+        let mut p = param.clone();
+        for j in 0..n {
+            let h = eps_cbrt * param[j].abs().max(float!(1.0));
+            p[j] = param[j] + h;
+            let forward: Vec<F> = (0..self.x.len()).map(|i| self.residual(i, &p)).collect();
+            p[j] = param[j] - h;
+            for (i, row) in jacobian.iter_mut().enumerate() {
+                row[j] = (forward[i] - self.residual(i, &p)) / (h + h);
+            }
+            p[j] = param[j];
+        }
+        Ok(jacobian)
+    }
+}
+
+/// # Curve fitting
+///
+/// Fits the parameters `p` of a model `y = model(x, p)` to data `(x_i, y_i)` by minimizing
+///
+/// `\sum_i ((model(x_i, p) - y_i) / sigma_i)^2`
+///
+/// where `sigma_i` are optional standard deviations of the observations `y_i` (see
+/// [`with_sigma`](`CurveFit::with_sigma`)). The Jacobian is computed via central differences and
+/// the problem is solved with [`GaussNewtonLS`] using a backtracking line search. Along with the
+/// fitted parameters, their covariance matrix and standard errors are returned (see
+/// [`CurveFitResult`] and [`FitStatistics`]).
+///
+/// For simple cases, [`curve_fit`] can be used instead.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::Error;
+/// use argmin::solver::gaussnewton::CurveFit;
+///
+/// # fn main() -> Result<(), Error> {
+/// let x: Vec<f64> = (0..20).map(|i| i as f64 * 0.5).collect();
+/// let y: Vec<f64> = x.iter().map(|x| 3.0 * (-0.4 * x).exp() + 0.5).collect();
+///
+/// let res = CurveFit::new(|x: &f64, p: &[f64]| p[0] * (-p[1] * x).exp() + p[2], x, y)?
+///     .with_max_iters(50)
+///     .fit(vec![1.0, 0.1, 0.0])?;
+///
+/// # assert!((res.param[0] - 3.0).abs() < 1e-6);
+/// # assert!((res.param[1] - 0.4).abs() < 1e-6);
+/// # assert!((res.param[2] - 0.5).abs() < 1e-6);
+/// let standard_errors = res.standard_errors();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CurveFit<M, X, F> {
+    /// Model `model(x, p)`
+    model: M,
+    /// Independent variable
+    x: Vec<X>,
+    /// Observations
+    y: Vec<F>,
+    /// Standard deviations of the observations
+    sigma: Option<Vec<F>>,
+    /// Whether `sigma` are absolute standard deviations
+    absolute_sigma: bool,
+    /// Maximum number of iterations
+    max_iters: u64,
+    /// Tolerance for the stopping criterion based on cost difference
+    tol: F,
+}
+
+impl<M, X, F> CurveFit<M, X, F>
+where
+    M: Fn(&X, &[F]) -> F,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`CurveFit`] from a model and the data.
+    ///
+    /// `x` and `y` must be nonempty and of the same length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::CurveFit;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let curve_fit = CurveFit::new(
+    ///     |x: &f64, p: &[f64]| p[0] + p[1] * x,
+    ///     vec![0.0, 1.0, 2.0],
+    ///     vec![1.0, 3.1, 4.9],
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(model: M, x: Vec<X>, y: Vec<F>) -> Result<Self, Error> {
+        if x.is_empty() || x.len() != y.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CurveFit`: `x` and `y` must be nonempty and of the same length."
+            ));
+        }
+        Ok(CurveFit {
+            model,
+            x,
+            y,
+            sigma: None,
+            absolute_sigma: false,
+            max_iters: 100,
+            tol: F::epsilon().sqrt(),
+        })
+    }
+
+    /// Set the standard deviations of the observations.
+    ///
+    /// Residuals are divided by `sigma`. `sigma` must be of the same length as `y` and all
+    /// entries must be positive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::CurveFit;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let x = vec![0.0, 1.0, 2.0];
+    /// # let y = vec![1.0, 3.1, 4.9];
+    /// let curve_fit = CurveFit::new(|x: &f64, p: &[f64]| p[0] + p[1] * x, x, y)?
+    ///     .with_sigma(vec![0.1, 0.1, 0.2])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_sigma(mut self, sigma: Vec<F>) -> Result<Self, Error> {
+        if sigma.len() != self.y.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CurveFit`: `sigma` must be of the same length as `y`."
+            ));
+        }
+        if sigma.iter().any(|s| !(*s > float!(0.0) && s.is_finite())) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CurveFit`: `sigma` must be positive and finite."
+            ));
+        }
+        self.sigma = Some(sigma);
+        Ok(self)
+    }
+
+    /// Treat `sigma` as absolute standard deviations.
+    ///
+    /// If `true`, the covariance matrix of the parameters is not scaled by the residual variance.
+    /// Defaults to `false`, which means that only the relative magnitudes of `sigma` matter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::CurveFit;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let x = vec![0.0, 1.0, 2.0];
+    /// # let y = vec![1.0, 3.1, 4.9];
+    /// let curve_fit = CurveFit::new(|x: &f64, p: &[f64]| p[0] + p[1] * x, x, y)?
+    ///     .with_sigma(vec![0.1, 0.1, 0.2])?
+    ///     .with_absolute_sigma(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_absolute_sigma(mut self, absolute_sigma: bool) -> Self {
+        self.absolute_sigma = absolute_sigma;
+        self
+    }
+
+    /// Set the maximum number of iterations.
+    ///
+    /// Defaults to `100`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::CurveFit;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let x = vec![0.0, 1.0, 2.0];
+    /// # let y = vec![1.0, 3.1, 4.9];
+    /// let curve_fit =
+    ///     CurveFit::new(|x: &f64, p: &[f64]| p[0] + p[1] * x, x, y)?.with_max_iters(20);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_max_iters(mut self, max_iters: u64) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+
+    /// Set tolerance for the stopping criterion based on cost difference.
+    ///
+    /// Tolerance must be larger than zero and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::CurveFit;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let x = vec![0.0, 1.0, 2.0];
+    /// # let y = vec![1.0, 3.1, 4.9];
+    /// let curve_fit =
+    ///     CurveFit::new(|x: &f64, p: &[f64]| p[0] + p[1] * x, x, y)?.with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CurveFit`: tol must be positive."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Fit the model to the data, starting from the initial guess `init_param`.
+    ///
+    /// Requires more observations than parameters.
+    pub fn fit(&self, init_param: Vec<F>) -> Result<CurveFitResult<F>, Error>
+    where
+        Vec<F>: ArgminMul<F, Vec<F>>
+            + ArgminNorm<F>
+            + ArgminDot<Vec<F>, F>
+            + ArgminScaledAdd<Vec<F>, F, Vec<F>>
+            + ArgminToVec<Vec<F>>,
+        Vec<Vec<F>>: ArgminTranspose<Vec<Vec<F>>>
+            + ArgminDot<Vec<Vec<F>>, Vec<Vec<F>>>
+            + ArgminDot<Vec<F>, Vec<F>>
+            + ArgminQrSolve<Vec<F>, Vec<F>>
+            + ArgminCholeskySolve<Vec<F>, Vec<F>>
+            + ArgminToVec<Vec<Vec<F>>>,
+    {
+        if init_param.is_empty() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CurveFit`: initial parameter vector must not be empty."
+            ));
+        }
+        if init_param.len() >= self.y.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CurveFit`: more observations than parameters required."
+            ));
+        }
+
+        let problem = CurveFitProblem {
+            model: &self.model,
+            x: &self.x,
+            y: &self.y,
+            sigma: self.sigma.as_deref(),
+        };
+
+        let linesearch: BacktrackingLineSearch<Vec<F>, Vec<F>, ArmijoCondition<F>, F> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(float!(1e-4))?);
+        let solver = GaussNewtonLS::new(linesearch).with_tolerance(self.tol)?;
+
+        let res = Executor::new(problem, solver)
+            .configure(|state| state.param(init_param).max_iters(self.max_iters))
+            .ctrlc(false)
+            .run()?;
+
+        let statistics = if self.absolute_sigma {
+            res.fit_statistics_absolute_sigma()?
+        } else {
+            res.fit_statistics()?
+        };
+        let chi_squared = statistics.residual_variance * float!(statistics.dof as f64);
+
+        Ok(CurveFitResult {
+            param: statistics.param.clone(),
+            statistics,
+            chi_squared,
+            iterations: res.state.get_iter(),
+            termination_reason: res.state.get_termination_reason(),
+        })
+    }
+}
+
+/// Fit the parameters `p` of a model `y = model(x, p)` to data `(x_i, y_i)`, starting from the
+/// initial guess `init_param`.
+///
+/// Shorthand for [`CurveFit::new`] followed by [`CurveFit::fit`] with default settings.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::Error;
+/// use argmin::solver::gaussnewton::curve_fit;
+///
+/// # fn main() -> Result<(), Error> {
+/// let x = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+/// let y = vec![1.1, 2.9, 5.2, 6.8, 9.1];
+///
+/// let res = curve_fit(|x: &f64, p: &[f64]| p[0] + p[1] * x, x, y, vec![0.0, 1.0])?;
+///
+/// let (intercept, slope) = (res.param[0], res.param[1]);
+/// let intervals = res.confidence_intervals(0.95)?;
+/// # assert!(intervals[1].0 < slope && slope < intervals[1].1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn curve_fit<M, X, F>(
+    model: M,
+    x: Vec<X>,
+    y: Vec<F>,
+    init_param: Vec<F>,
+) -> Result<CurveFitResult<F>, Error>
+where
+    M: Fn(&X, &[F]) -> F,
+    F: ArgminFloat,
+    Vec<F>: ArgminMul<F, Vec<F>>
+        + ArgminNorm<F>
+        + ArgminDot<Vec<F>, F>
+        + ArgminScaledAdd<Vec<F>, F, Vec<F>>
+        + ArgminToVec<Vec<F>>,
+    Vec<Vec<F>>: ArgminTranspose<Vec<Vec<F>>>
+        + ArgminDot<Vec<Vec<F>>, Vec<Vec<F>>>
+        + ArgminDot<Vec<F>, Vec<F>>
+        + ArgminQrSolve<Vec<F>, Vec<F>>
+        + ArgminCholeskySolve<Vec<F>, Vec<F>>
+        + ArgminToVec<Vec<Vec<F>>>,
+{
+    CurveFit::new(model, x, y)?.fit(init_param)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(curve_fit_result, CurveFitResult<f64>);
+
+    fn exponential(x: &f64, p: &[f64]) -> f64 {
+        p[0] * (-p[1] * x).exp() + p[2]
+    }
+
+    #[test]
+    fn test_jacobian() {
+        let x = vec![0.0, 1.0, 2.0];
+        let y = vec![0.0; 3];
+        let problem = CurveFitProblem {
+            model: &exponential,
+            x: &x,
+            y: &y,
+            sigma: None,
+        };
+        let p = vec![2.0, 0.5, 1.0];
+        let jacobian = problem.jacobian(&p).unwrap();
+        for (i, x) in x.iter().enumerate() {
+            let e = (-p[1] * x).exp();
+            assert_relative_eq!(jacobian[i][0], e, epsilon = 1e-8);
+            assert_relative_eq!(jacobian[i][1], -p[0] * x * e, epsilon = 1e-8);
+            assert_relative_eq!(jacobian[i][2], 1.0, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_exponential_fit() {
+        let x: Vec<f64> = (0..20).map(|i| i as f64 * 0.5).collect();
+        let y: Vec<f64> = x
+            .iter()
+            .enumerate()
+            .map(|(i, x)| exponential(x, &[3.0, 0.4, 0.5]) + 0.01 * (i as f64 * 2.1).sin())
+            .collect();
+
+        let res = CurveFit::new(exponential, x, y)
+            .unwrap()
+            .fit(vec![1.0, 0.1, 0.0])
+            .unwrap();
+
+        assert_relative_eq!(res.param[0], 3.0, epsilon = 0.05);
+        assert_relative_eq!(res.param[1], 0.4, epsilon = 0.01);
+        assert_relative_eq!(res.param[2], 0.5, epsilon = 0.02);
+        assert_eq!(res.statistics.dof, 17);
+        for (p, (lo, hi)) in [3.0, 0.4, 0.5]
+            .iter()
+            .zip(res.confidence_intervals(0.99).unwrap())
+        {
+            assert!(lo < *p && *p < hi);
+        }
+        assert!(res.standard_errors().iter().all(|s| *s > 0.0 && *s < 0.05));
+        assert_eq!(res.termination_reason, TerminationReason::NoChangeInCost);
+    }
+
+    #[test]
+    fn test_sigma() {
+        // Linear fit with known noise level
+        let x = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let y = vec![1.1, 2.9, 5.2, 6.8, 9.1];
+        let sigma = vec![0.1; 5];
+        let line = |x: &f64, p: &[f64]| p[0] + p[1] * x;
+
+        let plain = curve_fit(line, x.clone(), y.clone(), vec![0.0, 0.0]).unwrap();
+        let relative = CurveFit::new(line, x.clone(), y.clone())
+            .unwrap()
+            .with_sigma(sigma.clone())
+            .unwrap()
+            .fit(vec![0.0, 0.0])
+            .unwrap();
+        let absolute = CurveFit::new(line, x.clone(), y)
+            .unwrap()
+            .with_sigma(sigma)
+            .unwrap()
+            .with_absolute_sigma(true)
+            .fit(vec![0.0, 0.0])
+            .unwrap();
+
+        // Constant sigma does not change the parameters or their relative uncertainties
+        for i in 0..2 {
+            assert_relative_eq!(plain.param[i], relative.param[i], epsilon = 1e-8);
+            assert_relative_eq!(
+                plain.standard_errors()[i],
+                relative.standard_errors()[i],
+                epsilon = 1e-8
+            );
+        }
+        assert_relative_eq!(
+            relative.chi_squared,
+            plain.chi_squared / 0.01,
+            epsilon = 1e-6
+        );
+
+        // Absolute sigma: Var(slope) = sigma^2 / Sxx
+        let sxx: f64 = x.iter().map(|x| (x - 2.0).powi(2)).sum();
+        assert_relative_eq!(
+            absolute.standard_errors()[1],
+            0.1 / sxx.sqrt(),
+            epsilon = 1e-8
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let line = |x: &f64, p: &[f64]| p[0] + p[1] * x;
+
+        let res = CurveFit::new(line, vec![0.0, 1.0], vec![1.0]);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`CurveFit`: `x` and `y` must be nonempty and of the same length.\""
+        );
+
+        let fit = CurveFit::new(line, vec![0.0, 1.0, 2.0], vec![1.0, 2.0, 3.0]).unwrap();
+        assert_error!(
+            fit.clone().with_sigma(vec![1.0]),
+            ArgminError,
+            "Invalid parameter: \"`CurveFit`: `sigma` must be of the same length as `y`.\""
+        );
+        assert_error!(
+            fit.clone().with_sigma(vec![1.0, 0.0, 1.0]),
+            ArgminError,
+            "Invalid parameter: \"`CurveFit`: `sigma` must be positive and finite.\""
+        );
+        assert_error!(
+            fit.clone().with_tolerance(0.0),
+            ArgminError,
+            "Invalid parameter: \"`CurveFit`: tol must be positive.\""
+        );
+        assert_error!(
+            fit.fit(vec![]),
+            ArgminError,
+            "Invalid parameter: \"`CurveFit`: initial parameter vector must not be empty.\""
+        );
+        assert_error!(
+            fit.fit(vec![0.0, 0.0, 0.0]),
+            ArgminError,
+            "Invalid parameter: \"`CurveFit`: more observations than parameters required.\""
+        );
+    }
+}
@@ -5,12 +5,15 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use super::step::{gauss_newton_step, LinearSolver};
 use crate::core::{
     ArgminFloat, CostFunction, DeserializeOwnedAlias, Error, Executor, Gradient, IterState,
     Jacobian, LineSearch, Operator, OptimizationResult, Problem, SerializeAlias, Solver,
     TerminationReason, KV,
 };
-use argmin_math::{ArgminDot, ArgminInv, ArgminMul, ArgminNorm, ArgminTranspose};
+use argmin_math::{
+    ArgminCholeskySolve, ArgminDot, ArgminMul, ArgminNorm, ArgminQrSolve, ArgminTranspose,
+};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +21,11 @@ use serde::{Deserialize, Serialize};
 ///
 /// Gauss-Newton method where an appropriate step length is obtained by a line search.
 ///
+/// The search direction is obtained by solving the linear least squares problem `J p = r` via
+/// the [`LinearSolver`] set with
+/// [`with_linear_solver`](`GaussNewtonLS::with_linear_solver`) (default: QR decomposition of the
+/// Jacobian).
+///
 /// Requires an initial parameter vector.
 ///
 /// ## Requirements on the optimization problem
@@ -35,6 +43,8 @@ pub struct GaussNewtonLS<L, F> {
     linesearch: L,
     /// Tolerance for the stopping criterion based on cost difference
     tol: F,
+    /// Linear solver used to compute the search direction
+    linear_solver: LinearSolver,
 }
 
 impl<L, F: ArgminFloat> GaussNewtonLS<L, F> {
@@ -51,6 +61,7 @@ impl<L, F: ArgminFloat> GaussNewtonLS<L, F> {
         GaussNewtonLS {
             linesearch,
             tol: F::epsilon().sqrt(),
+            linear_solver: LinearSolver::default(),
         }
     }
 
@@ -79,6 +90,24 @@ impl<L, F: ArgminFloat> GaussNewtonLS<L, F> {
         self.tol = tol;
         Ok(self)
     }
+
+    /// Set the linear solver used to compute the search direction.
+    ///
+    /// Defaults to [`LinearSolver::Qr`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::{GaussNewtonLS, LinearSolver};
+    /// # let linesearch = ();
+    /// let gauss_newton_ls: GaussNewtonLS<_, f64> =
+    ///     GaussNewtonLS::new(linesearch).with_linear_solver(LinearSolver::Cholesky);
+    /// ```
+    #[must_use]
+    pub fn with_linear_solver(mut self, linear_solver: LinearSolver) -> Self {
+        self.linear_solver = linear_solver;
+        self
+    }
 }
 
 impl<O, L, F, P, G, J, U> Solver<O, IterState<P, G, J, (), F>> for GaussNewtonLS<L, F>
@@ -91,10 +120,10 @@ where
         + SerializeAlias
         + DeserializeOwnedAlias
         + ArgminTranspose<J>
-        + ArgminInv<J>
         + ArgminDot<J, J>
-        + ArgminDot<G, P>
-        + ArgminDot<U, G>,
+        + ArgminDot<U, G>
+        + ArgminQrSolve<U, P>
+        + ArgminCholeskySolve<G, P>,
     L: Clone + LineSearch<P, F> + Solver<LineSearchProblem<O, F>, IterState<P, G, (), (), F>>,
     F: ArgminFloat,
 {
@@ -117,7 +146,13 @@ where
         let jacobian_t = jacobian.clone().t();
         let grad = jacobian_t.dot(&residuals);
 
-        let p: P = jacobian_t.dot(&jacobian).inv()?.dot(&grad);
+        let p: P = gauss_newton_step(
+            self.linear_solver,
+            &jacobian,
+            &jacobian_t,
+            &residuals,
+            &grad,
+        )?;
 
         self.linesearch.search_direction(p.mul(&(float!(-1.0))));
 
@@ -125,7 +160,7 @@ where
         let OptimizationResult {
             problem: mut line_problem,
             state: mut linesearch_state,
-            ..
+            solver: line_solver,
         } = Executor::new(
             LineSearchProblem::new(problem.take_problem().ok_or_else(argmin_error_closure!(
                 PotentialBug,
@@ -137,6 +172,8 @@ where
         .ctrlc(false)
         .run()?;
 
+        self.linesearch.consume_line_search(line_solver);
+
         // Here we cannot use `consume_problem` because the problem we need is hidden inside a
         // `LineSearchProblem` hidden inside a `Problem`. Therefore we have to split this in two
         // separate tasks: first getting the problem, then dealing with the function counts.
@@ -246,10 +283,22 @@ mod tests {
         let GaussNewtonLS {
             linesearch: ls,
             tol: t,
+            linear_solver: l,
         } = GaussNewtonLS::<_, f64>::new(MyLinesearch {});
 
         assert_eq!(ls, MyLinesearch {});
         assert_eq!(t.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(l, LinearSolver::Qr);
+    }
+
+    #[test]
+    fn test_linear_solver() {
+        let linesearch = ();
+        let GaussNewtonLS {
+            linear_solver: l, ..
+        } = GaussNewtonLS::<_, f64>::new(linesearch).with_linear_solver(LinearSolver::Cholesky);
+
+        assert_eq!(l, LinearSolver::Cholesky);
     }
 
     #[test]
@@ -439,21 +488,13 @@ mod tests {
 
         assert!(kv.is_none());
 
-        assert_relative_eq!(
-            state.param.as_ref().unwrap()[0],
-            7.105427357601002e-15,
-            epsilon = f64::EPSILON
-        );
+        assert_relative_eq!(state.param.as_ref().unwrap()[0], 0.0, epsilon = 1e-12);
         assert_relative_eq!(
             state.param.as_ref().unwrap()[1],
             2.25f64,
             epsilon = f64::EPSILON
         );
-        assert_relative_eq!(
-            state.best_param.as_ref().unwrap()[0],
-            7.105427357601002e-15,
-            epsilon = f64::EPSILON
-        );
+        assert_relative_eq!(state.best_param.as_ref().unwrap()[0], 0.0, epsilon = 1e-12);
         assert_relative_eq!(
             state.best_param.as_ref().unwrap()[1],
             2.25f64,
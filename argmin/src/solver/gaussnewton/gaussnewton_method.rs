@@ -5,11 +5,15 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use super::step::{gauss_newton_step, LinearSolver};
 use crate::core::{
     ArgminFloat, Error, IterState, Jacobian, Operator, Problem, Solver, State, TerminationReason,
     KV,
 };
-use argmin_math::{ArgminDot, ArgminInv, ArgminMul, ArgminNorm, ArgminSub, ArgminTranspose};
+use argmin_math::{
+    ArgminCholeskySolve, ArgminDot, ArgminMul, ArgminNorm, ArgminQrSolve, ArgminSub,
+    ArgminTranspose,
+};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +21,10 @@ use serde::{Deserialize, Serialize};
 ///
 /// The Gauss-Newton method is used to solve non-linear least squares problems.
 ///
+/// The step is obtained by solving the linear least squares problem `J p = r` via the
+/// [`LinearSolver`] set with [`with_linear_solver`](`GaussNewton::with_linear_solver`)
+/// (default: QR decomposition of the Jacobian).
+///
 /// Requires an initial parameter vector.
 ///
 /// ## Requirements on the optimization problem
@@ -34,6 +42,8 @@ pub struct GaussNewton<F> {
     gamma: F,
     /// Tolerance for the stopping criterion based on cost difference
     tol: F,
+    /// Linear solver used to compute the step
+    linear_solver: LinearSolver,
 }
 
 impl<F: ArgminFloat> GaussNewton<F> {
@@ -49,6 +59,7 @@ impl<F: ArgminFloat> GaussNewton<F> {
         GaussNewton {
             gamma: float!(1.0),
             tol: F::epsilon().sqrt(),
+            linear_solver: LinearSolver::default(),
         }
     }
 
@@ -101,6 +112,23 @@ impl<F: ArgminFloat> GaussNewton<F> {
         self.tol = tol;
         Ok(self)
     }
+
+    /// Set the linear solver used to compute the step.
+    ///
+    /// Defaults to [`LinearSolver::Qr`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::{GaussNewton, LinearSolver};
+    /// let gauss_newton: GaussNewton<f64> =
+    ///     GaussNewton::new().with_linear_solver(LinearSolver::Cholesky);
+    /// ```
+    #[must_use]
+    pub fn with_linear_solver(mut self, linear_solver: LinearSolver) -> Self {
+        self.linear_solver = linear_solver;
+        self
+    }
 }
 
 impl<F: ArgminFloat> Default for GaussNewton<F> {
@@ -116,10 +144,10 @@ where
     U: ArgminNorm<F>,
     J: Clone
         + ArgminTranspose<J>
-        + ArgminInv<J>
         + ArgminDot<J, J>
         + ArgminDot<U, P>
-        + ArgminDot<P, P>,
+        + ArgminQrSolve<U, P>
+        + ArgminCholeskySolve<P, P>,
     F: ArgminFloat,
 {
     const NAME: &'static str = "Gauss-Newton method";
@@ -139,12 +167,16 @@ where
         let residuals = problem.apply(param)?;
         let jacobian = problem.jacobian(param)?;
 
-        let p = jacobian
-            .clone()
-            .t()
-            .dot(&jacobian)
-            .inv()?
-            .dot(&jacobian.t().dot(&residuals));
+        let jacobian_t = jacobian.clone().t();
+        let grad: P = jacobian_t.dot(&residuals);
+
+        let p: P = gauss_newton_step(
+            self.linear_solver,
+            &jacobian,
+            &jacobian_t,
+            &residuals,
+            &grad,
+        )?;
 
         let new_param = param.sub(&p.mul(&self.gamma));
 
@@ -173,10 +205,15 @@ mod tests {
 
     #[test]
     fn test_new() {
-        let GaussNewton { tol: t, gamma: g } = GaussNewton::<f64>::new();
+        let GaussNewton {
+            tol: t,
+            gamma: g,
+            linear_solver: l,
+        } = GaussNewton::<f64>::new();
 
         assert_eq!(g.to_ne_bytes(), (1.0f64).to_ne_bytes());
         assert_eq!(t.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(l, LinearSolver::Qr);
     }
 
     #[test]
@@ -235,6 +272,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_linear_solver() {
+        let GaussNewton {
+            linear_solver: l, ..
+        } = GaussNewton::<f64>::new().with_linear_solver(LinearSolver::Cholesky);
+
+        assert_eq!(l, LinearSolver::Cholesky);
+    }
+
+    #[test]
+    fn test_next_iter_nearly_singular() {
+        use crate::core::State;
+
+        // Linear least squares problem `A x - b` with a nearly rank deficient `A`. `AᵀA` is
+        // singular in double precision, therefore the step cannot be computed from its inverse.
+        struct NearlySingular {}
+
+        impl Operator for NearlySingular {
+            type Param = Vec<f64>;
+            type Output = Vec<f64>;
+
+            fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(vec![
+                    p[0] + p[1] - 3.0,
+                    1e-8 * (p[0] - 1.0),
+                    1e-8 * (p[1] - 2.0),
+                ])
+            }
+        }
+
+        impl Jacobian for NearlySingular {
+            type Param = Vec<f64>;
+            type Jacobian = Vec<Vec<f64>>;
+
+            fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+                Ok(vec![vec![1.0, 1.0], vec![1e-8, 0.0], vec![0.0, 1e-8]])
+            }
+        }
+
+        for linear_solver in [LinearSolver::Qr, LinearSolver::Cholesky] {
+            let mut gn = GaussNewton::<f64>::new().with_linear_solver(linear_solver);
+            let state = IterState::new().param(vec![0.0, 0.0]);
+            let (state, kv) = gn
+                .next_iter(&mut Problem::new(NearlySingular {}), state)
+                .unwrap();
+            assert!(kv.is_none());
+            let param = state.get_param().unwrap();
+            assert!((param[0] - 1.0).abs() < 1e-6);
+            assert!((param[1] - 2.0).abs() < 1e-6);
+        }
+    }
+
     #[cfg(feature = "_ndarrayl")]
     #[test]
     fn test_next_iter_param_not_initialized() {
@@ -10,13 +10,30 @@
 //! * [Gauss-Newton method](`GaussNewton`)
 //! * [Gauss-Newton method with line search](`GaussNewtonLS`)
 //!
+//! The Gauss-Newton step is computed from a QR decomposition of the Jacobian by default, which
+//! avoids forming `(JᵀJ)^-1` explicitly. See [`LinearSolver`] for alternatives.
+//!
+//! Observation weights and robust loss functions can be added to any least squares problem by
+//! wrapping it in a [`RobustProblem`], which transforms the residuals such that the robust
+//! objective is again a sum of squares.
+//!
+//! For fitting a model `y = f(x, p)` to data, [`curve_fit`] and [`CurveFit`] provide a high-level
+//! interface which computes the Jacobian via finite differences and returns the fitted parameters
+//! together with their uncertainties.
+//!
 //! ## Reference
 //!
 //! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+mod curvefit;
 mod gaussnewton_linesearch;
 mod gaussnewton_method;
+mod robust;
+mod step;
 
+pub use curvefit::{curve_fit, CurveFit, CurveFitResult};
 pub use gaussnewton_linesearch::GaussNewtonLS;
 pub use gaussnewton_method::GaussNewton;
+pub use robust::{RobustLoss, RobustProblem};
+pub use step::LinearSolver;
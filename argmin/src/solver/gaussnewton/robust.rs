@@ -0,0 +1,598 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error, Jacobian, Operator};
+use argmin_math::{ArgminMap, ArgminMul, ArgminScaleRows, ArgminToVec};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Loss function `rho` applied to each (weighted) residual `r`
+///
+/// All losses behave like `r^2 / 2` for small residuals. With `z = r / c`, where `c` is the scale
+/// parameter of the loss, the losses are defined as follows:
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum RobustLoss<F> {
+    /// Standard least squares: `r^2 / 2`
+    Linear,
+    /// `r^2 / 2` for `|r| <= c` and `c (|r| - c / 2)` otherwise
+    Huber(F),
+    /// `c^2 (sqrt(1 + z^2) - 1)`
+    SoftL1(F),
+    /// `c^2 / 2 ln(1 + z^2)`
+    Cauchy(F),
+    /// Tukey's biweight: `c^2 / 6 (1 - (1 - z^2)^3)` for `|z| <= 1` and `c^2 / 6` otherwise.
+    ///
+    /// Residuals larger than `c` are ignored entirely.
+    Tukey(F),
+    /// `c^2 / 2 arctan(z^2)`
+    Arctan(F),
+}
+
+impl<F: ArgminFloat> RobustLoss<F> {
+    /// Value of the loss function `rho(r)`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::RobustLoss;
+    /// let loss = RobustLoss::Huber(1.0f64);
+    /// assert_eq!(loss.rho(0.5), 0.125);
+    /// assert_eq!(loss.rho(3.0), 2.5);
+    /// ```
+    pub fn rho(&self, r: F) -> F {
+        let half = float!(0.5);
+        match *self {
+            RobustLoss::Linear => half * r * r,
+            RobustLoss::Huber(c) => {
+                if r.abs() <= c {
+                    half * r * r
+                } else {
+                    c * (r.abs() - half * c)
+                }
+            }
+            RobustLoss::SoftL1(c) => {
+                let z2 = (r / c).powi(2);
+                c * c * z2 / ((float!(1.0) + z2).sqrt() + float!(1.0))
+            }
+            RobustLoss::Cauchy(c) => {
+                let z = r / c;
+                half * c * c * (z * z).ln_1p()
+            }
+            RobustLoss::Tukey(c) => {
+                let z2 = (r / c).powi(2);
+                let sixth = c * c / float!(6.0);
+                if z2 <= float!(1.0) {
+                    sixth * z2 * (float!(3.0) - float!(3.0) * z2 + z2 * z2)
+                } else {
+                    sixth
+                }
+            }
+            RobustLoss::Arctan(c) => {
+                let z = r / c;
+                half * c * c * (z * z).atan()
+            }
+        }
+    }
+
+    /// Weight `rho'(r) / r`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::RobustLoss;
+    /// let loss = RobustLoss::Huber(1.0f64);
+    /// assert_eq!(loss.weight(0.5), 1.0);
+    /// assert_eq!(loss.weight(4.0), 0.25);
+    /// ```
+    pub fn weight(&self, r: F) -> F {
+        let one = float!(1.0);
+        match *self {
+            RobustLoss::Linear => one,
+            RobustLoss::Huber(c) => {
+                if r.abs() <= c {
+                    one
+                } else {
+                    c / r.abs()
+                }
+            }
+            RobustLoss::SoftL1(c) => {
+                let z = r / c;
+                one / (one + z * z).sqrt()
+            }
+            RobustLoss::Cauchy(c) => {
+                let z = r / c;
+                one / (one + z * z)
+            }
+            RobustLoss::Tukey(c) => {
+                let z = r / c;
+                if z.abs() <= one {
+                    (one - z * z).powi(2)
+                } else {
+                    float!(0.0)
+                }
+            }
+            RobustLoss::Arctan(c) => {
+                let z = r / c;
+                one / (one + z.powi(4))
+            }
+        }
+    }
+
+    /// Scale parameter `c` of the loss (`None` for [`RobustLoss::Linear`])
+    fn scale(&self) -> Option<F> {
+        match *self {
+            RobustLoss::Linear => None,
+            RobustLoss::Huber(c)
+            | RobustLoss::SoftL1(c)
+            | RobustLoss::Cauchy(c)
+            | RobustLoss::Tukey(c)
+            | RobustLoss::Arctan(c) => Some(c),
+        }
+    }
+}
+
+/// # Weighted and robust nonlinear least squares
+///
+/// Wraps a least squares problem implementing [`Operator`] (residuals `r`) and [`Jacobian`] and
+/// turns it into a weighted and/or robust least squares problem
+///
+/// `min_x \sum_i rho(sqrt(w_i) r_i(x))`
+///
+/// with observation weights `w_i` (see [`with_weights`](`RobustProblem::with_weights`), for
+/// instance `1 / sigma_i^2`) and a [`RobustLoss`] `rho` which reduces the influence of outliers.
+///
+/// The wrapped problem again implements [`Operator`] and [`Jacobian`] and can therefore be
+/// solved by the Gauss-Newton type solvers [`GaussNewton`](`crate::solver::gaussnewton::GaussNewton`)
+/// and [`GaussNewtonLS`](`crate::solver::gaussnewton::GaussNewtonLS`). With the weighted
+/// residuals `s_i = sqrt(w_i) r_i`, the wrapped problem returns the residuals
+/// `z_i = sign(s_i) sqrt(2 rho(s_i))`, such that `||z||^2 / 2` equals the robust objective, and
+/// the Jacobian of these residuals, i.e. the rows of the Jacobian are scaled by
+/// `sqrt(w_i) omega_i |s_i| / |z_i|`, where `omega_i = rho'(s_i) / s_i` (see
+/// [`RobustLoss::weight`]). Costs reported by the solver (and therefore line searches, stopping
+/// criteria and the best parameter vector) refer to the robust objective.
+///
+/// For losses which saturate for large residuals ([`RobustLoss::Tukey`] and
+/// [`RobustLoss::Arctan`]) the objective is flat far away from the data. These losses require a
+/// starting point at which a sufficient number of residuals lies within the scale of the loss and
+/// should be combined with [`GaussNewtonLS`](`crate::solver::gaussnewton::GaussNewtonLS`).
+///
+/// Note that computing the Jacobian requires an additional evaluation of the residuals of the
+/// wrapped problem. These evaluations are not part of the `operator_count` of the solver and are
+/// counted separately, see [`residual_count`](`RobustProblem::residual_count`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, Executor, Jacobian, Operator};
+/// use argmin::solver::gaussnewton::{GaussNewton, RobustLoss, RobustProblem};
+/// # #[derive(Clone)]
+/// # struct LineFit { x: Vec<f64>, y: Vec<f64> }
+/// # impl Operator for LineFit {
+/// #     type Param = Vec<f64>;
+/// #     type Output = Vec<f64>;
+/// #     fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok(self.x.iter().zip(self.y.iter()).map(|(x, y)| p[0] + p[1] * x - y).collect())
+/// #     }
+/// # }
+/// # impl Jacobian for LineFit {
+/// #     type Param = Vec<f64>;
+/// #     type Jacobian = Vec<Vec<f64>>;
+/// #     fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+/// #         Ok(self.x.iter().map(|x| vec![1.0, *x]).collect())
+/// #     }
+/// # }
+/// # fn main() -> Result<(), Error> {
+/// # let x: Vec<f64> = (0..10).map(|i| i as f64).collect();
+/// # let mut y: Vec<f64> = x.iter().map(|x| 1.0 + 2.0 * x).collect();
+/// # y[9] += 50.0;
+/// // `LineFit` implements `Operator` and `Jacobian`
+/// let problem = LineFit { x, y };
+///
+/// let robust_problem = RobustProblem::new(problem, RobustLoss::Huber(1.0))?
+///     .with_weights(vec![1.0; 10])?;
+///
+/// let res = Executor::new(robust_problem, GaussNewton::new())
+///     .configure(|state| state.param(vec![0.0, 0.0]).max_iters(50))
+///     .run()?;
+/// # let best = res.state.best_param.unwrap();
+/// # assert!((best[1] - 2.0).abs() < 0.1);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct RobustProblem<O, U, F> {
+    /// Wrapped least squares problem
+    problem: O,
+    /// Loss function
+    loss: RobustLoss<F>,
+    /// Observation weights
+    weights: Option<U>,
+    /// Number of evaluations of the residuals of the wrapped problem in `jacobian`
+    #[cfg_attr(feature = "serde1", serde(skip))]
+    residual_count: AtomicU64,
+}
+
+impl<O: Clone, U: Clone, F: Clone> Clone for RobustProblem<O, U, F> {
+    fn clone(&self) -> Self {
+        RobustProblem {
+            problem: self.problem.clone(),
+            loss: self.loss.clone(),
+            weights: self.weights.clone(),
+            residual_count: AtomicU64::new(self.residual_count.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl<O, U, F: ArgminFloat> RobustProblem<O, U, F> {
+    /// Construct a new instance of [`RobustProblem`] from a least squares problem and a loss
+    /// function.
+    ///
+    /// The scale parameter of the loss must be positive and finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::{RobustLoss, RobustProblem};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let problem = ();
+    /// let robust_problem: RobustProblem<_, Vec<f64>, f64> =
+    ///     RobustProblem::new(problem, RobustLoss::Cauchy(2.0))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(problem: O, loss: RobustLoss<F>) -> Result<Self, Error> {
+        if let Some(c) = loss.scale() {
+            if c <= float!(0.0) || !c.is_finite() {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`RobustProblem`: scale parameter of loss must be positive and finite."
+                ));
+            }
+        }
+        Ok(RobustProblem {
+            problem,
+            loss,
+            weights: None,
+            residual_count: AtomicU64::new(0),
+        })
+    }
+
+    /// Set nonnegative observation weights, one per residual.
+    ///
+    /// Defaults to all weights being `1`. Returns an error if any weight is negative or not
+    /// finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::{RobustLoss, RobustProblem};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let problem = ();
+    /// let robust_problem = RobustProblem::new(problem, RobustLoss::Huber(1.0f64))?
+    ///     .with_weights(vec![1.0f64, 0.5, 2.0])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_weights(mut self, weights: U) -> Result<Self, Error>
+    where
+        U: ArgminToVec<Vec<F>>,
+    {
+        if weights
+            .to_vec()
+            .iter()
+            .any(|w| *w < float!(0.0) || !w.is_finite())
+        {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RobustProblem`: weights must be nonnegative and finite."
+            ));
+        }
+        self.weights = Some(weights);
+        Ok(self)
+    }
+
+    /// Returns the number of evaluations of the residuals of the wrapped problem performed while
+    /// computing the Jacobian
+    pub fn residual_count(&self) -> u64 {
+        self.residual_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns a reference to the wrapped least squares problem
+    pub fn inner(&self) -> &O {
+        &self.problem
+    }
+
+    /// Consumes `self` and returns the wrapped least squares problem
+    pub fn into_inner(self) -> O {
+        self.problem
+    }
+}
+
+impl<O, U, F> RobustProblem<O, U, F>
+where
+    U: Clone + ArgminMap<F> + ArgminMul<U, U>,
+    F: ArgminFloat,
+{
+    /// Returns the transformed residuals `sign(s) sqrt(2 rho(s))` and the factors by which the
+    /// rows of the Jacobian are scaled.
+    fn scale_residuals(&self, residuals: &U) -> (U, U) {
+        let (weighted, sqrt_weights) = match self.weights.as_ref() {
+            Some(weights) => {
+                let sqrt_weights = weights.map(|w| w.sqrt());
+                (residuals.mul(&sqrt_weights), Some(sqrt_weights))
+            }
+            None => (residuals.clone(), None),
+        };
+        let transformed = weighted.map(|s| {
+            let z = (float!(2.0) * self.loss.rho(s)).sqrt();
+            if s < float!(0.0) {
+                -z
+            } else {
+                z
+            }
+        });
+        // Derivative of `sign(s) sqrt(2 rho(s))` with respect to `s`, which tends to
+        // `rho'(s) / s` for `s -> 0`
+        let derivatives = weighted.map(|s| {
+            let z = (float!(2.0) * self.loss.rho(s)).sqrt();
+            if z > float!(0.0) {
+                self.loss.weight(s) * s.abs() / z
+            } else {
+                self.loss.weight(s)
+            }
+        });
+        let factors = match sqrt_weights {
+            Some(sqrt_weights) => sqrt_weights.mul(&derivatives),
+            None => derivatives,
+        };
+        (transformed, factors)
+    }
+}
+
+impl<O, P, U, F> Operator for RobustProblem<O, U, F>
+where
+    O: Operator<Param = P, Output = U>,
+    U: Clone + ArgminMap<F> + ArgminMul<U, U>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Output = U;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(self.scale_residuals(&self.problem.apply(param)?).0)
+    }
+}
+
+impl<O, P, U, J, F> Jacobian for RobustProblem<O, U, F>
+where
+    O: Operator<Param = P, Output = U> + Jacobian<Param = P, Jacobian = J>,
+    U: Clone + ArgminMap<F> + ArgminMul<U, U>,
+    J: ArgminScaleRows<U>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Jacobian = J;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        self.residual_count.fetch_add(1, Ordering::Relaxed);
+        let (_, factors) = self.scale_residuals(&self.problem.apply(param)?);
+        Ok(self.problem.jacobian(param)?.scale_rows(&factors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::solver::gaussnewton::GaussNewtonLS;
+    use crate::solver::linesearch::{condition::ArmijoCondition, BacktrackingLineSearch};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(robust_loss, RobustLoss<f64>);
+
+    #[derive(Clone)]
+    struct LineFit {
+        x: Vec<f64>,
+        y: Vec<f64>,
+    }
+
+    impl LineFit {
+        /// `y = 1 + 2 x` with a single outlier
+        fn with_outlier() -> Self {
+            let x: Vec<f64> = (0..10).map(|i| i as f64).collect();
+            let mut y: Vec<f64> = x.iter().map(|x| 1.0 + 2.0 * x).collect();
+            y[9] += 50.0;
+            LineFit { x, y }
+        }
+    }
+
+    impl Operator for LineFit {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self
+                .x
+                .iter()
+                .zip(self.y.iter())
+                .map(|(x, y)| p[0] + p[1] * x - y)
+                .collect())
+        }
+    }
+
+    impl Jacobian for LineFit {
+        type Param = Vec<f64>;
+        type Jacobian = Vec<Vec<f64>>;
+
+        fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(self.x.iter().map(|x| vec![1.0, *x]).collect())
+        }
+    }
+
+    fn fit(problem: RobustProblem<LineFit, Vec<f64>, f64>) -> Vec<f64> {
+        fit_from(problem, vec![0.0, 0.0])
+    }
+
+    fn fit_from(problem: RobustProblem<LineFit, Vec<f64>, f64>, init_param: Vec<f64>) -> Vec<f64> {
+        let linesearch = BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+        Executor::new(problem, GaussNewtonLS::new(linesearch))
+            .configure(|state| state.param(init_param).max_iters(100))
+            .run()
+            .unwrap()
+            .state
+            .get_best_param()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_new_errors() {
+        for loss in [
+            RobustLoss::Huber(0.0),
+            RobustLoss::SoftL1(-1.0),
+            RobustLoss::Cauchy(f64::INFINITY),
+            RobustLoss::Tukey(f64::NAN),
+        ] {
+            let res: Result<RobustProblem<_, Vec<f64>, f64>, _> = RobustProblem::new((), loss);
+            assert_error!(
+                res,
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`RobustProblem`: scale parameter of loss must be ",
+                    "positive and finite.\""
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_weights_errors() {
+        for weight in [-1.0, f64::NAN, f64::INFINITY] {
+            let mut weights = vec![1.0; 10];
+            weights[3] = weight;
+            let res = RobustProblem::new(LineFit::with_outlier(), RobustLoss::Linear)
+                .unwrap()
+                .with_weights(weights);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`RobustProblem`: weights must be nonnegative and finite.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_loss_consistency() {
+        // The weight is `rho'(r) / r`, compare with central differences
+        let h = 1e-6;
+        for loss in [
+            RobustLoss::Linear,
+            RobustLoss::Huber(1.5),
+            RobustLoss::SoftL1(1.5),
+            RobustLoss::Cauchy(1.5),
+            RobustLoss::Tukey(1.5),
+            RobustLoss::Arctan(1.5),
+        ] {
+            for r in [-3.0f64, -1.0, -0.2, 0.3, 1.2, 4.0] {
+                let drho = (loss.rho(r + h) - loss.rho(r - h)) / (2.0 * h);
+                assert_relative_eq!(loss.weight(r) * r, drho, epsilon = 1e-6);
+            }
+            // Quadratic for small residuals
+            assert_relative_eq!(loss.rho(1e-4), 0.5e-8, epsilon = 1e-12);
+            assert_relative_eq!(loss.weight(0.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_weights() {
+        let problem = LineFit::with_outlier();
+        let mut weights = vec![1.0; 10];
+        weights[9] = 4.0;
+        let robust = RobustProblem::new(problem.clone(), RobustLoss::Linear)
+            .unwrap()
+            .with_weights(weights)
+            .unwrap();
+        let p = vec![0.5, 1.0];
+        let r = problem.apply(&p).unwrap();
+        let rw = robust.apply(&p).unwrap();
+        for i in 0..9 {
+            assert_relative_eq!(rw[i], r[i]);
+        }
+        assert_relative_eq!(rw[9], 2.0 * r[9]);
+        let jw = robust.jacobian(&p).unwrap();
+        assert_eq!(jw[0], vec![1.0, 0.0]);
+        assert_eq!(jw[9], vec![2.0, 18.0]);
+        assert_eq!(robust.residual_count(), 1);
+        assert_eq!(robust.clone().residual_count(), 1);
+
+        // Zero weight removes the outlier entirely
+        let mut weights = vec![1.0; 10];
+        weights[9] = 0.0;
+        let robust = RobustProblem::new(problem, RobustLoss::Linear)
+            .unwrap()
+            .with_weights(weights)
+            .unwrap();
+        let param = fit(robust);
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-8);
+        assert_relative_eq!(param[1], 2.0, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_robust_fit() {
+        let plain = fit(RobustProblem::new(LineFit::with_outlier(), RobustLoss::Linear).unwrap());
+        let plain_error = (plain[0] - 1.0).abs() + (plain[1] - 2.0).abs();
+        assert!(plain_error > 1.0);
+
+        for (loss, tol) in [
+            (RobustLoss::Huber(1.0), 0.5),
+            (RobustLoss::SoftL1(1.0), 0.5),
+            (RobustLoss::Cauchy(1.0), 1e-2),
+            (RobustLoss::Tukey(20.0), 1e-8),
+            (RobustLoss::Arctan(5.0), 1e-2),
+        ] {
+            let param = fit(RobustProblem::new(LineFit::with_outlier(), loss).unwrap());
+            let error = (param[0] - 1.0).abs() + (param[1] - 2.0).abs();
+            assert!(error < tol, "{:?}: {:?}", loss, param);
+        }
+    }
+
+    #[test]
+    fn test_robust_objective() {
+        let problem = LineFit::with_outlier();
+        for loss in [
+            RobustLoss::Huber(1.0),
+            RobustLoss::Cauchy(1.0),
+            RobustLoss::Tukey(4.0),
+        ] {
+            let robust = RobustProblem::new(problem.clone(), loss).unwrap();
+            for p in [vec![0.5, 1.0], vec![100.0, 0.0]] {
+                let z = robust.apply(&p).unwrap();
+                let r = problem.apply(&p).unwrap();
+                let cost: f64 = z.iter().map(|z| 0.5 * z * z).sum();
+                let expected: f64 = r.iter().map(|r| loss.rho(*r)).sum();
+                assert_relative_eq!(cost, expected, epsilon = 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tukey_far_start() {
+        let loss = RobustLoss::Tukey(4.0);
+        let problem = RobustProblem::new(LineFit::with_outlier(), loss).unwrap();
+        // Only the residuals of the points `x = 3, ..., 6` are within the scale of the loss.
+        // Moving all residuals beyond the scale maximizes the robust objective and must
+        // therefore never be accepted by the line search.
+        let param = fit_from(problem, vec![10.0, 0.0]);
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-6);
+        assert_relative_eq!(param[1], 2.0, epsilon = 1e-6);
+    }
+}
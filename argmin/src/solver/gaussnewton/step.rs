@@ -0,0 +1,93 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::Error;
+use argmin_math::{ArgminCholeskySolve, ArgminDot, ArgminQrSolve};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Linear solver used to compute the Gauss-Newton step `p` from `J p = r` in the least squares
+/// sense
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum LinearSolver {
+    /// QR decomposition of the Jacobian `J`. Avoids squaring the condition number.
+    #[default]
+    Qr,
+    /// Cholesky decomposition of `JᵀJ`. Cheaper than QR for problems with many residuals, but
+    /// squares the condition number. Falls back to QR if `JᵀJ` is not numerically positive
+    /// definite.
+    Cholesky,
+}
+
+/// Compute the Gauss-Newton step from the Jacobian, its transpose, the residuals and the
+/// gradient `Jᵀr`.
+pub(super) fn gauss_newton_step<P, G, J, U>(
+    linear_solver: LinearSolver,
+    jacobian: &J,
+    jacobian_t: &J,
+    residuals: &U,
+    grad: &G,
+) -> Result<P, Error>
+where
+    J: ArgminQrSolve<U, P> + ArgminCholeskySolve<G, P> + ArgminDot<J, J>,
+{
+    match linear_solver {
+        LinearSolver::Qr => jacobian.qr_solve(residuals),
+        LinearSolver::Cholesky => jacobian_t
+            .dot(jacobian)
+            .cholesky_solve(grad)
+            .or_else(|_| jacobian.qr_solve(residuals)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(LinearSolver::default(), LinearSolver::Qr);
+    }
+
+    #[test]
+    fn test_step() {
+        // Straight line through (0, 1), (1, 3), (2, 4)
+        let jacobian = vec![vec![1.0f64, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]];
+        let jacobian_t = vec![vec![1.0f64, 1.0, 1.0], vec![0.0, 1.0, 2.0]];
+        let residuals = vec![1.0f64, 3.0, 4.0];
+        let grad = vec![8.0f64, 11.0];
+        for linear_solver in [LinearSolver::Qr, LinearSolver::Cholesky] {
+            let p: Vec<f64> =
+                gauss_newton_step(linear_solver, &jacobian, &jacobian_t, &residuals, &grad)
+                    .unwrap();
+            assert_relative_eq!(p[0], 7.0 / 6.0, epsilon = 1e-12);
+            assert_relative_eq!(p[1], 1.5, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_cholesky_fallback() {
+        // `JᵀJ` is numerically singular while `J` itself is well conditioned enough for QR
+        let eps = 1e-9f64;
+        let jacobian = vec![vec![1.0f64, 1.0], vec![eps, 0.0], vec![0.0, eps]];
+        let jacobian_t = vec![vec![1.0f64, eps, 0.0], vec![1.0, 0.0, eps]];
+        let residuals = vec![2.0f64, eps, eps];
+        let grad = vec![2.0 + eps * eps, 2.0 + eps * eps];
+        let p: Vec<f64> = gauss_newton_step(
+            LinearSolver::Cholesky,
+            &jacobian,
+            &jacobian_t,
+            &residuals,
+            &grad,
+        )
+        .unwrap();
+        assert_relative_eq!(p[0], 1.0, epsilon = 1e-6);
+        assert_relative_eq!(p[1], 1.0, epsilon = 1e-6);
+    }
+}
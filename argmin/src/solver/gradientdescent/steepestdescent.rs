@@ -82,7 +82,7 @@ where
         let OptimizationResult {
             problem: line_problem,
             state: mut linesearch_state,
-            ..
+            solver: line_solver,
         } = Executor::new(
             problem.take_problem().ok_or_else(argmin_error_closure!(
                 PotentialBug,
@@ -94,6 +94,8 @@ where
         .ctrlc(false)
         .run()?;
 
+        self.linesearch.consume_line_search(line_solver);
+
         // Get back problem and function evaluation counts
         problem.consume_problem(line_problem);
 
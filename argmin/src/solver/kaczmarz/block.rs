@@ -0,0 +1,309 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Problem, RowAccess, SerializeAlias, Solver, State, KV,
+};
+use crate::solver::kaczmarz::{residual_norm, RowSampler, RowSampling};
+use argmin_math::{ArgminDot, ArgminScaledAdd, ArgminZeroLike};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Block Kaczmarz method
+///
+/// Solves a linear system `Ax = b` by moving the iterate towards the average of its projections
+/// onto the hyperplanes `a_iᵀx = b_i` of all rows `i` in a block `B`:
+///
+/// `x = x + lambda / |B| * Σ_{i ∈ B} (b_i - a_iᵀx) / ‖a_i‖² * a_i`
+///
+/// where `0 < lambda < 2` is a relaxation parameter (`1` by default, see
+/// [`with_relaxation`](`BlockKaczmarz::with_relaxation`)). All projections of a block are computed
+/// from the same iterate, therefore the rows of a block can be evaluated independently of each
+/// other.
+///
+/// The rows of a sweep are drawn according to [`RowSampling`] (a random permutation of all rows by
+/// default, see [`with_sampling`](`BlockKaczmarz::with_sampling`)) and split into consecutive
+/// blocks of `block_size` rows. One iteration consists of one sweep. As for
+/// [`RandomizedKaczmarz`](`crate::solver::kaczmarz::RandomizedKaczmarz`), the cost function value
+/// is the residual norm `‖Ax - b‖` of the iterate at the end of the sweep. Rows with `a_i = 0` are
+/// skipped.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`RowAccess`].
+///
+/// ## Reference
+///
+/// Ion Necoara (2019). Faster randomized block Kaczmarz algorithms. SIAM J. Matrix Anal. Appl.
+/// 40(4), 1425–1452.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BlockKaczmarz<F, R> {
+    /// Number of rows per block
+    block_size: usize,
+    /// Relaxation parameter
+    relaxation: F,
+    /// Row sampling
+    sampler: RowSampler<F>,
+    /// Random number generator
+    rng: R,
+}
+
+impl<F> BlockKaczmarz<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`BlockKaczmarz`]
+    ///
+    /// Takes the number of rows per block as input, which must be > 0.
+    ///
+    /// Uses the `Xoshiro256PlusPlus` RNG internally. For use of another RNG or a seeded RNG,
+    /// consider using [`BlockKaczmarz::new_with_rng`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::BlockKaczmarz;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: BlockKaczmarz<f64, _> = BlockKaczmarz::new(16)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(block_size: usize) -> Result<Self, Error> {
+        BlockKaczmarz::new_with_rng(block_size, Xoshiro256PlusPlus::from_entropy())
+    }
+}
+
+impl<F, R> BlockKaczmarz<F, R>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`BlockKaczmarz`]
+    ///
+    /// Takes the number of rows per block as input, which must be > 0.
+    /// Requires a RNG which must implement `rand::Rng` (and `serde::Serialize` if the `serde1`
+    /// feature is enabled).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::BlockKaczmarz;
+    /// # use argmin::core::Error;
+    /// use rand::SeedableRng;
+    /// use rand_xoshiro::Xoshiro256PlusPlus;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    /// let solver: BlockKaczmarz<f64, _> = BlockKaczmarz::new_with_rng(16, rng)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_rng(block_size: usize, rng: R) -> Result<Self, Error> {
+        if block_size == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BlockKaczmarz`: block size must be > 0."
+            ));
+        }
+        Ok(BlockKaczmarz {
+            block_size,
+            relaxation: float!(1.0),
+            sampler: RowSampler::new(RowSampling::Shuffled),
+            rng,
+        })
+    }
+
+    /// Set the relaxation parameter `lambda`.
+    ///
+    /// Must be in `(0, 2)` and defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::BlockKaczmarz;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: BlockKaczmarz<f64, _> = BlockKaczmarz::new(16)?.with_relaxation(1.5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_relaxation(mut self, relaxation: F) -> Result<Self, Error> {
+        if relaxation <= float!(0.0) || relaxation >= float!(2.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BlockKaczmarz`: relaxation parameter must be in (0, 2)."
+            ));
+        }
+        self.relaxation = relaxation;
+        Ok(self)
+    }
+
+    /// Set the order in which rows are visited.
+    ///
+    /// Defaults to [`RowSampling::Shuffled`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::{BlockKaczmarz, RowSampling};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: BlockKaczmarz<f64, _> =
+    ///     BlockKaczmarz::new(16)?.with_sampling(RowSampling::Uniform);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_sampling(mut self, sampling: RowSampling) -> Self {
+        self.sampler = RowSampler::new(sampling);
+        self
+    }
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), F>> for BlockKaczmarz<F, R>
+where
+    O: RowAccess<Param = P, Float = F>,
+    P: Clone + ArgminDot<P, F> + ArgminScaledAdd<P, F, P> + ArgminZeroLike,
+    F: ArgminFloat,
+    R: Rng + SerializeAlias,
+{
+    const NAME: &'static str = "Block Kaczmarz";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`BlockKaczmarz` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        self.sampler.init(problem)?;
+        Ok((state, None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let mut param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`BlockKaczmarz` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        for block in self.sampler.sweep(&mut self.rng).chunks(self.block_size) {
+            let mut direction = param.zero_like();
+            for &i in block {
+                let (a, b) = problem.row(i)?;
+                let residual = b - a.dot(&param);
+                let norm_sq = a.dot(&a);
+                if norm_sq > float!(0.0) {
+                    direction = direction.scaled_add(&(residual / norm_sq), &a);
+                }
+            }
+            let step = self.relaxation / F::from_usize(block.len()).unwrap();
+            param = param.scaled_add(&step, &direction);
+        }
+        let cost = residual_norm(problem, &param)?;
+        Ok((state.param(param).cost(cost), None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::kaczmarz::tests::System;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(block_kaczmarz, BlockKaczmarz<f64, Xoshiro256PlusPlus>);
+
+    #[test]
+    fn test_new() {
+        let BlockKaczmarz {
+            block_size,
+            relaxation,
+            sampler,
+            rng: _,
+        }: BlockKaczmarz<f64, _> = BlockKaczmarz::new(2).unwrap();
+        assert_eq!(block_size, 2);
+        assert_eq!(relaxation.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(sampler, RowSampler::new(RowSampling::Shuffled));
+
+        let res: Result<BlockKaczmarz<f64, _>, _> = BlockKaczmarz::new(0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`BlockKaczmarz`: block size must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_with_relaxation() {
+        let solver: BlockKaczmarz<f64, _> =
+            BlockKaczmarz::new(2).unwrap().with_relaxation(1.5).unwrap();
+        assert_eq!(solver.relaxation.to_ne_bytes(), 1.5f64.to_ne_bytes());
+
+        for relaxation in [0.0, -1.0, 2.0, 3.0] {
+            let res: Result<BlockKaczmarz<f64, _>, _> =
+                BlockKaczmarz::new(2).unwrap().with_relaxation(relaxation);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`BlockKaczmarz`: relaxation parameter must be in (0, 2).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut solver: BlockKaczmarz<f64, _> = BlockKaczmarz::new(2).unwrap();
+        let res = solver.init(&mut Problem::new(System::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`BlockKaczmarz` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        for (block_size, sampling) in [
+            (1, RowSampling::Cyclic),
+            (2, RowSampling::Shuffled),
+            (3, RowSampling::Uniform),
+            (5, RowSampling::Proportional),
+        ] {
+            let solver =
+                BlockKaczmarz::new_with_rng(block_size, Xoshiro256PlusPlus::seed_from_u64(42))
+                    .unwrap()
+                    .with_sampling(sampling);
+            let res = Executor::new(System::new(), solver)
+                .configure(|state| state.param(vec![0.0; 3]).max_iters(2000))
+                .run()
+                .unwrap();
+            let param = res.state().get_param().unwrap();
+            assert_relative_eq!(param[0], 1.0, epsilon = 1e-6);
+            assert_relative_eq!(param[1], 2.0, epsilon = 1e-6);
+            assert_relative_eq!(param[2], 3.0, epsilon = 1e-6);
+            assert_eq!(res.problem().counts["row_count"] % 5, 0);
+        }
+    }
+}
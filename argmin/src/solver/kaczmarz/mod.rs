@@ -0,0 +1,292 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Row-action methods for linear systems
+//!
+//! Row-action methods solve (possibly huge) linear systems `Ax = b` by only ever looking at
+//! individual rows `a_i` of `A` and the corresponding entries `b_i` of `b`, which are provided
+//! via the [`RowAccess`](`crate::core::RowAccess`) trait. This makes them the methods of choice
+//! for tomography-style problems where the full system matrix cannot be formed. This module
+//! provides
+//!
+//! * [`RandomizedKaczmarz`], which projects the iterate onto the hyperplane `a_iᵀx = b_i` of a
+//!   single row at a time (also known as the algebraic reconstruction technique, ART),
+//! * [`BlockKaczmarz`], which averages the projections onto all hyperplanes of a block of rows,
+//!   and
+//! * [`SART`], the simultaneous algebraic reconstruction technique for systems with nonnegative
+//!   entries.
+//!
+//! One iteration of each solver is a sweep over as many rows as the system has. The order in
+//! which rows are visited is determined by [`RowSampling`]. The random number generator is part
+//! of the solver, hence seeded runs are reproducible and can be checkpointed. After each sweep,
+//! the residual norm `‖Ax - b‖` of the new iterate is computed in an additional pass over all rows
+//! and stored as cost function value, which allows stopping via `target_cost`.
+//!
+//! ## References
+//!
+//! S. Kaczmarz (1937). Angenäherte Auflösung von Systemen linearer Gleichungen. Bull. Int. Acad.
+//! Polon. Sci. Lett. A, 35, 355–357.
+//!
+//! Thomas Strohmer and Roman Vershynin (2009). A randomized Kaczmarz algorithm with exponential
+//! convergence. J. Fourier Anal. Appl. 15, 262–278.
+//!
+//! Ion Necoara (2019). Faster randomized block Kaczmarz algorithms. SIAM J. Matrix Anal. Appl.
+//! 40(4), 1425–1452.
+//!
+//! A. H. Andersen and A. C. Kak (1984). Simultaneous algebraic reconstruction technique (SART):
+//! a superior implementation of the ART algorithm. Ultrasonic Imaging 6(1), 81–94.
+
+mod block;
+mod randomized;
+mod sart;
+
+pub use self::block::BlockKaczmarz;
+pub use self::randomized::RandomizedKaczmarz;
+pub use self::sart::SART;
+
+use crate::core::{ArgminFloat, Error, Problem, RowAccess};
+use argmin_math::ArgminDot;
+use rand::prelude::*;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Order in which the rows of a linear system are visited during one sweep
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum RowSampling {
+    /// Visit all rows in their natural order
+    Cyclic,
+    /// Visit all rows in a random order, which is drawn anew for each sweep
+    Shuffled,
+    /// Draw rows uniformly at random with replacement
+    Uniform,
+    /// Draw rows at random with replacement with probabilities proportional to the squared row
+    /// norms `‖a_i‖²` (Strohmer and Vershynin). The row norms are computed once in `init`.
+    Proportional,
+}
+
+/// Draws the sequence of rows visited during one sweep
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub(crate) struct RowSampler<F> {
+    /// Sampling strategy
+    sampling: RowSampling,
+    /// Number of rows of the system
+    num_rows: usize,
+    /// Cumulative squared row norms (only used for `RowSampling::Proportional`)
+    cumulative_norms: Vec<F>,
+}
+
+impl<F> RowSampler<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new sampler
+    pub(crate) fn new(sampling: RowSampling) -> Self {
+        RowSampler {
+            sampling,
+            num_rows: 0,
+            cumulative_norms: vec![],
+        }
+    }
+
+    /// Query the number of rows and compute the row norms if needed
+    pub(crate) fn init<O, P>(&mut self, problem: &mut Problem<O>) -> Result<(), Error>
+    where
+        O: RowAccess<Param = P, Float = F>,
+        P: ArgminDot<P, F>,
+    {
+        self.num_rows = problem.num_rows();
+        if self.num_rows == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "Row-action methods require a linear system with at least one row."
+            ));
+        }
+        if self.sampling == RowSampling::Proportional {
+            let mut total = float!(0.0);
+            self.cumulative_norms = (0..self.num_rows)
+                .map(|i| {
+                    let (a, _) = problem.row(i)?;
+                    total = total + a.dot(&a);
+                    Ok(total)
+                })
+                .collect::<Result<Vec<F>, Error>>()?;
+            if total <= float!(0.0) {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`RowSampling::Proportional` requires at least one nonzero row."
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Number of rows of the system
+    pub(crate) fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Draw the row indices of one sweep
+    pub(crate) fn sweep<R: Rng>(&self, rng: &mut R) -> Vec<usize> {
+        let m = self.num_rows;
+        match self.sampling {
+            RowSampling::Cyclic => (0..m).collect(),
+            RowSampling::Shuffled => {
+                let mut order: Vec<usize> = (0..m).collect();
+                order.shuffle(rng);
+                order
+            }
+            RowSampling::Uniform => (0..m).map(|_| rng.gen_range(0..m)).collect(),
+            RowSampling::Proportional => {
+                let total = self.cumulative_norms[m - 1];
+                (0..m)
+                    .map(|_| {
+                        let u = float!(rng.gen::<f64>()) * total;
+                        self.cumulative_norms
+                            .partition_point(|&c| c <= u)
+                            .min(m - 1)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Residual norm `‖Ax - b‖` at `param`, computed in a pass over all rows
+pub(crate) fn residual_norm<O, P, F>(problem: &mut Problem<O>, param: &P) -> Result<F, Error>
+where
+    O: RowAccess<Param = P, Float = F>,
+    P: ArgminDot<P, F>,
+    F: ArgminFloat,
+{
+    let mut residual_sq = float!(0.0);
+    for i in 0..problem.num_rows() {
+        let (a, b) = problem.row(i)?;
+        let residual = b - a.dot(param);
+        residual_sq = residual_sq + residual * residual;
+    }
+    Ok(residual_sq.sqrt())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    /// Consistent overdetermined system with nonnegative entries and solution `[1, 2, 3]`
+    pub(crate) struct System {
+        pub(crate) matrix: Vec<Vec<f64>>,
+    }
+
+    impl System {
+        pub(crate) fn new() -> Self {
+            System {
+                matrix: vec![
+                    vec![2.0, 1.0, 0.0],
+                    vec![0.0, 1.0, 3.0],
+                    vec![1.0, 0.0, 1.0],
+                    vec![1.0, 1.0, 1.0],
+                    vec![0.0, 0.0, 0.0],
+                ],
+            }
+        }
+    }
+
+    impl RowAccess for System {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn num_rows(&self) -> usize {
+            self.matrix.len()
+        }
+
+        fn row(&self, index: usize) -> Result<(Vec<f64>, f64), Error> {
+            let a = self.matrix[index].clone();
+            let b = a
+                .iter()
+                .zip([1.0, 2.0, 3.0].iter())
+                .map(|(a, x)| a * x)
+                .sum();
+            Ok((a, b))
+        }
+    }
+
+    #[test]
+    fn test_sweep() {
+        let mut problem = Problem::new(System::new());
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+
+        let mut sampler: RowSampler<f64> = RowSampler::new(RowSampling::Cyclic);
+        sampler.init(&mut problem).unwrap();
+        assert_eq!(sampler.num_rows(), 5);
+        assert_eq!(sampler.sweep(&mut rng), vec![0, 1, 2, 3, 4]);
+
+        let mut sampler: RowSampler<f64> = RowSampler::new(RowSampling::Shuffled);
+        sampler.init(&mut problem).unwrap();
+        let mut order = sampler.sweep(&mut rng);
+        order.sort_unstable();
+        assert_eq!(order, vec![0, 1, 2, 3, 4]);
+
+        let mut sampler: RowSampler<f64> = RowSampler::new(RowSampling::Uniform);
+        sampler.init(&mut problem).unwrap();
+        assert!(sampler.sweep(&mut rng).iter().all(|&i| i < 5));
+        assert!(!problem.counts.contains_key("row_count"));
+
+        // the zero row must never be drawn
+        let mut sampler: RowSampler<f64> = RowSampler::new(RowSampling::Proportional);
+        sampler.init(&mut problem).unwrap();
+        assert_eq!(problem.counts["row_count"], 5);
+        for _ in 0..100 {
+            assert!(sampler.sweep(&mut rng).iter().all(|&i| i < 4));
+        }
+    }
+
+    #[test]
+    fn test_sweep_reproducible() {
+        let mut problem = Problem::new(System::new());
+        let mut sampler: RowSampler<f64> = RowSampler::new(RowSampling::Proportional);
+        sampler.init(&mut problem).unwrap();
+        let order1 = sampler.sweep(&mut Xoshiro256PlusPlus::seed_from_u64(7));
+        let order2 = sampler.sweep(&mut Xoshiro256PlusPlus::seed_from_u64(7));
+        assert_eq!(order1, order2);
+    }
+
+    #[test]
+    fn test_residual_norm() {
+        let mut problem = Problem::new(System::new());
+        let norm: f64 = residual_norm(&mut problem, &vec![1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(norm.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        // Residuals `(2, 0, 1, 1, 0)` at `(0, 2, 3)`
+        let norm: f64 = residual_norm(&mut problem, &vec![0.0, 2.0, 3.0]).unwrap();
+        assert_relative_eq!(norm, 6.0f64.sqrt(), epsilon = 1e-12);
+        assert_eq!(problem.counts["row_count"], 10);
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let mut problem = Problem::new(System { matrix: vec![] });
+        let mut sampler: RowSampler<f64> = RowSampler::new(RowSampling::Cyclic);
+        assert_error!(
+            sampler.init(&mut problem),
+            ArgminError,
+            "Invalid parameter: \"Row-action methods require a linear system with at least one row.\""
+        );
+
+        let mut problem = Problem::new(System {
+            matrix: vec![vec![0.0, 0.0, 0.0]],
+        });
+        let mut sampler: RowSampler<f64> = RowSampler::new(RowSampling::Proportional);
+        assert_error!(
+            sampler.init(&mut problem),
+            ArgminError,
+            "Invalid parameter: \"`RowSampling::Proportional` requires at least one nonzero row.\""
+        );
+    }
+}
@@ -0,0 +1,311 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Problem, RowAccess, SerializeAlias, Solver, State, KV,
+};
+use crate::solver::kaczmarz::{residual_norm, RowSampler, RowSampling};
+use argmin_math::{ArgminDot, ArgminScaledAdd};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Randomized Kaczmarz method
+///
+/// Solves a linear system `Ax = b` by successively projecting the iterate onto the hyperplanes
+/// `a_iᵀx = b_i` of single rows:
+///
+/// `x = x + lambda * (b_i - a_iᵀx) / ‖a_i‖² * a_i`
+///
+/// where `0 < lambda < 2` is a relaxation parameter (`1` by default, see
+/// [`with_relaxation`](`RandomizedKaczmarz::with_relaxation`)). With cyclic row order this is the
+/// classical Kaczmarz method, also known as the algebraic reconstruction technique (ART). By
+/// default, rows are drawn with probabilities proportional to `‖a_i‖²` as proposed by Strohmer and
+/// Vershynin, which converges linearly in expectation for consistent systems. See
+/// [`with_sampling`](`RandomizedKaczmarz::with_sampling`) and [`RowSampling`] for alternatives.
+///
+/// One iteration consists of as many row projections as the system has rows. The cost function
+/// value stored in the state is the residual norm `‖Ax - b‖` of the iterate at the end of the
+/// sweep, which requires an additional pass over all rows. It can be used as a stopping criterion
+/// via `target_cost`. Rows with `a_i = 0` are skipped.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`RowAccess`].
+///
+/// ## References
+///
+/// S. Kaczmarz (1937). Angenäherte Auflösung von Systemen linearer Gleichungen. Bull. Int. Acad.
+/// Polon. Sci. Lett. A, 35, 355–357.
+///
+/// Thomas Strohmer and Roman Vershynin (2009). A randomized Kaczmarz algorithm with exponential
+/// convergence. J. Fourier Anal. Appl. 15, 262–278.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct RandomizedKaczmarz<F, R> {
+    /// Relaxation parameter
+    relaxation: F,
+    /// Row sampling
+    sampler: RowSampler<F>,
+    /// Random number generator
+    rng: R,
+}
+
+impl<F> RandomizedKaczmarz<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`RandomizedKaczmarz`]
+    ///
+    /// Uses the `Xoshiro256PlusPlus` RNG internally. For use of another RNG or a seeded RNG,
+    /// consider using [`RandomizedKaczmarz::new_with_rng`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::RandomizedKaczmarz;
+    /// let solver: RandomizedKaczmarz<f64, _> = RandomizedKaczmarz::new();
+    /// ```
+    pub fn new() -> Self {
+        RandomizedKaczmarz::new_with_rng(Xoshiro256PlusPlus::from_entropy())
+    }
+}
+
+impl<F> Default for RandomizedKaczmarz<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        RandomizedKaczmarz::new()
+    }
+}
+
+impl<F, R> RandomizedKaczmarz<F, R>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`RandomizedKaczmarz`]
+    ///
+    /// Requires a RNG which must implement `rand::Rng` (and `serde::Serialize` if the `serde1`
+    /// feature is enabled).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::RandomizedKaczmarz;
+    /// use rand::SeedableRng;
+    /// use rand_xoshiro::Xoshiro256PlusPlus;
+    ///
+    /// let rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    /// let solver: RandomizedKaczmarz<f64, _> = RandomizedKaczmarz::new_with_rng(rng);
+    /// ```
+    pub fn new_with_rng(rng: R) -> Self {
+        RandomizedKaczmarz {
+            relaxation: float!(1.0),
+            sampler: RowSampler::new(RowSampling::Proportional),
+            rng,
+        }
+    }
+
+    /// Set the relaxation parameter `lambda`.
+    ///
+    /// Must be in `(0, 2)` and defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::RandomizedKaczmarz;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: RandomizedKaczmarz<f64, _> = RandomizedKaczmarz::new().with_relaxation(0.5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_relaxation(mut self, relaxation: F) -> Result<Self, Error> {
+        if relaxation <= float!(0.0) || relaxation >= float!(2.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RandomizedKaczmarz`: relaxation parameter must be in (0, 2)."
+            ));
+        }
+        self.relaxation = relaxation;
+        Ok(self)
+    }
+
+    /// Set the order in which rows are visited.
+    ///
+    /// Defaults to [`RowSampling::Proportional`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::{RandomizedKaczmarz, RowSampling};
+    /// // Classical Kaczmarz method (ART)
+    /// let solver: RandomizedKaczmarz<f64, _> =
+    ///     RandomizedKaczmarz::new().with_sampling(RowSampling::Cyclic);
+    /// ```
+    #[must_use]
+    pub fn with_sampling(mut self, sampling: RowSampling) -> Self {
+        self.sampler = RowSampler::new(sampling);
+        self
+    }
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), F>> for RandomizedKaczmarz<F, R>
+where
+    O: RowAccess<Param = P, Float = F>,
+    P: Clone + ArgminDot<P, F> + ArgminScaledAdd<P, F, P>,
+    F: ArgminFloat,
+    R: Rng + SerializeAlias,
+{
+    const NAME: &'static str = "Randomized Kaczmarz";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`RandomizedKaczmarz` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        self.sampler.init(problem)?;
+        Ok((state, None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let mut param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`RandomizedKaczmarz` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        for i in self.sampler.sweep(&mut self.rng) {
+            let (a, b) = problem.row(i)?;
+            let residual = b - a.dot(&param);
+            let norm_sq = a.dot(&a);
+            if norm_sq > float!(0.0) {
+                param = param.scaled_add(&(self.relaxation * residual / norm_sq), &a);
+            }
+        }
+        let cost = residual_norm(problem, &param)?;
+        Ok((state.param(param).cost(cost), None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::kaczmarz::tests::System;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(randomized_kaczmarz, RandomizedKaczmarz<f64, Xoshiro256PlusPlus>);
+
+    #[test]
+    fn test_new() {
+        let RandomizedKaczmarz {
+            relaxation,
+            sampler,
+            rng: _,
+        }: RandomizedKaczmarz<f64, _> = RandomizedKaczmarz::new();
+        assert_eq!(relaxation.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(sampler, RowSampler::new(RowSampling::Proportional));
+    }
+
+    #[test]
+    fn test_with_relaxation() {
+        let solver: RandomizedKaczmarz<f64, _> =
+            RandomizedKaczmarz::new().with_relaxation(1.5).unwrap();
+        assert_eq!(solver.relaxation.to_ne_bytes(), 1.5f64.to_ne_bytes());
+
+        for relaxation in [0.0, -1.0, 2.0, 3.0] {
+            let res: Result<RandomizedKaczmarz<f64, _>, _> =
+                RandomizedKaczmarz::new().with_relaxation(relaxation);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`RandomizedKaczmarz`: relaxation parameter must be in (0, 2).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut solver: RandomizedKaczmarz<f64, _> = RandomizedKaczmarz::new();
+        let res = solver.init(&mut Problem::new(System::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`RandomizedKaczmarz` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        for sampling in [
+            RowSampling::Cyclic,
+            RowSampling::Shuffled,
+            RowSampling::Uniform,
+            RowSampling::Proportional,
+        ] {
+            let solver = RandomizedKaczmarz::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(42))
+                .with_sampling(sampling);
+            let res = Executor::new(System::new(), solver)
+                .configure(|state| state.param(vec![0.0; 3]).max_iters(500))
+                .run()
+                .unwrap();
+            let param = res.state().get_param().unwrap();
+            assert_relative_eq!(param[0], 1.0, epsilon = 1e-6);
+            assert_relative_eq!(param[1], 2.0, epsilon = 1e-6);
+            assert_relative_eq!(param[2], 3.0, epsilon = 1e-6);
+            assert!(res.state().get_cost() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_cost_is_residual_norm_after_sweep() {
+        let mut solver = RandomizedKaczmarz::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(42));
+        let mut problem = Problem::new(System::new());
+        let state = IterState::new().param(vec![0.0; 3]);
+        let (state, _) = solver.init(&mut problem, state).unwrap();
+        let (state, _) = solver.next_iter(&mut problem, state).unwrap();
+        let expected: f64 = residual_norm(&mut problem, state.get_param().unwrap()).unwrap();
+        assert_eq!(state.get_cost().to_ne_bytes(), expected.to_ne_bytes());
+        assert!(state.get_cost() > 0.0);
+    }
+
+    #[test]
+    fn test_seeded_runs_reproducible() {
+        let run = |seed: u64| {
+            let solver = RandomizedKaczmarz::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(seed));
+            Executor::new(System::new(), solver)
+                .configure(|state| state.param(vec![0.0; 3]).max_iters(3))
+                .run()
+                .unwrap()
+                .state()
+                .get_param()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(run(1), run(1));
+        assert_ne!(run(1), run(2));
+    }
+}
@@ -0,0 +1,359 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Problem, RowAccess, SerializeAlias, Solver, State, KV,
+};
+use crate::solver::kaczmarz::{residual_norm, RowSampler, RowSampling};
+use argmin_math::{ArgminAdd, ArgminDot, ArgminMap, ArgminMul, ArgminScaledAdd, ArgminZeroLike};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Simultaneous algebraic reconstruction technique (SART)
+///
+/// Solves a linear system `Ax = b` with nonnegative entries in `A`, such as the projection
+/// matrices of tomography. For a block `B` of rows, the update is
+///
+/// `x_j = x_j + lambda / Σ_{i ∈ B} a_ij * Σ_{i ∈ B} a_ij * (b_i - a_iᵀx) / Σ_k a_ik`
+///
+/// i.e. the residual of each row is normalized by its row sum and backprojected, and the result
+/// is normalized by the column sums of the block. `0 < lambda < 2` is a relaxation parameter (`1`
+/// by default, see [`with_relaxation`](`SART::with_relaxation`)). Columns without entries in the
+/// block are left unchanged, rows with vanishing row sum are skipped. Nonnegativity of `A` is
+/// assumed but not checked.
+///
+/// By default, a block consists of all rows (classical SART with a single update per sweep,
+/// which is also known as SIRT). Smaller blocks (ordered subsets) can be set with
+/// [`with_block_size`](`SART::with_block_size`) and typically converge much faster. The rows of a
+/// sweep are visited in their natural order by default, see
+/// [`with_sampling`](`SART::with_sampling`). One iteration consists of one sweep. As for
+/// [`RandomizedKaczmarz`](`crate::solver::kaczmarz::RandomizedKaczmarz`), the cost function value
+/// is the residual norm `‖Ax - b‖` of the iterate at the end of the sweep.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`RowAccess`].
+///
+/// ## Reference
+///
+/// A. H. Andersen and A. C. Kak (1984). Simultaneous algebraic reconstruction technique (SART):
+/// a superior implementation of the ART algorithm. Ultrasonic Imaging 6(1), 81–94.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SART<F, R> {
+    /// Number of rows per block (all rows if `None`)
+    block_size: Option<usize>,
+    /// Relaxation parameter
+    relaxation: F,
+    /// Row sampling
+    sampler: RowSampler<F>,
+    /// Random number generator
+    rng: R,
+}
+
+impl<F> SART<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`SART`]
+    ///
+    /// Uses the `Xoshiro256PlusPlus` RNG internally, which is only needed for randomized row
+    /// sampling. For use of another RNG or a seeded RNG, consider using [`SART::new_with_rng`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::SART;
+    /// let solver: SART<f64, _> = SART::new();
+    /// ```
+    pub fn new() -> Self {
+        SART::new_with_rng(Xoshiro256PlusPlus::from_entropy())
+    }
+}
+
+impl<F> Default for SART<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        SART::new()
+    }
+}
+
+impl<F, R> SART<F, R>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`SART`]
+    ///
+    /// Requires a RNG which must implement `rand::Rng` (and `serde::Serialize` if the `serde1`
+    /// feature is enabled).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::SART;
+    /// use rand::SeedableRng;
+    /// use rand_xoshiro::Xoshiro256PlusPlus;
+    ///
+    /// let rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    /// let solver: SART<f64, _> = SART::new_with_rng(rng);
+    /// ```
+    pub fn new_with_rng(rng: R) -> Self {
+        SART {
+            block_size: None,
+            relaxation: float!(1.0),
+            sampler: RowSampler::new(RowSampling::Cyclic),
+            rng,
+        }
+    }
+
+    /// Set the number of rows per block.
+    ///
+    /// Must be > 0. Defaults to all rows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::SART;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: SART<f64, _> = SART::new().with_block_size(32)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_block_size(mut self, block_size: usize) -> Result<Self, Error> {
+        if block_size == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SART`: block size must be > 0."
+            ));
+        }
+        self.block_size = Some(block_size);
+        Ok(self)
+    }
+
+    /// Set the relaxation parameter `lambda`.
+    ///
+    /// Must be in `(0, 2)` and defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::SART;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: SART<f64, _> = SART::new().with_relaxation(0.5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_relaxation(mut self, relaxation: F) -> Result<Self, Error> {
+        if relaxation <= float!(0.0) || relaxation >= float!(2.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SART`: relaxation parameter must be in (0, 2)."
+            ));
+        }
+        self.relaxation = relaxation;
+        Ok(self)
+    }
+
+    /// Set the order in which rows are visited.
+    ///
+    /// Defaults to [`RowSampling::Cyclic`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::kaczmarz::{RowSampling, SART};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: SART<f64, _> = SART::new()
+    ///     .with_block_size(32)?
+    ///     .with_sampling(RowSampling::Shuffled);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_sampling(mut self, sampling: RowSampling) -> Self {
+        self.sampler = RowSampler::new(sampling);
+        self
+    }
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), F>> for SART<F, R>
+where
+    O: RowAccess<Param = P, Float = F>,
+    P: Clone
+        + ArgminDot<P, F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminAdd<P, P>
+        + ArgminMul<P, P>
+        + ArgminMap<F>
+        + ArgminZeroLike,
+    F: ArgminFloat,
+    R: Rng + SerializeAlias,
+{
+    const NAME: &'static str = "SART";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`SART` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        self.sampler.init(problem)?;
+        Ok((state, None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let mut param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`SART` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let ones = param.map(|_| float!(1.0));
+        let block_size = self.block_size.unwrap_or_else(|| self.sampler.num_rows());
+        for block in self.sampler.sweep(&mut self.rng).chunks(block_size) {
+            let mut backprojection = param.zero_like();
+            let mut column_sums = param.zero_like();
+            for &i in block {
+                let (a, b) = problem.row(i)?;
+                let residual = b - a.dot(&param);
+                let row_sum = a.dot(&ones);
+                if row_sum > float!(0.0) {
+                    backprojection = backprojection.scaled_add(&(residual / row_sum), &a);
+                    column_sums = column_sums.add(&a);
+                }
+            }
+            let inv_column_sums = column_sums.map(|c| {
+                if c > float!(0.0) {
+                    float!(1.0) / c
+                } else {
+                    float!(0.0)
+                }
+            });
+            param = param.scaled_add(&self.relaxation, &inv_column_sums.mul(&backprojection));
+        }
+        let cost = residual_norm(problem, &param)?;
+        Ok((state.param(param).cost(cost), None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::kaczmarz::tests::System;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(sart, SART<f64, Xoshiro256PlusPlus>);
+
+    #[test]
+    fn test_new() {
+        let SART {
+            block_size,
+            relaxation,
+            sampler,
+            rng: _,
+        }: SART<f64, _> = SART::new();
+        assert_eq!(block_size, None);
+        assert_eq!(relaxation.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(sampler, RowSampler::new(RowSampling::Cyclic));
+    }
+
+    #[test]
+    fn test_with_block_size() {
+        let solver: SART<f64, _> = SART::new().with_block_size(3).unwrap();
+        assert_eq!(solver.block_size, Some(3));
+
+        let res: Result<SART<f64, _>, _> = SART::new().with_block_size(0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`SART`: block size must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_with_relaxation() {
+        let solver: SART<f64, _> = SART::new().with_relaxation(1.5).unwrap();
+        assert_eq!(solver.relaxation.to_ne_bytes(), 1.5f64.to_ne_bytes());
+
+        for relaxation in [0.0, -1.0, 2.0, 3.0] {
+            let res: Result<SART<f64, _>, _> = SART::new().with_relaxation(relaxation);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`SART`: relaxation parameter must be in (0, 2).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut solver: SART<f64, _> = SART::new();
+        let res = solver.init(&mut Problem::new(System::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`SART` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        for (block_size, sampling) in [
+            (None, RowSampling::Cyclic),
+            (Some(1), RowSampling::Cyclic),
+            (Some(2), RowSampling::Shuffled),
+        ] {
+            let mut solver =
+                SART::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(42)).with_sampling(sampling);
+            if let Some(block_size) = block_size {
+                solver = solver.with_block_size(block_size).unwrap();
+            }
+            let res = Executor::new(System::new(), solver)
+                .configure(|state| state.param(vec![0.0; 3]).max_iters(2000))
+                .run()
+                .unwrap();
+            let param = res.state().get_param().unwrap();
+            assert_relative_eq!(param[0], 1.0, epsilon = 1e-6);
+            assert_relative_eq!(param[1], 2.0, epsilon = 1e-6);
+            assert_relative_eq!(param[2], 3.0, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_target_cost() {
+        let solver = SART::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(42));
+        let res = Executor::new(System::new(), solver)
+            .configure(|state| state.param(vec![0.0; 3]).max_iters(10000).target_cost(1e-3))
+            .run()
+            .unwrap();
+        assert!(res.state().get_cost() <= 1e-3);
+        assert!(res.state().get_iter() < 10000);
+    }
+}
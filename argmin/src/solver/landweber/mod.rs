@@ -15,10 +15,20 @@
 //! Landweber, L. (1951): An iteration formula for Fredholm integral equations of the first
 //! kind. Amer. J. Math. 73, 615–624
 //!
+//! Heinz W. Engl, Martin Hanke and Andreas Neubauer (1996). Regularization of Inverse Problems.
+//! Kluwer Academic Publishers.
+//!
 //! <https://en.wikipedia.org/wiki/Landweber_iteration>
 
-use crate::core::{ArgminFloat, Error, Gradient, IterState, Problem, Solver, KV};
-use argmin_math::ArgminScaledSub;
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Gradient, IterState, Problem, SerializeAlias, Solver, State,
+    TerminationReason, KV,
+};
+use crate::solver::operatornorm::{power_iteration, pseudo_random_like};
+use crate::solver::projectedgradient::{NoProjection, Projection};
+use argmin_math::{
+    ArgminAdd, ArgminMap, ArgminMul, ArgminNorm, ArgminScaledSub, ArgminSub, ArgminZeroLike,
+};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -29,26 +39,59 @@ use serde::{Deserialize, Serialize};
 /// In iteration `k`, the new parameter vector `x_{k+1}` is calculated from the previous parameter
 /// vector `x_k` and the gradient at `x_k` according to the following update rule:
 ///
-/// `x_{k+1} = x_k - omega * \nabla f(x_k)`
+/// `x_{k+1} = P_C(x_k - omega * (\nabla f(x_k) + alpha * x_k))`
+///
+/// where `P_C` is the projection onto a closed convex set `C` (projected Landweber iteration, see
+/// [`with_projection`](`Landweber::with_projection`), no projection by default) and `alpha >= 0`
+/// is a Tikhonov regularization parameter (see [`with_tikhonov`](`Landweber::with_tikhonov`),
+/// `0` by default). With `alpha > 0`, the iteration minimizes `f(x) + alpha/2 ‖x‖²`.
+///
+/// For `f(x) = 1/2 ‖Ax - b‖²` the iteration converges for `0 < omega < 2/(‖A‖² + alpha)`.
+/// Instead of providing `omega`, [`Landweber::automatic`] estimates `‖A‖²`, the Lipschitz
+/// constant of the gradient, in `init` by power iteration on gradient differences
+/// `\nabla f(x_0 + v) - \nabla f(x_0) = AᵀAv` and sets `omega = 1/(‖A‖² + alpha)`. This
+/// requires the gradient to be of the same type as the parameter vector.
+///
+/// For noisy data `b` with known noise level `‖b - b_exact‖ <= delta`, the iteration itself acts
+/// as regularization if it is stopped early. With the discrepancy principle (see
+/// [`with_discrepancy_principle`](`Landweber::with_discrepancy_principle`)), the solver
+/// terminates at the first iterate with residual `‖Ax_k - b‖ <= tau * delta`. The residual is
+/// computed from the cost function as `sqrt(2 f(x_k))` and the cost function value of every
+/// iterate is stored in the state.
 ///
 /// ## Requirements on the optimization problem
 ///
-/// The optimization problem is required to implement [`Gradient`].
+/// The optimization problem is required to implement [`Gradient`]. The discrepancy principle
+/// additionally requires [`CostFunction`] with `f(x) = 1/2 ‖Ax - b‖²`.
 ///
 /// ## References
 ///
 /// Landweber, L. (1951): An iteration formula for Fredholm integral equations of the first
 /// kind. Amer. J. Math. 73, 615–624
 ///
+/// Heinz W. Engl, Martin Hanke and Andreas Neubauer (1996). Regularization of Inverse Problems.
+/// Kluwer Academic Publishers.
+///
 /// <https://en.wikipedia.org/wiki/Landweber_iteration>
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct Landweber<F> {
+pub struct Landweber<F, C = NoProjection, S = FixedStepLength, D = NoStoppingRule> {
     /// omega
-    omega: F,
+    omega: Option<F>,
+    /// Choice of omega
+    step_length: S,
+    /// Projection onto the constraint set
+    projection: C,
+    /// Tikhonov regularization parameter
+    alpha: F,
+    /// Stopping rule
+    stopping_rule: D,
 }
 
-impl<F> Landweber<F> {
+impl<F> Landweber<F>
+where
+    F: ArgminFloat,
+{
     /// Construct a new instance of [`Landweber`]
     ///
     /// # Example
@@ -59,18 +102,294 @@ impl<F> Landweber<F> {
     /// let landweber = Landweber::new(omega);
     /// ```
     pub fn new(omega: F) -> Self {
-        Landweber { omega }
+        Landweber {
+            omega: Some(omega),
+            step_length: FixedStepLength {},
+            projection: NoProjection::new(),
+            alpha: float!(0.0),
+            stopping_rule: NoStoppingRule {},
+        }
+    }
+
+    /// Construct a new instance of [`Landweber`] which chooses `omega = 1/(‖A‖² + alpha)` in
+    /// `init`
+    ///
+    /// The Lipschitz constant `‖A‖²` of the gradient is estimated by at most 100 power
+    /// iterations on gradient differences, which assumes that the cost function is a linear least
+    /// squares problem `f(x) = 1/2 ‖Ax - b‖²`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::landweber::Landweber;
+    /// let landweber: Landweber<f64, _, _> = Landweber::automatic();
+    /// ```
+    pub fn automatic() -> Landweber<F, NoProjection, AutomaticStepLength> {
+        Landweber {
+            omega: None,
+            step_length: AutomaticStepLength {},
+            projection: NoProjection::new(),
+            alpha: float!(0.0),
+            stopping_rule: NoStoppingRule {},
+        }
+    }
+}
+
+impl<F, C, S, D> Landweber<F, C, S, D>
+where
+    F: ArgminFloat,
+{
+    /// Project every iterate onto a closed convex set (projected Landweber iteration).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::landweber::Landweber;
+    /// # use argmin::solver::projectedgradient::BoxProjection;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// // Nonnegativity constraints
+    /// let landweber = Landweber::new(0.5f64)
+    ///     .with_projection(BoxProjection::new(vec![0.0; 3], vec![f64::INFINITY; 3])?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_projection<C2>(self, projection: C2) -> Landweber<F, C2, S, D> {
+        Landweber {
+            omega: self.omega,
+            step_length: self.step_length,
+            projection,
+            alpha: self.alpha,
+            stopping_rule: self.stopping_rule,
+        }
+    }
+
+    /// Set the Tikhonov regularization parameter `alpha`.
+    ///
+    /// Must be non-negative and defaults to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::landweber::Landweber;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let landweber = Landweber::new(0.5f64).with_tikhonov(1e-3)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tikhonov(mut self, alpha: F) -> Result<Self, Error> {
+        if alpha < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Landweber`: Tikhonov regularization parameter must be non-negative."
+            ));
+        }
+        self.alpha = alpha;
+        Ok(self)
+    }
+
+    /// Stop according to the discrepancy principle.
+    ///
+    /// The solver terminates once `‖Ax_k - b‖ <= tau * noise_level`. The noise level must be
+    /// non-negative and `tau` must be at least `1`. Typical choices of `tau` are between `1.01`
+    /// and `2`. Requires the problem to implement [`CostFunction`], which is evaluated once per
+    /// iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::landweber::Landweber;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let landweber = Landweber::new(0.5f64).with_discrepancy_principle(0.01, 1.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_discrepancy_principle(
+        self,
+        noise_level: F,
+        tau: F,
+    ) -> Result<Landweber<F, C, S, DiscrepancyPrinciple<F>>, Error> {
+        if noise_level < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Landweber`: noise level must be non-negative."
+            ));
+        }
+        if tau < float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Landweber`: tau must be at least 1."
+            ));
+        }
+        Ok(Landweber {
+            omega: self.omega,
+            step_length: self.step_length,
+            projection: self.projection,
+            alpha: self.alpha,
+            stopping_rule: DiscrepancyPrinciple { noise_level, tau },
+        })
+    }
+}
+
+/// Choice of the step length `omega` of the [`Landweber`] iteration
+pub trait StepLength<O, P, F>: SerializeAlias {
+    /// Returns the step length determined at the initial parameter vector `param`, or `None` if
+    /// the step length passed to [`Landweber::new`] is used.
+    fn init_step_length(
+        &self,
+        problem: &mut Problem<O>,
+        param: &P,
+        alpha: F,
+    ) -> Result<Option<F>, Error>;
+}
+
+/// Step length `omega` provided by the user via [`Landweber::new`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct FixedStepLength {}
+
+impl<O, P, F> StepLength<O, P, F> for FixedStepLength {
+    fn init_step_length(
+        &self,
+        _problem: &mut Problem<O>,
+        _param: &P,
+        _alpha: F,
+    ) -> Result<Option<F>, Error> {
+        Ok(None)
     }
 }
 
-impl<O, F, P, G> Solver<O, IterState<P, G, (), (), F>> for Landweber<F>
+/// Step length `omega = 1/(‖A‖² + alpha)` estimated in `init`, see [`Landweber::automatic`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct AutomaticStepLength {}
+
+impl<O, P, F> StepLength<O, P, F> for AutomaticStepLength
+where
+    O: Gradient<Param = P, Gradient = P>,
+    P: ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminNorm<F>
+        + ArgminZeroLike
+        + ArgminMap<F>,
+    F: ArgminFloat,
+{
+    fn init_step_length(
+        &self,
+        problem: &mut Problem<O>,
+        param: &P,
+        alpha: F,
+    ) -> Result<Option<F>, Error> {
+        let grad = problem.gradient(param)?;
+        let lipschitz = power_iteration(
+            pseudo_random_like(param),
+            |v| Ok(problem.gradient(&param.add(v))?.sub(&grad)),
+            100,
+            float!(1e-8),
+        )?;
+        if lipschitz + alpha <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Landweber`: estimated Lipschitz constant of the gradient is zero."
+            ));
+        }
+        Ok(Some(float!(1.0) / (lipschitz + alpha)))
+    }
+}
+
+/// Stopping rule of the [`Landweber`] iteration
+pub trait StoppingRule<O, P, F>: SerializeAlias {
+    /// Returns the cost function value at `param`, or `None` if the stopping rule does not
+    /// require it.
+    fn cost(&self, problem: &mut Problem<O>, param: &P) -> Result<Option<F>, Error>;
+
+    /// Returns `true` if the iteration should be stopped at an iterate with cost function value
+    /// `cost`.
+    fn stop(&self, cost: F) -> bool;
+}
+
+/// Plain Landweber iteration without early stopping
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NoStoppingRule {}
+
+impl<O, P, F> StoppingRule<O, P, F> for NoStoppingRule {
+    fn cost(&self, _problem: &mut Problem<O>, _param: &P) -> Result<Option<F>, Error> {
+        Ok(None)
+    }
+
+    fn stop(&self, _cost: F) -> bool {
+        false
+    }
+}
+
+/// Discrepancy principle, see [`Landweber::with_discrepancy_principle`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct DiscrepancyPrinciple<F> {
+    /// Noise level `delta`
+    noise_level: F,
+    /// Safety factor `tau`
+    tau: F,
+}
+
+impl<O, P, F> StoppingRule<O, P, F> for DiscrepancyPrinciple<F>
+where
+    O: CostFunction<Param = P, Output = F>,
+    F: ArgminFloat,
+{
+    fn cost(&self, problem: &mut Problem<O>, param: &P) -> Result<Option<F>, Error> {
+        Ok(Some(problem.cost(param)?))
+    }
+
+    fn stop(&self, cost: F) -> bool {
+        (float!(2.0) * cost).sqrt() <= self.tau * self.noise_level
+    }
+}
+
+impl<O, F, P, G, C, S, D> Solver<O, IterState<P, G, (), (), F>> for Landweber<F, C, S, D>
 where
     O: Gradient<Param = P, Gradient = G>,
-    P: Clone + ArgminScaledSub<G, F, P>,
+    C: Projection<P>,
+    S: StepLength<O, P, F>,
+    D: StoppingRule<O, P, F>,
+    P: Clone + ArgminScaledSub<G, F, P> + ArgminMul<F, P>,
     F: ArgminFloat,
 {
     const NAME: &'static str = "Landweber";
 
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`Landweber` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let cost = self.stopping_rule.cost(problem, param)?;
+        let omega = self
+            .step_length
+            .init_step_length(problem, param, self.alpha)?;
+        let state = match cost {
+            Some(cost) => state.cost(cost),
+            None => state,
+        };
+        match omega {
+            Some(omega) => {
+                self.omega = Some(omega);
+                Ok((state, Some(make_kv!("omega" => omega;))))
+            }
+            None => Ok((state, None)),
+        }
+    }
+
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
@@ -83,26 +402,272 @@ where
                 "Please provide an initial guess via `Executor`s `configure` method."
             )
         ))?;
+        let omega = self.omega.ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`Landweber`: omega not set"
+        ))?;
         let grad = problem.gradient(&param)?;
-        let new_param = param.scaled_sub(&self.omega, &grad);
+        // x - omega * (grad + alpha * x) = (1 - omega * alpha) * x - omega * grad
+        let param = if self.alpha > float!(0.0) {
+            param.mul(&(float!(1.0) - omega * self.alpha))
+        } else {
+            param
+        };
+        let new_param = self.projection.project(&param.scaled_sub(&omega, &grad))?;
+        let state = match self.stopping_rule.cost(problem, &new_param)? {
+            Some(cost) => state.cost(cost),
+            None => state,
+        };
         Ok((state.param(new_param), None))
     }
+
+    fn terminate(&mut self, state: &IterState<P, G, (), (), F>) -> TerminationReason {
+        if self.stopping_rule.stop(state.get_cost()) {
+            return TerminationReason::TargetCostReached;
+        }
+        TerminationReason::NotTerminated
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{test_utils::TestProblem, ArgminError, Problem, State};
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor, Problem};
+    use crate::solver::projectedgradient::BoxProjection;
     use crate::test_trait_impl;
     use approx::assert_relative_eq;
 
     test_trait_impl!(landweber, Landweber<f64>);
+    test_trait_impl!(
+        landweber_automatic,
+        Landweber<f64, NoProjection, AutomaticStepLength>
+    );
+    test_trait_impl!(fixed_step_length, FixedStepLength);
+    test_trait_impl!(automatic_step_length, AutomaticStepLength);
+    test_trait_impl!(no_stopping_rule, NoStoppingRule);
+    test_trait_impl!(discrepancy_principle, DiscrepancyPrinciple<f64>);
 
     #[test]
     fn test_new() {
         let omega_in: f64 = 0.5;
-        let Landweber { omega } = Landweber::new(omega_in);
-        assert_eq!(omega.to_ne_bytes(), omega_in.to_ne_bytes());
+        let Landweber {
+            omega,
+            step_length,
+            projection,
+            alpha,
+            stopping_rule,
+        } = Landweber::new(omega_in);
+        assert_eq!(omega.unwrap().to_ne_bytes(), omega_in.to_ne_bytes());
+        assert_eq!(step_length, FixedStepLength {});
+        assert_eq!(projection, NoProjection::new());
+        assert_eq!(alpha.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(stopping_rule, NoStoppingRule {});
+    }
+
+    #[test]
+    fn test_automatic() {
+        let Landweber {
+            omega, step_length, ..
+        } = Landweber::<f64>::automatic();
+        assert!(omega.is_none());
+        assert_eq!(step_length, AutomaticStepLength {});
+    }
+
+    /// `f(x) = 1/2 ‖Ax - b‖²` with `A = diag(1, 2)` and `b = (1, -1)`
+    struct LeastSquares {}
+
+    impl CostFunction for LeastSquares {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(0.5 * ((x[0] - 1.0).powi(2) + (2.0 * x[1] + 1.0).powi(2)))
+        }
+    }
+
+    impl Gradient for LeastSquares {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, x: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(vec![x[0] - 1.0, 2.0 * (2.0 * x[1] + 1.0)])
+        }
+    }
+
+    #[test]
+    fn test_builders() {
+        let l = Landweber::new(0.5f64)
+            .with_tikhonov(0.1)
+            .unwrap()
+            .with_discrepancy_principle(0.01, 1.5)
+            .unwrap()
+            .with_projection(BoxProjection::new(vec![0.0; 2], vec![1.0; 2]).unwrap());
+        assert_eq!(l.omega, Some(0.5));
+        assert_eq!(l.alpha.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(
+            l.stopping_rule,
+            DiscrepancyPrinciple {
+                noise_level: 0.01,
+                tau: 1.5
+            }
+        );
+        assert_eq!(
+            l.projection,
+            BoxProjection::new(vec![0.0; 2], vec![1.0; 2]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        assert_error!(
+            Landweber::new(0.5f64).with_tikhonov(-1.0),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`Landweber`: Tikhonov regularization parameter must be ",
+                "non-negative.\""
+            )
+        );
+        assert_error!(
+            Landweber::new(0.5f64).with_discrepancy_principle(-1.0, 1.1),
+            ArgminError,
+            "Invalid parameter: \"`Landweber`: noise level must be non-negative.\""
+        );
+        assert_error!(
+            Landweber::new(0.5f64).with_discrepancy_principle(0.1, 0.9),
+            ArgminError,
+            "Invalid parameter: \"`Landweber`: tau must be at least 1.\""
+        );
+    }
+
+    #[test]
+    fn test_init_automatic() {
+        let mut landweber = Landweber::automatic();
+        let (_, kv) = landweber
+            .init(
+                &mut Problem::new(LeastSquares {}),
+                IterState::new().param(vec![0.0, 0.0]),
+            )
+            .unwrap();
+        assert_relative_eq!(landweber.omega.unwrap(), 0.25, epsilon = 1e-6);
+        assert_eq!(kv.unwrap().kv[0].0, "omega");
+
+        let res = Executor::new(LeastSquares {}, Landweber::automatic())
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(100))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        let x = res.state().get_param().unwrap();
+        assert_relative_eq!(x[0], 1.0, epsilon = 1e-6);
+        assert_relative_eq!(x[1], -0.5, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_projected() {
+        // Constrained to x >= 0: minimizer (1, 0)
+        let solver = Landweber::new(0.2)
+            .with_projection(BoxProjection::new(vec![0.0; 2], vec![f64::INFINITY; 2]).unwrap());
+        let res = Executor::new(LeastSquares {}, solver)
+            .configure(|state| state.param(vec![3.0, 3.0]).max_iters(200))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        let x = res.state().get_param().unwrap();
+        assert_relative_eq!(x[0], 1.0, epsilon = 1e-10);
+        assert_eq!(x[1].to_ne_bytes(), 0.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_cost_only_with_discrepancy_principle() {
+        /// Same as `LeastSquares`, but without `CostFunction`
+        struct GradientOnly {}
+
+        impl Gradient for GradientOnly {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, x: &Self::Param) -> Result<Self::Gradient, Error> {
+                LeastSquares {}.gradient(x)
+            }
+        }
+
+        let res = Executor::new(GradientOnly {}, Landweber::new(0.2))
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(10))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        assert!(res.state().get_cost().is_infinite());
+
+        let res = Executor::new(LeastSquares {}, Landweber::new(0.2))
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(10))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        assert!(res.state().get_func_counts().get("cost_count").is_none());
+
+        let solver = Landweber::new(0.2)
+            .with_discrepancy_principle(0.0, 1.0)
+            .unwrap();
+        let res = Executor::new(LeastSquares {}, solver)
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(10))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        assert_eq!(res.state().get_func_counts()["cost_count"], 11);
+    }
+
+    #[test]
+    fn test_tikhonov() {
+        // Minimizer of f(x) + alpha/2 ‖x‖² is (AᵀA + alpha I)⁻¹ Aᵀb = (1 / 1.5, -2 / 4.5)
+        let solver = Landweber::automatic().with_tikhonov(0.5).unwrap();
+        let res = Executor::new(LeastSquares {}, solver)
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(200))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        let x = res.state().get_param().unwrap();
+        assert_relative_eq!(x[0], 1.0 / 1.5, epsilon = 1e-8);
+        assert_relative_eq!(x[1], -2.0 / 4.5, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_discrepancy_principle() {
+        let solver = Landweber::new(0.1)
+            .with_discrepancy_principle(0.1, 1.2)
+            .unwrap();
+        let res = Executor::new(LeastSquares {}, solver)
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(1000))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        let state = res.state();
+        assert_eq!(
+            state.get_termination_reason(),
+            TerminationReason::TargetCostReached
+        );
+        let residual = (2.0 * state.get_cost()).sqrt();
+        assert!(residual <= 0.12);
+        // Stopped at the first iterate below the threshold
+        let mut problem = Problem::new(LeastSquares {});
+        let mut x = vec![0.0, 0.0];
+        for _ in 1..state.get_iter() {
+            let g = problem.gradient(&x).unwrap();
+            x = vec![x[0] - 0.1 * g[0], x[1] - 0.1 * g[1]];
+        }
+        assert!((2.0 * problem.cost(&x).unwrap()).sqrt() > 0.12);
+    }
+
+    #[test]
+    fn test_init_automatic_not_initialized() {
+        let mut landweber: Landweber<f64, _, _> = Landweber::automatic();
+        let res = landweber.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`Landweber` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
     }
 
     #[test]
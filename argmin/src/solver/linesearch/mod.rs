@@ -19,6 +19,8 @@
 //! * [Backtracking line search](`BacktrackingLineSearch`)
 //! * [More-Thuente line search](`MoreThuenteLineSearch`)
 //! * [Hager-Zhang line search](`HagerZhangLineSearch`)
+//! * [Nonmonotone line search](`NonmonotoneLineSearch`) (Grippo-Lampariello-Lucidi and
+//!   Zhang-Hager)
 //!
 //! ## References
 //!
@@ -32,16 +34,27 @@
 //! \[2\] William W. Hager and Hongchao Zhang. "A new conjugate gradient method with guaranteed
 //! descent and an efficient line search." SIAM J. Optim. 16(1), 2006, 170-192.
 //! DOI: <https://doi.org/10.1137/030601880>
+//!
+//! \[3\] Luigi Grippo, Francesco Lampariello and Stefano Lucidi. "A nonmonotone line search
+//! technique for Newton's method." SIAM J. Numer. Anal. 23(4), 1986, 707-716.
+//! DOI: <https://doi.org/10.1137/0723046>
+//!
+//! \[4\] Hongchao Zhang and William W. Hager. "A nonmonotone line search technique and its
+//! application to unconstrained optimization." SIAM J. Optim. 14(4), 2004, 1043-1056.
+//! DOI: <https://doi.org/10.1137/S1052623403428208>
 
 mod backtracking;
 /// Acceptance conditions
 pub mod condition;
 mod hagerzhang;
 mod morethuente;
+mod nonmonotone;
 
 pub use self::backtracking::BacktrackingLineSearch;
 pub use self::hagerzhang::HagerZhangLineSearch;
 pub use self::morethuente::MoreThuenteLineSearch;
+pub(crate) use self::nonmonotone::NonmonotoneMemory;
+pub use self::nonmonotone::{NonmonotoneLineSearch, NonmonotoneStrategy};
 
 /// # Line search trait
 ///
@@ -84,4 +97,16 @@ pub trait LineSearch<P, F> {
     ///
     /// This indicates the first step length which will be tried.
     fn initial_step_length(&mut self, step_length: F) -> Result<(), crate::core::Error>;
+
+    /// Take over information which is kept from one line search to the next
+    ///
+    /// Optimization methods run a clone of their line search in every iteration and pass the
+    /// finished clone to this method afterwards. Line searches which depend on previous line
+    /// searches, such as [`NonmonotoneLineSearch`], override it. The default implementation does
+    /// nothing.
+    fn consume_line_search(&mut self, _finished: Self)
+    where
+        Self: Sized,
+    {
+    }
 }
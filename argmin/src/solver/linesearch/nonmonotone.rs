@@ -0,0 +1,619 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Gradient, IterState, LineSearch, Problem, SerializeAlias,
+    Solver, State, TerminationReason, KV,
+};
+use argmin_math::{ArgminDot, ArgminScaledAdd};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Reference cost function value of a [`NonmonotoneLineSearch`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum NonmonotoneStrategy<F> {
+    /// Maximum of the last `m` cost function values (Grippo, Lampariello and Lucidi)
+    Max(usize),
+    /// Weighted average `C_k` of all previous cost function values (Zhang and Hager):
+    ///
+    /// `Q_{k+1} = eta Q_k + 1`, `C_{k+1} = (eta Q_k C_k + f_{k+1}) / Q_{k+1}`
+    ///
+    /// with `C_0 = f_0` and `Q_0 = 1`. `eta = 0` leads to a monotone line search and `eta = 1` to
+    /// the average of all previous cost function values.
+    Average(F),
+}
+
+/// History of cost function values used for nonmonotone acceptance conditions
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub(crate) struct NonmonotoneMemory<F> {
+    /// Recent cost function values (`Max`)
+    costs: VecDeque<F>,
+    /// Weighted average of cost function values (`Average`)
+    c: Option<F>,
+    /// Weight (`Average`)
+    q: F,
+}
+
+impl<F: ArgminFloat> NonmonotoneMemory<F> {
+    /// Construct an empty history
+    pub(crate) fn new() -> Self {
+        NonmonotoneMemory {
+            costs: VecDeque::new(),
+            c: None,
+            q: float!(1.0),
+        }
+    }
+
+    /// Add the cost function value at the start of a new line search and return the reference
+    /// cost function value.
+    pub(crate) fn push(&mut self, strategy: &NonmonotoneStrategy<F>, cost: F) -> F {
+        match *strategy {
+            NonmonotoneStrategy::Max(m) => {
+                self.costs.push_back(cost);
+                while self.costs.len() > m {
+                    self.costs.pop_front();
+                }
+                self.costs.iter().fold(F::neg_infinity(), |a, &b| a.max(b))
+            }
+            NonmonotoneStrategy::Average(eta) => {
+                let c = match self.c {
+                    Some(c) => {
+                        let q = eta * self.q + float!(1.0);
+                        let c = (eta * self.q * c + cost) / q;
+                        self.q = q;
+                        c
+                    }
+                    None => {
+                        self.q = float!(1.0);
+                        cost
+                    }
+                };
+                self.c = Some(c);
+                c
+            }
+        }
+    }
+}
+
+/// # Nonmonotone line search
+///
+/// Backtracking line search which accepts a step length `alpha` if
+///
+/// `f(x_k + alpha p_k) <= C_k + c alpha <\nabla f(x_k), p_k>`
+///
+/// where the reference value `C_k` is either the maximum of the last `m` cost function values
+/// ([`NonmonotoneStrategy::Max`], Grippo, Lampariello and Lucidi) or a weighted average of all
+/// previous cost function values ([`NonmonotoneStrategy::Average`], Zhang and Hager). Compared to
+/// the (monotone) [`BacktrackingLineSearch`](`crate::solver::linesearch::BacktrackingLineSearch`)
+/// with the Armijo condition, this allows the cost function to increase occasionally, which is
+/// beneficial for methods such as Barzilai-Borwein steps.
+///
+/// The step length starts at `1` (or the value set via
+/// [`initial_step_length`](`LineSearch::initial_step_length`)) and is reduced by a factor `rho`
+/// until the condition is met.
+///
+/// Each line search run records the cost function value at its starting point. Optimization
+/// methods such as [`SteepestDescent`](`crate::solver::gradientdescent::SteepestDescent`),
+/// [`LBFGS`](`crate::solver::quasinewton::LBFGS`) and
+/// [`NonlinearConjugateGradient`](`crate::solver::conjugategradient::NonlinearConjugateGradient`)
+/// run a clone of their line search in every iteration and take over this history via
+/// [`consume_line_search`](`LineSearch::consume_line_search`) afterwards. Clones of an instance
+/// do not share the history.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
+///
+/// ## References
+///
+/// Luigi Grippo, Francesco Lampariello and Stefano Lucidi (1986). A nonmonotone line search
+/// technique for Newton's method. SIAM Journal on Numerical Analysis 23(4), pp. 707-716.
+///
+/// Hongchao Zhang and William W. Hager (2004). A nonmonotone line search technique and its
+/// application to unconstrained optimization. SIAM Journal on Optimization 14(4), pp. 1043-1056.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NonmonotoneLineSearch<P, G, F> {
+    /// initial parameter vector
+    init_param: Option<P>,
+    /// initial gradient
+    init_grad: Option<G>,
+    /// Search direction
+    search_direction: Option<P>,
+    /// Reference cost function value
+    reference_cost: F,
+    /// Strategy for computing the reference cost function value
+    strategy: NonmonotoneStrategy<F>,
+    /// History of cost function values
+    memory: NonmonotoneMemory<F>,
+    /// Sufficient decrease parameter c
+    c: F,
+    /// Contraction factor rho
+    rho: F,
+    /// alpha
+    alpha: F,
+}
+
+impl<P, G, F> NonmonotoneLineSearch<P, G, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `NonmonotoneLineSearch`
+    ///
+    /// For [`NonmonotoneStrategy::Max`] the memory `m` must be at least 1, for
+    /// [`NonmonotoneStrategy::Average`] `eta` must be in `[0, 1]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linesearch::{NonmonotoneLineSearch, NonmonotoneStrategy};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gll: NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64> =
+    ///     NonmonotoneLineSearch::new(NonmonotoneStrategy::Max(10))?;
+    /// let zhang_hager: NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64> =
+    ///     NonmonotoneLineSearch::new(NonmonotoneStrategy::Average(0.85))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(strategy: NonmonotoneStrategy<F>) -> Result<Self, Error> {
+        match strategy {
+            NonmonotoneStrategy::Max(m) if m < 1 => {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`NonmonotoneLineSearch`: memory must be >= 1."
+                ));
+            }
+            NonmonotoneStrategy::Average(eta) if !(float!(0.0)..=float!(1.0)).contains(&eta) => {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`NonmonotoneLineSearch`: eta must be in [0, 1]."
+                ));
+            }
+            _ => {}
+        }
+        Ok(NonmonotoneLineSearch {
+            init_param: None,
+            init_grad: None,
+            search_direction: None,
+            reference_cost: F::infinity(),
+            strategy,
+            memory: NonmonotoneMemory::new(),
+            c: float!(1e-4),
+            rho: float!(0.5),
+            alpha: float!(1.0),
+        })
+    }
+
+    /// Set sufficient decrease parameter c
+    ///
+    /// Must be in `(0, 1)` and defaults to `1e-4`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linesearch::{NonmonotoneLineSearch, NonmonotoneStrategy};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let linesearch: NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64> =
+    ///     NonmonotoneLineSearch::new(NonmonotoneStrategy::Max(10))?.with_c(0.01)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_c(mut self, c: F) -> Result<Self, Error> {
+        if c <= float!(0.0) || c >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NonmonotoneLineSearch`: Parameter c must be in (0, 1)."
+            ));
+        }
+        self.c = c;
+        Ok(self)
+    }
+
+    /// Set contraction factor rho
+    ///
+    /// Must be in `(0, 1)` and defaults to `0.5`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linesearch::{NonmonotoneLineSearch, NonmonotoneStrategy};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let linesearch: NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64> =
+    ///     NonmonotoneLineSearch::new(NonmonotoneStrategy::Max(10))?.with_rho(0.9)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_rho(mut self, rho: F) -> Result<Self, Error> {
+        if rho <= float!(0.0) || rho >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NonmonotoneLineSearch`: Contraction factor rho must be in (0, 1)."
+            ));
+        }
+        self.rho = rho;
+        Ok(self)
+    }
+}
+
+impl<P, G, F> LineSearch<P, F> for NonmonotoneLineSearch<P, G, F>
+where
+    F: ArgminFloat,
+{
+    /// Set search direction
+    fn search_direction(&mut self, search_direction: P) {
+        self.search_direction = Some(search_direction);
+    }
+
+    /// Set initial step length
+    fn initial_step_length(&mut self, alpha: F) -> Result<(), Error> {
+        if alpha <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "LineSearch: Initial alpha must be > 0."
+            ));
+        }
+        self.alpha = alpha;
+        Ok(())
+    }
+
+    /// Take over the history of cost function values
+    fn consume_line_search(&mut self, finished: Self) {
+        self.memory = finished.memory;
+    }
+}
+
+impl<P, G, F> NonmonotoneLineSearch<P, G, F>
+where
+    P: ArgminScaledAdd<P, F, P>,
+    IterState<P, G, (), (), F>: State<Float = F>,
+    F: ArgminFloat,
+{
+    /// Evaluate the cost function at the current step length
+    fn step<O>(
+        &self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), F>,
+    ) -> Result<IterState<P, G, (), (), F>, Error>
+    where
+        O: CostFunction<Param = P, Output = F>,
+    {
+        let new_param = self
+            .init_param
+            .as_ref()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`NonmonotoneLineSearch`: Initial parameter vector not set."
+            ))?
+            .scaled_add(
+                &self.alpha,
+                self.search_direction
+                    .as_ref()
+                    .ok_or_else(argmin_error_closure!(
+                        PotentialBug,
+                        "`NonmonotoneLineSearch`: Search direction not set."
+                    ))?,
+            );
+        let cost = problem.cost(&new_param)?;
+        Ok(state.param(new_param).cost(cost))
+    }
+}
+
+impl<O, P, G, F> Solver<O, IterState<P, G, (), (), F>> for NonmonotoneLineSearch<P, G, F>
+where
+    P: Clone + SerializeAlias + ArgminScaledAdd<P, F, P>,
+    G: SerializeAlias + ArgminDot<P, F>,
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Nonmonotone line search";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), F>, Option<KV>), Error> {
+        if self.search_direction.is_none() {
+            return Err(argmin_error!(
+                NotInitialized,
+                "`NonmonotoneLineSearch`: search_direction must be set."
+            ));
+        }
+
+        let init_param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`NonmonotoneLineSearch` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let cost = state.get_cost();
+        let init_cost = if cost.is_infinite() {
+            problem.cost(&init_param)?
+        } else {
+            cost
+        };
+
+        let init_grad = state
+            .take_grad()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&init_param))?;
+
+        self.reference_cost = self.memory.push(&self.strategy, init_cost);
+
+        self.init_param = Some(init_param);
+        self.init_grad = Some(init_grad);
+        let state = self.step(problem, state)?;
+        Ok((
+            state,
+            Some(make_kv!["reference_cost" => self.reference_cost;]),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), F>, Option<KV>), Error> {
+        self.alpha = self.alpha * self.rho;
+        let state = self.step(problem, state)?;
+        Ok((state, None))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, G, (), (), F>) -> TerminationReason {
+        let slope = self
+            .init_grad
+            .as_ref()
+            .unwrap()
+            .dot(self.search_direction.as_ref().unwrap());
+        if state.cost <= self.reference_cost + self.c * self.alpha * slope {
+            TerminationReason::LineSearchConditionMet
+        } else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(nonmonotone, NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64>);
+
+    #[test]
+    fn test_new() {
+        let ls: NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64> =
+            NonmonotoneLineSearch::new(NonmonotoneStrategy::Max(5)).unwrap();
+        assert_eq!(ls.strategy, NonmonotoneStrategy::Max(5));
+        assert_eq!(ls.c.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(ls.rho.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(ls.alpha.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(ls.init_param.is_none());
+        assert!(ls.init_grad.is_none());
+        assert!(ls.search_direction.is_none());
+
+        for eta in [0.0, 0.5, 1.0] {
+            let res: Result<NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64>, _> =
+                NonmonotoneLineSearch::new(NonmonotoneStrategy::Average(eta));
+            assert!(res.is_ok());
+        }
+
+        let res: Result<NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64>, _> =
+            NonmonotoneLineSearch::new(NonmonotoneStrategy::Max(0));
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`NonmonotoneLineSearch`: memory must be >= 1.\""
+        );
+
+        for eta in [-f64::EPSILON, 1.0 + f64::EPSILON, f64::NAN] {
+            let res: Result<NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64>, _> =
+                NonmonotoneLineSearch::new(NonmonotoneStrategy::Average(eta));
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`NonmonotoneLineSearch`: eta must be in [0, 1].\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_c_and_rho() {
+        for val in [f64::EPSILON, 0.5, 1.0 - f64::EPSILON] {
+            let ls: NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64> =
+                NonmonotoneLineSearch::new(NonmonotoneStrategy::Max(5)).unwrap();
+            let ls = ls.with_c(val).unwrap().with_rho(val).unwrap();
+            assert_eq!(ls.c.to_ne_bytes(), val.to_ne_bytes());
+            assert_eq!(ls.rho.to_ne_bytes(), val.to_ne_bytes());
+        }
+
+        for val in [0.0, 1.0, -1.0, 2.0] {
+            let ls: NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64> =
+                NonmonotoneLineSearch::new(NonmonotoneStrategy::Max(5)).unwrap();
+            assert_error!(
+                ls.clone().with_c(val),
+                ArgminError,
+                "Invalid parameter: \"`NonmonotoneLineSearch`: Parameter c must be in (0, 1).\""
+            );
+            assert_error!(
+                ls.with_rho(val),
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`NonmonotoneLineSearch`: ",
+                    "Contraction factor rho must be in (0, 1).\""
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_memory_max() {
+        let strategy = NonmonotoneStrategy::Max(3);
+        let mut memory: NonmonotoneMemory<f64> = NonmonotoneMemory::new();
+        let refs: Vec<f64> = [5.0, 3.0, 4.0, 1.0, 2.0, 0.5]
+            .iter()
+            .map(|&c| memory.push(&strategy, c))
+            .collect();
+        assert_eq!(refs, vec![5.0, 5.0, 5.0, 4.0, 4.0, 2.0]);
+    }
+
+    #[test]
+    fn test_memory_average() {
+        // eta = 0: monotone
+        let strategy = NonmonotoneStrategy::Average(0.0);
+        let mut memory: NonmonotoneMemory<f64> = NonmonotoneMemory::new();
+        for c in [5.0, 3.0, 4.0] {
+            assert_relative_eq!(memory.push(&strategy, c), c, epsilon = f64::EPSILON);
+        }
+
+        // eta = 1: arithmetic mean
+        let strategy = NonmonotoneStrategy::Average(1.0);
+        let mut memory: NonmonotoneMemory<f64> = NonmonotoneMemory::new();
+        let refs: Vec<f64> = [6.0, 3.0, 0.0]
+            .iter()
+            .map(|&c| memory.push(&strategy, c))
+            .collect();
+        assert_relative_eq!(refs[0], 6.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(refs[1], 4.5, epsilon = f64::EPSILON);
+        assert_relative_eq!(refs[2], 3.0, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_init_search_direction_not_set() {
+        let mut ls: NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64> =
+            NonmonotoneLineSearch::new(NonmonotoneStrategy::Max(5)).unwrap();
+        let res = ls.init(
+            &mut Problem::new(TestProblem::new()),
+            IterState::new().param(vec![1.0, 2.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            "Not initialized: \"`NonmonotoneLineSearch`: search_direction must be set.\""
+        );
+    }
+
+    #[test]
+    fn test_consume_line_search() {
+        let mut ls: NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64> =
+            NonmonotoneLineSearch::new(NonmonotoneStrategy::Max(5)).unwrap();
+        ls.search_direction(vec![-1.0, -2.0]);
+
+        // Clones do not share the history
+        let res = Executor::new(TestProblem::new(), ls.clone())
+            .configure(|state| state.param(vec![1.0, 2.0]).cost(10.0))
+            .run()
+            .unwrap();
+        assert!(ls.memory.costs.is_empty());
+        assert_eq!(res.solver.memory.costs, vec![10.0]);
+
+        // ... unless it is taken over from the finished line search
+        ls.consume_line_search(res.solver);
+        let res = Executor::new(TestProblem::new(), ls.clone())
+            .configure(|state| state.param(vec![1.0, 2.0]).cost(20.0))
+            .run()
+            .unwrap();
+        assert_eq!(res.solver.memory.costs, vec![10.0, 20.0]);
+    }
+
+    #[test]
+    fn test_nonmonotone_acceptance() {
+        struct Quadratic {}
+
+        impl CostFunction for Quadratic {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p[0].powi(2))
+            }
+        }
+
+        impl Gradient for Quadratic {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![2.0 * p[0]])
+            }
+        }
+
+        let mut ls: NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64> =
+            NonmonotoneLineSearch::new(NonmonotoneStrategy::Max(5)).unwrap();
+
+        // Previous cost function value of 100 is in memory
+        ls.memory.push(&NonmonotoneStrategy::Max(5), 100.0);
+
+        // A step from 1 to -4 increases the cost function value, but is accepted because it is
+        // below the maximum of the previous cost function values.
+        ls.search_direction(vec![-5.0]);
+        let res = Executor::new(Quadratic {}, ls.clone())
+            .configure(|state| state.param(vec![1.0]))
+            .run()
+            .unwrap();
+        assert_relative_eq!(res.state.param.as_ref().unwrap()[0], -4.0, epsilon = 1e-12);
+        assert_eq!(res.state.get_iter(), 0);
+
+        // A monotone line search needs to backtrack
+        let mut ls: NonmonotoneLineSearch<Vec<f64>, Vec<f64>, f64> =
+            NonmonotoneLineSearch::new(NonmonotoneStrategy::Max(1)).unwrap();
+        ls.search_direction(vec![-5.0]);
+        let res = Executor::new(Quadratic {}, ls)
+            .configure(|state| state.param(vec![1.0]))
+            .run()
+            .unwrap();
+        assert!(res.state.param.as_ref().unwrap()[0].abs() < 1.0);
+        assert!(res.state.get_iter() > 0);
+    }
+
+    #[test]
+    fn test_steepest_descent() {
+        use crate::solver::gradientdescent::SteepestDescent;
+        use argmin_testfunctions::{rosenbrock_2d, rosenbrock_2d_derivative};
+
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(rosenbrock_2d(p, 1.0, 100.0))
+            }
+        }
+
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(rosenbrock_2d_derivative(p, 1.0, 100.0))
+            }
+        }
+
+        for strategy in [
+            NonmonotoneStrategy::Max(10),
+            NonmonotoneStrategy::Average(0.85),
+        ] {
+            let linesearch = NonmonotoneLineSearch::new(strategy).unwrap();
+            let res = Executor::new(Rosenbrock {}, SteepestDescent::new(linesearch))
+                .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(20000))
+                .run()
+                .unwrap();
+            assert!(res.state.get_best_cost() < 1e-6);
+        }
+    }
+}
@@ -5,16 +5,32 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+pub mod basinhopping;
+pub mod bayesianoptimization;
+pub mod branchandbound;
 pub mod brent;
 pub mod conjugategradient;
+pub mod coordinatedescent;
+pub mod crossentropy;
+pub mod direct;
 pub mod gaussnewton;
 pub mod goldensectionsearch;
 pub mod gradientdescent;
+pub mod kaczmarz;
 pub mod landweber;
 pub mod linesearch;
 pub mod neldermead;
+pub mod nes;
 pub mod newton;
+pub mod nnls;
+pub mod nonsmooth;
+pub mod operatornorm;
 pub mod particleswarm;
+pub mod primaldual;
+pub mod projectedgradient;
+pub mod quadraticprogramming;
 pub mod quasinewton;
+pub mod riemannian;
 pub mod simulatedannealing;
+pub mod spsa;
 pub mod trustregion;
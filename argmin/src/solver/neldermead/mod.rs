@@ -49,6 +49,14 @@ use std::fmt;
 /// 4) Shrink (Parameter `sigma`, defaults to `0.5`, configurable via
 ///    [`with_sigma`](`NelderMead::with_sigma`))
 ///
+/// By default, an initial parameter vector provided via the
+/// [`configure`](`crate::core::Executor::configure`) method of the
+/// [`Executor`](`crate::core::Executor`) is ignored. With
+/// [`with_simplex_translation`](`NelderMead::with_simplex_translation`), the simplex is instead
+/// translated such that its first vertex coincides with the provided parameter vector, which
+/// allows running the solver from different starting points (for instance within
+/// [`BasinHopping`](`crate::solver::basinhopping::BasinHopping`)).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`].
@@ -73,6 +81,8 @@ pub struct NelderMead<P, F> {
     params: Vec<(P, F)>,
     /// Sample standard deviation tolerance
     sd_tolerance: F,
+    /// Translate simplex to the initial parameter vector
+    translate: bool,
 }
 
 impl<P, F> NelderMead<P, F>
@@ -100,6 +110,7 @@ where
             sigma: float!(0.5),
             params: params.into_iter().map(|p| (p, F::nan())).collect(),
             sd_tolerance: F::epsilon(),
+            translate: false,
         }
     }
 
@@ -130,6 +141,27 @@ where
         Ok(self)
     }
 
+    /// Translate the simplex to the initial parameter vector
+    ///
+    /// If enabled, the simplex is translated such that its first vertex coincides with the initial
+    /// parameter vector provided via the [`configure`](`crate::core::Executor::configure`) method
+    /// of the [`Executor`](`crate::core::Executor`). Defaults to `false`, in which case the
+    /// initial parameter vector is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::neldermead::NelderMead;
+    /// # let vec_of_parameters = vec![vec![1.0], vec![2.0], vec![3.0]];
+    /// let nm: NelderMead<Vec<f64>, f64> =
+    ///     NelderMead::new(vec_of_parameters).with_simplex_translation(true);
+    /// ```
+    #[must_use]
+    pub fn with_simplex_translation(mut self, translate: bool) -> Self {
+        self.translate = translate;
+        self
+    }
+
     /// Set alpha parameter for reflection
     ///
     /// Must be larger than 0 and defaults to 1.
@@ -327,8 +359,15 @@ where
     fn init(
         &mut self,
         problem: &mut Problem<O>,
-        state: IterState<P, (), (), (), F>,
+        mut state: IterState<P, (), (), (), F>,
     ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        if self.translate {
+            if let Some(param) = state.take_param() {
+                let shift = param.sub(&self.params[0].0);
+                self.params.iter_mut().for_each(|(p, _)| *p = p.add(&shift));
+            }
+        }
+
         self.params
             .iter_mut()
             .for_each(|(p, c)| *c = problem.cost(p).unwrap());
@@ -462,6 +501,7 @@ mod tests {
             sigma,
             params,
             sd_tolerance,
+            translate,
         } = nm;
 
         assert_eq!(alpha.to_ne_bytes(), 1.0f64.to_ne_bytes());
@@ -473,6 +513,7 @@ mod tests {
         assert_eq!(params[0].1.to_ne_bytes(), f64::NAN.to_ne_bytes());
         assert_eq!(params[1].1.to_ne_bytes(), f64::NAN.to_ne_bytes());
         assert_eq!(sd_tolerance.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert!(!translate);
     }
 
     #[test]
@@ -717,6 +758,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_init_ignores_param() {
+        let params: Vec<Vec<f64>> = vec![vec![1.0, 1.0], vec![1.5, 1.0], vec![1.0, 1.5]];
+        let mut nm: NelderMead<_, f64> = NelderMead::new(params);
+        let state: IterState<Vec<f64>, (), (), (), f64> = IterState::new().param(vec![0.0, -1.0]);
+        let problem = MwProblem {};
+        nm.init(&mut Problem::new(problem), state).unwrap();
+
+        let mut vertices: Vec<Vec<f64>> = nm.params.iter().map(|(p, _)| p.clone()).collect();
+        vertices.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(
+            vertices,
+            vec![vec![1.0, 1.0], vec![1.0, 1.5], vec![1.5, 1.0]]
+        );
+    }
+
+    #[test]
+    fn test_init_translates_simplex() {
+        let params: Vec<Vec<f64>> = vec![vec![1.0, 1.0], vec![1.5, 1.0], vec![1.0, 1.5]];
+        let mut nm: NelderMead<_, f64> = NelderMead::new(params).with_simplex_translation(true);
+        let state: IterState<Vec<f64>, (), (), (), f64> = IterState::new().param(vec![0.0, -1.0]);
+        let problem = MwProblem {};
+        let (state_out, _) = nm.init(&mut Problem::new(problem), state).unwrap();
+
+        let mut vertices: Vec<Vec<f64>> = nm.params.iter().map(|(p, _)| p.clone()).collect();
+        vertices.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(
+            vertices,
+            vec![vec![0.0, -1.0], vec![0.0, -0.5], vec![0.5, -1.0]]
+        );
+        assert_eq!(state_out.get_param().unwrap(), &vec![0.0, -0.5]);
+    }
+
     #[test]
     fn test_next_iter_reflection() {
         let params: Vec<Vec<f64>> = vec![vec![-1.0, 0.0], vec![-0.1, 0.65], vec![-0.1, -0.95]];
@@ -0,0 +1,499 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Natural evolution strategies
+//!
+//! Sampling-based optimization which follows the natural gradient of the expected cost function
+//! value with respect to the parameters of a search distribution. See [`SeparableNES`] for
+//! details.
+//!
+//! ## References
+//!
+//! Tom Schaul, Tobias Glasmachers and Jürgen Schmidhuber (2011). High dimensions and heavy tails
+//! for natural evolution strategies. Proceedings of the 13th Annual Conference on Genetic and
+//! Evolutionary Computation (GECCO), 845–852.
+//!
+//! Daan Wierstra, Tom Schaul, Tobias Glasmachers, Yi Sun, Jan Peters and Jürgen Schmidhuber
+//! (2014). Natural evolution strategies. Journal of Machine Learning Research 15, 949–980.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, PopulationState, Problem, SerializeAlias, Solver, SyncAlias,
+    TerminationReason, KV,
+};
+use crate::solver::crossentropy::standard_normal_like;
+use argmin_math::{ArgminAdd, ArgminMap, ArgminMul, ArgminNorm, ArgminScaledAdd, ArgminZeroLike};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Separable natural evolution strategy (SNES)
+///
+/// The search distribution is a normal distribution with mean `μ` and diagonal covariance matrix
+/// `diag(σ²)`. In each iteration (generation), `λ` samples `x_k = μ + σ ⊙ z_k` with
+/// `z_k ~ N(0, I)` are drawn and scored via [`bulk_cost`](`crate::core::Problem::bulk_cost`),
+/// which computes the cost function values in parallel if the `rayon` feature is enabled. Instead
+/// of the cost function values, rank-based utilities `u_k` (fitness shaping) of the samples enter
+/// the natural gradients
+///
+/// `∇μ = Σ_k u_k z_k` and `∇σ = Σ_k u_k (z_k ⊙ z_k - 1)`,
+///
+/// which makes the method invariant to monotonic transformations of the cost function. The
+/// distribution is updated according to
+///
+/// `μ = μ + η_μ σ ⊙ ∇μ` and `σ = σ ⊙ exp(η_σ / 2 ∇σ)`.
+///
+/// For a problem of dimension `d`, the defaults are `λ = 4 + ⌊3 ln(d)⌋` (see
+/// [`with_population_size`](`SeparableNES::with_population_size`)), `η_μ = 1` and
+/// `η_σ = (3 + ln(d)) / (5 √d)` (see [`with_learning_rates`](`SeparableNES::with_learning_rates`)).
+///
+/// The state holds the best sample of the current generation as individual and all samples of
+/// the current generation as population. The current mean and standard deviations can be
+/// obtained via [`mean`](`SeparableNES::mean`) and [`std_dev`](`SeparableNES::std_dev`). The
+/// solver terminates once the norm of the standard deviations falls below a tolerance (`0` by
+/// default, see [`with_tolerance`](`SeparableNES::with_tolerance`)). The random number generator
+/// is part of the solver, hence seeded runs are reproducible and can be checkpointed.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## References
+///
+/// Tom Schaul, Tobias Glasmachers and Jürgen Schmidhuber (2011). High dimensions and heavy tails
+/// for natural evolution strategies. Proceedings of the 13th Annual Conference on Genetic and
+/// Evolutionary Computation (GECCO), 845–852.
+///
+/// Daan Wierstra, Tom Schaul, Tobias Glasmachers, Yi Sun, Jan Peters and Jürgen Schmidhuber
+/// (2014). Natural evolution strategies. Journal of Machine Learning Research 15, 949–980.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SeparableNES<P, F, R> {
+    /// Mean of the search distribution
+    mean: P,
+    /// Standard deviations of the search distribution
+    std_dev: P,
+    /// Number of samples per generation
+    population_size: Option<usize>,
+    /// Learning rate of the mean
+    learning_rate_mean: F,
+    /// Learning rate of the standard deviations
+    learning_rate_std: Option<F>,
+    /// Tolerance on the norm of the standard deviations
+    tol: F,
+    /// Random number generator
+    rng: R,
+}
+
+impl<P, F> SeparableNES<P, F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`SeparableNES`]
+    ///
+    /// Takes the initial mean and standard deviations of the search distribution as input.
+    ///
+    /// Uses the `Xoshiro256PlusPlus` RNG internally. For use of another RNG or a seeded RNG,
+    /// consider using [`SeparableNES::new_with_rng`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nes::SeparableNES;
+    /// let nes: SeparableNES<_, f64, _> = SeparableNES::new(vec![0.0, 0.0], vec![1.0, 1.0]);
+    /// ```
+    pub fn new(mean: P, std_dev: P) -> Self {
+        SeparableNES::new_with_rng(mean, std_dev, Xoshiro256PlusPlus::from_entropy())
+    }
+}
+
+impl<P, F, R> SeparableNES<P, F, R>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`SeparableNES`]
+    ///
+    /// Takes the initial mean and standard deviations of the search distribution as input.
+    /// Requires a RNG which must implement `rand::Rng` (and `serde::Serialize` if the `serde1`
+    /// feature is enabled).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nes::SeparableNES;
+    /// use rand::SeedableRng;
+    /// use rand_xoshiro::Xoshiro256PlusPlus;
+    ///
+    /// let rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    /// let nes: SeparableNES<_, f64, _> =
+    ///     SeparableNES::new_with_rng(vec![0.0, 0.0], vec![1.0, 1.0], rng);
+    /// ```
+    pub fn new_with_rng(mean: P, std_dev: P, rng: R) -> Self {
+        SeparableNES {
+            mean,
+            std_dev,
+            population_size: None,
+            learning_rate_mean: float!(1.0),
+            learning_rate_std: None,
+            tol: float!(0.0),
+            rng,
+        }
+    }
+
+    /// Set the number of samples per generation.
+    ///
+    /// Must be at least 2 and defaults to `4 + ⌊3 ln(d)⌋`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nes::SeparableNES;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nes: SeparableNES<_, f64, _> =
+    ///     SeparableNES::new(vec![0.0, 0.0], vec![1.0, 1.0]).with_population_size(20)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_population_size(mut self, population_size: usize) -> Result<Self, Error> {
+        if population_size < 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SeparableNES`: population size must be at least 2."
+            ));
+        }
+        self.population_size = Some(population_size);
+        Ok(self)
+    }
+
+    /// Set the learning rates `η_μ` of the mean and `η_σ` of the standard deviations.
+    ///
+    /// Both must be > 0. Defaults to `η_μ = 1` and `η_σ = (3 + ln(d)) / (5 √d)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nes::SeparableNES;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nes: SeparableNES<_, f64, _> =
+    ///     SeparableNES::new(vec![0.0, 0.0], vec![1.0, 1.0]).with_learning_rates(1.0, 0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_learning_rates(mut self, mean: F, std_dev: F) -> Result<Self, Error> {
+        if mean <= float!(0.0) || std_dev <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SeparableNES`: learning rates must be > 0."
+            ));
+        }
+        self.learning_rate_mean = mean;
+        self.learning_rate_std = Some(std_dev);
+        Ok(self)
+    }
+
+    /// Set the tolerance on the norm of the standard deviations.
+    ///
+    /// Must be non-negative and defaults to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nes::SeparableNES;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nes: SeparableNES<_, f64, _> =
+    ///     SeparableNES::new(vec![0.0, 0.0], vec![1.0, 1.0]).with_tolerance(1e-8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SeparableNES`: tolerance must be non-negative."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Returns the mean of the search distribution
+    pub fn mean(&self) -> &P {
+        &self.mean
+    }
+
+    /// Returns the standard deviations of the search distribution
+    pub fn std_dev(&self) -> &P {
+        &self.std_dev
+    }
+}
+
+/// Rank-based utilities of `population_size` samples, sorted from best to worst
+fn utilities<F: ArgminFloat>(population_size: usize) -> Vec<F> {
+    let lambda = F::from_usize(population_size).unwrap();
+    let raw: Vec<F> = (1..=population_size)
+        .map(|rank| {
+            ((lambda / float!(2.0) + float!(1.0)).ln() - F::from_usize(rank).unwrap().ln())
+                .max(float!(0.0))
+        })
+        .collect();
+    let total = raw.iter().fold(float!(0.0), |acc, &u| acc + u);
+    raw.into_iter()
+        .map(|u| u / total - float!(1.0) / lambda)
+        .collect()
+}
+
+impl<O, P, F, R> Solver<O, PopulationState<P, F>> for SeparableNES<P, F, R>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone
+        + SerializeAlias
+        + SyncAlias
+        + ArgminAdd<P, P>
+        + ArgminMul<P, P>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminMap<F>
+        + ArgminNorm<F>
+        + ArgminZeroLike,
+    F: ArgminFloat,
+    R: Rng + SerializeAlias,
+{
+    const NAME: &'static str = "Separable NES";
+
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: PopulationState<P, F>,
+    ) -> Result<(PopulationState<P, F>, Option<KV>), Error> {
+        // squared norm of a vector of ones
+        let dim = self.mean.map(|_| float!(1.0)).norm().powi(2).round();
+        let population_size = *self
+            .population_size
+            .get_or_insert_with(|| 4 + (float!(3.0) * dim.ln()).floor().to_usize().unwrap());
+        let learning_rate_std = *self
+            .learning_rate_std
+            .get_or_insert_with(|| (float!(3.0) + dim.ln()) / (float!(5.0) * dim.sqrt()));
+        Ok((
+            state,
+            Some(make_kv!(
+                "population_size" => population_size;
+                "learning_rate_std" => learning_rate_std;
+            )),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: PopulationState<P, F>,
+    ) -> Result<(PopulationState<P, F>, Option<KV>), Error> {
+        let population_size = self.population_size.ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`SeparableNES`: population size not set."
+        ))?;
+        let learning_rate_std = self.learning_rate_std.ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`SeparableNES`: learning rate of the standard deviations not set."
+        ))?;
+
+        let noise: Vec<P> = (0..population_size)
+            .map(|_| standard_normal_like(&self.mean, &mut self.rng))
+            .collect();
+        let samples: Vec<P> = noise
+            .iter()
+            .map(|z| self.mean.add(&self.std_dev.mul(z)))
+            .collect();
+        let costs = problem.bulk_cost(&samples)?;
+
+        let mut order: Vec<usize> = (0..population_size).collect();
+        order.sort_by(|&a, &b| {
+            costs[a]
+                .partial_cmp(&costs[b])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut grad_mean = self.mean.zero_like();
+        let mut grad_std = self.mean.zero_like();
+        for (&i, u) in order.iter().zip(utilities::<F>(population_size)) {
+            let z = &noise[i];
+            grad_mean = grad_mean.scaled_add(&u, z);
+            grad_std = grad_std.scaled_add(&u, &z.mul(z).map(|v| v - float!(1.0)));
+        }
+        self.mean = self
+            .mean
+            .scaled_add(&self.learning_rate_mean, &self.std_dev.mul(&grad_mean));
+        let half_rate = learning_rate_std / float!(2.0);
+        self.std_dev = self.std_dev.mul(&grad_std.map(|g| (half_rate * g).exp()));
+
+        let best = order[0];
+        Ok((
+            state
+                .individual(samples[best].clone())
+                .cost(costs[best])
+                .population(samples),
+            Some(make_kv!("spread" => self.std_dev.norm();)),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &PopulationState<P, F>) -> TerminationReason {
+        if self.std_dev.norm() <= self.tol {
+            return TerminationReason::TargetToleranceReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor, State};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+    use argmin_testfunctions::rosenbrock_2d;
+
+    test_trait_impl!(nes, SeparableNES<Vec<f64>, f64, Xoshiro256PlusPlus>);
+
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Vec<f64>) -> Result<f64, Error> {
+            Ok(rosenbrock_2d(p, 1.0, 100.0))
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let SeparableNES {
+            mean,
+            std_dev,
+            population_size,
+            learning_rate_mean,
+            learning_rate_std,
+            tol,
+            rng: _,
+        }: SeparableNES<_, f64, _> = SeparableNES::new(vec![1.0, 2.0], vec![0.5, 0.5]);
+        assert_eq!(mean, vec![1.0, 2.0]);
+        assert_eq!(std_dev, vec![0.5, 0.5]);
+        assert!(population_size.is_none());
+        assert_eq!(learning_rate_mean.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(learning_rate_std.is_none());
+        assert_eq!(tol.to_ne_bytes(), 0.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_builders() {
+        let nes: SeparableNES<_, f64, _> = SeparableNES::new(vec![1.0, 2.0], vec![0.5, 0.5]);
+        assert_error!(
+            nes.clone().with_population_size(1),
+            ArgminError,
+            "Invalid parameter: \"`SeparableNES`: population size must be at least 2.\""
+        );
+        for (mean, std_dev) in [(0.0, 0.1), (0.1, 0.0), (-1.0, 1.0)] {
+            assert_error!(
+                nes.clone().with_learning_rates(mean, std_dev),
+                ArgminError,
+                "Invalid parameter: \"`SeparableNES`: learning rates must be > 0.\""
+            );
+        }
+        assert_error!(
+            nes.clone().with_tolerance(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`SeparableNES`: tolerance must be non-negative.\""
+        );
+        let nes = nes
+            .with_population_size(10)
+            .unwrap()
+            .with_learning_rates(0.5, 0.2)
+            .unwrap()
+            .with_tolerance(1e-4)
+            .unwrap();
+        assert_eq!(nes.population_size, Some(10));
+        assert_eq!(nes.learning_rate_mean.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(nes.learning_rate_std, Some(0.2));
+        assert_eq!(nes.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_utilities() {
+        let u: Vec<f64> = utilities(6);
+        assert_relative_eq!(u.iter().sum::<f64>(), 0.0, epsilon = 1e-12);
+        assert!(u.windows(2).all(|w| w[0] >= w[1]));
+        assert!(u[0] > 0.0);
+        assert_relative_eq!(u[5], -1.0 / 6.0);
+    }
+
+    #[test]
+    fn test_init_defaults() {
+        let mut nes: SeparableNES<_, f64, _> = SeparableNES::new(vec![0.0; 10], vec![1.0; 10]);
+        let (_, kv) = nes
+            .init(
+                &mut Problem::new(TestProblem::new()),
+                PopulationState::new(),
+            )
+            .unwrap();
+        let kv = kv.unwrap();
+        assert_eq!(kv.kv[0].0, "population_size");
+        assert_eq!(nes.population_size, Some(10));
+        assert_relative_eq!(
+            nes.learning_rate_std.unwrap(),
+            (3.0 + 10.0f64.ln()) / (5.0 * 10.0f64.sqrt())
+        );
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let nes = SeparableNES::new_with_rng(
+            vec![-1.2, 1.0],
+            vec![0.5, 0.5],
+            Xoshiro256PlusPlus::seed_from_u64(1),
+        )
+        .with_population_size(20)
+        .unwrap()
+        .with_tolerance(1e-8)
+        .unwrap();
+        let res = Executor::new(Rosenbrock {}, nes)
+            .configure(|state| state.max_iters(20000))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            TerminationReason::TargetToleranceReached
+        );
+        let mean = res.solver().mean();
+        assert_relative_eq!(mean[0], 1.0, epsilon = 1e-4);
+        assert_relative_eq!(mean[1], 1.0, epsilon = 1e-4);
+        assert_eq!(
+            res.problem().counts["cost_count"],
+            20 * res.state().get_iter()
+        );
+    }
+
+    #[test]
+    fn test_seeded_runs_reproducible() {
+        let run = |seed: u64| {
+            let nes = SeparableNES::new_with_rng(
+                vec![-1.2, 1.0],
+                vec![0.5, 0.5],
+                Xoshiro256PlusPlus::seed_from_u64(seed),
+            );
+            Executor::new(Rosenbrock {}, nes)
+                .configure(|state| state.max_iters(5))
+                .run()
+                .unwrap()
+                .solver()
+                .mean()
+                .clone()
+        };
+        assert_eq!(run(3), run(3));
+        assert_ne!(run(3), run(4));
+    }
+}
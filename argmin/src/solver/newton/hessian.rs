@@ -0,0 +1,192 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{Error, Hessian, HessianVectorProduct, Problem};
+use argmin_math::ArgminDot;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Computes the product of a Hessian representation `H` with a vector.
+///
+/// Used by solvers such as [`Steihaug`](`crate::solver::trustregion::Steihaug`) which only need
+/// the action of the Hessian on a vector. Implemented by [`FullHessian`] and
+/// [`MatrixFreeHessian`].
+pub trait HessianProduct<O, P, H> {
+    /// Compute the product of the Hessian at `param` and `vec`
+    ///
+    /// Depending on the implementation, either `hessian` or `param` may be unused.
+    fn hessian_vector_product(
+        problem: &mut Problem<O>,
+        param: Option<&P>,
+        hessian: &H,
+        vec: &P,
+    ) -> Result<P, Error>;
+}
+
+/// Obtains a Hessian representation `H` from the optimization problem.
+///
+/// Used by [`NewtonCG`](`crate::solver::newton::NewtonCG`) and
+/// [`TrustRegion`](`crate::solver::trustregion::TrustRegion`). Implemented by [`FullHessian`] and
+/// [`MatrixFreeHessian`].
+pub trait HessianSource<O, P, H>: HessianProduct<O, P, H> {
+    /// Compute the Hessian representation at `param`
+    fn hessian(problem: &mut Problem<O>, param: &P) -> Result<H, Error>;
+}
+
+/// The full Hessian is computed via [`Hessian`] and multiplied with vectors via [`ArgminDot`].
+///
+/// This is the default for all solvers which support both modes. The product alone works with any
+/// `H` which implements [`ArgminDot`], which includes Hessian approximations provided by
+/// quasi-Newton methods.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct FullHessian;
+
+impl<O, P, H> HessianProduct<O, P, H> for FullHessian
+where
+    H: ArgminDot<P, P>,
+{
+    fn hessian_vector_product(
+        _problem: &mut Problem<O>,
+        _param: Option<&P>,
+        hessian: &H,
+        vec: &P,
+    ) -> Result<P, Error> {
+        Ok(hessian.dot(vec))
+    }
+}
+
+impl<O, P, H> HessianSource<O, P, H> for FullHessian
+where
+    O: Hessian<Param = P, Hessian = H>,
+    H: ArgminDot<P, P>,
+{
+    fn hessian(problem: &mut Problem<O>, param: &P) -> Result<H, Error> {
+        problem.hessian(param)
+    }
+}
+
+/// Hessian-vector products are computed via [`HessianVectorProduct`] without forming the Hessian.
+///
+/// There is no Hessian representation (`H = ()`); products are evaluated at the parameter vector
+/// passed to [`HessianProduct::hessian_vector_product`], which is therefore required.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct MatrixFreeHessian;
+
+impl<O, P> HessianProduct<O, P, ()> for MatrixFreeHessian
+where
+    O: HessianVectorProduct<Param = P>,
+{
+    fn hessian_vector_product(
+        problem: &mut Problem<O>,
+        param: Option<&P>,
+        _hessian: &(),
+        vec: &P,
+    ) -> Result<P, Error> {
+        let param = param.ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`MatrixFreeHessian` requires the parameter vector at which the Hessian ",
+                "is evaluated."
+            )
+        ))?;
+        problem.hessian_vector_product(param, vec)
+    }
+}
+
+impl<O, P> HessianSource<O, P, ()> for MatrixFreeHessian
+where
+    O: HessianVectorProduct<Param = P>,
+{
+    fn hessian(_problem: &mut Problem<O>, _param: &P) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError};
+
+    struct Quadratic {}
+
+    impl Hessian for Quadratic {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, _param: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(vec![vec![2.0, 1.0], vec![1.0, 4.0]])
+        }
+    }
+
+    impl HessianVectorProduct for Quadratic {
+        type Param = Vec<f64>;
+
+        fn hessian_vector_product(
+            &self,
+            _param: &Self::Param,
+            vec: &Self::Param,
+        ) -> Result<Self::Param, Error> {
+            Ok(vec![2.0 * vec[0] + vec[1], vec[0] + 4.0 * vec[1]])
+        }
+    }
+
+    #[test]
+    fn test_full_hessian() {
+        let mut problem = Problem::new(Quadratic {});
+        let param = vec![1.0, 1.0];
+        let h: Vec<Vec<f64>> = FullHessian::hessian(&mut problem, &param).unwrap();
+        let hv =
+            FullHessian::hessian_vector_product(&mut problem, None, &h, &vec![1.0, 2.0]).unwrap();
+        assert_eq!(hv, vec![4.0, 9.0]);
+        assert_eq!(problem.counts["hessian_count"], 1);
+    }
+
+    #[test]
+    fn test_full_hessian_product_without_problem_hessian() {
+        // The product does not require the problem to implement `Hessian`
+        let mut problem = Problem::new(TestProblem::new());
+        let h = vec![vec![2.0, 1.0], vec![1.0, 4.0]];
+        let hv =
+            FullHessian::hessian_vector_product(&mut problem, None, &h, &vec![1.0, 2.0]).unwrap();
+        assert_eq!(hv, vec![4.0, 9.0]);
+        assert!(problem.counts.is_empty());
+    }
+
+    #[test]
+    fn test_matrix_free_hessian() {
+        let mut problem = Problem::new(Quadratic {});
+        let param = vec![1.0, 1.0];
+        MatrixFreeHessian::hessian(&mut problem, &param).unwrap();
+        let hv = MatrixFreeHessian::hessian_vector_product(
+            &mut problem,
+            Some(&param),
+            &(),
+            &vec![1.0, 2.0],
+        )
+        .unwrap();
+        assert_eq!(hv, vec![4.0, 9.0]);
+        assert_eq!(problem.counts["hessian_vector_product_count"], 1);
+        assert!(!problem.counts.contains_key("hessian_count"));
+    }
+
+    #[test]
+    fn test_matrix_free_hessian_without_param() {
+        let mut problem = Problem::new(Quadratic {});
+        let res = MatrixFreeHessian::hessian_vector_product(&mut problem, None, &(), &vec![1.0]);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`MatrixFreeHessian` requires the parameter vector at which ",
+                "the Hessian is evaluated.\""
+            )
+        );
+        assert!(problem.counts.is_empty());
+    }
+}
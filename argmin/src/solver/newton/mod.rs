@@ -10,15 +10,22 @@
 //! * [`Newton`]
 //! * [`NewtonCG`]
 //!
+//! [`NewtonCG`] can either use the full Hessian of the problem ([`FullHessian`], the default) or
+//! only Hessian-vector products ([`MatrixFreeHessian`]) provided via
+//! [`HessianVectorProduct`](`crate::core::HessianVectorProduct`).
+//!
 //! # Reference
 //!
 //! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+/// Access to the Hessian of a problem
+mod hessian;
 /// Newton-CG method
 mod newton_cg;
 /// Newton's method
 mod newton_method;
 
+pub use self::hessian::{FullHessian, HessianProduct, HessianSource, MatrixFreeHessian};
 pub use self::newton_cg::NewtonCG;
 pub use self::newton_method::Newton;
@@ -6,15 +6,18 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, DeserializeOwnedAlias, Error, Executor, Gradient, Hessian, IterState, LineSearch,
-    Operator, OptimizationResult, Problem, SerializeAlias, Solver, State, TerminationReason, KV,
+    ArgminFloat, DeserializeOwnedAlias, Error, Executor, Gradient, IterState, LineSearch, Operator,
+    OptimizationResult, Problem, SerializeAlias, Solver, State, TerminationReason, KV,
 };
 use crate::solver::conjugategradient::ConjugateGradient;
+use crate::solver::newton::{FullHessian, HessianProduct, HessianSource, MatrixFreeHessian};
 use argmin_math::{
     ArgminConj, ArgminDot, ArgminMul, ArgminNorm, ArgminScaledAdd, ArgminSub, ArgminZeroLike,
 };
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::sync::{Mutex, PoisonError};
 
 /// # Newton-Conjugate-Gradient (Newton-CG) method
 ///
@@ -23,7 +26,10 @@ use serde::{Deserialize, Serialize};
 ///
 /// ## Requirements on the optimization problem
 ///
-/// The optimization problem is required to implement [`Gradient`] and [`Hessian`].
+/// The optimization problem is required to implement [`Gradient`] and
+/// [`Hessian`](`crate::core::Hessian`). If the full Hessian is not available, the solver can be
+/// turned into a matrix-free method via [`NewtonCG::matrix_free`], which only requires the problem
+/// to implement [`HessianVectorProduct`](`crate::core::HessianVectorProduct`).
 ///
 /// ## Reference
 ///
@@ -31,13 +37,15 @@ use serde::{Deserialize, Serialize};
 /// Springer. ISBN 0-387-30303-0.
 #[derive(Clone)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct NewtonCG<L, F> {
+pub struct NewtonCG<L, F, M = FullHessian> {
     /// line search
     linesearch: L,
     /// curvature_threshold
     curvature_threshold: F,
     /// Tolerance for the stopping criterion based on cost difference
     tol: F,
+    /// Access to the Hessian
+    mode: PhantomData<M>,
 }
 
 impl<L, F> NewtonCG<L, F>
@@ -58,9 +66,38 @@ where
             linesearch,
             curvature_threshold: float!(0.0),
             tol: F::epsilon(),
+            mode: PhantomData,
         }
     }
 
+    /// Use Hessian-vector products instead of the full Hessian
+    ///
+    /// The optimization problem is then required to implement
+    /// [`HessianVectorProduct`](`crate::core::HessianVectorProduct`) instead of
+    /// [`Hessian`](`crate::core::Hessian`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newton::{MatrixFreeHessian, NewtonCG};
+    /// # let linesearch = ();
+    /// let ncg: NewtonCG<_, f64, MatrixFreeHessian> = NewtonCG::new(linesearch).matrix_free();
+    /// ```
+    #[must_use]
+    pub fn matrix_free(self) -> NewtonCG<L, F, MatrixFreeHessian> {
+        NewtonCG {
+            linesearch: self.linesearch,
+            curvature_threshold: self.curvature_threshold,
+            tol: self.tol,
+            mode: PhantomData,
+        }
+    }
+}
+
+impl<L, F, M> NewtonCG<L, F, M>
+where
+    F: ArgminFloat,
+{
     /// Set curvature threshold
     ///
     /// Defaults to 0.
@@ -105,9 +142,9 @@ where
     }
 }
 
-impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, F>> for NewtonCG<L, F>
+impl<O, L, P, G, H, F, M> Solver<O, IterState<P, G, (), H, F>> for NewtonCG<L, F, M>
 where
-    O: Gradient<Param = P, Gradient = G> + Hessian<Param = P, Hessian = H>,
+    O: Gradient<Param = P, Gradient = G>,
     P: Clone
         + SerializeAlias
         + DeserializeOwnedAlias
@@ -118,9 +155,10 @@ where
         + ArgminConj
         + ArgminZeroLike,
     G: SerializeAlias + DeserializeOwnedAlias + ArgminNorm<F> + ArgminMul<F, P>,
-    H: Clone + SerializeAlias + DeserializeOwnedAlias + ArgminDot<P, P>,
+    H: Clone + SerializeAlias + DeserializeOwnedAlias,
     L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), F>>,
     F: ArgminFloat + ArgminNorm<F>,
+    M: HessianSource<O, P, H>,
 {
     const NAME: &'static str = "Newton-CG";
 
@@ -143,44 +181,71 @@ where
         let hessian = state
             .take_hessian()
             .map(Result::Ok)
-            .unwrap_or_else(|| problem.hessian(&param))?;
+            .unwrap_or_else(|| M::hessian(problem, &param))?;
+
+        // Solve CG subproblem. The problem is moved into the subproblem such that Hessian-vector
+        // products can be computed (and counted) during the CG iterations.
+        let inner_problem = Mutex::new(Problem::new(problem.take_problem().ok_or_else(
+            argmin_error_closure!(
+                PotentialBug,
+                "`NewtonCG`: Failed to take `problem` for CG subproblem"
+            ),
+        )?));
+
+        let direction = (|| -> Result<P, Error> {
+            let mut cg_problem = Problem::new(CGSubProblem::<_, _, _, M>::new(
+                &inner_problem,
+                &param,
+                &hessian,
+            ));
 
-        // Solve CG subproblem
-        let mut cg_problem = Problem::new(CGSubProblem::new(&hessian));
+            let mut x_p = param.zero_like();
+            let mut x = param.zero_like();
+            let mut cg = ConjugateGradient::new(grad.mul(&(float!(-1.0))));
 
-        let mut x_p = param.zero_like();
-        let mut x = param.zero_like();
-        let mut cg = ConjugateGradient::new(grad.mul(&(float!(-1.0))));
+            let (mut cg_state, _) =
+                cg.init(&mut cg_problem, IterState::new().param(x_p.clone()))?;
 
-        let (mut cg_state, _) = cg.init(&mut cg_problem, IterState::new().param(x_p.clone()))?;
+            let grad_norm_factor = float!(0.5).min(grad.norm().sqrt()) * grad.norm();
 
-        let grad_norm_factor = float!(0.5).min(grad.norm().sqrt()) * grad.norm();
+            for iter in 0.. {
+                (cg_state, _) = cg.next_iter(&mut cg_problem, cg_state)?;
 
-        for iter in 0.. {
-            (cg_state, _) = cg.next_iter(&mut cg_problem, cg_state)?;
+                let cost = cg_state.get_cost();
 
-            let cost = cg_state.get_cost();
+                x = cg_state.take_param().ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`NewtonCG`: No parameters returned by CG subproblem."
+                ))?;
+                let p = cg.get_prev_p()?;
 
-            x = cg_state.take_param().unwrap();
-            let p = cg.get_prev_p()?;
+                let curvature = p.dot(&cg_problem.apply(p)?);
+                if curvature <= self.curvature_threshold {
+                    if iter == 0 {
+                        x = grad.mul(&(float!(-1.0)));
+                    } else {
+                        x = x_p;
+                    }
+                    break;
+                }
 
-            let curvature = p.dot(&hessian.dot(p));
-            if curvature <= self.curvature_threshold {
-                if iter == 0 {
-                    x = grad.mul(&(float!(-1.0)));
-                } else {
-                    x = x_p;
+                if cost <= grad_norm_factor {
+                    break;
                 }
-                break;
-            }
 
-            if cost <= grad_norm_factor {
-                break;
+                cg_state = cg_state.param(x.clone()).cost(cost);
+                x_p = x.clone();
             }
-
-            cg_state = cg_state.param(x.clone()).cost(cost);
-            x_p = x.clone();
-        }
+            Ok(x)
+        })();
+
+        // Give the problem back before errors of the CG subproblem are propagated.
+        problem.consume_problem(
+            inner_problem
+                .into_inner()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        let x = direction?;
 
         // perform line search
         // TODO: Should the algorithm stop when search direction is close to 0?
@@ -192,18 +257,31 @@ where
         let OptimizationResult {
             problem: line_problem,
             state: mut linesearch_state,
-            ..
-        } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
-            .configure(|state| state.param(param).grad(grad).cost(line_cost))
-            .ctrlc(false)
-            .run()?;
+            solver: line_solver,
+        } = Executor::new(
+            problem.take_problem().ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`NewtonCG`: Failed to take `problem` for line search"
+            ))?,
+            self.linesearch.clone(),
+        )
+        .configure(|state| state.param(param).grad(grad).cost(line_cost))
+        .ctrlc(false)
+        .run()?;
+
+        self.linesearch.consume_line_search(line_solver);
 
         problem.consume_problem(line_problem);
 
+        let new_param = linesearch_state
+            .take_param()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`NewtonCG`: No parameters returned by line search."
+            ))?;
+
         Ok((
-            state
-                .param(linesearch_state.take_param().unwrap())
-                .cost(linesearch_state.get_cost()),
+            state.param(new_param).cost(linesearch_state.get_cost()),
             None,
         ))
     }
@@ -218,30 +296,39 @@ where
 }
 
 #[derive(Clone)]
-struct CGSubProblem<'a, P, H> {
+struct CGSubProblem<'a, O, P, H, M> {
+    problem: &'a Mutex<Problem<O>>,
+    param: &'a P,
     hessian: &'a H,
-    phantom: std::marker::PhantomData<P>,
+    phantom: PhantomData<(P, M)>,
 }
 
-impl<'a, P, H> CGSubProblem<'a, P, H> {
+impl<'a, O, P, H, M> CGSubProblem<'a, O, P, H, M> {
     /// Constructor
-    fn new(hessian: &'a H) -> Self {
+    fn new(problem: &'a Mutex<Problem<O>>, param: &'a P, hessian: &'a H) -> Self {
         CGSubProblem {
+            problem,
+            param,
             hessian,
-            phantom: std::marker::PhantomData,
+            phantom: PhantomData,
         }
     }
 }
 
-impl<'a, P, H> Operator for CGSubProblem<'a, P, H>
+impl<'a, O, P, H, M> Operator for CGSubProblem<'a, O, P, H, M>
 where
-    H: ArgminDot<P, P>,
+    M: HessianProduct<O, P, H>,
 {
     type Param = P;
     type Output = P;
 
     fn apply(&self, p: &P) -> Result<P, Error> {
-        Ok(self.hessian.dot(p))
+        M::hessian_vector_product(
+            &mut self.problem.lock().unwrap(),
+            Some(self.param),
+            self.hessian,
+            p,
+        )
     }
 }
 
@@ -259,7 +346,15 @@ mod tests {
         NewtonCG<MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, f64>
     );
 
-    test_trait_impl!(cg_subproblem, CGSubProblem<Vec<f64>, Vec<Vec<f64>>>);
+    test_trait_impl!(
+        newton_cg_matrix_free,
+        NewtonCG<MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, f64, MatrixFreeHessian>
+    );
+
+    test_trait_impl!(
+        cg_subproblem,
+        CGSubProblem<TestProblem, Vec<f64>, Vec<Vec<f64>>, FullHessian>
+    );
 
     #[test]
     fn test_tolerance() {
@@ -284,6 +379,7 @@ mod tests {
             linesearch,
             curvature_threshold,
             tol,
+            mode: _,
         } = ncg;
         assert_eq!(linesearch, ls);
         assert_eq!(curvature_threshold.to_ne_bytes(), 0.0f64.to_ne_bytes());
@@ -300,6 +396,7 @@ mod tests {
             linesearch,
             curvature_threshold,
             tol,
+            mode: _,
         } = ncg;
         assert_eq!(linesearch, ls);
         assert_eq!(curvature_threshold.to_ne_bytes(), 1e-6f64.to_ne_bytes());
@@ -340,5 +437,125 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_matrix_free_rosenbrock() {
+        use crate::core::{CostFunction, Executor, Hessian, HessianVectorProduct};
+        use argmin_testfunctions::{
+            rosenbrock_2d, rosenbrock_2d_derivative, rosenbrock_2d_hessian,
+        };
+
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(rosenbrock_2d(p, 1.0, 100.0))
+            }
+        }
+
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(rosenbrock_2d_derivative(p, 1.0, 100.0))
+            }
+        }
+
+        impl Hessian for Rosenbrock {
+            type Param = Vec<f64>;
+            type Hessian = Vec<Vec<f64>>;
+
+            fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+                let h = rosenbrock_2d_hessian(p, 1.0, 100.0);
+                Ok(vec![vec![h[0], h[1]], vec![h[2], h[3]]])
+            }
+        }
+
+        impl HessianVectorProduct for Rosenbrock {
+            type Param = Vec<f64>;
+
+            fn hessian_vector_product(
+                &self,
+                p: &Self::Param,
+                v: &Self::Param,
+            ) -> Result<Self::Param, Error> {
+                let h = rosenbrock_2d_hessian(p, 1.0, 100.0);
+                Ok(vec![h[0] * v[0] + h[1] * v[1], h[2] * v[0] + h[3] * v[1]])
+            }
+        }
+
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+
+        let full = Executor::new(Rosenbrock {}, NewtonCG::new(linesearch.clone()))
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(20))
+            .run()
+            .unwrap();
+
+        let res = Executor::new(Rosenbrock {}, NewtonCG::new(linesearch).matrix_free())
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(20))
+            .run()
+            .unwrap();
+
+        let param = res.state.get_best_param().unwrap();
+        let full_param = full.state.get_best_param().unwrap();
+        assert!((param[0] - full_param[0]).abs() < 1e-10);
+        assert!((param[1] - full_param[1]).abs() < 1e-10);
+        assert!(res.state.get_best_cost() < 0.5 * rosenbrock_2d(&[-1.2, 1.0], 1.0, 100.0));
+        assert_eq!(full.problem.counts["hessian_count"], 20);
+        assert!(!res.problem.counts.contains_key("hessian_count"));
+        assert!(res.problem.counts["hessian_vector_product_count"] > 0);
+    }
+
+    #[test]
+    fn test_next_iter_restores_problem_on_cg_error() {
+        use crate::core::{CostFunction, HessianVectorProduct};
+
+        struct FailingProblem {}
+
+        impl CostFunction for FailingProblem {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(0.5 * p.iter().map(|x| x * x).sum::<f64>())
+            }
+        }
+
+        impl Gradient for FailingProblem {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(p.clone())
+            }
+        }
+
+        impl HessianVectorProduct for FailingProblem {
+            type Param = Vec<f64>;
+
+            fn hessian_vector_product(
+                &self,
+                _param: &Self::Param,
+                _vec: &Self::Param,
+            ) -> Result<Self::Param, Error> {
+                Err(argmin_error!(InvalidParameter, "no Hessian"))
+            }
+        }
+
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let mut ncg = NewtonCG::new(linesearch).matrix_free();
+        let mut problem = Problem::new(FailingProblem {});
+        let res = ncg.next_iter(&mut problem, IterState::new().param(vec![1.0, 2.0]));
+        assert_error!(res, ArgminError, "Invalid parameter: \"no Hessian\"");
+        assert!(problem.problem.is_some());
+        assert_eq!(problem.counts["gradient_count"], 1);
+        assert_eq!(problem.counts["hessian_vector_product_count"], 1);
+    }
+
     // TODO: Test next_iter.
 }
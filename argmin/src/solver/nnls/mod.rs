@@ -0,0 +1,700 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Nonnegative least squares
+//!
+//! Solves linear least squares problems `min_x ‖Ax - b‖₂` subject to `x >= 0`.
+//! See [`NNLS`] for details.
+//!
+//! ## References
+//!
+//! Charles L. Lawson and Richard J. Hanson (1995). Solving Least Squares Problems.
+//! SIAM. ISBN 0-89871-356-0.
+//!
+//! Rasmus Bro and Sijmen De Jong (1997). A fast non-negativity-constrained least squares
+//! algorithm. Journal of Chemometrics 11, pp. 393-401.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Jacobian, Operator, Problem, Solver, TerminationReason, KV,
+};
+use crate::solver::quadraticprogramming::dot;
+use argmin_math::{ArgminCholeskySolve, ArgminFromVec, ArgminQrSolve, ArgminToVec};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Algorithm used to solve the unconstrained least squares subproblems of [`NNLS`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum NNLSVariant {
+    /// Lawson-Hanson: QR decomposition of the columns of `A` of the passive set (default)
+    #[default]
+    LawsonHanson,
+    /// Fast NNLS (Bro & De Jong): Cholesky decomposition of the corresponding submatrix of the
+    /// precomputed `AᵀA`. Faster if `A` has many more rows than columns, but less accurate for
+    /// ill-conditioned `A`.
+    Fast,
+}
+
+/// # Nonnegative least squares
+///
+/// Active set method of Lawson and Hanson for linear least squares problems with nonnegativity
+/// constraints, `min_x ‖Ax - b‖₂` subject to `x >= 0`.
+///
+/// Variables are split into a passive set, which is free, and an active set, which is held at
+/// zero. In each iteration, the variable of the active set with the largest component of the
+/// negative gradient `w = Aᵀ(b - Ax)` is moved to the passive set and the unconstrained least
+/// squares problem on the passive set is solved. Variables which would become negative are moved
+/// back to the active set. The algorithm terminates once no component of `w` on the active set is
+/// positive (up to the tolerance set with [`with_tolerance`](`NNLS::with_tolerance`)), which
+/// means that the Karush-Kuhn-Tucker conditions are satisfied. If round-off errors prevent the
+/// entering variable from staying in the passive set, no further progress is possible and the
+/// solver terminates with [`TerminationReason::NoChangeInCost`].
+///
+/// The subproblems are solved either via QR decomposition (Lawson-Hanson) or via the normal
+/// equations (FNNLS), see [`NNLSVariant`].
+///
+/// The problem is defined by the residuals `r(x) = Ax - b`, in the same way as for
+/// [`GaussNewton`](`crate::solver::gaussnewton::GaussNewton`). `A` and `b` are obtained once
+/// from the Jacobian and the residuals at the initial parameter vector. Any dense vector and
+/// matrix types of the `argmin-math` backends can be used. The initial parameter vector is
+/// clipped to the feasible set and its nonzero entries form the initial passive set.
+///
+/// The number of variables in the active set is reported as `active_set_size` in each
+/// iteration.
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`] and [`Jacobian`].
+///
+/// ## References
+///
+/// Charles L. Lawson and Richard J. Hanson (1995). Solving Least Squares Problems.
+/// SIAM. ISBN 0-89871-356-0.
+///
+/// Rasmus Bro and Sijmen De Jong (1997). A fast non-negativity-constrained least squares
+/// algorithm. Journal of Chemometrics 11, pp. 393-401.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NNLS<F> {
+    /// Algorithm used for the subproblems
+    variant: NNLSVariant,
+    /// Tolerance for the stopping criterion
+    tol: F,
+    /// Matrix `A` (rows)
+    a: Vec<Vec<F>>,
+    /// Vector `b`
+    b: Vec<F>,
+    /// `AᵀA` (only computed for FNNLS)
+    ata: Vec<Vec<F>>,
+    /// `Aᵀb`
+    atb: Vec<F>,
+    /// Passive set
+    passive: Vec<bool>,
+    /// Negative gradient `Aᵀ(b - Ax)` at the current parameter vector
+    w: Vec<F>,
+    /// Scaling of the stopping criterion
+    scale: F,
+    /// Set if the variable added to the passive set was immediately removed again
+    stalled: bool,
+}
+
+impl<F: ArgminFloat> NNLS<F> {
+    /// Construct a new instance of [`NNLS`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nnls::NNLS;
+    /// let nnls: NNLS<f64> = NNLS::new();
+    /// ```
+    pub fn new() -> Self {
+        NNLS {
+            variant: NNLSVariant::default(),
+            tol: F::epsilon().sqrt(),
+            a: vec![],
+            b: vec![],
+            ata: vec![],
+            atb: vec![],
+            passive: vec![],
+            w: vec![],
+            scale: float!(1.0),
+            stalled: false,
+        }
+    }
+
+    /// Set the algorithm used to solve the subproblems.
+    ///
+    /// Defaults to [`NNLSVariant::LawsonHanson`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nnls::{NNLS, NNLSVariant};
+    /// let nnls: NNLS<f64> = NNLS::new().with_variant(NNLSVariant::Fast);
+    /// ```
+    #[must_use]
+    pub fn with_variant(mut self, variant: NNLSVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Set tolerance for the stopping criterion.
+    ///
+    /// The solver terminates once all components of `Aᵀ(b - Ax)` on the active set are smaller
+    /// than `tol * max(1, max|Aᵀb|)`. Tolerance must be larger than zero and defaults to
+    /// `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nnls::NNLS;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nnls = NNLS::new().with_tolerance(1e-10f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NNLS`: tol must be positive."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Number of variables held at zero
+    fn active_set_size(&self) -> usize {
+        self.passive.iter().filter(|p| !**p).count()
+    }
+
+    /// Residual norm `‖Ax - b‖₂`
+    fn residual_norm(&self, x: &[F]) -> F {
+        self.a
+            .iter()
+            .zip(self.b.iter())
+            .fold(float!(0.0), |acc, (a_i, b_i)| {
+                let r = dot(a_i, x) - *b_i;
+                acc + r * r
+            })
+            .sqrt()
+    }
+
+    /// Negative gradient `Aᵀ(b - Ax)`
+    fn dual(&self, x: &[F]) -> Vec<F> {
+        match self.variant {
+            NNLSVariant::LawsonHanson => {
+                let mut w = vec![float!(0.0); x.len()];
+                for (a_i, b_i) in self.a.iter().zip(self.b.iter()) {
+                    let r = *b_i - dot(a_i, x);
+                    for (w_j, a_ij) in w.iter_mut().zip(a_i.iter()) {
+                        *w_j = *w_j + *a_ij * r;
+                    }
+                }
+                w
+            }
+            NNLSVariant::Fast => self
+                .atb
+                .iter()
+                .zip(self.ata.iter())
+                .map(|(atb_j, ata_j)| *atb_j - dot(ata_j, x))
+                .collect(),
+        }
+    }
+}
+
+impl<F> NNLS<F>
+where
+    F: ArgminFloat,
+    Vec<Vec<F>>: ArgminQrSolve<Vec<F>, Vec<F>> + ArgminCholeskySolve<Vec<F>, Vec<F>>,
+{
+    /// Unconstrained least squares solution on the passive set, zero on the active set
+    fn solve_passive(&self) -> Result<Vec<F>, Error> {
+        let idx: Vec<usize> = (0..self.passive.len())
+            .filter(|&j| self.passive[j])
+            .collect();
+        let mut z = vec![float!(0.0); self.passive.len()];
+        if idx.is_empty() {
+            return Ok(z);
+        }
+        let z_p: Vec<F> = match self.variant {
+            NNLSVariant::LawsonHanson => {
+                let a_p: Vec<Vec<F>> = self
+                    .a
+                    .iter()
+                    .map(|a_i| idx.iter().map(|&j| a_i[j]).collect())
+                    .collect();
+                a_p.qr_solve(&self.b)?
+            }
+            NNLSVariant::Fast => {
+                let ata_p: Vec<Vec<F>> = idx
+                    .iter()
+                    .map(|&i| idx.iter().map(|&j| self.ata[i][j]).collect())
+                    .collect();
+                let atb_p: Vec<F> = idx.iter().map(|&j| self.atb[j]).collect();
+                ata_p.cholesky_solve(&atb_p).map_err(|_| -> Error {
+                    argmin_error!(
+                        ConditionViolated,
+                        "`NNLS`: columns of `A` in the passive set are linearly dependent."
+                    )
+                })?
+            }
+        };
+        for (&j, z_j) in idx.iter().zip(z_p) {
+            z[j] = z_j;
+        }
+        Ok(z)
+    }
+
+    /// Solves the subproblem on the passive set and moves variables which would become negative
+    /// to the active set until the solution of the subproblem is feasible. `x` must be feasible.
+    fn restore_feasibility(&mut self, mut x: Vec<F>) -> Result<Vec<F>, Error> {
+        loop {
+            let z = self.solve_passive()?;
+            // Largest step from `x` towards `z` which keeps all variables nonnegative
+            let blocking = (0..x.len())
+                .filter(|&j| self.passive[j] && z[j] <= float!(0.0))
+                .map(|j| (j, x[j] / (x[j] - z[j])))
+                .fold(None, |acc: Option<(usize, F)>, (j, alpha)| match acc {
+                    Some((_, best)) if best <= alpha => acc,
+                    _ => Some((j, alpha)),
+                });
+            let (k, alpha) = match blocking {
+                Some(blocking) => blocking,
+                None => return Ok(z),
+            };
+            for (x_j, z_j) in x.iter_mut().zip(z.iter()) {
+                *x_j = *x_j + alpha * (*z_j - *x_j);
+            }
+            x[k] = float!(0.0);
+            self.passive[k] = false;
+            for (x_j, p_j) in x.iter_mut().zip(self.passive.iter_mut()) {
+                if *p_j && *x_j <= float!(0.0) {
+                    *x_j = float!(0.0);
+                    *p_j = false;
+                }
+            }
+        }
+    }
+}
+
+impl<F: ArgminFloat> Default for NNLS<F> {
+    fn default() -> NNLS<F> {
+        NNLS::new()
+    }
+}
+
+impl<O, P, U, J, F> Solver<O, IterState<P, (), (), (), F>> for NNLS<F>
+where
+    O: Operator<Param = P, Output = U> + Jacobian<Param = P, Jacobian = J>,
+    P: Clone + ArgminToVec<Vec<F>> + ArgminFromVec<Vec<F>>,
+    U: ArgminToVec<Vec<F>>,
+    J: ArgminToVec<Vec<Vec<F>>>,
+    F: ArgminFloat,
+    Vec<Vec<F>>: ArgminQrSolve<Vec<F>, Vec<F>> + ArgminCholeskySolve<Vec<F>, Vec<F>>,
+{
+    const NAME: &'static str = "NNLS";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`NNLS` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let residuals = problem.apply(&param)?.to_vec();
+        let a = problem.jacobian(&param)?.to_vec();
+        let x0 = param.to_vec();
+
+        if a.len() != residuals.len() || a.iter().any(|a_i| a_i.len() != x0.len()) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NNLS`: dimensions of Jacobian, residuals and parameter vector do not match."
+            ));
+        }
+
+        // `r(x) = Ax - b`, therefore `b = A x0 - r(x0)`
+        self.b = a
+            .iter()
+            .zip(residuals.iter())
+            .map(|(a_i, r_i)| dot(a_i, &x0) - *r_i)
+            .collect();
+        self.atb = (0..x0.len())
+            .map(|j| {
+                a.iter()
+                    .zip(self.b.iter())
+                    .fold(float!(0.0), |acc, (a_i, b_i)| acc + a_i[j] * *b_i)
+            })
+            .collect();
+        self.ata = match self.variant {
+            NNLSVariant::LawsonHanson => vec![],
+            NNLSVariant::Fast => (0..x0.len())
+                .map(|i| {
+                    (0..x0.len())
+                        .map(|j| a.iter().fold(float!(0.0), |acc, a_k| acc + a_k[i] * a_k[j]))
+                        .collect()
+                })
+                .collect(),
+        };
+        self.a = a;
+        self.scale = self
+            .atb
+            .iter()
+            .fold(float!(1.0), |acc: F, atb_j| acc.max(atb_j.abs()));
+        self.stalled = false;
+
+        let x: Vec<F> = x0.iter().map(|x| x.max(float!(0.0))).collect();
+        self.passive = x.iter().map(|x| *x > float!(0.0)).collect();
+        let x = self.restore_feasibility(x)?;
+        self.w = self.dual(&x);
+        let cost = self.residual_norm(&x);
+
+        Ok((
+            state.param(P::from_vec(x)).cost(cost),
+            Some(make_kv!["active_set_size" => self.active_set_size();]),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        _problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let x = state
+            .take_param()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`NNLS`: Parameter vector in state not set."
+            ))?
+            .to_vec();
+
+        // Move the variable with the largest component of the negative gradient to the passive set
+        let t = (0..x.len())
+            .filter(|&j| !self.passive[j])
+            .fold(None, |acc: Option<usize>, j| match acc {
+                Some(k) if self.w[k] >= self.w[j] => acc,
+                _ => Some(j),
+            })
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`NNLS`: active set is empty."
+            ))?;
+        self.passive[t] = true;
+
+        let x = self.restore_feasibility(x)?;
+        // Only possible due to round-off errors: no further progress can be made
+        self.stalled = !self.passive[t];
+        self.w = self.dual(&x);
+        let cost = self.residual_norm(&x);
+
+        Ok((
+            state.param(P::from_vec(x)).cost(cost),
+            Some(make_kv![
+                "active_set_size" => self.active_set_size();
+                "entering" => t;
+                "stalled" => self.stalled;
+            ]),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, (), (), (), F>) -> TerminationReason {
+        let max_w = self
+            .w
+            .iter()
+            .zip(self.passive.iter())
+            .filter(|(_, p)| !**p)
+            .fold(F::neg_infinity(), |acc, (w, _)| acc.max(*w));
+        if self.stalled {
+            return TerminationReason::NoChangeInCost;
+        }
+        if max_w <= self.tol * self.scale {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(nnls, NNLS<f64>);
+
+    struct Linear {
+        a: Vec<Vec<f64>>,
+        b: Vec<f64>,
+    }
+
+    impl Operator for Linear {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self
+                .a
+                .iter()
+                .zip(self.b.iter())
+                .map(|(a_i, b_i)| dot(a_i, x) - b_i)
+                .collect())
+        }
+    }
+
+    impl Jacobian for Linear {
+        type Param = Vec<f64>;
+        type Jacobian = Vec<Vec<f64>>;
+
+        fn jacobian(&self, _x: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(self.a.clone())
+        }
+    }
+
+    fn problem() -> Linear {
+        Linear {
+            a: vec![
+                vec![1.0, 2.0, 0.5, -1.0],
+                vec![0.3, -1.0, 2.0, 0.7],
+                vec![2.0, 0.1, -0.4, 1.5],
+                vec![-0.6, 1.2, 1.0, 0.2],
+                vec![1.1, 0.4, -0.9, -2.0],
+                vec![0.5, -0.8, 0.3, 1.0],
+            ],
+            b: vec![1.0, -2.0, 0.5, 3.0, -1.0, 0.2],
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let NNLS {
+            variant, tol, a, b, ..
+        } = NNLS::<f64>::new();
+
+        assert_eq!(variant, NNLSVariant::LawsonHanson);
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(a.is_empty());
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_variant() {
+        let NNLS { variant, .. } = NNLS::<f64>::new().with_variant(NNLSVariant::Fast);
+        assert_eq!(variant, NNLSVariant::Fast);
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let NNLS { tol, .. } = NNLS::new().with_tolerance(1e-10f64).unwrap();
+        assert_eq!(tol.to_ne_bytes(), 1e-10f64.to_ne_bytes());
+
+        for tol in [0.0, -1.0] {
+            let error = NNLS::new().with_tolerance(tol);
+            assert_error!(
+                error,
+                ArgminError,
+                "Invalid parameter: \"`NNLS`: tol must be positive.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut nnls = NNLS::<f64>::new();
+        let res = nnls.init(&mut Problem::new(problem()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`NNLS` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init_dimension_mismatch() {
+        let mut nnls = NNLS::<f64>::new();
+        let res = nnls.init(
+            &mut Problem::new(problem()),
+            IterState::new().param(vec![0.0; 3]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`NNLS`: dimensions of Jacobian, residuals and parameter ",
+                "vector do not match.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_kkt_conditions() {
+        let p = problem();
+        for variant in [NNLSVariant::LawsonHanson, NNLSVariant::Fast] {
+            for init in [vec![0.0; 4], vec![1.0, -1.0, 2.0, 0.5]] {
+                let res = Executor::new(problem(), NNLS::new().with_variant(variant))
+                    .configure(|state| state.param(init).max_iters(20))
+                    .ctrlc(false)
+                    .run()
+                    .unwrap();
+                assert_eq!(
+                    res.state().get_termination_reason(),
+                    TerminationReason::TargetPrecisionReached
+                );
+                let x = res.state().get_best_param().unwrap();
+                let r = p.apply(x).unwrap();
+                for (j, x_j) in x.iter().enumerate() {
+                    // Negative gradient `Aᵀ(b - Ax)`
+                    let w_j =
+                        -p.a.iter()
+                            .zip(r.iter())
+                            .fold(0.0, |acc, (a_i, r_i)| acc + a_i[j] * r_i);
+                    assert!(*x_j >= 0.0);
+                    if *x_j > 0.0 {
+                        assert_relative_eq!(w_j, 0.0, epsilon = 1e-10);
+                    } else {
+                        assert!(w_j <= 1e-10);
+                    }
+                }
+                assert_relative_eq!(
+                    res.state().get_best_cost(),
+                    r.iter().map(|r| r * r).sum::<f64>().sqrt(),
+                    epsilon = 1e-12
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unconstrained_solution_feasible() {
+        // Solution of the unconstrained problem is nonnegative
+        let p = Linear {
+            a: vec![vec![1.0, 0.0], vec![1.0, 0.0], vec![0.0, 1.0]],
+            b: vec![2.0, 1.0, 1.0],
+        };
+        let res = Executor::new(p, NNLS::new())
+            .configure(|state| state.param(vec![0.0, 0.0]))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        let x = res.state().get_best_param().unwrap();
+        assert_relative_eq!(x[0], 1.5, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 1.0, epsilon = 1e-12);
+        assert_eq!(res.state().get_iter(), 2);
+    }
+
+    #[test]
+    fn test_next_iter_active_set_size() {
+        // Unconstrained solution is (1, -1), NNLS solution is (1, 0)
+        let p = Linear {
+            a: vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+            b: vec![1.0, -1.0],
+        };
+        let mut problem = Problem::new(p);
+        let mut nnls = NNLS::new();
+        let (state, kv) = nnls
+            .init(&mut problem, IterState::new().param(vec![0.0, 0.0]))
+            .unwrap();
+        let kv = kv.unwrap();
+        assert_eq!(kv.kv[0].0, "active_set_size");
+        assert_eq!(format!("{}", kv.kv[0].1), "2");
+        assert_eq!(
+            Solver::<Linear, _>::terminate(&mut nnls, &state),
+            TerminationReason::NotTerminated
+        );
+        let (state, kv) = nnls.next_iter(&mut problem, state).unwrap();
+        let kv = kv.unwrap();
+        assert_eq!(kv.kv[0].0, "active_set_size");
+        assert_eq!(format!("{}", kv.kv[0].1), "1");
+        assert_eq!(kv.kv[1].0, "entering");
+        assert_eq!(format!("{}", kv.kv[1].1), "0");
+        assert_eq!(state.get_param().unwrap(), &vec![1.0, 0.0]);
+        assert_eq!(
+            Solver::<Linear, _>::terminate(&mut nnls, &state),
+            TerminationReason::TargetPrecisionReached
+        );
+    }
+
+    #[test]
+    fn test_fast_linearly_dependent() {
+        let p = Linear {
+            a: vec![vec![1.0, 0.0], vec![2.0, 0.0]],
+            b: vec![1.0, 1.0],
+        };
+        let mut nnls = NNLS::new().with_variant(NNLSVariant::Fast);
+        let res = nnls.init(&mut Problem::new(p), IterState::new().param(vec![1.0, 1.0]));
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Condition violated: \"`NNLS`: columns of `A` in the passive set are linearly ",
+                "dependent.\""
+            )
+        );
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    #[test]
+    fn test_nalgebra() {
+        use nalgebra::{DMatrix, DVector};
+
+        struct Dense {
+            a: DMatrix<f64>,
+            b: DVector<f64>,
+        }
+
+        impl Operator for Dense {
+            type Param = DVector<f64>;
+            type Output = DVector<f64>;
+
+            fn apply(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(&self.a * x - &self.b)
+            }
+        }
+
+        impl Jacobian for Dense {
+            type Param = DVector<f64>;
+            type Jacobian = DMatrix<f64>;
+
+            fn jacobian(&self, _x: &Self::Param) -> Result<Self::Jacobian, Error> {
+                Ok(self.a.clone())
+            }
+        }
+
+        let p = problem();
+        let dense = Dense {
+            a: DMatrix::from_fn(6, 4, |i, j| p.a[i][j]),
+            b: DVector::from_vec(p.b.clone()),
+        };
+        let expected = Executor::new(p, NNLS::new())
+            .configure(|state| state.param(vec![0.0; 4]))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        let res = Executor::new(dense, NNLS::new())
+            .configure(|state| state.param(DVector::zeros(4)))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        let expected = expected.state().get_best_param().unwrap();
+        let x = res.state().get_best_param().unwrap();
+        for j in 0..4 {
+            assert_relative_eq!(x[j], expected[j], epsilon = 1e-12);
+        }
+    }
+}
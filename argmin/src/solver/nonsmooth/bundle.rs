@@ -0,0 +1,638 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Gradient, IterState, Problem, SerializeAlias, Solver, State,
+    TerminationReason, KV,
+};
+use argmin_math::{ArgminDot, ArgminMul, ArgminScaledAdd, ArgminZeroLike};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Cutting plane `f(x) >= f(x̂) - e + gᵀ(x - x̂)` relative to the stability center `x̂`
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct Cut<P, F> {
+    /// Subgradient
+    g: P,
+    /// Linearization error at the stability center
+    e: F,
+}
+
+/// # Proximal bundle method
+///
+/// Minimizes convex, but not necessarily differentiable, cost functions. All subgradients
+/// (as returned by [`Gradient`]) collected so far form a cutting-plane model
+///
+/// `m(x) = max_i f(y_i) + g_iᵀ(x - y_i)`
+///
+/// of the cost function, which is a lower bound of the cost function. The next trial point
+/// minimizes the model plus the proximal term `mu/2 ‖x - x̂‖²` around the stability center `x̂`.
+/// This subproblem is solved via its dual, a quadratic program over the unit simplex. If the
+/// cost function value at the trial point decreases by at least a fraction `m` (see
+/// [`with_descent_parameter`](`ProximalBundle::with_descent_parameter`)) of the decrease
+/// predicted by the model, the trial point becomes the new stability center (serious step).
+/// Otherwise, only the model is improved by the new cut (null step).
+///
+/// The method terminates once the predicted decrease falls below
+/// `tol * (1 + |f(x̂)|)` (see [`with_tolerance`](`ProximalBundle::with_tolerance`)).
+///
+/// The cost function value of the trial points is not monotone, the best parameter vector found
+/// so far is tracked by the state. Inactive cuts are removed once the bundle exceeds the
+/// maximum size set with [`with_max_bundle_size`](`ProximalBundle::with_max_bundle_size`); if
+/// this is not sufficient, the bundle is replaced by the aggregate cut.
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`], where
+/// the gradient may be any subgradient.
+///
+/// ## Reference
+///
+/// Krzysztof C. Kiwiel (1990). Proximity control in bundle methods for convex nondifferentiable
+/// minimization. Mathematical Programming 46, pp. 105-122.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ProximalBundle<P, F> {
+    /// Proximal parameter `mu`
+    mu: F,
+    /// Descent parameter `m`
+    m: F,
+    /// Tolerance for the predicted decrease
+    tol: F,
+    /// Maximum number of cuts
+    max_bundle_size: usize,
+    /// Stability center
+    center: Option<P>,
+    /// Cost function value at the stability center
+    center_cost: F,
+    /// Cutting planes
+    bundle: Vec<Cut<P, F>>,
+    /// Decrease predicted by the model
+    predicted_decrease: F,
+}
+
+impl<P, F> ProximalBundle<P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`ProximalBundle`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonsmooth::ProximalBundle;
+    /// let bundle: ProximalBundle<Vec<f64>, f64> = ProximalBundle::new();
+    /// ```
+    pub fn new() -> Self {
+        ProximalBundle {
+            mu: float!(1.0),
+            m: float!(0.1),
+            tol: F::epsilon().sqrt(),
+            max_bundle_size: 50,
+            center: None,
+            center_cost: F::infinity(),
+            bundle: vec![],
+            predicted_decrease: F::infinity(),
+        }
+    }
+
+    /// Set the proximal parameter `mu`.
+    ///
+    /// Larger values lead to shorter steps. Must be positive and defaults to `1.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonsmooth::ProximalBundle;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bundle: ProximalBundle<Vec<f64>, f64> =
+    ///     ProximalBundle::new().with_proximal_parameter(10.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_proximal_parameter(mut self, mu: F) -> Result<Self, Error> {
+        if mu <= float!(0.0) || !mu.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ProximalBundle`: proximal parameter must be positive and finite."
+            ));
+        }
+        self.mu = mu;
+        Ok(self)
+    }
+
+    /// Set the descent parameter `m`.
+    ///
+    /// A step is a serious step if the cost function decreases by at least `m` times the decrease
+    /// predicted by the model. Must be in `(0, 1)` and defaults to `0.1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonsmooth::ProximalBundle;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bundle: ProximalBundle<Vec<f64>, f64> =
+    ///     ProximalBundle::new().with_descent_parameter(0.2)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_descent_parameter(mut self, m: F) -> Result<Self, Error> {
+        if m <= float!(0.0) || m >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ProximalBundle`: descent parameter must be in (0, 1)."
+            ));
+        }
+        self.m = m;
+        Ok(self)
+    }
+
+    /// Set the tolerance for the predicted decrease.
+    ///
+    /// Must be positive and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonsmooth::ProximalBundle;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bundle: ProximalBundle<Vec<f64>, f64> = ProximalBundle::new().with_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ProximalBundle`: tol must be positive."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set the maximum number of cuts in the bundle.
+    ///
+    /// Must be at least 2 and defaults to `50`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonsmooth::ProximalBundle;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bundle: ProximalBundle<Vec<f64>, f64> = ProximalBundle::new().with_max_bundle_size(20)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_max_bundle_size(mut self, size: usize) -> Result<Self, Error> {
+        if size < 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ProximalBundle`: maximum bundle size must be at least 2."
+            ));
+        }
+        self.max_bundle_size = size;
+        Ok(self)
+    }
+}
+
+impl<P, F> Default for ProximalBundle<P, F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        ProximalBundle::new()
+    }
+}
+
+impl<O, P, F> Solver<O, IterState<P, P, (), (), F>> for ProximalBundle<P, F>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = P>,
+    P: Clone
+        + SerializeAlias
+        + ArgminDot<P, F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminMul<F, P>
+        + ArgminZeroLike,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Proximal bundle method";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, P, (), (), F>,
+    ) -> Result<(IterState<P, P, (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`ProximalBundle` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let cost = problem.cost(param)?;
+        let grad = problem.gradient(param)?;
+        self.center = Some(param.clone());
+        self.center_cost = cost;
+        self.bundle = vec![Cut {
+            g: grad.clone(),
+            e: float!(0.0),
+        }];
+        self.predicted_decrease = F::infinity();
+        Ok((state.cost(cost).grad(grad), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, P, (), (), F>,
+    ) -> Result<(IterState<P, P, (), (), F>, Option<KV>), Error> {
+        let center = self.center.take().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`ProximalBundle`: Field `center` not set"
+        ))?;
+        let f_center = self.center_cost;
+
+        // Dual of the proximal subproblem: min_{lambda in simplex} 1/(2 mu) ‖Σ lambda_i g_i‖² +
+        // Σ lambda_i e_i
+        let n_cuts = self.bundle.len();
+        let q: Vec<Vec<F>> = self
+            .bundle
+            .iter()
+            .map(|cut_i| {
+                self.bundle
+                    .iter()
+                    .map(|cut_j| cut_i.g.dot(&cut_j.g) / self.mu)
+                    .collect()
+            })
+            .collect();
+        let e: Vec<F> = self.bundle.iter().map(|cut| cut.e).collect();
+        let gap_tol = float!(0.01) * self.tol * (float!(1.0) + f_center.abs());
+        let lambda = simplex_qp(&q, &e, gap_tol);
+
+        // Aggregate cut and step `d = -g_agg / mu`
+        let (g_agg, e_agg) = self
+            .bundle
+            .iter()
+            .zip(lambda.iter())
+            .fold((center.zero_like(), float!(0.0)), |(g, e), (cut, l)| {
+                (g.scaled_add(l, &cut.g), e + *l * cut.e)
+            });
+        let g_agg_sq = g_agg.dot(&g_agg);
+        self.predicted_decrease = g_agg_sq / self.mu + e_agg;
+
+        if self.predicted_decrease <= self.tol * (float!(1.0) + f_center.abs()) {
+            self.center = Some(center.clone());
+            return Ok((
+                state.param(center).cost(f_center),
+                Some(make_kv!(
+                    "serious_step" => false;
+                    "bundle_size" => n_cuts;
+                    "predicted_decrease" => self.predicted_decrease;
+                )),
+            ));
+        }
+
+        let d = g_agg.mul(&(float!(-1.0) / self.mu));
+        let new_param = center.scaled_add(&float!(1.0), &d);
+        let new_cost = problem.cost(&new_param)?;
+        let new_grad = problem.gradient(&new_param)?;
+
+        // Bundle compression: drop inactive cuts, aggregate if this is not sufficient
+        if n_cuts >= self.max_bundle_size {
+            let bundle = std::mem::take(&mut self.bundle);
+            self.bundle = bundle
+                .into_iter()
+                .zip(lambda.iter())
+                .filter(|(_, l)| **l > float!(0.0))
+                .map(|(cut, _)| cut)
+                .collect();
+            if self.bundle.len() >= self.max_bundle_size {
+                self.bundle = vec![Cut { g: g_agg, e: e_agg }];
+            }
+        }
+
+        let serious = new_cost <= f_center - self.m * self.predicted_decrease;
+        let new_e = if serious {
+            // Move linearization errors to the new stability center
+            for cut in self.bundle.iter_mut() {
+                cut.e = (cut.e + new_cost - f_center - cut.g.dot(&d)).max(float!(0.0));
+            }
+            self.center = Some(new_param.clone());
+            self.center_cost = new_cost;
+            float!(0.0)
+        } else {
+            self.center = Some(center);
+            (f_center - new_cost + new_grad.dot(&d)).max(float!(0.0))
+        };
+        self.bundle.push(Cut {
+            g: new_grad.clone(),
+            e: new_e,
+        });
+
+        Ok((
+            state.param(new_param).cost(new_cost).grad(new_grad),
+            Some(make_kv!(
+                "serious_step" => serious;
+                "bundle_size" => self.bundle.len();
+                "predicted_decrease" => self.predicted_decrease;
+            )),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, P, (), (), F>) -> TerminationReason {
+        if self.predicted_decrease <= self.tol * (float!(1.0) + self.center_cost.abs()) {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+/// Minimizes `1/2 λᵀQλ + cᵀλ` over the unit simplex with the accelerated projected gradient
+/// method until the Frank-Wolfe duality gap is below `gap_tol`.
+fn simplex_qp<F: ArgminFloat>(q: &[Vec<F>], c: &[F], gap_tol: F) -> Vec<F> {
+    let n = c.len();
+    let grad = |l: &[F]| -> Vec<F> {
+        q.iter()
+            .zip(c.iter())
+            .map(|(q_i, c_i)| {
+                q_i.iter()
+                    .zip(l.iter())
+                    .fold(*c_i, |acc, (q, l)| acc + *q * *l)
+            })
+            .collect()
+    };
+    // Lipschitz constant of the gradient (bound on the largest eigenvalue of `Q`)
+    let lipschitz = q
+        .iter()
+        .map(|q_i| q_i.iter().fold(float!(0.0), |acc: F, q| acc + q.abs()))
+        .fold(F::epsilon(), |acc, s| acc.max(s));
+
+    let mut lambda = vec![float!(1.0) / float!(n as f64); n];
+    let mut y = lambda.clone();
+    let mut t = float!(1.0);
+    for _ in 0..(1000 * n.max(10)) {
+        let g = grad(&lambda);
+        let min_g = g.iter().fold(F::infinity(), |acc, g| acc.min(*g));
+        let gap = g
+            .iter()
+            .zip(lambda.iter())
+            .fold(float!(0.0), |acc, (g, l)| acc + *g * *l)
+            - min_g;
+        if gap <= gap_tol {
+            break;
+        }
+
+        let g_y = grad(&y);
+        let step: Vec<F> = y
+            .iter()
+            .zip(g_y.iter())
+            .map(|(y, g)| *y - *g / lipschitz)
+            .collect();
+        let lambda_new = project_simplex(&step);
+        let t_new = (float!(1.0) + (float!(1.0) + float!(4.0) * t * t).sqrt()) / float!(2.0);
+        let momentum = (t - float!(1.0)) / t_new;
+        // Restart the momentum if the objective function increases along the momentum direction
+        let restart = g_y
+            .iter()
+            .zip(lambda_new.iter().zip(lambda.iter()))
+            .fold(float!(0.0), |acc, (g, (ln, l))| acc + *g * (*ln - *l))
+            > float!(0.0);
+        if restart {
+            t = float!(1.0);
+            y = lambda_new.clone();
+        } else {
+            y = lambda_new
+                .iter()
+                .zip(lambda.iter())
+                .map(|(ln, l)| *ln + momentum * (*ln - *l))
+                .collect();
+            t = t_new;
+        }
+        lambda = lambda_new;
+    }
+    lambda
+}
+
+/// Euclidean projection onto the unit simplex `{λ : λ >= 0, Σ λ_i = 1}`
+fn project_simplex<F: ArgminFloat>(v: &[F]) -> Vec<F> {
+    let mut sorted = v.to_vec();
+    sorted.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    let mut cumsum = float!(0.0);
+    let mut theta = float!(0.0);
+    for (k, u) in sorted.iter().enumerate() {
+        cumsum = cumsum + *u;
+        let t = (cumsum - float!(1.0)) / float!((k + 1) as f64);
+        if *u - t > float!(0.0) {
+            theta = t;
+        }
+    }
+    v.iter().map(|v| (*v - theta).max(float!(0.0))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(proximal_bundle, ProximalBundle<Vec<f64>, f64>);
+
+    /// Maximum of affine functions `max_i a_iᵀx + b_i`
+    struct MaxAffine {
+        a: Vec<Vec<f64>>,
+        b: Vec<f64>,
+    }
+
+    impl MaxAffine {
+        fn active(&self, x: &[f64]) -> usize {
+            let values: Vec<f64> = self
+                .a
+                .iter()
+                .zip(self.b.iter())
+                .map(|(a, b)| a.iter().zip(x.iter()).map(|(a, x)| a * x).sum::<f64>() + b)
+                .collect();
+            (0..values.len())
+                .max_by(|&i, &j| values[i].partial_cmp(&values[j]).unwrap())
+                .unwrap()
+        }
+    }
+
+    impl CostFunction for MaxAffine {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+            let i = self.active(x);
+            Ok(self.a[i]
+                .iter()
+                .zip(x.iter())
+                .map(|(a, x)| a * x)
+                .sum::<f64>()
+                + self.b[i])
+        }
+    }
+
+    impl Gradient for MaxAffine {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, x: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(self.a[self.active(x)].clone())
+        }
+    }
+
+    fn max_affine() -> MaxAffine {
+        // max(|x_0| + |x_1|, ...) shifted: minimum 0 at (1, -2)
+        MaxAffine {
+            a: vec![
+                vec![1.0, 1.0],
+                vec![1.0, -1.0],
+                vec![-1.0, 1.0],
+                vec![-1.0, -1.0],
+            ],
+            b: vec![1.0, -3.0, 3.0, -1.0],
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let ProximalBundle {
+            mu,
+            m,
+            tol,
+            max_bundle_size,
+            center,
+            bundle,
+            ..
+        } = ProximalBundle::<Vec<f64>, f64>::new();
+        assert_eq!(mu.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(m.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(max_bundle_size, 50);
+        assert!(center.is_none());
+        assert!(bundle.is_empty());
+    }
+
+    #[test]
+    fn test_builders() {
+        let b: ProximalBundle<Vec<f64>, f64> = ProximalBundle::new()
+            .with_proximal_parameter(2.0)
+            .unwrap()
+            .with_descent_parameter(0.3)
+            .unwrap()
+            .with_tolerance(1e-4)
+            .unwrap()
+            .with_max_bundle_size(5)
+            .unwrap();
+        assert_eq!(b.mu.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(b.m.to_ne_bytes(), 0.3f64.to_ne_bytes());
+        assert_eq!(b.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(b.max_bundle_size, 5);
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        for mu in [0.0, -1.0, f64::INFINITY] {
+            assert_error!(
+                ProximalBundle::<Vec<f64>, f64>::new().with_proximal_parameter(mu),
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`ProximalBundle`: proximal parameter must be positive ",
+                    "and finite.\""
+                )
+            );
+        }
+        for m in [0.0, 1.0] {
+            assert_error!(
+                ProximalBundle::<Vec<f64>, f64>::new().with_descent_parameter(m),
+                ArgminError,
+                "Invalid parameter: \"`ProximalBundle`: descent parameter must be in (0, 1).\""
+            );
+        }
+        assert_error!(
+            ProximalBundle::<Vec<f64>, f64>::new().with_tolerance(0.0),
+            ArgminError,
+            "Invalid parameter: \"`ProximalBundle`: tol must be positive.\""
+        );
+        assert_error!(
+            ProximalBundle::<Vec<f64>, f64>::new().with_max_bundle_size(1),
+            ArgminError,
+            "Invalid parameter: \"`ProximalBundle`: maximum bundle size must be at least 2.\""
+        );
+    }
+
+    #[test]
+    fn test_project_simplex() {
+        let p = project_simplex(&[0.5f64, 0.5, 0.5]);
+        for p in p {
+            assert_relative_eq!(p, 1.0 / 3.0, epsilon = 1e-15);
+        }
+        let p = project_simplex(&[2.0f64, 0.0, -1.0]);
+        assert_eq!(p, vec![1.0, 0.0, 0.0]);
+        let p = project_simplex(&[0.8f64, 0.6, -1.0]);
+        assert_relative_eq!(p[0], 0.6, epsilon = 1e-15);
+        assert_relative_eq!(p[1], 0.4, epsilon = 1e-15);
+        assert_relative_eq!(p[2], 0.0, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_simplex_qp() {
+        // min 1/2 (l0² + l1²) + 0.1 l0, solution (0.45, 0.55)
+        let l = simplex_qp(&[vec![1.0, 0.0], vec![0.0, 1.0]], &[0.1, 0.0], 1e-14);
+        assert_relative_eq!(l[0], 0.45, epsilon = 1e-7);
+        assert_relative_eq!(l[1], 0.55, epsilon = 1e-7);
+        // Linear objective: all weight on the smallest coefficient
+        let l = simplex_qp(&vec![vec![0.0; 3]; 3], &[0.3, -0.2, 0.1], 1e-14);
+        assert_relative_eq!(l[1], 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut b: ProximalBundle<Vec<f64>, f64> = ProximalBundle::new();
+        let res = b.init(&mut Problem::new(max_affine()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`ProximalBundle` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_max_affine() {
+        for max_bundle_size in [50, 2] {
+            let solver = ProximalBundle::new()
+                .with_max_bundle_size(max_bundle_size)
+                .unwrap();
+            let res = Executor::new(max_affine(), solver)
+                .configure(|state| state.param(vec![5.0, 3.0]).max_iters(200))
+                .ctrlc(false)
+                .run()
+                .unwrap();
+            let state = res.state();
+            assert_eq!(
+                state.get_termination_reason(),
+                TerminationReason::TargetPrecisionReached
+            );
+            assert_relative_eq!(state.get_best_cost(), 0.0, epsilon = 1e-7);
+            let x = state.get_best_param().unwrap();
+            assert_relative_eq!(x[0], 1.0, epsilon = 1e-6);
+            assert_relative_eq!(x[1], -2.0, epsilon = 1e-6);
+        }
+    }
+}
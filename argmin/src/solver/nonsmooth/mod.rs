@@ -0,0 +1,32 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Nonsmooth convex optimization
+//!
+//! Solvers for convex cost functions which are not differentiable everywhere, such as maxima of
+//! affine functions or hinge losses. The [`Gradient`](`crate::core::Gradient`) of the problem
+//! may return any subgradient.
+//!
+//! * [Subgradient method](`SubgradientMethod`)
+//! * [Proximal bundle method](`ProximalBundle`)
+//!
+//! Since the cost function value is not monotone in these methods, the best parameter vector
+//! found so far is tracked by the state.
+//!
+//! ## References
+//!
+//! Stephen Boyd, Lin Xiao and Almir Mutapcic (2003). Subgradient methods. Lecture notes of EE392o,
+//! Stanford University.
+//!
+//! Krzysztof C. Kiwiel (1990). Proximity control in bundle methods for convex nondifferentiable
+//! minimization. Mathematical Programming 46, pp. 105-122.
+
+mod bundle;
+mod subgradient;
+
+pub use self::bundle::ProximalBundle;
+pub use self::subgradient::{SubgradientMethod, SubgradientStep};
@@ -0,0 +1,392 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Gradient, IterState, Problem, Solver, State,
+    TerminationReason, KV,
+};
+use argmin_math::{ArgminNorm, ArgminScaledSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Step length rules of the [`SubgradientMethod`]
+///
+/// `k` is the iteration number (starting at zero) and `g` the subgradient at the current iterate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum SubgradientStep<F> {
+    /// Constant step length: `a / ‖g‖`
+    Constant(F),
+    /// Diminishing step length: `a / (sqrt(k + 1) ‖g‖)`
+    Diminishing(F),
+    /// Square summable but not summable step size: `a / ((k + 1) ‖g‖)`
+    SquareSummable(F),
+    /// Polyak step length: `(f(x_k) - f*) / ‖g‖²` with the (estimated) optimal cost function
+    /// value `f*`
+    Polyak(F),
+}
+
+impl<F: ArgminFloat> SubgradientStep<F> {
+    /// Step size `alpha` such that the step is `-alpha g`
+    fn step_size(&self, iter: u64, cost: F, grad_norm: F) -> F {
+        let k = float!((iter + 1) as f64);
+        match *self {
+            SubgradientStep::Constant(a) => a / grad_norm,
+            SubgradientStep::Diminishing(a) => a / (k.sqrt() * grad_norm),
+            SubgradientStep::SquareSummable(a) => a / (k * grad_norm),
+            SubgradientStep::Polyak(f_opt) => {
+                (cost - f_opt).max(float!(0.0)) / (grad_norm * grad_norm)
+            }
+        }
+    }
+}
+
+/// # Subgradient method
+///
+/// Minimizes convex, but not necessarily differentiable, cost functions such as maxima of affine
+/// functions or hinge losses. In each iteration, a step `x_{k+1} = x_k - alpha_k g_k` along the
+/// negative of a subgradient `g_k` (as returned by [`Gradient`]) is taken, where the step size
+/// `alpha_k` is determined by a [`SubgradientStep`] rule (default: diminishing step length
+/// `1 / (sqrt(k + 1) ‖g‖)`).
+///
+/// Subgradient steps are not descent steps, therefore the cost function value is not monotone.
+/// The best parameter vector found so far is tracked by the state and available via
+/// `get_best_param`.
+///
+/// The method terminates if the subgradient is zero, which means that the current iterate is
+/// optimal, or once the step length falls below the tolerance set with
+/// [`with_tolerance`](`SubgradientMethod::with_tolerance`).
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`], where
+/// the gradient may be any subgradient.
+///
+/// ## Reference
+///
+/// Stephen Boyd, Lin Xiao and Almir Mutapcic (2003). Subgradient methods. Lecture notes of EE392o,
+/// Stanford University.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SubgradientMethod<F> {
+    /// Step length rule
+    step: SubgradientStep<F>,
+    /// Tolerance for the step length
+    tol: F,
+    /// Length of the last step
+    step_length: F,
+}
+
+impl<F: ArgminFloat> SubgradientMethod<F> {
+    /// Construct a new instance of [`SubgradientMethod`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonsmooth::SubgradientMethod;
+    /// let subgradient: SubgradientMethod<f64> = SubgradientMethod::new();
+    /// ```
+    pub fn new() -> Self {
+        SubgradientMethod {
+            step: SubgradientStep::Diminishing(float!(1.0)),
+            tol: F::epsilon(),
+            step_length: F::infinity(),
+        }
+    }
+
+    /// Set the step length rule.
+    ///
+    /// The parameters of [`SubgradientStep::Constant`], [`SubgradientStep::Diminishing`] and
+    /// [`SubgradientStep::SquareSummable`] must be positive and the one of
+    /// [`SubgradientStep::Polyak`] must be finite. Defaults to
+    /// `SubgradientStep::Diminishing(1.0)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonsmooth::{SubgradientMethod, SubgradientStep};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let subgradient = SubgradientMethod::new().with_step(SubgradientStep::Polyak(0.0f64))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step(mut self, step: SubgradientStep<F>) -> Result<Self, Error> {
+        let valid = match step {
+            SubgradientStep::Constant(a)
+            | SubgradientStep::Diminishing(a)
+            | SubgradientStep::SquareSummable(a) => a > float!(0.0) && a.is_finite(),
+            SubgradientStep::Polyak(f_opt) => f_opt.is_finite(),
+        };
+        if !valid {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SubgradientMethod`: step length parameter must be positive and finite."
+            ));
+        }
+        self.step = step;
+        Ok(self)
+    }
+
+    /// Set the tolerance for the step length.
+    ///
+    /// The method terminates once the length of a step falls below `tol`. Tolerance must be
+    /// non-negative and defaults to `EPSILON`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonsmooth::SubgradientMethod;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let subgradient = SubgradientMethod::new().with_tolerance(1e-10f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SubgradientMethod`: tol must be non-negative."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+}
+
+impl<F: ArgminFloat> Default for SubgradientMethod<F> {
+    fn default() -> Self {
+        SubgradientMethod::new()
+    }
+}
+
+impl<O, P, G, F> Solver<O, IterState<P, G, (), (), F>> for SubgradientMethod<F>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
+    P: Clone + ArgminScaledSub<G, F, P>,
+    G: ArgminNorm<F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Subgradient method";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`SubgradientMethod` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let cost = problem.cost(param)?;
+        let grad = problem.gradient(param)?;
+        self.step_length = F::infinity();
+        Ok((state.cost(cost).grad(grad), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`SubgradientMethod`: No `param` in `state`"
+        ))?;
+        let grad = state.take_grad().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`SubgradientMethod`: No `grad` in `state`"
+        ))?;
+
+        let grad_norm = grad.norm();
+        let alpha = self
+            .step
+            .step_size(state.get_iter(), state.get_cost(), grad_norm);
+        self.step_length = alpha * grad_norm;
+
+        let new_param = param.scaled_sub(&alpha, &grad);
+        let new_cost = problem.cost(&new_param)?;
+        let new_grad = problem.gradient(&new_param)?;
+
+        Ok((
+            state.param(new_param).cost(new_cost).grad(new_grad),
+            Some(make_kv!(
+                "step_length" => self.step_length;
+                "subgradient_norm" => grad_norm;
+            )),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, G, (), (), F>) -> TerminationReason {
+        if let Some(grad) = state.get_grad() {
+            if grad.norm() <= float!(0.0) {
+                return TerminationReason::TargetPrecisionReached;
+            }
+        }
+        if self.step_length <= self.tol {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(subgradient, SubgradientMethod<f64>);
+
+    /// `f(x) = |x_0 - 1| + 2 |x_1 + 0.5|`
+    struct L1 {}
+
+    impl CostFunction for L1 {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((x[0] - 1.0).abs() + 2.0 * (x[1] + 0.5).abs())
+        }
+    }
+
+    impl Gradient for L1 {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, x: &Self::Param) -> Result<Self::Gradient, Error> {
+            let sign = |v: f64| if v == 0.0 { 0.0 } else { v.signum() };
+            Ok(vec![sign(x[0] - 1.0), 2.0 * sign(x[1] + 0.5)])
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let SubgradientMethod {
+            step,
+            tol,
+            step_length,
+        } = SubgradientMethod::<f64>::new();
+        assert_eq!(step, SubgradientStep::Diminishing(1.0));
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert!(step_length.is_infinite());
+    }
+
+    #[test]
+    fn test_with_step() {
+        for step in [
+            SubgradientStep::Constant(0.1f64),
+            SubgradientStep::Diminishing(2.0),
+            SubgradientStep::SquareSummable(1.0),
+            SubgradientStep::Polyak(-3.0),
+        ] {
+            let s = SubgradientMethod::new().with_step(step).unwrap();
+            assert_eq!(s.step, step);
+        }
+        for step in [
+            SubgradientStep::Constant(0.0f64),
+            SubgradientStep::Diminishing(-1.0),
+            SubgradientStep::SquareSummable(f64::INFINITY),
+            SubgradientStep::Polyak(f64::NAN),
+        ] {
+            assert_error!(
+                SubgradientMethod::new().with_step(step),
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`SubgradientMethod`: step length parameter must be ",
+                    "positive and finite.\""
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let s = SubgradientMethod::new().with_tolerance(0.0f64).unwrap();
+        assert_eq!(s.tol.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_error!(
+            SubgradientMethod::new().with_tolerance(-1.0f64),
+            ArgminError,
+            "Invalid parameter: \"`SubgradientMethod`: tol must be non-negative.\""
+        );
+    }
+
+    #[test]
+    fn test_step_size() {
+        assert_relative_eq!(
+            SubgradientStep::Constant(0.5f64).step_size(3, 1.0, 2.0),
+            0.25
+        );
+        assert_relative_eq!(
+            SubgradientStep::Diminishing(1.0f64).step_size(3, 1.0, 2.0),
+            0.25
+        );
+        assert_relative_eq!(
+            SubgradientStep::SquareSummable(1.0f64).step_size(3, 1.0, 2.0),
+            0.125
+        );
+        assert_relative_eq!(SubgradientStep::Polyak(0.2f64).step_size(3, 1.0, 2.0), 0.2);
+        assert_relative_eq!(SubgradientStep::Polyak(2.0f64).step_size(3, 1.0, 2.0), 0.0);
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut s = SubgradientMethod::<f64>::new();
+        let res = s.init(&mut Problem::new(L1 {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`SubgradientMethod` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_diminishing_tracks_best() {
+        let res = Executor::new(L1 {}, SubgradientMethod::new())
+            .configure(|state| state.param(vec![3.0, 2.0]).max_iters(2000))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        let state = res.state();
+        // Iterates oscillate around the minimizer; the best one is kept
+        assert!(state.get_best_cost() <= state.get_cost());
+        assert!(state.get_best_cost() < 0.05);
+        let best = state.get_best_param().unwrap();
+        assert_relative_eq!(best[0], 1.0, epsilon = 0.05);
+        assert_relative_eq!(best[1], -0.5, epsilon = 0.05);
+    }
+
+    #[test]
+    fn test_polyak() {
+        let res = Executor::new(
+            L1 {},
+            SubgradientMethod::new()
+                .with_step(SubgradientStep::Polyak(0.0))
+                .unwrap(),
+        )
+        .configure(|state| state.param(vec![3.0, 2.0]).max_iters(1000))
+        .ctrlc(false)
+        .run()
+        .unwrap();
+        let state = res.state();
+        assert_eq!(
+            state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        assert!(state.get_best_cost() < 1e-12);
+    }
+}
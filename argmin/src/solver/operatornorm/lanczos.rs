@@ -0,0 +1,443 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Operator, Problem, SerializeAlias, Solver, TerminationReason, KV,
+};
+use argmin_math::{ArgminDot, ArgminMul, ArgminNorm, ArgminScaledSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Lanczos method
+///
+/// Estimates the extreme eigenvalues of a symmetric linear [`Operator`] `A` (with identical
+/// parameter and output types). For the operator norm of a non-symmetric operator `K`, apply it
+/// to [`NormalOperator`](`crate::solver::operatornorm::NormalOperator`)`(K)`, whose largest
+/// eigenvalue is `‖K‖²`.
+///
+/// Each iteration extends the Krylov subspace spanned by the initial vector `v_0` by one
+/// dimension via the three-term recurrence
+///
+/// `beta_{k+1} v_{k+1} = A v_k - alpha_k v_k - beta_k v_{k-1}`, with `alpha_k = v_kᵀ A v_k`,
+///
+/// and computes the extreme eigenvalues of the tridiagonal matrix of the coefficients `alpha` and
+/// `beta` by bisection. The estimate of the eigenvalue of largest magnitude is available via
+/// [`eigenvalue`](`Lanczos::eigenvalue`), the smallest and largest eigenvalue estimates via
+/// [`min_eigenvalue`](`Lanczos::min_eigenvalue`) and [`max_eigenvalue`](`Lanczos::max_eigenvalue`).
+/// The parameter vector of the state is the current Lanczos vector `v_k`.
+///
+/// The solver terminates once the relative change of the estimate falls below the tolerance
+/// (default `1e-10`, see [`with_tolerance`](`Lanczos::with_tolerance`)) or the Krylov subspace
+/// is invariant (`beta_{k+1} = 0`), in which case the estimates are exact. The Lanczos vectors are
+/// not reorthogonalized; this does not affect the convergence of the extreme eigenvalues.
+///
+/// Requires a nonzero initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`] with `Param` equal to
+/// `Output`.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Lanczos<P, F> {
+    /// Relative tolerance for the change of the estimate
+    tol: F,
+    /// Diagonal of the tridiagonal matrix
+    alpha: Vec<F>,
+    /// Off-diagonal of the tridiagonal matrix
+    beta: Vec<F>,
+    /// Previous Lanczos vector
+    prev: Option<P>,
+    /// Smallest eigenvalue estimate
+    min_eigenvalue: F,
+    /// Largest eigenvalue estimate
+    max_eigenvalue: F,
+    /// Change of the estimate of the eigenvalue of largest magnitude in the last iteration
+    change: F,
+}
+
+impl<P, F> Lanczos<P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`Lanczos`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::operatornorm::Lanczos;
+    /// let lanczos: Lanczos<Vec<f64>, f64> = Lanczos::new();
+    /// ```
+    pub fn new() -> Self {
+        Lanczos {
+            tol: float!(1e-10),
+            alpha: vec![],
+            beta: vec![],
+            prev: None,
+            min_eigenvalue: float!(0.0),
+            max_eigenvalue: float!(0.0),
+            change: F::infinity(),
+        }
+    }
+
+    /// Set the relative tolerance for the change of the eigenvalue estimate.
+    ///
+    /// Must be non-negative and defaults to `1e-10`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::operatornorm::Lanczos;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let lanczos: Lanczos<Vec<f64>, f64> = Lanczos::new().with_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Lanczos`: tol must be non-negative."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Returns the current estimate of the eigenvalue of largest magnitude.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::operatornorm::Lanczos;
+    /// # let lanczos: Lanczos<Vec<f64>, f64> = Lanczos::new();
+    /// let eigenvalue = lanczos.eigenvalue();
+    /// # assert_eq!(eigenvalue.to_ne_bytes(), 0.0f64.to_ne_bytes());
+    /// ```
+    pub fn eigenvalue(&self) -> F {
+        if self.min_eigenvalue.abs() > self.max_eigenvalue.abs() {
+            self.min_eigenvalue
+        } else {
+            self.max_eigenvalue
+        }
+    }
+
+    /// Returns the current estimate of the smallest eigenvalue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::operatornorm::Lanczos;
+    /// # let lanczos: Lanczos<Vec<f64>, f64> = Lanczos::new();
+    /// let eigenvalue = lanczos.min_eigenvalue();
+    /// # assert_eq!(eigenvalue.to_ne_bytes(), 0.0f64.to_ne_bytes());
+    /// ```
+    pub fn min_eigenvalue(&self) -> F {
+        self.min_eigenvalue
+    }
+
+    /// Returns the current estimate of the largest eigenvalue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::operatornorm::Lanczos;
+    /// # let lanczos: Lanczos<Vec<f64>, f64> = Lanczos::new();
+    /// let eigenvalue = lanczos.max_eigenvalue();
+    /// # assert_eq!(eigenvalue.to_ne_bytes(), 0.0f64.to_ne_bytes());
+    /// ```
+    pub fn max_eigenvalue(&self) -> F {
+        self.max_eigenvalue
+    }
+}
+
+impl<P, F> Default for Lanczos<P, F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        Lanczos::new()
+    }
+}
+
+impl<O, P, F> Solver<O, IterState<P, (), (), (), F>> for Lanczos<P, F>
+where
+    O: Operator<Param = P, Output = P>,
+    P: Clone + SerializeAlias + ArgminDot<P, F> + ArgminNorm<F> + ArgminMul<F, P>,
+    P: ArgminScaledSub<P, F, P>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Lanczos";
+
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`Lanczos` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let norm = param.norm();
+        if norm <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Lanczos`: initial parameter vector must not be zero."
+            ));
+        }
+        self.alpha = vec![];
+        self.beta = vec![];
+        self.prev = None;
+        self.change = F::infinity();
+        Ok((state.param(param.mul(&(float!(1.0) / norm))), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let v = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`Lanczos`: Parameter vector in state not set."
+        ))?;
+
+        let mut w = problem.apply(&v)?;
+        if let (Some(prev), Some(beta)) = (self.prev.as_ref(), self.beta.last()) {
+            w = w.scaled_sub(beta, prev);
+        }
+        let alpha = v.dot(&w);
+        let w = w.scaled_sub(&alpha, &v);
+        let beta = w.norm();
+        self.alpha.push(alpha);
+
+        let previous = self.eigenvalue();
+        let (min, max) = tridiagonal_extreme_eigenvalues(&self.alpha, &self.beta);
+        self.min_eigenvalue = min;
+        self.max_eigenvalue = max;
+        let eigenvalue = self.eigenvalue();
+        self.change = if self.alpha.len() > 1 {
+            (eigenvalue - previous).abs()
+        } else {
+            F::infinity()
+        };
+
+        let kv = make_kv!(
+            "eigenvalue" => eigenvalue;
+            "beta" => beta;
+        );
+
+        // Invariant subspace found: the estimates are exact
+        let scale = self
+            .alpha
+            .iter()
+            .chain(self.beta.iter())
+            .fold(F::epsilon(), |acc, x| acc.max(x.abs()));
+        if beta <= F::epsilon() * scale {
+            self.change = float!(0.0);
+            self.prev = None;
+            return Ok((state.param(v), Some(kv)));
+        }
+
+        self.beta.push(beta);
+        let next = w.mul(&(float!(1.0) / beta));
+        self.prev = Some(v);
+        Ok((state.param(next), Some(kv)))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, (), (), (), F>) -> TerminationReason {
+        if self.change <= self.tol * self.eigenvalue().abs() {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+/// Smallest and largest eigenvalue of the symmetric tridiagonal matrix with diagonal `alpha` and
+/// off-diagonal `beta`, computed by bisection with Sturm sequences.
+fn tridiagonal_extreme_eigenvalues<F: ArgminFloat>(alpha: &[F], beta: &[F]) -> (F, F) {
+    let n = alpha.len();
+    // Number of eigenvalues smaller than `x`
+    let count = |x: F| -> usize {
+        let mut d = float!(1.0);
+        let mut count = 0;
+        for i in 0..n {
+            let b2 = if i > 0 {
+                beta[i - 1] * beta[i - 1]
+            } else {
+                float!(0.0)
+            };
+            d = alpha[i] - x - b2 / d;
+            if d == float!(0.0) {
+                d = F::epsilon() * (x.abs() + F::min_positive_value());
+            }
+            if d < float!(0.0) {
+                count += 1;
+            }
+        }
+        count
+    };
+    // Gershgorin bounds
+    let (lower, upper) = (0..n).fold((F::infinity(), F::neg_infinity()), |(lo, hi), i| {
+        let r = if i > 0 {
+            beta[i - 1].abs()
+        } else {
+            float!(0.0)
+        } + if i + 1 < n {
+            beta[i].abs()
+        } else {
+            float!(0.0)
+        };
+        (lo.min(alpha[i] - r), hi.max(alpha[i] + r))
+    });
+    let bisect = |target: usize| -> F {
+        // Smallest `x` such that at least `target` eigenvalues are smaller than `x`
+        let (mut lo, mut hi) = (lower, upper);
+        let tol = F::epsilon() * lower.abs().max(upper.abs()).max(F::min_positive_value());
+        while hi - lo > float!(2.0) * tol {
+            let mid = (lo + hi) / float!(2.0);
+            if mid <= lo || mid >= hi {
+                break;
+            }
+            if count(mid) >= target {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        (lo + hi) / float!(2.0)
+    };
+    (bisect(1), bisect(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::solver::operatornorm::{tests::Differences, NormalOperator};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(lanczos, Lanczos<Vec<f64>, f64>);
+
+    /// Diagonal operator
+    struct Diagonal {
+        d: Vec<f64>,
+    }
+
+    impl Operator for Diagonal {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self.d.iter().zip(x.iter()).map(|(d, x)| d * x).collect())
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let l: Lanczos<Vec<f64>, f64> = Lanczos::new();
+        assert_eq!(l.tol.to_ne_bytes(), 1e-10f64.to_ne_bytes());
+        assert!(l.alpha.is_empty());
+        assert!(l.beta.is_empty());
+        assert!(l.prev.is_none());
+        assert_eq!(l.eigenvalue().to_ne_bytes(), 0.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let l: Lanczos<Vec<f64>, f64> = Lanczos::new().with_tolerance(1e-3).unwrap();
+        assert_eq!(l.tol.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+        assert_error!(
+            Lanczos::<Vec<f64>, f64>::new().with_tolerance(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`Lanczos`: tol must be non-negative.\""
+        );
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let mut l: Lanczos<Vec<f64>, f64> = Lanczos::new();
+        let problem = || Problem::new(Diagonal { d: vec![1.0; 2] });
+        let res = l.init(&mut problem(), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`Lanczos` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+        let res = l.init(&mut problem(), IterState::new().param(vec![0.0; 2]));
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Lanczos`: initial parameter vector must not be zero.\""
+        );
+    }
+
+    #[test]
+    fn test_tridiagonal_extreme_eigenvalues() {
+        // [[2, 1, 0], [1, 2, 1], [0, 1, 2]] has eigenvalues 2 - sqrt(2), 2 and 2 + sqrt(2)
+        let (min, max) = tridiagonal_extreme_eigenvalues(&[2.0f64, 2.0, 2.0], &[1.0, 1.0]);
+        assert_relative_eq!(min, 2.0 - 2.0f64.sqrt(), epsilon = 1e-14);
+        assert_relative_eq!(max, 2.0 + 2.0f64.sqrt(), epsilon = 1e-14);
+        let (min, max) = tridiagonal_extreme_eigenvalues(&[-3.0f64], &[]);
+        assert_relative_eq!(min, -3.0, epsilon = 1e-14);
+        assert_relative_eq!(max, -3.0, epsilon = 1e-14);
+    }
+
+    #[test]
+    fn test_invariant_subspace() {
+        // The Krylov subspace of a diagonal operator with 3 distinct values has dimension 3
+        let d: Vec<f64> = (0..30).map(|i| [1.0, -5.0, 4.0][i % 3]).collect();
+        let res = Executor::new(Diagonal { d }, Lanczos::new())
+            .configure(|state| state.param(vec![1.0; 30]).max_iters(100))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        assert_eq!(res.state().get_iter(), 3);
+        assert_relative_eq!(res.solver().eigenvalue(), -5.0, epsilon = 1e-12);
+        assert_relative_eq!(res.solver().min_eigenvalue(), -5.0, epsilon = 1e-12);
+        assert_relative_eq!(res.solver().max_eigenvalue(), 4.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_clustered_spectrum() {
+        // Largest eigenvalues 1.0 and 0.99: power iteration would need thousands of iterations
+        let d: Vec<f64> = (0..200).map(|i| 1.0 - f64::from(i) * 0.01 / 2.0).collect();
+        let res = Executor::new(Diagonal { d }, Lanczos::new())
+            .configure(|state| state.param(vec![1.0; 200]).max_iters(200))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        assert!(res.state().get_iter() < 100);
+        assert_relative_eq!(res.solver().max_eigenvalue(), 1.0, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_normal_operator() {
+        let res = Executor::new(NormalOperator::new(Differences {}), Lanczos::new())
+            .configure(|state| state.param(vec![1.0, -1.0, 0.5, 0.0]).max_iters(100))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        assert_relative_eq!(
+            res.solver().eigenvalue(),
+            2.0 + 2.0f64.sqrt(),
+            epsilon = 1e-10
+        );
+    }
+}
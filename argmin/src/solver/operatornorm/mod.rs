@@ -0,0 +1,263 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Operator norm and extreme eigenvalue estimation
+//!
+//! Many first-order methods for linear inverse problems (such as
+//! [`Landweber`](`crate::solver::landweber::Landweber`) or
+//! [`ChambollePock`](`crate::solver::primaldual::ChambollePock`)) require step sizes which depend
+//! on the norm of a linear operator. This module provides
+//!
+//! * [`operator_norm`], a utility which estimates `‖K‖` of an
+//!   [`AdjointOperator`](`crate::core::AdjointOperator`) by power iteration on `KᵀK`,
+//! * the [`PowerIteration`] and [`Lanczos`] solvers, which estimate the eigenvalue of largest
+//!   magnitude of a symmetric [`Operator`](`crate::core::Operator`), and
+//! * [`NormalOperator`], which turns an `AdjointOperator` `K` into the symmetric operator `KᵀK`,
+//!   whose largest eigenvalue is `‖K‖²`.
+//!
+//! Both methods approach the largest eigenvalue from below, therefore step sizes derived from the
+//! estimates should include a safety margin.
+//!
+//! ## References
+//!
+//! Gene H. Golub and Charles F. Van Loan (2013). Matrix Computations, 4th edition. Johns Hopkins
+//! University Press.
+
+mod lanczos;
+mod poweriteration;
+
+pub use self::lanczos::Lanczos;
+pub use self::poweriteration::PowerIteration;
+
+use crate::core::{AdjointOperator, ArgminFloat, Error, Operator, Problem};
+use argmin_math::{ArgminMap, ArgminMul, ArgminNorm, ArgminZeroLike};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+/// Symmetric operator `KᵀK` of an [`AdjointOperator`] `K`
+///
+/// The largest eigenvalue of `KᵀK` is the square of the operator norm (largest singular value)
+/// of `K`.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{AdjointOperator, Error, Operator};
+/// use argmin::solver::operatornorm::NormalOperator;
+/// # struct Scale {}
+/// # impl Operator for Scale {
+/// #     type Param = Vec<f64>;
+/// #     type Output = Vec<f64>;
+/// #     fn apply(&self, x: &Vec<f64>) -> Result<Vec<f64>, Error> {
+/// #         Ok(x.iter().map(|x| 2.0 * x).collect())
+/// #     }
+/// # }
+/// # impl AdjointOperator for Scale {
+/// #     fn apply_adjoint(&self, y: &Vec<f64>) -> Result<Vec<f64>, Error> {
+/// #         self.apply(y)
+/// #     }
+/// # }
+///
+/// // `Scale` implements `AdjointOperator`
+/// let normal = NormalOperator::new(Scale {});
+/// assert_eq!(normal.apply(&vec![1.0, 2.0]).unwrap(), vec![4.0, 8.0]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NormalOperator<O> {
+    /// Operator `K`
+    operator: O,
+}
+
+impl<O> NormalOperator<O> {
+    /// Construct a new instance of [`NormalOperator`] from an operator `K`.
+    pub fn new(operator: O) -> Self {
+        NormalOperator { operator }
+    }
+}
+
+impl<O: AdjointOperator> Operator for NormalOperator<O> {
+    type Param = O::Param;
+    type Output = O::Param;
+
+    /// Computes `KᵀKx`
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.operator.apply_adjoint(&self.operator.apply(param)?)
+    }
+}
+
+/// Estimates the operator norm `‖K‖` of an [`AdjointOperator`] by power iteration on `KᵀK`.
+///
+/// `param` only determines the shape of the deterministic pseudo-random start vector. The
+/// iteration stops after `max_iters` iterations or once the relative change of the estimate is
+/// below `1e-8`.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{AdjointOperator, Error, Operator, Problem};
+/// use argmin::solver::operatornorm::operator_norm;
+/// # struct Diag {}
+/// # impl Operator for Diag {
+/// #     type Param = Vec<f64>;
+/// #     type Output = Vec<f64>;
+/// #     fn apply(&self, x: &Vec<f64>) -> Result<Vec<f64>, Error> {
+/// #         Ok(vec![3.0 * x[0], -4.0 * x[1]])
+/// #     }
+/// # }
+/// # impl AdjointOperator for Diag {
+/// #     fn apply_adjoint(&self, y: &Vec<f64>) -> Result<Vec<f64>, Error> {
+/// #         self.apply(y)
+/// #     }
+/// # }
+///
+/// // `Diag` implements `AdjointOperator`
+/// let mut problem = Problem::new(Diag {});
+/// let norm: f64 = operator_norm(&mut problem, &vec![0.0, 0.0], 100)?;
+/// # assert!((norm - 4.0).abs() < 1e-6);
+/// # Ok::<(), Error>(())
+/// ```
+pub fn operator_norm<O, P, D, F>(
+    problem: &mut Problem<O>,
+    param: &P,
+    max_iters: u64,
+) -> Result<F, Error>
+where
+    O: AdjointOperator<Param = P, Output = D>,
+    P: ArgminMul<F, P> + ArgminNorm<F> + ArgminZeroLike + ArgminMap<F>,
+    F: ArgminFloat,
+{
+    let estimate = power_iteration(
+        pseudo_random_like(param),
+        |v| {
+            let kv = problem.apply(v)?;
+            problem.apply_adjoint(&kv)
+        },
+        max_iters,
+        float!(1e-8),
+    )?;
+    Ok(estimate.sqrt())
+}
+
+/// Power iteration for the largest eigenvalue of the symmetric positive semidefinite linear map
+/// `apply`. Returns zero if an iterate vanishes.
+pub(crate) fn power_iteration<P, F, A>(
+    start: P,
+    mut apply: A,
+    max_iters: u64,
+    tol: F,
+) -> Result<F, Error>
+where
+    P: ArgminMul<F, P> + ArgminNorm<F>,
+    F: ArgminFloat,
+    A: FnMut(&P) -> Result<P, Error>,
+{
+    let mut v = start;
+    let mut estimate = float!(0.0);
+    for _ in 0..max_iters {
+        let norm = v.norm();
+        if norm <= float!(0.0) {
+            return Ok(float!(0.0));
+        }
+        let w = apply(&v.mul(&(float!(1.0) / norm)))?;
+        let new_estimate = w.norm();
+        let converged = (new_estimate - estimate).abs() <= tol * new_estimate;
+        estimate = new_estimate;
+        v = w;
+        if converged {
+            break;
+        }
+    }
+    Ok(estimate)
+}
+
+/// Deterministic pseudo-random vector shaped like `param` with elements in `[-1, 1)`
+pub(crate) fn pseudo_random_like<P, F>(param: &P) -> P
+where
+    P: ArgminZeroLike + ArgminMap<F>,
+    F: ArgminFloat,
+{
+    // xorshift sequence
+    let seed = Cell::new(0x2545_f491_4f6c_dd1du64);
+    param.zero_like().map(|_| {
+        let mut s = seed.get();
+        s ^= s << 13;
+        s ^= s >> 7;
+        s ^= s << 17;
+        seed.set(s);
+        float!((s >> 11) as f64 / (1u64 << 52) as f64 - 1.0)
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// Forward differences `(Kx)_i = x_{i+1} - x_i`
+    pub(crate) struct Differences {}
+
+    impl Operator for Differences {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(x.windows(2).map(|w| w[1] - w[0]).collect())
+        }
+    }
+
+    impl AdjointOperator for Differences {
+        fn apply_adjoint(&self, y: &Self::Output) -> Result<Self::Param, Error> {
+            let mut x = vec![0.0; y.len() + 1];
+            for (i, y) in y.iter().enumerate() {
+                x[i] -= y;
+                x[i + 1] += y;
+            }
+            Ok(x)
+        }
+    }
+
+    #[test]
+    fn test_pseudo_random_like() {
+        let v: Vec<f64> = pseudo_random_like(&vec![0.0; 100]);
+        assert_eq!(v.len(), 100);
+        assert!(v.iter().all(|v| (-1.0..1.0).contains(v)));
+        assert_eq!(v, pseudo_random_like(&vec![5.0; 100]));
+        let v1: Vec<f64> = pseudo_random_like(&vec![0.0; 1]);
+        assert_eq!(v1[0].to_ne_bytes(), v[0].to_ne_bytes());
+    }
+
+    #[test]
+    fn test_operator_norm() {
+        // Eigenvalues of KᵀK for forward differences on 4 points: 2 - 2 cos(k pi / 4)
+        let mut problem = Problem::new(Differences {});
+        let norm: f64 = operator_norm(&mut problem, &vec![0.0; 4], 1000).unwrap();
+        assert_relative_eq!(norm, (2.0 + 2.0f64.sqrt()).sqrt(), epsilon = 1e-6);
+        assert!(problem.counts["operator_count"] > 0);
+        assert_eq!(
+            problem.counts["operator_count"],
+            problem.counts["adjoint_operator_count"]
+        );
+    }
+
+    #[test]
+    fn test_power_iteration_zero() {
+        let estimate: f64 =
+            power_iteration(vec![1.0, 1.0], |v| Ok(vec![0.0; v.len()]), 10, 1e-8).unwrap();
+        assert_eq!(estimate.to_ne_bytes(), 0.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_normal_operator() {
+        let normal = NormalOperator::new(Differences {});
+        assert_eq!(
+            normal.apply(&vec![0.0, 1.0, 0.0]).unwrap(),
+            vec![-1.0, 2.0, -1.0]
+        );
+    }
+}
@@ -0,0 +1,310 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Operator, Problem, SerializeAlias, Solver, TerminationReason, KV,
+};
+use argmin_math::{ArgminDot, ArgminMul, ArgminNorm, ArgminScaledSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Power iteration
+///
+/// Estimates the eigenvalue of largest magnitude and the corresponding eigenvector of a
+/// symmetric linear [`Operator`] `A` (with identical parameter and output types). For the
+/// operator norm of a non-symmetric operator `K`, apply it to
+/// [`NormalOperator`](`crate::solver::operatornorm::NormalOperator`)`(K)`, whose largest
+/// eigenvalue is `‖K‖²`.
+///
+/// In each iteration, the normalized parameter vector `v` is replaced by `Av / ‖Av‖`. The
+/// eigenvalue estimate is the Rayleigh quotient `vᵀAv` and the cost function value is the
+/// eigenpair residual `‖Av - (vᵀAv) v‖`. The solver terminates once the residual is below
+/// `tol * |vᵀAv|` (see [`with_tolerance`](`PowerIteration::with_tolerance`)). The estimate is
+/// available via [`eigenvalue`](`PowerIteration::eigenvalue`).
+///
+/// Convergence is linear with rate `|λ_2 / λ_1|`, where `λ_1` and `λ_2` are the eigenvalues of
+/// largest and second largest magnitude. If these are close, [`Lanczos`](`super::Lanczos`)
+/// converges considerably faster.
+///
+/// Requires an initial parameter vector, which must not be orthogonal to the dominant
+/// eigenvector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`] with `Param` equal to
+/// `Output`.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct PowerIteration<P, F> {
+    /// Relative tolerance for the eigenpair residual
+    tol: F,
+    /// Current eigenvalue estimate
+    eigenvalue: F,
+    /// `Av` of the current parameter vector
+    av: Option<P>,
+}
+
+impl<P, F> PowerIteration<P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`PowerIteration`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::operatornorm::PowerIteration;
+    /// let power: PowerIteration<Vec<f64>, f64> = PowerIteration::new();
+    /// ```
+    pub fn new() -> Self {
+        PowerIteration {
+            tol: float!(1e-6),
+            eigenvalue: float!(0.0),
+            av: None,
+        }
+    }
+
+    /// Set the relative tolerance for the eigenpair residual.
+    ///
+    /// Must be non-negative and defaults to `1e-6`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::operatornorm::PowerIteration;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let power: PowerIteration<Vec<f64>, f64> = PowerIteration::new().with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`PowerIteration`: tol must be non-negative."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Returns the current estimate of the eigenvalue of largest magnitude.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::operatornorm::PowerIteration;
+    /// # let power: PowerIteration<Vec<f64>, f64> = PowerIteration::new();
+    /// let eigenvalue = power.eigenvalue();
+    /// # assert_eq!(eigenvalue.to_ne_bytes(), 0.0f64.to_ne_bytes());
+    /// ```
+    pub fn eigenvalue(&self) -> F {
+        self.eigenvalue
+    }
+}
+
+impl<P, F> Default for PowerIteration<P, F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        PowerIteration::new()
+    }
+}
+
+impl<P, F> PowerIteration<P, F>
+where
+    P: ArgminDot<P, F> + ArgminNorm<F> + ArgminScaledSub<P, F, P>,
+    F: ArgminFloat,
+{
+    /// Rayleigh quotient and residual of the normalized vector `v` with `av = Av`
+    fn rayleigh(v: &P, av: &P) -> (F, F) {
+        let eigenvalue = v.dot(av);
+        (eigenvalue, av.scaled_sub(&eigenvalue, v).norm())
+    }
+}
+
+impl<O, P, F> Solver<O, IterState<P, (), (), (), F>> for PowerIteration<P, F>
+where
+    O: Operator<Param = P, Output = P>,
+    P: Clone + SerializeAlias + ArgminDot<P, F> + ArgminNorm<F> + ArgminMul<F, P>,
+    P: ArgminScaledSub<P, F, P>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Power iteration";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`PowerIteration` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let norm = param.norm();
+        if norm <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`PowerIteration`: initial parameter vector must not be zero."
+            ));
+        }
+        let v = param.mul(&(float!(1.0) / norm));
+        let av = problem.apply(&v)?;
+        let (eigenvalue, residual) = Self::rayleigh(&v, &av);
+        self.eigenvalue = eigenvalue;
+        self.av = Some(av);
+        Ok((
+            state.param(v).cost(residual),
+            Some(make_kv!("eigenvalue" => eigenvalue;)),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let av = self.av.take().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`PowerIteration`: Field `av` not set"
+        ))?;
+        let norm = av.norm();
+        if norm <= float!(0.0) {
+            // The parameter vector is in the null space, hence all eigenvalues are zero or the
+            // initial vector is orthogonal to all eigenvectors of nonzero eigenvalues.
+            self.eigenvalue = float!(0.0);
+            self.av = Some(av);
+            return Ok((state.cost(float!(0.0)), None));
+        }
+        let v = av.mul(&(float!(1.0) / norm));
+        let av = problem.apply(&v)?;
+        let (eigenvalue, residual) = Self::rayleigh(&v, &av);
+        self.eigenvalue = eigenvalue;
+        self.av = Some(av);
+        Ok((
+            state.param(v).cost(residual),
+            Some(make_kv!("eigenvalue" => eigenvalue;)),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, (), (), (), F>) -> TerminationReason {
+        if state.get_cost() <= self.tol * self.eigenvalue.abs() {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::solver::operatornorm::{tests::Differences, NormalOperator};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(power_iteration, PowerIteration<Vec<f64>, f64>);
+
+    /// Symmetric matrix with eigenvalues -3, 2 and 1
+    struct Matrix {}
+
+    impl Operator for Matrix {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+            // Q diag(-3, 2, 1) Qᵀ with Q a rotation in the first two coordinates by 45 degrees
+            Ok(vec![
+                -0.5 * x[0] - 2.5 * x[1],
+                -2.5 * x[0] - 0.5 * x[1],
+                x[2],
+            ])
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let PowerIteration {
+            tol,
+            eigenvalue,
+            av,
+        } = PowerIteration::<Vec<f64>, f64>::new();
+        assert_eq!(tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(eigenvalue.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert!(av.is_none());
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let p: PowerIteration<Vec<f64>, f64> = PowerIteration::new().with_tolerance(1e-3).unwrap();
+        assert_eq!(p.tol.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+        assert_error!(
+            PowerIteration::<Vec<f64>, f64>::new().with_tolerance(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`PowerIteration`: tol must be non-negative.\""
+        );
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let mut p: PowerIteration<Vec<f64>, f64> = PowerIteration::new();
+        let res = p.init(&mut Problem::new(Matrix {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`PowerIteration` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+        let res = p.init(
+            &mut Problem::new(Matrix {}),
+            IterState::new().param(vec![0.0; 3]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`PowerIteration`: initial parameter vector must not be zero.\""
+        );
+    }
+
+    #[test]
+    fn test_negative_eigenvalue() {
+        let res = Executor::new(Matrix {}, PowerIteration::new())
+            .configure(|state| state.param(vec![1.0, 0.3, 1.0]).max_iters(200))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        assert_relative_eq!(res.solver().eigenvalue(), -3.0, epsilon = 1e-9);
+        let v = res.state().get_best_param().unwrap();
+        assert_relative_eq!(v[0].abs(), 0.5f64.sqrt(), epsilon = 1e-5);
+        assert_relative_eq!(v[0], v[1], epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_normal_operator() {
+        let res = Executor::new(NormalOperator::new(Differences {}), PowerIteration::new())
+            .configure(|state| state.param(vec![1.0, -1.0, 0.5, 0.0]).max_iters(1000))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        assert_relative_eq!(
+            res.solver().eigenvalue(),
+            2.0 + 2.0f64.sqrt(),
+            epsilon = 1e-9
+        );
+    }
+}
@@ -0,0 +1,637 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    AdjointOperator, ArgminFloat, Error, IterState, Problem, SerializeAlias, Solver, State,
+    TerminationReason, KV,
+};
+use crate::solver::operatornorm::operator_norm;
+use crate::solver::primaldual::ProximalMap;
+use argmin_math::{ArgminMap, ArgminMul, ArgminNorm, ArgminScaledAdd, ArgminSub, ArgminZeroLike};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Chambolle-Pock primal-dual hybrid gradient method
+///
+/// Solves saddle-point problems of the form
+///
+/// `min_x max_y <Kx, y> + G(x) - F*(y)`
+///
+/// which is the primal-dual formulation of `min_x G(x) + F(Kx)`. Here, `K` is a linear operator
+/// (provided by the problem via [`Operator`](`crate::core::Operator`) and [`AdjointOperator`])
+/// and `G` and `F*` (the convex conjugate of `F`) are convex functions of which only the proximal
+/// maps are required (see [`ProximalMap`]).
+///
+/// Each iteration performs
+///
+/// ```text
+/// y_{k+1} = prox_{sigma F*}(y_k + sigma K x̄_k)
+/// x_{k+1} = prox_{tau G}(x_k - tau Kᵀ y_{k+1})
+/// x̄_{k+1} = x_{k+1} + theta (x_{k+1} - x_k)
+/// ```
+///
+/// which converges for `theta = 1` if `tau * sigma * ‖K‖² < 1`. Unless set via
+/// [`with_step_sizes`](`ChambollePock::with_step_sizes`), the step sizes are chosen as
+/// `tau = sigma = 0.95 / ‖K‖`, where the operator norm is either provided via
+/// [`with_operator_norm`](`ChambollePock::with_operator_norm`) or estimated in `init` with
+/// [`operator_norm`](`crate::solver::operatornorm::operator_norm`).
+///
+/// The primal and dual residuals, which vanish at a saddle point, are reported in every
+/// iteration. The method terminates once both are below the tolerance (default `1e-6`, see
+/// [`with_tolerance`](`ChambollePock::with_tolerance`)). If both proximal maps provide the
+/// function and conjugate function values, the primal objective `G(x) + F(Kx)` is used as the
+/// cost function value and the primal-dual gap `G(x) + F(Kx) + G*(-Kᵀy) + F*(y)` is reported.
+///
+/// Requires an initial parameter vector. The initial dual vector defaults to zero and can be set
+/// via [`with_dual`](`ChambollePock::with_dual`); the final dual vector is available via
+/// [`dual`](`ChambollePock::dual`).
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`AdjointOperator`] (and therefore
+/// [`Operator`](`crate::core::Operator`)).
+///
+/// ## Reference
+///
+/// Antonin Chambolle and Thomas Pock (2011). A first-order primal-dual algorithm for convex
+/// problems with applications to imaging. Journal of Mathematical Imaging and Vision 40,
+/// pp. 120-145.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ChambollePock<G, FC, D, F> {
+    /// Proximal map of `G`
+    g: G,
+    /// Proximal map of `F*`
+    f_conj: FC,
+    /// Primal step size
+    tau: Option<F>,
+    /// Dual step size
+    sigma: Option<F>,
+    /// Operator norm of `K`
+    operator_norm: Option<F>,
+    /// Extrapolation parameter
+    theta: F,
+    /// Tolerance for primal and dual residuals
+    tol: F,
+    /// Maximum number of power iterations
+    power_iters: u64,
+    /// Dual vector
+    y: Option<D>,
+    /// `K x_k`
+    kx: Option<D>,
+    /// `K x̄_k`
+    kx_bar: Option<D>,
+    /// Primal residual
+    primal_residual: F,
+    /// Dual residual
+    dual_residual: F,
+}
+
+impl<G, FC, D, F> ChambollePock<G, FC, D, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`ChambollePock`] from the proximal maps of `G` and `F*`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::primaldual::ChambollePock;
+    /// # use argmin::solver::projectedgradient::BoxProjection;
+    /// # let g = BoxProjection::new(vec![0.0f64; 2], vec![1.0; 2]).unwrap();
+    /// # let f_conj = BoxProjection::new(vec![-1.0f64; 2], vec![1.0; 2]).unwrap();
+    /// let pdhg: ChambollePock<_, _, Vec<f64>, f64> = ChambollePock::new(g, f_conj);
+    /// ```
+    pub fn new(g: G, f_conj: FC) -> Self {
+        ChambollePock {
+            g,
+            f_conj,
+            tau: None,
+            sigma: None,
+            operator_norm: None,
+            theta: float!(1.0),
+            tol: float!(1e-6),
+            power_iters: 100,
+            y: None,
+            kx: None,
+            kx_bar: None,
+            primal_residual: F::infinity(),
+            dual_residual: F::infinity(),
+        }
+    }
+
+    /// Set the primal step size `tau` and dual step size `sigma`.
+    ///
+    /// Both must be positive. Convergence requires `tau * sigma * ‖K‖² < 1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::primaldual::ChambollePock;
+    /// # use argmin::solver::projectedgradient::BoxProjection;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let g = BoxProjection::new(vec![0.0f64; 2], vec![1.0; 2]).unwrap();
+    /// # let f_conj = BoxProjection::new(vec![-1.0f64; 2], vec![1.0; 2]).unwrap();
+    /// let pdhg: ChambollePock<_, _, Vec<f64>, f64> =
+    ///     ChambollePock::new(g, f_conj).with_step_sizes(0.1, 0.5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step_sizes(mut self, tau: F, sigma: F) -> Result<Self, Error> {
+        if tau <= float!(0.0) || sigma <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ChambollePock`: step sizes must be positive."
+            ));
+        }
+        self.tau = Some(tau);
+        self.sigma = Some(sigma);
+        Ok(self)
+    }
+
+    /// Set the operator norm `‖K‖`, which avoids estimating it by power iteration.
+    ///
+    /// Must be positive. Ignored if the step sizes are set via
+    /// [`with_step_sizes`](`ChambollePock::with_step_sizes`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::primaldual::ChambollePock;
+    /// # use argmin::solver::projectedgradient::BoxProjection;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let g = BoxProjection::new(vec![0.0f64; 2], vec![1.0; 2]).unwrap();
+    /// # let f_conj = BoxProjection::new(vec![-1.0f64; 2], vec![1.0; 2]).unwrap();
+    /// let pdhg: ChambollePock<_, _, Vec<f64>, f64> =
+    ///     ChambollePock::new(g, f_conj).with_operator_norm(2.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_operator_norm(mut self, norm: F) -> Result<Self, Error> {
+        if norm <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ChambollePock`: operator norm must be positive."
+            ));
+        }
+        self.operator_norm = Some(norm);
+        Ok(self)
+    }
+
+    /// Set the extrapolation parameter `theta`.
+    ///
+    /// Must be in `[0, 1]` and defaults to `1.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::primaldual::ChambollePock;
+    /// # use argmin::solver::projectedgradient::BoxProjection;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let g = BoxProjection::new(vec![0.0f64; 2], vec![1.0; 2]).unwrap();
+    /// # let f_conj = BoxProjection::new(vec![-1.0f64; 2], vec![1.0; 2]).unwrap();
+    /// let pdhg: ChambollePock<_, _, Vec<f64>, f64> =
+    ///     ChambollePock::new(g, f_conj).with_theta(0.5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_theta(mut self, theta: F) -> Result<Self, Error> {
+        if theta < float!(0.0) || theta > float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ChambollePock`: theta must be in [0, 1]."
+            ));
+        }
+        self.theta = theta;
+        Ok(self)
+    }
+
+    /// Set the tolerance for the primal and dual residuals.
+    ///
+    /// Must be non-negative and defaults to `1e-6`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::primaldual::ChambollePock;
+    /// # use argmin::solver::projectedgradient::BoxProjection;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let g = BoxProjection::new(vec![0.0f64; 2], vec![1.0; 2]).unwrap();
+    /// # let f_conj = BoxProjection::new(vec![-1.0f64; 2], vec![1.0; 2]).unwrap();
+    /// let pdhg: ChambollePock<_, _, Vec<f64>, f64> =
+    ///     ChambollePock::new(g, f_conj).with_tolerance(1e-8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ChambollePock`: tol must be non-negative."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set the initial dual vector.
+    ///
+    /// Defaults to zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::primaldual::ChambollePock;
+    /// # use argmin::solver::projectedgradient::BoxProjection;
+    /// # let g = BoxProjection::new(vec![0.0f64; 2], vec![1.0; 2]).unwrap();
+    /// # let f_conj = BoxProjection::new(vec![-1.0f64; 2], vec![1.0; 2]).unwrap();
+    /// let pdhg: ChambollePock<_, _, Vec<f64>, f64> =
+    ///     ChambollePock::new(g, f_conj).with_dual(vec![0.5, 0.5]);
+    /// ```
+    #[must_use]
+    pub fn with_dual(mut self, y: D) -> Self {
+        self.y = Some(y);
+        self
+    }
+
+    /// Returns the current dual vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::primaldual::ChambollePock;
+    /// # use argmin::solver::projectedgradient::BoxProjection;
+    /// # let g = BoxProjection::new(vec![0.0f64; 2], vec![1.0; 2]).unwrap();
+    /// # let f_conj = BoxProjection::new(vec![-1.0f64; 2], vec![1.0; 2]).unwrap();
+    /// let pdhg: ChambollePock<_, _, Vec<f64>, f64> =
+    ///     ChambollePock::new(g, f_conj).with_dual(vec![0.5, 0.5]);
+    /// assert_eq!(pdhg.dual(), Some(&vec![0.5, 0.5]));
+    /// ```
+    pub fn dual(&self) -> Option<&D> {
+        self.y.as_ref()
+    }
+}
+
+impl<O, G, FC, P, D, F> Solver<O, IterState<P, (), (), (), F>> for ChambollePock<G, FC, D, F>
+where
+    O: AdjointOperator<Param = P, Output = D>,
+    G: ProximalMap<P, F>,
+    FC: ProximalMap<D, F>,
+    P: Clone
+        + SerializeAlias
+        + ArgminSub<P, P>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminMul<F, P>
+        + ArgminNorm<F>
+        + ArgminZeroLike
+        + ArgminMap<F>,
+    D: Clone
+        + SerializeAlias
+        + ArgminSub<D, D>
+        + ArgminScaledAdd<D, F, D>
+        + ArgminNorm<F>
+        + ArgminZeroLike,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Chambolle-Pock";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`ChambollePock` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let kx = problem.apply(param)?;
+
+        let mut kv = make_kv!();
+        if self.tau.is_none() || self.sigma.is_none() {
+            let norm = match self.operator_norm {
+                Some(norm) => norm,
+                None => {
+                    let norm = operator_norm(problem, param, self.power_iters)?;
+                    self.operator_norm = Some(norm);
+                    norm
+                }
+            };
+            if norm <= float!(0.0) {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`ChambollePock`: estimated operator norm is zero."
+                ));
+            }
+            let step = float!(0.95) / norm;
+            self.tau = Some(step);
+            self.sigma = Some(step);
+            kv = make_kv!("operator_norm" => norm;);
+        }
+
+        if self.y.is_none() {
+            self.y = Some(kx.zero_like());
+        }
+        self.kx_bar = Some(kx.clone());
+        self.kx = Some(kx);
+        self.primal_residual = F::infinity();
+        self.dual_residual = F::infinity();
+
+        Ok((
+            state,
+            Some(kv.merge(make_kv!(
+                "tau" => self.tau.unwrap();
+                "sigma" => self.sigma.unwrap();
+            ))),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let x = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`ChambollePock` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let (tau, sigma) = match (self.tau, self.sigma) {
+            (Some(tau), Some(sigma)) => (tau, sigma),
+            _ => {
+                return Err(argmin_error!(
+                    PotentialBug,
+                    "`ChambollePock`: step sizes not set"
+                ))
+            }
+        };
+        let y = self.y.take().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`ChambollePock`: Field `y` not set"
+        ))?;
+        let kx = self.kx.take().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`ChambollePock`: Field `kx` not set"
+        ))?;
+        let kx_bar = self.kx_bar.take().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`ChambollePock`: Field `kx_bar` not set"
+        ))?;
+
+        let new_y = self.f_conj.prox(&y.scaled_add(&sigma, &kx_bar), sigma)?;
+        let kty = problem.apply_adjoint(&new_y)?;
+        let new_x = self.g.prox(&x.scaled_add(&(-tau), &kty), tau)?;
+        let new_kx = problem.apply(&new_x)?;
+
+        // Residuals of the optimality conditions `0 ∈ ∂G(x) + Kᵀy` and `0 ∈ ∂F*(y) - Kx`
+        self.primal_residual = x.sub(&new_x).norm() / tau;
+        self.dual_residual = y
+            .sub(&new_y)
+            .scaled_add(&sigma, &kx_bar.sub(&new_kx))
+            .norm()
+            / sigma;
+
+        let mut kv = make_kv!(
+            "primal_residual" => self.primal_residual;
+            "dual_residual" => self.dual_residual;
+        );
+        let g = self.g.value(&new_x)?;
+        let f = self.f_conj.conjugate_value(&new_kx)?;
+        if let (Some(g), Some(f)) = (g, f) {
+            state = state.cost(g + f);
+            let g_conj = self.g.conjugate_value(&kty.mul(&float!(-1.0)))?;
+            let f_conj = self.f_conj.value(&new_y)?;
+            if let (Some(g_conj), Some(f_conj)) = (g_conj, f_conj) {
+                kv = kv.merge(make_kv!("gap" => g + f + g_conj + f_conj;));
+            }
+        }
+
+        self.kx_bar = Some(new_kx.scaled_add(&self.theta, &new_kx.sub(&kx)));
+        self.kx = Some(new_kx);
+        self.y = Some(new_y);
+
+        Ok((state.param(new_x), Some(kv)))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, (), (), (), F>) -> TerminationReason {
+        if self.primal_residual <= self.tol && self.dual_residual <= self.tol {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::operatornorm::tests::Differences;
+    use crate::solver::projectedgradient::BoxProjection;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        chambolle_pock,
+        ChambollePock<BoxProjection<Vec<f64>>, BoxProjection<Vec<f64>>, Vec<f64>, f64>
+    );
+
+    /// `G(x) = 1/2 ‖x - f‖²`
+    #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+    struct Denoise {
+        f: Vec<f64>,
+    }
+
+    impl ProximalMap<Vec<f64>, f64> for Denoise {
+        fn prox(&self, v: &Vec<f64>, step: f64) -> Result<Vec<f64>, Error> {
+            Ok(v.iter()
+                .zip(self.f.iter())
+                .map(|(v, f)| (v + step * f) / (1.0 + step))
+                .collect())
+        }
+
+        fn value(&self, x: &Vec<f64>) -> Result<Option<f64>, Error> {
+            Ok(Some(
+                0.5 * x
+                    .iter()
+                    .zip(self.f.iter())
+                    .map(|(x, f)| (x - f).powi(2))
+                    .sum::<f64>(),
+            ))
+        }
+
+        fn conjugate_value(&self, v: &Vec<f64>) -> Result<Option<f64>, Error> {
+            Ok(Some(
+                v.iter()
+                    .zip(self.f.iter())
+                    .map(|(v, f)| 0.5 * v * v + v * f)
+                    .sum(),
+            ))
+        }
+    }
+
+    /// `F*(y)` indicator of `|y_i| <= lambda`, hence `F(z) = lambda ‖z‖₁`
+    #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+    struct TotalVariation {
+        lambda: f64,
+    }
+
+    impl ProximalMap<Vec<f64>, f64> for TotalVariation {
+        fn prox(&self, v: &Vec<f64>, _step: f64) -> Result<Vec<f64>, Error> {
+            Ok(v.iter()
+                .map(|v| v.clamp(-self.lambda, self.lambda))
+                .collect())
+        }
+
+        fn value(&self, y: &Vec<f64>) -> Result<Option<f64>, Error> {
+            let inside = y.iter().all(|y| y.abs() <= self.lambda * (1.0 + 1e-12));
+            Ok(Some(if inside { 0.0 } else { f64::INFINITY }))
+        }
+
+        fn conjugate_value(&self, z: &Vec<f64>) -> Result<Option<f64>, Error> {
+            Ok(Some(self.lambda * z.iter().map(|z| z.abs()).sum::<f64>()))
+        }
+    }
+
+    type TvSolver = ChambollePock<Denoise, TotalVariation, Vec<f64>, f64>;
+
+    fn tv_solver() -> TvSolver {
+        ChambollePock::new(
+            Denoise {
+                f: vec![0.0, 0.0, 1.0, 1.0],
+            },
+            TotalVariation { lambda: 0.4 },
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        let ChambollePock {
+            tau,
+            sigma,
+            operator_norm,
+            theta,
+            tol,
+            power_iters,
+            y,
+            ..
+        } = tv_solver();
+        assert!(tau.is_none());
+        assert!(sigma.is_none());
+        assert!(operator_norm.is_none());
+        assert_eq!(theta.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(power_iters, 100);
+        assert!(y.is_none());
+    }
+
+    #[test]
+    fn test_builders() {
+        let s = tv_solver()
+            .with_step_sizes(0.1, 0.2)
+            .unwrap()
+            .with_operator_norm(3.0)
+            .unwrap()
+            .with_theta(0.5)
+            .unwrap()
+            .with_tolerance(1e-3)
+            .unwrap()
+            .with_dual(vec![1.0, 2.0, 3.0]);
+        assert_eq!(s.tau, Some(0.1));
+        assert_eq!(s.sigma, Some(0.2));
+        assert_eq!(s.operator_norm, Some(3.0));
+        assert_eq!(s.theta.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(s.tol.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+        assert_eq!(s.dual(), Some(&vec![1.0, 2.0, 3.0]));
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        for (tau, sigma) in [(0.0, 1.0), (1.0, -1.0)] {
+            assert_error!(
+                tv_solver().with_step_sizes(tau, sigma),
+                ArgminError,
+                "Invalid parameter: \"`ChambollePock`: step sizes must be positive.\""
+            );
+        }
+        assert_error!(
+            tv_solver().with_operator_norm(0.0),
+            ArgminError,
+            "Invalid parameter: \"`ChambollePock`: operator norm must be positive.\""
+        );
+        for theta in [-0.1, 1.1] {
+            assert_error!(
+                tv_solver().with_theta(theta),
+                ArgminError,
+                "Invalid parameter: \"`ChambollePock`: theta must be in [0, 1].\""
+            );
+        }
+        assert_error!(
+            tv_solver().with_tolerance(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`ChambollePock`: tol must be non-negative.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut s = tv_solver();
+        let res = s.init(&mut Problem::new(Differences {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`ChambollePock` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init_step_sizes() {
+        let mut s = tv_solver();
+        let (_, kv) = s
+            .init(
+                &mut Problem::new(Differences {}),
+                IterState::new().param(vec![0.0; 4]),
+            )
+            .unwrap();
+        let kv = kv.unwrap();
+        assert_eq!(kv.kv[0].0, "operator_norm");
+        let step = 0.95 / (2.0 + 2.0f64.sqrt()).sqrt();
+        assert_relative_eq!(s.tau.unwrap(), step, epsilon = 1e-6);
+        assert_relative_eq!(s.sigma.unwrap(), step, epsilon = 1e-6);
+        assert_eq!(s.dual(), Some(&vec![0.0; 3]));
+    }
+
+    #[test]
+    fn test_tv_denoising() {
+        let res = Executor::new(Differences {}, tv_solver().with_tolerance(1e-9).unwrap())
+            .configure(|state| state.param(vec![0.0; 4]).max_iters(10000))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        let state = res.state();
+        assert_eq!(
+            state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        let x = state.get_best_param().unwrap();
+        for (x, expected) in x.iter().zip([0.2, 0.2, 0.8, 0.8]) {
+            assert_relative_eq!(*x, expected, epsilon = 1e-7);
+        }
+        // 1/2 ‖x - f‖² + lambda ‖Kx‖₁ = 4 * 0.02 + 0.4 * 0.6
+        assert_relative_eq!(state.get_best_cost(), 0.32, epsilon = 1e-7);
+    }
+}
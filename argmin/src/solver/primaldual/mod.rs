@@ -0,0 +1,29 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Primal-dual methods
+//!
+//! Solvers for convex-concave saddle-point problems
+//!
+//! `min_x max_y <Kx, y> + G(x) - F*(y)`
+//!
+//! where `K` is a linear operator and `G` and `F*` are accessed via their proximal maps
+//! (see [`ProximalMap`]).
+//!
+//! * [Chambolle-Pock primal-dual hybrid gradient method](`ChambollePock`)
+//!
+//! ## Reference
+//!
+//! Antonin Chambolle and Thomas Pock (2011). A first-order primal-dual algorithm for convex
+//! problems with applications to imaging. Journal of Mathematical Imaging and Vision 40,
+//! pp. 120-145.
+
+mod chambollepock;
+mod prox;
+
+pub use self::chambollepock::ChambollePock;
+pub use self::prox::ProximalMap;
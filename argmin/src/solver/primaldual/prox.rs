@@ -0,0 +1,96 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error, SerializeAlias};
+use crate::solver::projectedgradient::{BoxProjection, Projection};
+use argmin_math::ArgminMinMax;
+
+/// Proximal map of a proper, convex and lower semicontinuous function `h`
+///
+/// `prox(v, step)` returns `argmin_z h(z) + 1/(2 step) ‖z - v‖²`.
+///
+/// Optionally, the function value `h(v)` and the value of the convex conjugate `h*(v)` can be
+/// provided. If these are available for both functions of a saddle-point problem, solvers report
+/// the primal-dual gap. Functions which are not finite everywhere (such as indicator functions)
+/// may return infinity.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::Error;
+/// use argmin::solver::primaldual::ProximalMap;
+/// #[cfg(feature = "serde1")]
+/// use serde::{Deserialize, Serialize};
+///
+/// /// `h(x) = lambda ‖x‖₁`
+/// #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+/// struct L1Norm {
+///     lambda: f64,
+/// }
+///
+/// impl ProximalMap<Vec<f64>, f64> for L1Norm {
+///     /// Soft thresholding
+///     fn prox(&self, param: &Vec<f64>, step: f64) -> Result<Vec<f64>, Error> {
+///         let t = self.lambda * step;
+///         Ok(param.iter().map(|v| v.signum() * (v.abs() - t).max(0.0)).collect())
+///     }
+///
+///     fn value(&self, param: &Vec<f64>) -> Result<Option<f64>, Error> {
+///         Ok(Some(self.lambda * param.iter().map(|v| v.abs()).sum::<f64>()))
+///     }
+///
+///     /// The conjugate is the indicator function of the `lambda`-ball of the maximum norm
+///     fn conjugate_value(&self, param: &Vec<f64>) -> Result<Option<f64>, Error> {
+///         let inside = param.iter().all(|v| v.abs() <= self.lambda * (1.0 + 1e-12));
+///         Ok(Some(if inside { 0.0 } else { f64::INFINITY }))
+///     }
+/// }
+/// # let l1 = L1Norm { lambda: 1.0 };
+/// # assert_eq!(l1.prox(&vec![3.0, -0.5], 2.0).unwrap(), vec![1.0, 0.0]);
+/// ```
+pub trait ProximalMap<P, F>: SerializeAlias {
+    /// Evaluate the proximal map of the function with step length `step` at `param`
+    fn prox(&self, param: &P, step: F) -> Result<P, Error>;
+
+    /// Function value at `param`, if available
+    fn value(&self, _param: &P) -> Result<Option<F>, Error> {
+        Ok(None)
+    }
+
+    /// Value of the convex conjugate at `param`, if available
+    fn conjugate_value(&self, _param: &P) -> Result<Option<F>, Error> {
+        Ok(None)
+    }
+}
+
+/// The proximal map of the indicator function of a box is the projection onto the box,
+/// independent of the step length.
+impl<P, F> ProximalMap<P, F> for BoxProjection<P>
+where
+    P: SerializeAlias + ArgminMinMax,
+    F: ArgminFloat,
+{
+    fn prox(&self, param: &P, _step: F) -> Result<P, Error> {
+        self.project(param)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_box_prox() {
+        let b = BoxProjection::new(vec![-1.0f64, 0.0], vec![1.0, 2.0]).unwrap();
+        let p = ProximalMap::<_, f64>::prox(&b, &vec![-2.0, 1.0], 10.0).unwrap();
+        assert_eq!(p, vec![-1.0, 1.0]);
+        assert!(ProximalMap::<_, f64>::value(&b, &p).unwrap().is_none());
+        assert!(ProximalMap::<_, f64>::conjugate_value(&b, &p)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Projected gradient methods
+//!
+//! Gradient based methods for problems constrained to a convex set. The set is given by a
+//! [`Projection`] onto it.
+//!
+//! * [Spectral projected gradient method](`SpectralProjectedGradient`)
+//!
+//! ## Available projections
+//!
+//! * [`BoxProjection`]
+//!
+//! ## Reference
+//!
+//! Ernesto G. Birgin, José Mario Martínez and Marcos Raydan (2000). Nonmonotone spectral
+//! projected gradient methods on convex sets. SIAM Journal on Optimization 10(4), pp. 1196-1211.
+
+mod projection;
+mod spg;
+
+pub use self::projection::{BoxProjection, NoProjection, Projection};
+pub use self::spg::{BBStep, SpectralProjectedGradient};
@@ -0,0 +1,176 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error, SerializeAlias};
+use argmin_math::{ArgminMinMax, ArgminToVec};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Projection onto a closed convex set
+///
+/// Returns the point of the set closest to `param` (in the Euclidean norm).
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::Error;
+/// use argmin::solver::projectedgradient::Projection;
+/// #[cfg(feature = "serde1")]
+/// use serde::{Deserialize, Serialize};
+///
+/// /// Projection onto the nonnegative orthant
+/// #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+/// struct NonNegative {}
+///
+/// impl Projection<Vec<f64>> for NonNegative {
+///     fn project(&self, param: &Vec<f64>) -> Result<Vec<f64>, Error> {
+///         Ok(param.iter().map(|x| x.max(0.0)).collect())
+///     }
+/// }
+/// # assert_eq!(NonNegative {}.project(&vec![-1.0, 2.0]).unwrap(), vec![0.0, 2.0]);
+/// ```
+pub trait Projection<P>: SerializeAlias {
+    /// Project `param` onto the set
+    fn project(&self, param: &P) -> Result<P, Error>;
+}
+
+/// Identity map, i.e. no constraints
+///
+/// Used as default by solvers with optional projection.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NoProjection {}
+
+impl NoProjection {
+    /// Construct a new instance of `NoProjection`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::projectedgradient::NoProjection;
+    /// let projection = NoProjection::new();
+    /// ```
+    pub fn new() -> Self {
+        NoProjection {}
+    }
+}
+
+impl<P> Projection<P> for NoProjection
+where
+    P: Clone,
+{
+    /// Returns a copy of `param`.
+    fn project(&self, param: &P) -> Result<P, Error> {
+        Ok(param.clone())
+    }
+}
+
+/// Projection onto a box `lower <= x <= upper` (elementwise)
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BoxProjection<P> {
+    /// Lower bound
+    lower: P,
+    /// Upper bound
+    upper: P,
+}
+
+impl<P> BoxProjection<P> {
+    /// Construct a new instance of `BoxProjection` from lower and upper bounds.
+    ///
+    /// Returns an error if the bounds differ in length or if a lower bound exceeds the
+    /// corresponding upper bound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::projectedgradient::BoxProjection;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let projection = BoxProjection::new(vec![-1.0f64, 0.0], vec![1.0, 2.0])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new<F>(lower: P, upper: P) -> Result<Self, Error>
+    where
+        F: ArgminFloat,
+        P: ArgminToVec<Vec<F>>,
+    {
+        let (l, u) = (lower.to_vec(), upper.to_vec());
+        if l.len() != u.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BoxProjection`: lower and upper bound must be of the same length."
+            ));
+        }
+        if l.iter().zip(u.iter()).any(|(l, u)| l > u) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BoxProjection`: lower bound must not exceed upper bound."
+            ));
+        }
+        Ok(BoxProjection { lower, upper })
+    }
+}
+
+impl<P> Projection<P> for BoxProjection<P>
+where
+    P: SerializeAlias + ArgminMinMax,
+{
+    /// Clamp `param` elementwise to `[lower, upper]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::projectedgradient::{BoxProjection, Projection};
+    /// let projection = BoxProjection::new(vec![-1.0f64, 0.0], vec![1.0, 2.0]).unwrap();
+    /// let projected = projection.project(&vec![-2.0, 1.0]).unwrap();
+    /// # assert_eq!(projected, vec![-1.0, 1.0]);
+    /// ```
+    fn project(&self, param: &P) -> Result<P, Error> {
+        Ok(P::max(&self.lower, &P::min(param, &self.upper)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use crate::test_trait_impl;
+
+    test_trait_impl!(box_projection, BoxProjection<Vec<f64>>);
+    test_trait_impl!(no_projection, NoProjection);
+
+    #[test]
+    fn test_no_projection() {
+        let projected = NoProjection::new().project(&vec![-2.0f64, 1.0]).unwrap();
+        assert_eq!(projected, vec![-2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_box_projection() {
+        let projection = BoxProjection::new(vec![-1.0f64, 0.0, 2.0], vec![1.0, 2.0, 3.0]).unwrap();
+        let projected = projection.project(&vec![-2.0, 1.0, 4.0]).unwrap();
+        assert_eq!(projected, vec![-1.0, 1.0, 3.0]);
+    }
+
+    #[test]
+    fn test_box_projection_errors() {
+        assert_error!(
+            BoxProjection::new(vec![0.0f64; 2], vec![1.0; 3]),
+            ArgminError,
+            "Invalid parameter: \"`BoxProjection`: lower and upper bound must be of the same length.\""
+        );
+        assert_error!(
+            BoxProjection::new(vec![0.0f64, 2.0], vec![1.0, 1.0]),
+            ArgminError,
+            "Invalid parameter: \"`BoxProjection`: lower bound must not exceed upper bound.\""
+        );
+        // Degenerate boxes are allowed
+        assert!(BoxProjection::new(vec![1.0f64; 2], vec![1.0; 2]).is_ok());
+    }
+}
@@ -0,0 +1,586 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::Projection;
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Gradient, IterState, Problem, SerializeAlias, Solver, State,
+    TerminationReason, KV,
+};
+use crate::solver::linesearch::{NonmonotoneMemory, NonmonotoneStrategy};
+use argmin_math::{ArgminDot, ArgminNorm, ArgminScaledAdd, ArgminScaledSub, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Barzilai-Borwein step lengths
+///
+/// With `s = x_{k+1} - x_k` and `y = \nabla f(x_{k+1}) - \nabla f(x_k)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum BBStep {
+    /// `sᵀs / sᵀy`
+    #[default]
+    BB1,
+    /// `sᵀy / yᵀy`
+    BB2,
+    /// Alternate between `BB1` (even iterations) and `BB2` (odd iterations)
+    Alternating,
+}
+
+/// # Spectral projected gradient method
+///
+/// Minimizes a cost function over a closed convex set given by a [`Projection`]. In each
+/// iteration, a step along the projected gradient direction
+///
+/// `d_k = P(x_k - lambda_k \nabla f(x_k)) - x_k`
+///
+/// is taken, where `P` is the projection and `lambda_k` is a Barzilai-Borwein (spectral) step
+/// length (see [`BBStep`]) safeguarded to `[lambda_min, lambda_max]`. The step along `d_k` is
+/// accepted based on a nonmonotone condition (see [`NonmonotoneStrategy`], defaults to the
+/// maximum of the last 10 cost function values) and reduced by safeguarded quadratic
+/// interpolation otherwise.
+///
+/// The algorithm stops if the norm of the projected gradient `||P(x_k - \nabla f(x_k)) - x_k||`
+/// is below the tolerance set with
+/// [`with_tolerance_pg`](`SpectralProjectedGradient::with_tolerance_pg`) (default:
+/// `sqrt(EPSILON)`).
+///
+/// An initial parameter vector is required, which is projected onto the feasible set before the
+/// cost function and gradient are evaluated.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
+///
+/// ## Reference
+///
+/// Ernesto G. Birgin, José Mario Martínez and Marcos Raydan (2000). Nonmonotone spectral
+/// projected gradient methods on convex sets. SIAM Journal on Optimization 10(4), pp. 1196-1211.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SpectralProjectedGradient<PR, F> {
+    /// Projection onto the feasible set
+    projection: PR,
+    /// Barzilai-Borwein step length
+    step: BBStep,
+    /// Current spectral step length
+    lambda: F,
+    /// Lower bound of spectral step length
+    lambda_min: F,
+    /// Upper bound of spectral step length
+    lambda_max: F,
+    /// Sufficient decrease parameter
+    gamma: F,
+    /// Strategy for nonmonotone acceptance
+    strategy: NonmonotoneStrategy<F>,
+    /// History of cost function values
+    memory: NonmonotoneMemory<F>,
+    /// Tolerance on the norm of the projected gradient
+    tol_pg: F,
+    /// Norm of the current projected gradient
+    pg_norm: F,
+}
+
+impl<PR, F> SpectralProjectedGradient<PR, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`SpectralProjectedGradient`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::projectedgradient::{BoxProjection, SpectralProjectedGradient};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let projection = BoxProjection::new(vec![-1.0f64, -1.0], vec![1.0, 1.0])?;
+    /// let spg: SpectralProjectedGradient<_, f64> = SpectralProjectedGradient::new(projection);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(projection: PR) -> Self {
+        SpectralProjectedGradient {
+            projection,
+            step: BBStep::BB1,
+            lambda: float!(1.0),
+            lambda_min: float!(1e-30),
+            lambda_max: float!(1e30),
+            gamma: float!(1e-4),
+            strategy: NonmonotoneStrategy::Max(10),
+            memory: NonmonotoneMemory::new(),
+            tol_pg: F::epsilon().sqrt(),
+            pg_norm: F::infinity(),
+        }
+    }
+
+    /// Set the Barzilai-Borwein step length. Defaults to [`BBStep::BB1`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::projectedgradient::{BBStep, BoxProjection, SpectralProjectedGradient};
+    /// # let projection = BoxProjection::new(vec![-1.0f64, -1.0], vec![1.0, 1.0]).unwrap();
+    /// let spg: SpectralProjectedGradient<_, f64> =
+    ///     SpectralProjectedGradient::new(projection).with_step(BBStep::Alternating);
+    /// ```
+    #[must_use]
+    pub fn with_step(mut self, step: BBStep) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set the bounds `[lambda_min, lambda_max]` of the spectral step length.
+    ///
+    /// Requires `0 < lambda_min < lambda_max`. Defaults to `[1e-30, 1e30]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::projectedgradient::{BoxProjection, SpectralProjectedGradient};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let projection = BoxProjection::new(vec![-1.0f64, -1.0], vec![1.0, 1.0]).unwrap();
+    /// let spg: SpectralProjectedGradient<_, f64> =
+    ///     SpectralProjectedGradient::new(projection).with_lambda_bounds(1e-10, 1e10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_lambda_bounds(mut self, lambda_min: F, lambda_max: F) -> Result<Self, Error> {
+        if lambda_min <= float!(0.0) || lambda_min >= lambda_max {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SpectralProjectedGradient`: bounds must satisfy 0 < lambda_min < lambda_max."
+            ));
+        }
+        self.lambda_min = lambda_min;
+        self.lambda_max = lambda_max;
+        Ok(self)
+    }
+
+    /// Set the sufficient decrease parameter `gamma` of the nonmonotone acceptance condition
+    ///
+    /// Must be in `(0, 1)` and defaults to `1e-4`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::projectedgradient::{BoxProjection, SpectralProjectedGradient};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let projection = BoxProjection::new(vec![-1.0f64, -1.0], vec![1.0, 1.0]).unwrap();
+    /// let spg: SpectralProjectedGradient<_, f64> =
+    ///     SpectralProjectedGradient::new(projection).with_gamma(1e-3)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_gamma(mut self, gamma: F) -> Result<Self, Error> {
+        if gamma <= float!(0.0) || gamma >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SpectralProjectedGradient`: gamma must be in (0, 1)."
+            ));
+        }
+        self.gamma = gamma;
+        Ok(self)
+    }
+
+    /// Set the strategy of the nonmonotone acceptance condition.
+    ///
+    /// Defaults to `NonmonotoneStrategy::Max(10)`. `NonmonotoneStrategy::Max(1)` leads to a
+    /// monotone method.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::projectedgradient::{BoxProjection, SpectralProjectedGradient};
+    /// # use argmin::solver::linesearch::NonmonotoneStrategy;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let projection = BoxProjection::new(vec![-1.0f64, -1.0], vec![1.0, 1.0]).unwrap();
+    /// let spg: SpectralProjectedGradient<_, f64> = SpectralProjectedGradient::new(projection)
+    ///     .with_nonmonotone_strategy(NonmonotoneStrategy::Average(0.85))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_nonmonotone_strategy(
+        mut self,
+        strategy: NonmonotoneStrategy<F>,
+    ) -> Result<Self, Error> {
+        match strategy {
+            NonmonotoneStrategy::Max(m) if m < 1 => Err(argmin_error!(
+                InvalidParameter,
+                "`SpectralProjectedGradient`: memory must be >= 1."
+            )),
+            NonmonotoneStrategy::Average(eta) if !(float!(0.0)..=float!(1.0)).contains(&eta) => {
+                Err(argmin_error!(
+                    InvalidParameter,
+                    "`SpectralProjectedGradient`: eta must be in [0, 1]."
+                ))
+            }
+            _ => {
+                self.strategy = strategy;
+                Ok(self)
+            }
+        }
+    }
+
+    /// The algorithm stops if the norm of the projected gradient is below `tol_pg`.
+    ///
+    /// The provided value must be non-negative. Defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::projectedgradient::{BoxProjection, SpectralProjectedGradient};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let projection = BoxProjection::new(vec![-1.0f64, -1.0], vec![1.0, 1.0]).unwrap();
+    /// let spg: SpectralProjectedGradient<_, f64> =
+    ///     SpectralProjectedGradient::new(projection).with_tolerance_pg(1e-8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_pg(mut self, tol_pg: F) -> Result<Self, Error> {
+        if tol_pg < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SpectralProjectedGradient`: projected gradient tolerance must be >= 0."
+            ));
+        }
+        self.tol_pg = tol_pg;
+        Ok(self)
+    }
+
+    /// Safeguard the spectral step length
+    fn safeguard(&self, lambda: F) -> F {
+        if lambda.is_nan() {
+            self.lambda_max
+        } else {
+            lambda.max(self.lambda_min).min(self.lambda_max)
+        }
+    }
+}
+
+impl<O, PR, P, G, F> Solver<O, IterState<P, G, (), (), F>> for SpectralProjectedGradient<PR, F>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
+    PR: Projection<P>,
+    P: Clone
+        + SerializeAlias
+        + ArgminSub<P, P>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<G, F, P>
+        + ArgminDot<P, F>
+        + ArgminDot<G, F>
+        + ArgminNorm<F>,
+    G: Clone + SerializeAlias + ArgminSub<G, G> + ArgminDot<P, F> + ArgminDot<G, F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Spectral projected gradient";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`SpectralProjectedGradient` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let param = self.projection.project(&param)?;
+        let cost = problem.cost(&param)?;
+        let grad = problem.gradient(&param)?;
+
+        let pg: P = self
+            .projection
+            .project(&param.scaled_sub(&float!(1.0), &grad))?
+            .sub(&param);
+        self.pg_norm = pg.norm();
+        self.lambda = self.safeguard(float!(1.0) / self.pg_norm);
+        self.memory = NonmonotoneMemory::new();
+
+        Ok((
+            state.param(param).cost(cost).grad(grad),
+            Some(make_kv!["pg_norm" => self.pg_norm;]),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`SpectralProjectedGradient`: Parameter vector in state not set."
+        ))?;
+        let grad = state.take_grad().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`SpectralProjectedGradient`: Gradient in state not set."
+        ))?;
+        let cost = state.get_cost();
+
+        let reference_cost = self.memory.push(&self.strategy, cost);
+
+        // Spectral projected gradient direction
+        let direction: P = self
+            .projection
+            .project(&param.scaled_sub(&self.lambda, &grad))?
+            .sub(&param);
+        let slope: F = grad.dot(&direction);
+
+        // Nonmonotone backtracking with safeguarded quadratic interpolation
+        let mut alpha = float!(1.0);
+        let (new_param, new_cost) = loop {
+            let new_param = param.scaled_add(&alpha, &direction);
+            let new_cost = problem.cost(&new_param)?;
+            if new_cost <= reference_cost + self.gamma * alpha * slope || alpha < F::epsilon() {
+                break (new_param, new_cost);
+            }
+            let alpha_tmp =
+                float!(-0.5) * alpha.powi(2) * slope / (new_cost - cost - alpha * slope);
+            alpha = if alpha_tmp >= float!(0.1) && alpha_tmp <= float!(0.9) * alpha {
+                alpha_tmp
+            } else {
+                alpha / float!(2.0)
+            };
+        };
+
+        let new_grad = problem.gradient(&new_param)?;
+
+        // Spectral step length
+        let s = new_param.sub(&param);
+        let y = new_grad.sub(&grad);
+        let sy: F = s.dot(&y);
+        self.lambda = if sy <= float!(0.0) {
+            self.lambda_max
+        } else {
+            let bb1 = match self.step {
+                BBStep::BB1 => true,
+                BBStep::BB2 => false,
+                BBStep::Alternating => state.get_iter().is_multiple_of(2),
+            };
+            if bb1 {
+                let ss: F = s.dot(&s);
+                self.safeguard(ss / sy)
+            } else {
+                let yy: F = y.dot(&y);
+                self.safeguard(sy / yy)
+            }
+        };
+
+        let pg: P = self
+            .projection
+            .project(&new_param.scaled_sub(&float!(1.0), &new_grad))?
+            .sub(&new_param);
+        self.pg_norm = pg.norm();
+
+        Ok((
+            state.param(new_param).cost(new_cost).grad(new_grad),
+            Some(make_kv![
+                "alpha" => alpha;
+                "lambda" => self.lambda;
+                "pg_norm" => self.pg_norm;
+            ]),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, G, (), (), F>) -> TerminationReason {
+        if self.pg_norm < self.tol_pg {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor};
+    use crate::solver::projectedgradient::BoxProjection;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+    use argmin_testfunctions::{rosenbrock_2d, rosenbrock_2d_derivative};
+
+    test_trait_impl!(spg, SpectralProjectedGradient<BoxProjection<Vec<f64>>, f64>);
+
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(rosenbrock_2d(p, 1.0, 100.0))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(rosenbrock_2d_derivative(p, 1.0, 100.0))
+        }
+    }
+
+    fn unit_box() -> BoxProjection<Vec<f64>> {
+        BoxProjection::new(vec![-1.0, -1.0], vec![1.0, 1.0]).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        let spg: SpectralProjectedGradient<_, f64> = SpectralProjectedGradient::new(unit_box());
+        assert_eq!(spg.projection, unit_box());
+        assert_eq!(spg.step, BBStep::BB1);
+        assert_eq!(spg.lambda_min.to_ne_bytes(), 1e-30f64.to_ne_bytes());
+        assert_eq!(spg.lambda_max.to_ne_bytes(), 1e30f64.to_ne_bytes());
+        assert_eq!(spg.gamma.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(spg.strategy, NonmonotoneStrategy::Max(10));
+        assert_eq!(spg.tol_pg.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(spg.pg_norm.is_infinite());
+    }
+
+    #[test]
+    fn test_builders() {
+        let spg: SpectralProjectedGradient<_, f64> = SpectralProjectedGradient::new(unit_box())
+            .with_step(BBStep::BB2)
+            .with_lambda_bounds(1e-5, 1e5)
+            .unwrap()
+            .with_gamma(0.5)
+            .unwrap()
+            .with_nonmonotone_strategy(NonmonotoneStrategy::Average(0.5))
+            .unwrap()
+            .with_tolerance_pg(1e-3)
+            .unwrap();
+        assert_eq!(spg.step, BBStep::BB2);
+        assert_eq!(spg.lambda_min.to_ne_bytes(), 1e-5f64.to_ne_bytes());
+        assert_eq!(spg.lambda_max.to_ne_bytes(), 1e5f64.to_ne_bytes());
+        assert_eq!(spg.gamma.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(spg.strategy, NonmonotoneStrategy::Average(0.5));
+        assert_eq!(spg.tol_pg.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+
+        let spg =
+            || -> SpectralProjectedGradient<_, f64> { SpectralProjectedGradient::new(unit_box()) };
+
+        for (min, max) in [(0.0, 1.0), (-1.0, 1.0), (1.0, 1.0), (2.0, 1.0)] {
+            assert_error!(
+                spg().with_lambda_bounds(min, max),
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`SpectralProjectedGradient`: ",
+                    "bounds must satisfy 0 < lambda_min < lambda_max.\""
+                )
+            );
+        }
+        for gamma in [0.0, 1.0, -1.0] {
+            assert_error!(
+                spg().with_gamma(gamma),
+                ArgminError,
+                "Invalid parameter: \"`SpectralProjectedGradient`: gamma must be in (0, 1).\""
+            );
+        }
+        assert_error!(
+            spg().with_nonmonotone_strategy(NonmonotoneStrategy::Max(0)),
+            ArgminError,
+            "Invalid parameter: \"`SpectralProjectedGradient`: memory must be >= 1.\""
+        );
+        assert_error!(
+            spg().with_nonmonotone_strategy(NonmonotoneStrategy::Average(1.5)),
+            ArgminError,
+            "Invalid parameter: \"`SpectralProjectedGradient`: eta must be in [0, 1].\""
+        );
+        assert_error!(
+            spg().with_tolerance_pg(-1.0),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`SpectralProjectedGradient`: ",
+                "projected gradient tolerance must be >= 0.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut spg: SpectralProjectedGradient<_, f64> = SpectralProjectedGradient::new(unit_box());
+        let res = spg.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`SpectralProjectedGradient` requires an initial parameter ",
+                "vector. Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init_projects_param() {
+        let mut spg: SpectralProjectedGradient<_, f64> = SpectralProjectedGradient::new(unit_box());
+        let (state, kv) = spg
+            .init(
+                &mut Problem::new(Rosenbrock {}),
+                IterState::new().param(vec![-3.0, 0.5]),
+            )
+            .unwrap();
+        assert!(kv.is_some());
+        assert_eq!(state.param.as_ref().unwrap(), &vec![-1.0, 0.5]);
+        assert_relative_eq!(
+            state.get_cost(),
+            rosenbrock_2d(&[-1.0, 0.5], 1.0, 100.0),
+            epsilon = f64::EPSILON
+        );
+        assert!(spg.pg_norm.is_finite());
+    }
+
+    #[test]
+    fn test_unconstrained_minimum_inside_box() {
+        for step in [BBStep::BB1, BBStep::BB2, BBStep::Alternating] {
+            let spg = SpectralProjectedGradient::new(
+                BoxProjection::new(vec![-2.0, -2.0], vec![2.0, 2.0]).unwrap(),
+            )
+            .with_step(step)
+            .with_tolerance_pg(1e-8)
+            .unwrap();
+            let res = Executor::new(Rosenbrock {}, spg)
+                .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(10000))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                TerminationReason::TargetPrecisionReached
+            );
+            let param = res.state.get_best_param().unwrap();
+            assert_relative_eq!(param[0], 1.0, epsilon = 1e-6);
+            assert_relative_eq!(param[1], 1.0, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_active_bound() {
+        // Minimum on the box [-1, 0.5] x [-1, 0.5] is at (0.5, 0.25)
+        let spg = SpectralProjectedGradient::new(
+            BoxProjection::new(vec![-1.0, -1.0], vec![0.5, 0.5]).unwrap(),
+        )
+        .with_tolerance_pg(1e-10)
+        .unwrap();
+        let res = Executor::new(Rosenbrock {}, spg)
+            .configure(|state| state.param(vec![-1.0, 1.0]).max_iters(10000))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 0.5, epsilon = 1e-8);
+        assert_relative_eq!(param[1], 0.25, epsilon = 1e-8);
+    }
+}
@@ -0,0 +1,792 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::dot;
+use crate::core::{
+    ArgminFloat, Error, IterState, Problem, QuadraticProgram, Solver, TerminationReason, KV,
+};
+use argmin_math::{ArgminCholeskySolve, ArgminQrSolve};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Data of a quadratic program as returned by the [`QuadraticProgram`] trait
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct QpData<F> {
+    h: Vec<Vec<F>>,
+    c: Vec<F>,
+    a_eq: Vec<Vec<F>>,
+    b_eq: Vec<F>,
+    a_ineq: Vec<Vec<F>>,
+    b_ineq: Vec<F>,
+}
+
+impl<F: ArgminFloat> QpData<F> {
+    /// Fetch the data from the problem and check the dimensions
+    fn from_problem<O: QuadraticProgram<Float = F>>(qp: &O) -> Result<Self, Error> {
+        let data = QpData {
+            h: qp.H()?,
+            c: qp.c()?,
+            a_eq: qp.A_eq()?,
+            b_eq: qp.b_eq()?,
+            a_ineq: qp.A_ineq()?,
+            b_ineq: qp.b_ineq()?,
+        };
+        let n = data.c.len();
+        if n == 0 || data.h.len() != n || data.h.iter().any(|row| row.len() != n) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GoldfarbIdnani`: `H` must be a square matrix matching the length of `c`."
+            ));
+        }
+        if data.a_eq.len() != data.b_eq.len() || data.a_eq.iter().any(|row| row.len() != n) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GoldfarbIdnani`: dimensions of `A_eq` and `b_eq` do not match."
+            ));
+        }
+        if data.a_ineq.len() != data.b_ineq.len() || data.a_ineq.iter().any(|row| row.len() != n) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GoldfarbIdnani`: dimensions of `A_ineq` and `b_ineq` do not match."
+            ));
+        }
+        Ok(data)
+    }
+
+    /// `1/2 xᵀHx + cᵀx`
+    fn cost(&self, x: &[F]) -> F {
+        let xhx = self
+            .h
+            .iter()
+            .zip(x.iter())
+            .fold(float!(0.0), |acc, (row, xi)| acc + *xi * dot(row, x));
+        float!(0.5) * xhx + dot(&self.c, x)
+    }
+
+    /// Cost function value if `x` is feasible (up to `tol`) and infinity otherwise
+    fn feasible_cost(&self, x: &[F], tol: F) -> F {
+        if self.max_violation(x) <= tol {
+            self.cost(x)
+        } else {
+            F::infinity()
+        }
+    }
+
+    /// Violation `a_iᵀx - b_i` of the `i`th inequality constraint
+    fn violation(&self, x: &[F], i: usize) -> F {
+        dot(&self.a_ineq[i], x) - self.b_ineq[i]
+    }
+
+    /// Largest violation of all inequality constraints (zero if all are satisfied)
+    fn max_violation(&self, x: &[F]) -> F {
+        (0..self.b_ineq.len()).fold(float!(0.0), |acc, i| acc.max(self.violation(x, i)))
+    }
+
+    /// Right hand side of the constraints which are treated as equalities
+    fn rhs(&self, active: &[usize]) -> Vec<F> {
+        self.b_eq
+            .iter()
+            .chain(active.iter().map(|&i| &self.b_ineq[i]))
+            .cloned()
+            .collect()
+    }
+
+    /// Solves the KKT system
+    ///
+    /// `[H Nᵀ; N 0] [x; u] = [rhs_x; rhs_n]`
+    ///
+    /// where the rows of `N` are the normals of the equality constraints followed by those of the
+    /// active inequality constraints.
+    fn kkt_solve(
+        &self,
+        active: &[usize],
+        rhs_x: &[F],
+        rhs_n: &[F],
+    ) -> Result<(Vec<F>, Vec<F>), Error>
+    where
+        Vec<Vec<F>>: ArgminQrSolve<Vec<F>, Vec<F>>,
+    {
+        let n = self.c.len();
+        let normals: Vec<&Vec<F>> = self
+            .a_eq
+            .iter()
+            .chain(active.iter().map(|&i| &self.a_ineq[i]))
+            .collect();
+        let m = normals.len();
+
+        let mut kkt = vec![vec![float!(0.0); n + m]; n + m];
+        for (row, h_row) in kkt.iter_mut().zip(self.h.iter()) {
+            row[..n].copy_from_slice(h_row);
+        }
+        for (k, a) in normals.iter().enumerate() {
+            for (j, aj) in a.iter().enumerate() {
+                kkt[j][n + k] = *aj;
+                kkt[n + k][j] = *aj;
+            }
+        }
+        let rhs: Vec<F> = rhs_x.iter().chain(rhs_n.iter()).cloned().collect();
+
+        let mut x = kkt.qr_solve(&rhs)?;
+        let u = x.split_off(n);
+        Ok((x, u))
+    }
+}
+
+/// # Goldfarb-Idnani dual active set method
+///
+/// Solves strictly convex quadratic programs
+///
+/// `min_x 1/2 xᵀHx + cᵀx` subject to `A_eq x = b_eq` and `A_ineq x <= b_ineq`
+///
+/// with a symmetric positive definite matrix `H` (see [`QuadraticProgram`]).
+///
+/// The method starts from the minimizer of the cost function subject to the equality
+/// constraints only, which is optimal but not necessarily feasible. In each iteration, the most
+/// violated inequality constraint is selected and a step along the primal and dual directions is
+/// taken until either this constraint becomes active or the Lagrange multiplier of another
+/// active constraint drops to zero, in which case that constraint is removed from the active
+/// set. The algorithm terminates once all inequality constraints are satisfied up to the
+/// tolerance set with [`with_tolerance`](`GoldfarbIdnani::with_tolerance`) (default:
+/// `sqrt(EPSILON)`). An error is returned if the problem is infeasible.
+///
+/// The solver can be warm started from the active set of a previous run (for instance of a
+/// closely related problem) via [`with_active_set`](`GoldfarbIdnani::with_active_set`). The
+/// constraints of the initial active set are treated as equalities, constraints with negative
+/// Lagrange multipliers are then removed one by one. If the given constraints are linearly
+/// dependent, the solver falls back to a cold start.
+///
+/// Iterates which violate inequality constraints are assigned an infinite cost function value,
+/// hence the best parameter vector is the final, feasible one. No initial parameter vector is
+/// required. After the run, the active set and the Lagrange
+/// multipliers are available from the solver stored in the
+/// [`OptimizationResult`](`crate::core::OptimizationResult`). The multipliers `lambda_eq` and
+/// `lambda_ineq >= 0` satisfy
+///
+/// `Hx + c + A_eqᵀ lambda_eq + A_ineqᵀ lambda_ineq = 0`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`QuadraticProgram`].
+///
+/// ## Reference
+///
+/// D. Goldfarb and A. Idnani (1983). A numerically stable dual method for solving strictly convex
+/// quadratic programs. Mathematical Programming 27, pp. 1-33.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct GoldfarbIdnani<F> {
+    /// Tolerance on the violation of inequality constraints
+    tol: F,
+    /// Inequality constraints initially treated as active
+    initial_active_set: Vec<usize>,
+    /// Data of the quadratic program
+    data: QpData<F>,
+    /// Indices of the active inequality constraints
+    active_set: Vec<usize>,
+    /// Lagrange multipliers of the equality constraints
+    multipliers_eq: Vec<F>,
+    /// Lagrange multipliers of the active inequality constraints
+    multipliers_active: Vec<F>,
+    /// Violated constraint which is currently added and its Lagrange multiplier
+    violated: Option<(usize, F)>,
+    /// Largest violation of the inequality constraints
+    max_violation: F,
+}
+
+impl<F> GoldfarbIdnani<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`GoldfarbIdnani`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quadraticprogramming::GoldfarbIdnani;
+    /// let solver: GoldfarbIdnani<f64> = GoldfarbIdnani::new();
+    /// ```
+    pub fn new() -> Self {
+        GoldfarbIdnani {
+            tol: F::epsilon().sqrt(),
+            initial_active_set: vec![],
+            data: QpData {
+                h: vec![],
+                c: vec![],
+                a_eq: vec![],
+                b_eq: vec![],
+                a_ineq: vec![],
+                b_ineq: vec![],
+            },
+            active_set: vec![],
+            multipliers_eq: vec![],
+            multipliers_active: vec![],
+            violated: None,
+            max_violation: F::infinity(),
+        }
+    }
+
+    /// Set the tolerance on the violation of the inequality constraints.
+    ///
+    /// Must be non-negative and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quadraticprogramming::GoldfarbIdnani;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: GoldfarbIdnani<f64> = GoldfarbIdnani::new().with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GoldfarbIdnani`: tolerance must be >= 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Warm start from the given active set, i.e. indices of inequality constraints.
+    ///
+    /// Typically, this is the [`active_set`](`GoldfarbIdnani::active_set`) of a previous run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quadraticprogramming::GoldfarbIdnani;
+    /// let solver: GoldfarbIdnani<f64> = GoldfarbIdnani::new().with_active_set(vec![0, 3]);
+    /// ```
+    #[must_use]
+    pub fn with_active_set(mut self, active_set: Vec<usize>) -> Self {
+        self.initial_active_set = active_set;
+        self
+    }
+
+    /// Indices of the active inequality constraints
+    pub fn active_set(&self) -> &[usize] {
+        &self.active_set
+    }
+
+    /// Lagrange multipliers of the equality constraints
+    pub fn lagrange_multipliers_eq(&self) -> &[F] {
+        &self.multipliers_eq
+    }
+
+    /// Lagrange multipliers of the inequality constraints (zero for inactive constraints)
+    pub fn lagrange_multipliers_ineq(&self) -> Vec<F> {
+        let mut multipliers = vec![float!(0.0); self.data.b_ineq.len()];
+        for (i, u) in self.active_set.iter().zip(self.multipliers_active.iter()) {
+            multipliers[*i] = *u;
+        }
+        multipliers
+    }
+}
+
+impl<F> Default for GoldfarbIdnani<F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        GoldfarbIdnani::new()
+    }
+}
+
+impl<O, F> Solver<O, IterState<Vec<F>, (), (), (), F>> for GoldfarbIdnani<F>
+where
+    O: QuadraticProgram<Param = Vec<F>, Float = F>,
+    F: ArgminFloat,
+    Vec<Vec<F>>: ArgminQrSolve<Vec<F>, Vec<F>> + ArgminCholeskySolve<Vec<F>, Vec<F>>,
+{
+    const NAME: &'static str = "Goldfarb-Idnani";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<Vec<F>, (), (), (), F>,
+    ) -> Result<(IterState<Vec<F>, (), (), (), F>, Option<KV>), Error> {
+        let data = problem.problem("qp_count", |qp| QpData::from_problem(qp))?;
+
+        if self
+            .initial_active_set
+            .iter()
+            .any(|&i| i >= data.b_ineq.len())
+        {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GoldfarbIdnani`: initial active set contains an invalid constraint index."
+            ));
+        }
+        data.h.cholesky_solve(&data.c).map_err(|_| -> Error {
+            argmin_error!(
+                InvalidParameter,
+                "`GoldfarbIdnani`: `H` must be positive definite."
+            )
+        })?;
+
+        let m_eq = data.b_eq.len();
+        let neg_c: Vec<F> = data.c.iter().map(|c| -*c).collect();
+        let mut active = self.initial_active_set.clone();
+        active.sort_unstable();
+        active.dedup();
+
+        // Minimizer subject to equality constraints and the constraints of the initial active
+        // set, dropping constraints with negative Lagrange multipliers.
+        let (param, mut multipliers) = loop {
+            match data.kkt_solve(&active, &neg_c, &data.rhs(&active)) {
+                Ok((param, multipliers)) => {
+                    let most_negative = multipliers[m_eq..]
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, u)| **u < float!(0.0))
+                        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+                        .map(|(k, _)| k);
+                    match most_negative {
+                        Some(k) => {
+                            active.remove(k);
+                        }
+                        None => break (param, multipliers),
+                    }
+                }
+                Err(_) if !active.is_empty() => active.clear(),
+                Err(_) => {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        "`GoldfarbIdnani`: equality constraints are linearly dependent."
+                    ))
+                }
+            }
+        };
+
+        self.multipliers_active = multipliers.split_off(m_eq);
+        self.multipliers_eq = multipliers;
+        self.active_set = active;
+        self.violated = None;
+        self.max_violation = data.max_violation(&param);
+        let cost = data.feasible_cost(&param, self.tol);
+        self.data = data;
+
+        Ok((
+            state.param(param).cost(cost),
+            Some(make_kv![
+                "active_constraints" => self.active_set.len();
+                "max_violation" => self.max_violation;
+            ]),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        _problem: &mut Problem<O>,
+        mut state: IterState<Vec<F>, (), (), (), F>,
+    ) -> Result<(IterState<Vec<F>, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`GoldfarbIdnani`: Parameter vector in state not set."
+        ))?;
+        let data = &self.data;
+        let m_eq = data.b_eq.len();
+
+        // Constraint to be added: continue with the current one or pick the most violated one
+        let (p, u_p) = match self.violated {
+            Some(violated) => violated,
+            None => {
+                let most_violated = (0..data.b_ineq.len())
+                    .map(|i| (i, data.violation(&param, i)))
+                    .filter(|(_, s)| *s > self.tol)
+                    .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                match most_violated {
+                    Some((p, _)) => (p, float!(0.0)),
+                    None => return Ok((state.param(param), None)),
+                }
+            }
+        };
+        let a_p = &data.a_ineq[p];
+
+        // Primal direction `z` and dual direction `r = -w`
+        let zeros = vec![float!(0.0); m_eq + self.active_set.len()];
+        let (z, w) = data.kkt_solve(&self.active_set, a_p, &zeros)?;
+
+        let h_max = data
+            .h
+            .iter()
+            .flatten()
+            .fold(float!(0.0), |acc: F, h| acc.max(h.abs()));
+        let dependent = dot(&z, &z).sqrt() <= F::epsilon().sqrt() * dot(a_p, a_p).sqrt() / h_max;
+
+        // Full step, after which constraint `p` is satisfied
+        let t_full = if dependent {
+            F::infinity()
+        } else {
+            data.violation(&param, p) / dot(a_p, &z)
+        };
+        // Partial step, after which the multiplier of an active constraint becomes zero
+        let (drop, t_partial) = self
+            .multipliers_active
+            .iter()
+            .zip(w[m_eq..].iter())
+            .enumerate()
+            .filter(|(_, (_, w))| **w > float!(0.0))
+            .map(|(k, (u, w))| (Some(k), *u / *w))
+            .fold(
+                (None, F::infinity()),
+                |acc, x| if x.1 < acc.1 { x } else { acc },
+            );
+
+        if t_full.is_infinite() && t_partial.is_infinite() {
+            return Err(argmin_error!(
+                ConditionViolated,
+                "`GoldfarbIdnani`: quadratic program is infeasible."
+            ));
+        }
+        let t = t_full.min(t_partial);
+
+        let new_param: Vec<F> = if dependent {
+            param
+        } else {
+            param
+                .iter()
+                .zip(z.iter())
+                .map(|(x, z)| *x - t * *z)
+                .collect()
+        };
+        for (u, w) in self
+            .multipliers_eq
+            .iter_mut()
+            .chain(self.multipliers_active.iter_mut())
+            .zip(w.iter())
+        {
+            *u = *u - t * *w;
+        }
+        let u_p = u_p + t;
+
+        match drop {
+            Some(k) if t_partial < t_full => {
+                self.active_set.remove(k);
+                self.multipliers_active.remove(k);
+                self.violated = Some((p, u_p));
+            }
+            _ => {
+                self.active_set.push(p);
+                self.multipliers_active.push(u_p);
+                self.violated = None;
+            }
+        }
+
+        self.max_violation = data.max_violation(&new_param);
+        let cost = data.feasible_cost(&new_param, self.tol);
+
+        Ok((
+            state.param(new_param).cost(cost),
+            Some(make_kv![
+                "step" => t;
+                "active_constraints" => self.active_set.len();
+                "max_violation" => self.max_violation;
+            ]),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<Vec<F>, (), (), (), F>) -> TerminationReason {
+        if self.violated.is_none() && self.max_violation <= self.tol {
+            return TerminationReason::TargetToleranceReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(goldfarb_idnani, GoldfarbIdnani<f64>);
+
+    #[derive(Clone, Default)]
+    struct Qp {
+        h: Vec<Vec<f64>>,
+        c: Vec<f64>,
+        a_eq: Vec<Vec<f64>>,
+        b_eq: Vec<f64>,
+        a_ineq: Vec<Vec<f64>>,
+        b_ineq: Vec<f64>,
+    }
+
+    impl QuadraticProgram for Qp {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn H(&self) -> Result<Vec<Vec<f64>>, Error> {
+            Ok(self.h.clone())
+        }
+
+        fn c(&self) -> Result<Vec<f64>, Error> {
+            Ok(self.c.clone())
+        }
+
+        fn A_eq(&self) -> Result<Vec<Vec<f64>>, Error> {
+            Ok(self.a_eq.clone())
+        }
+
+        fn b_eq(&self) -> Result<Vec<f64>, Error> {
+            Ok(self.b_eq.clone())
+        }
+
+        fn A_ineq(&self) -> Result<Vec<Vec<f64>>, Error> {
+            Ok(self.a_ineq.clone())
+        }
+
+        fn b_ineq(&self) -> Result<Vec<f64>, Error> {
+            Ok(self.b_ineq.clone())
+        }
+    }
+
+    /// Example 16.4 of Nocedal & Wright: min (x1 - 1)^2 + (x2 - 2.5)^2 subject to five linear
+    /// inequality constraints, solution (1.4, 1.7) with constraint 0 active.
+    fn nocedal_wright() -> Qp {
+        Qp {
+            h: vec![vec![2.0, 0.0], vec![0.0, 2.0]],
+            c: vec![-2.0, -5.0],
+            a_ineq: vec![
+                vec![-1.0, 2.0],
+                vec![1.0, 2.0],
+                vec![1.0, -2.0],
+                vec![-1.0, 0.0],
+                vec![0.0, -1.0],
+            ],
+            b_ineq: vec![2.0, 6.0, 2.0, 0.0, 0.0],
+            ..Qp::default()
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let solver: GoldfarbIdnani<f64> = GoldfarbIdnani::new();
+        assert_eq!(solver.tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(solver.initial_active_set.is_empty());
+        assert!(solver.violated.is_none());
+    }
+
+    #[test]
+    fn test_inequality_constraints() {
+        let res = Executor::new(nocedal_wright(), GoldfarbIdnani::new())
+            .configure(|state| state.max_iters(20))
+            .run()
+            .unwrap();
+
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.4, epsilon = 1e-10);
+        assert_relative_eq!(param[1], 1.7, epsilon = 1e-10);
+        assert_eq!(res.solver.active_set(), &[0]);
+        let multipliers = res.solver.lagrange_multipliers_ineq();
+        assert_relative_eq!(multipliers[0], 0.8, epsilon = 1e-10);
+        for u in &multipliers[1..] {
+            assert_eq!(u.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        }
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetToleranceReached
+        );
+    }
+
+    #[test]
+    fn test_equality_and_inequality_constraints() {
+        // min x^2 + y^2 + z^2 subject to x + y + z = 3 and x <= 0.5
+        let qp = Qp {
+            h: vec![
+                vec![2.0, 0.0, 0.0],
+                vec![0.0, 2.0, 0.0],
+                vec![0.0, 0.0, 2.0],
+            ],
+            c: vec![0.0, 0.0, 0.0],
+            a_eq: vec![vec![1.0, 1.0, 1.0]],
+            b_eq: vec![3.0],
+            a_ineq: vec![vec![1.0, 0.0, 0.0]],
+            b_ineq: vec![0.5],
+        };
+        let res = Executor::new(qp, GoldfarbIdnani::new())
+            .configure(|state| state.max_iters(20))
+            .run()
+            .unwrap();
+
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 0.5, epsilon = 1e-10);
+        assert_relative_eq!(param[1], 1.25, epsilon = 1e-10);
+        assert_relative_eq!(param[2], 1.25, epsilon = 1e-10);
+        assert_relative_eq!(
+            res.solver.lagrange_multipliers_eq()[0],
+            -2.5,
+            epsilon = 1e-10
+        );
+        assert_relative_eq!(
+            res.solver.lagrange_multipliers_ineq()[0],
+            1.5,
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    fn test_warm_start() {
+        let res = Executor::new(nocedal_wright(), GoldfarbIdnani::new())
+            .configure(|state| state.max_iters(20))
+            .run()
+            .unwrap();
+        let active_set = res.solver.active_set().to_vec();
+
+        // Optimal active set: no iterations required
+        let res = Executor::new(
+            nocedal_wright(),
+            GoldfarbIdnani::new().with_active_set(active_set),
+        )
+        .configure(|state| state.max_iters(20))
+        .run()
+        .unwrap();
+        assert_eq!(res.state.get_iter(), 0);
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.4, epsilon = 1e-10);
+        assert_relative_eq!(param[1], 1.7, epsilon = 1e-10);
+
+        // Wrong active set: constraints with negative multipliers are dropped
+        let res = Executor::new(
+            nocedal_wright(),
+            GoldfarbIdnani::new().with_active_set(vec![3, 4]),
+        )
+        .configure(|state| state.max_iters(20))
+        .run()
+        .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.4, epsilon = 1e-10);
+        assert_relative_eq!(param[1], 1.7, epsilon = 1e-10);
+        assert_eq!(res.solver.active_set(), &[0]);
+    }
+
+    #[test]
+    fn test_dependent_constraints() {
+        // x + y >= 2 given three times, x - y <= 0 and y <= 1
+        let qp = Qp {
+            h: vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+            c: vec![0.0, 0.0],
+            a_ineq: vec![
+                vec![-1.0, -1.0],
+                vec![-2.0, -2.0],
+                vec![-1.0, -1.0],
+                vec![1.0, -1.0],
+                vec![0.0, 1.0],
+            ],
+            b_ineq: vec![-2.0, -4.0, -2.0, 0.0, 1.0],
+            ..Qp::default()
+        };
+        let res = Executor::new(qp, GoldfarbIdnani::new())
+            .configure(|state| state.max_iters(20))
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-10);
+        assert_relative_eq!(param[1], 1.0, epsilon = 1e-10);
+
+        // Stationarity of the Lagrangian
+        let u = res.solver.lagrange_multipliers_ineq();
+        assert!(u.iter().all(|u| *u >= 0.0));
+        let grad_x = param[0] - u[0] - 2.0 * u[1] - u[2] + u[3];
+        let grad_y = param[1] - u[0] - 2.0 * u[1] - u[2] - u[3] + u[4];
+        assert_relative_eq!(grad_x, 0.0, epsilon = 1e-10);
+        assert_relative_eq!(grad_y, 0.0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_infeasible() {
+        // x <= 0 and x >= 1
+        let qp = Qp {
+            h: vec![vec![1.0]],
+            c: vec![0.0],
+            a_ineq: vec![vec![1.0], vec![-1.0]],
+            b_ineq: vec![0.0, -1.0],
+            ..Qp::default()
+        };
+        let res = Executor::new(qp, GoldfarbIdnani::new())
+            .configure(|state| state.max_iters(20))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            "Condition violated: \"`GoldfarbIdnani`: quadratic program is infeasible.\""
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_error!(
+            GoldfarbIdnani::new().with_tolerance(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`GoldfarbIdnani`: tolerance must be >= 0.\""
+        );
+
+        let run = |qp: Qp, solver: GoldfarbIdnani<f64>| Executor::new(qp, solver).run();
+
+        let mut qp = nocedal_wright();
+        qp.h = vec![vec![1.0, 0.0], vec![0.0, -1.0]];
+        assert_error!(
+            run(qp, GoldfarbIdnani::new()),
+            ArgminError,
+            "Invalid parameter: \"`GoldfarbIdnani`: `H` must be positive definite.\""
+        );
+
+        let mut qp = nocedal_wright();
+        qp.c = vec![1.0];
+        assert_error!(
+            run(qp, GoldfarbIdnani::new()),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`GoldfarbIdnani`: `H` must be a square matrix matching ",
+                "the length of `c`.\""
+            )
+        );
+
+        let mut qp = nocedal_wright();
+        qp.b_ineq.pop();
+        assert_error!(
+            run(qp, GoldfarbIdnani::new()),
+            ArgminError,
+            "Invalid parameter: \"`GoldfarbIdnani`: dimensions of `A_ineq` and `b_ineq` do not match.\""
+        );
+
+        let mut qp = nocedal_wright();
+        qp.a_eq = vec![vec![1.0, 1.0]];
+        assert_error!(
+            run(qp, GoldfarbIdnani::new()),
+            ArgminError,
+            "Invalid parameter: \"`GoldfarbIdnani`: dimensions of `A_eq` and `b_eq` do not match.\""
+        );
+
+        let mut qp = nocedal_wright();
+        qp.a_eq = vec![vec![1.0, 1.0], vec![2.0, 2.0]];
+        qp.b_eq = vec![1.0, 2.0];
+        assert_error!(
+            run(qp, GoldfarbIdnani::new()),
+            ArgminError,
+            "Invalid parameter: \"`GoldfarbIdnani`: equality constraints are linearly dependent.\""
+        );
+
+        assert_error!(
+            run(
+                nocedal_wright(),
+                GoldfarbIdnani::new().with_active_set(vec![5])
+            ),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`GoldfarbIdnani`: initial active set contains an invalid ",
+                "constraint index.\""
+            )
+        );
+    }
+}
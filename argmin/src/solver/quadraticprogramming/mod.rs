@@ -0,0 +1,31 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Quadratic programming
+//!
+//! Solvers for convex quadratic programs defined via the
+//! [`QuadraticProgram`](`crate::core::QuadraticProgram`) trait.
+//!
+//! * [Goldfarb-Idnani dual active set method](`GoldfarbIdnani`)
+//!
+//! ## Reference
+//!
+//! D. Goldfarb and A. Idnani (1983). A numerically stable dual method for solving strictly convex
+//! quadratic programs. Mathematical Programming 27, pp. 1-33.
+
+mod goldfarbidnani;
+
+pub use self::goldfarbidnani::GoldfarbIdnani;
+
+use crate::core::ArgminFloat;
+
+/// Dot product of two slices
+pub(crate) fn dot<F: ArgminFloat>(a: &[F], b: &[F]) -> F {
+    a.iter()
+        .zip(b.iter())
+        .fold(float!(0.0), |acc, (a, b)| acc + *a * *b)
+}
@@ -5,9 +5,10 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use super::{InvHessianScaling, UpdatePolicy};
 use crate::core::{
     ArgminFloat, CostFunction, DeserializeOwnedAlias, Error, Executor, Gradient, IterState,
-    LineSearch, OptimizationResult, Problem, SerializeAlias, Solver, TerminationReason, KV,
+    LineSearch, OptimizationResult, Problem, SerializeAlias, Solver, State, TerminationReason, KV,
 };
 use argmin_math::{
     ArgminAdd, ArgminDot, ArgminEye, ArgminMul, ArgminNorm, ArgminSub, ArgminTranspose,
@@ -37,6 +38,14 @@ use serde::{Deserialize, Serialize};
 /// other. If the change is below this tolerance (default: `EPSILON`), the algorithm stops. This
 /// parameter can be set via [`with_tolerance_cost`](`BFGS::with_tolerance_cost`).
 ///
+/// If the line search does not enforce the (strong) Wolfe conditions, the curvature condition
+/// `sᵀy > 0` may be violated and the update may destroy the positive definiteness of the inverse
+/// Hessian approximation. Powell's damped update (enabled with
+/// [`with_damping`](`BFGS::with_damping`)) modifies `y` such that the curvature condition holds.
+/// Updates which remain unsafe are handled according to the [`UpdatePolicy`] set with
+/// [`with_update_policy`](`BFGS::with_update_policy`). The initial inverse Hessian can be scaled
+/// with [`with_inv_hessian_scaling`](`BFGS::with_inv_hessian_scaling`).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
@@ -54,6 +63,12 @@ pub struct BFGS<L, F> {
     tol_grad: F,
     /// Tolerance for the stopping criterion based on the change of the cost stopping criterion
     tol_cost: F,
+    /// Threshold of Powell's damped update (disabled if `None`)
+    damping: Option<F>,
+    /// Scaling of the inverse Hessian
+    scaling: InvHessianScaling,
+    /// Handling of unsafe updates
+    update_policy: UpdatePolicy,
 }
 
 impl<L, F> BFGS<L, F>
@@ -74,6 +89,9 @@ where
             linesearch,
             tol_grad: F::epsilon().sqrt(),
             tol_cost: F::epsilon(),
+            damping: None,
+            scaling: InvHessianScaling::None,
+            update_policy: UpdatePolicy::Always,
         }
     }
 
@@ -128,6 +146,73 @@ where
         self.tol_cost = tol_cost;
         Ok(self)
     }
+
+    /// Enable Powell's damped BFGS update.
+    ///
+    /// If `sᵀy < damping * sᵀBs`, where `B` is the current Hessian approximation, `y` is replaced
+    /// by `θy + (1 - θ)Bs` with `θ` chosen such that `sᵀy = damping * sᵀBs`. This guarantees that
+    /// the inverse Hessian approximation stays positive definite, even if the line search does not
+    /// satisfy the Wolfe conditions. The product `Bs` is obtained from the search direction and
+    /// does not require the Hessian approximation itself.
+    ///
+    /// `damping` must be in `(0, 1)`; a common choice is `0.2`. Damping is disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::BFGS;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let bfgs: BFGS<_, f64> = BFGS::new(linesearch).with_damping(0.2)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_damping(mut self, damping: F) -> Result<Self, Error> {
+        if damping <= float!(0.0) || damping >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BFGS`: damping must be in (0, 1)."
+            ));
+        }
+        self.damping = Some(damping);
+        Ok(self)
+    }
+
+    /// Set the scaling strategy of the inverse Hessian approximation.
+    ///
+    /// Defaults to [`InvHessianScaling::None`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::{BFGS, InvHessianScaling};
+    /// # let linesearch = ();
+    /// let bfgs: BFGS<_, f64> =
+    ///     BFGS::new(linesearch).with_inv_hessian_scaling(InvHessianScaling::Initial);
+    /// ```
+    #[must_use]
+    pub fn with_inv_hessian_scaling(mut self, scaling: InvHessianScaling) -> Self {
+        self.scaling = scaling;
+        self
+    }
+
+    /// Set how updates which violate the curvature condition are handled.
+    ///
+    /// Defaults to [`UpdatePolicy::Always`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::{BFGS, UpdatePolicy};
+    /// # let linesearch = ();
+    /// let bfgs: BFGS<_, f64> = BFGS::new(linesearch).with_update_policy(UpdatePolicy::Skip);
+    /// ```
+    #[must_use]
+    pub fn with_update_policy(mut self, update_policy: UpdatePolicy) -> Self {
+        self.update_policy = update_policy;
+        self
+    }
 }
 
 impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, F>> for BFGS<L, F>
@@ -138,13 +223,16 @@ where
         + DeserializeOwnedAlias
         + ArgminSub<P, P>
         + ArgminDot<G, H>
-        + ArgminDot<P, H>,
+        + ArgminDot<P, H>
+        + ArgminNorm<F>,
     G: Clone
         + SerializeAlias
         + DeserializeOwnedAlias
         + ArgminNorm<F>
         + ArgminMul<F, P>
+        + ArgminMul<F, G>
         + ArgminDot<P, F>
+        + ArgminDot<G, F>
         + ArgminSub<G, G>,
     H: SerializeAlias
         + DeserializeOwnedAlias
@@ -225,7 +313,9 @@ where
             "`BFGS`: Inverse Hessian in state not set."
         ))?;
 
-        let p = inv_hessian.dot(&prev_grad).mul(&float!(-1.0));
+        let p: G = inv_hessian.dot(&prev_grad);
+        let p: P = p.mul(&float!(-1.0));
+        let p_norm = p.norm();
 
         self.linesearch.search_direction(p);
 
@@ -233,7 +323,7 @@ where
         let OptimizationResult {
             problem: line_problem,
             state: mut sub_state,
-            ..
+            solver: line_solver,
         } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
             .configure(|config| {
                 config
@@ -244,6 +334,8 @@ where
             .ctrlc(false)
             .run()?;
 
+        self.linesearch.consume_line_search(line_solver);
+
         let xk1 = sub_state.take_param().ok_or_else(argmin_error_closure!(
             PotentialBug,
             "`BFGS`: No parameters returned by line search."
@@ -256,32 +348,77 @@ where
 
         let grad = problem.gradient(&xk1)?;
 
-        let yk = grad.sub(&prev_grad);
+        let mut yk = grad.sub(&prev_grad);
 
         let sk = xk1.sub(&param);
 
-        let yksk: F = yk.dot(&sk);
-        let rhok = float!(1.0) / yksk;
-
-        let e = inv_hessian.eye_like();
-        let mat1: H = sk.dot(&yk);
-        let mat1 = mat1.mul(&rhok);
-
-        let tmp1 = e.sub(&mat1);
-
-        let mat2 = mat1.t();
-        let tmp2 = e.sub(&mat2);
-
-        let sksk: H = sk.dot(&sk);
-        let sksk = sksk.mul(&rhok);
-
-        // if state.get_iter() == 0 {
-        //     let ykyk: f64 = yk.dot(&yk);
-        //     self.inv_hessian = self.inv_hessian.eye_like().mul(&(yksk / ykyk));
-        //     println!("{:?}", self.inv_hessian);
-        // }
+        let mut yksk: F = yk.dot(&sk);
+
+        let mut damped = false;
+        if let Some(damping) = self.damping {
+            // Since `p = -H g` and `s = alpha p`, the product of the Hessian approximation and
+            // the step is `B s = -alpha g`.
+            let alpha = sk.norm() / p_norm;
+            let sbs: F = -alpha * prev_grad.dot(&sk);
+            if yksk < damping * sbs {
+                let theta = (float!(1.0) - damping) * sbs / (sbs - yksk);
+                let bs: G = prev_grad.mul(&((float!(1.0) - theta) * alpha));
+                let tmp: G = yk.mul(&theta);
+                yk = tmp.sub(&bs);
+                yksk = yk.dot(&sk);
+                damped = true;
+            }
+        }
 
-        let inv_hessian = tmp1.dot(&inv_hessian.dot(&tmp2)).add(&sksk);
+        let ykyk: F = yk.dot(&yk);
+        let unsafe_update = yksk <= F::epsilon() * sk.norm() * ykyk.sqrt();
+
+        let (inv_hessian, update) = match (unsafe_update, self.update_policy) {
+            (true, UpdatePolicy::Skip) => (inv_hessian, "skipped"),
+            (true, UpdatePolicy::Reset) => {
+                let gamma = yksk / ykyk;
+                let gamma = if gamma > float!(0.0) && gamma.is_finite() {
+                    gamma
+                } else {
+                    float!(1.0)
+                };
+                (inv_hessian.eye_like().mul(&gamma), "reset")
+            }
+            _ => {
+                let inv_hessian = match self.scaling {
+                    InvHessianScaling::Initial if state.get_iter() == 0 && !unsafe_update => {
+                        inv_hessian.eye_like().mul(&(yksk / ykyk))
+                    }
+                    InvHessianScaling::SelfScaling if !unsafe_update => {
+                        let hy: G = inv_hessian.dot(&yk);
+                        let yhy: F = yk.dot(&hy);
+                        if yhy > float!(0.0) {
+                            inv_hessian.mul(&(yksk / yhy))
+                        } else {
+                            inv_hessian
+                        }
+                    }
+                    _ => inv_hessian,
+                };
+
+                let rhok = float!(1.0) / yksk;
+
+                let e = inv_hessian.eye_like();
+                let mat1: H = sk.dot(&yk);
+                let mat1 = mat1.mul(&rhok);
+
+                let tmp1 = e.sub(&mat1);
+
+                let mat2 = mat1.t();
+                let tmp2 = e.sub(&mat2);
+
+                let sksk: H = sk.dot(&sk);
+                let sksk = sksk.mul(&rhok);
+
+                let inv_hessian = tmp1.dot(&inv_hessian.dot(&tmp2)).add(&sksk);
+                (inv_hessian, if damped { "damped" } else { "applied" })
+            }
+        };
 
         Ok((
             state
@@ -289,7 +426,7 @@ where
                 .cost(next_cost)
                 .grad(grad)
                 .inv_hessian(inv_hessian),
-            None,
+            Some(make_kv!["update" => update;]),
         ))
     }
 
@@ -308,8 +445,52 @@ where
 mod tests {
     use super::*;
     use crate::core::{test_utils::TestProblem, ArgminError, IterState, State};
-    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::linesearch::{
+        condition::ArmijoCondition, BacktrackingLineSearch, MoreThuenteLineSearch,
+    };
     use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    /// Nonconvex problem `f(x) = sum(cos(x_i))`: A step from `0.5` with unit inverse Hessian
+    /// violates the curvature condition.
+    #[derive(Clone)]
+    struct Cosine {}
+
+    impl CostFunction for Cosine {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter().map(|x| x.cos()).sum())
+        }
+    }
+
+    impl Gradient for Cosine {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(p.iter().map(|x| -x.sin()).collect())
+        }
+    }
+
+    type ArmijoBFGS =
+        BFGS<BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64>, f64>;
+
+    fn cosine_step(mut bfgs: ArmijoBFGS) -> (f64, String) {
+        let mut problem = Problem::new(Cosine {});
+        let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, f64> = IterState::new()
+            .param(vec![0.5])
+            .inv_hessian(vec![vec![1.0]]);
+        let (state, _) = bfgs.init(&mut problem, state).unwrap();
+        let (mut state, kv) = bfgs.next_iter(&mut problem, state).unwrap();
+        let kv = kv.unwrap();
+        assert_eq!(kv.kv[0].0, "update");
+        (
+            state.take_inv_hessian().unwrap()[0][0],
+            format!("{}", kv.kv[0].1),
+        )
+    }
 
     test_trait_impl!(
         bfgs,
@@ -326,11 +507,17 @@ mod tests {
             linesearch,
             tol_grad,
             tol_cost,
+            damping,
+            scaling,
+            update_policy,
         } = bfgs;
 
         assert_eq!(linesearch, MyFakeLineSearch {});
         assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
         assert_eq!(tol_cost.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert!(damping.is_none());
+        assert_eq!(scaling, InvHessianScaling::None);
+        assert_eq!(update_policy, UpdatePolicy::Always);
     }
 
     #[test]
@@ -506,4 +693,147 @@ mod tests {
             assert_eq!(s.to_ne_bytes(), g.to_ne_bytes());
         }
     }
+
+    #[test]
+    fn test_with_damping() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        // correct parameters
+        for damping in [f64::EPSILON, 0.2, 0.5, 1.0 - f64::EPSILON] {
+            let bfgs: BFGS<_, f64> = BFGS::new(MyFakeLineSearch {});
+            let res = bfgs.with_damping(damping);
+            assert!(res.is_ok());
+
+            let nm = res.unwrap();
+            assert_eq!(nm.damping.unwrap().to_ne_bytes(), damping.to_ne_bytes());
+        }
+
+        // incorrect parameters
+        for damping in [0.0, -1.0, 1.0, 42.0] {
+            let bfgs: BFGS<_, f64> = BFGS::new(MyFakeLineSearch {});
+            let res = bfgs.with_damping(damping);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`BFGS`: damping must be in (0, 1).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_iter_curvature_violated() {
+        let linesearch = || BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+
+        let s = 0.5f64.sin();
+        let y = 0.5f64.sin() - (0.5 + s).sin();
+        assert!(y * s < 0.0);
+
+        // Plain update destroys positive definiteness
+        let (h, update) = cosine_step(BFGS::new(linesearch()));
+        assert_relative_eq!(h, s / y, epsilon = 1e-12);
+        assert_eq!(update, "applied");
+
+        // Damped update
+        let (h, update) = cosine_step(BFGS::new(linesearch()).with_damping(0.2).unwrap());
+        assert!(h > 0.0);
+        assert_eq!(update, "damped");
+
+        // Skipped update
+        let (h, update) =
+            cosine_step(BFGS::new(linesearch()).with_update_policy(UpdatePolicy::Skip));
+        assert_relative_eq!(h, 1.0, epsilon = f64::EPSILON);
+        assert_eq!(update, "skipped");
+
+        // Reset to identity, because scaling factor is negative
+        let (h, update) =
+            cosine_step(BFGS::new(linesearch()).with_update_policy(UpdatePolicy::Reset));
+        assert_relative_eq!(h, 1.0, epsilon = f64::EPSILON);
+        assert_eq!(update, "reset");
+    }
+
+    #[test]
+    fn test_inv_hessian_scaling() {
+        #[derive(Clone)]
+        struct Quadratic {}
+
+        impl CostFunction for Quadratic {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(50.0 * p[0].powi(2) + 0.5 * p[1].powi(2))
+            }
+        }
+
+        impl Gradient for Quadratic {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![100.0 * p[0], p[1]])
+            }
+        }
+
+        // After the first iteration, the inverse Hessian is the BFGS update of `γ H_0` with
+        // `γ = 1` without scaling and `γ = sᵀy / yᵀy` otherwise (`H_0 = I`, hence
+        // `sᵀy / yᵀH_0y = sᵀy / yᵀy` for `SelfScaling`).
+        for scaling in [
+            InvHessianScaling::None,
+            InvHessianScaling::Initial,
+            InvHessianScaling::SelfScaling,
+        ] {
+            let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+                MoreThuenteLineSearch::new();
+            let mut bfgs: BFGS<_, f64> = BFGS::new(linesearch).with_inv_hessian_scaling(scaling);
+            let mut problem = Problem::new(Quadratic {});
+            let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, f64> = IterState::new()
+                .param(vec![1.0, 1.0])
+                .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+            let (state, _) = bfgs.init(&mut problem, state).unwrap();
+            let (mut state, _) = bfgs.next_iter(&mut problem, state).unwrap();
+
+            let x1 = state.get_param().unwrap();
+            let s = [x1[0] - 1.0, x1[1] - 1.0];
+            let y = [100.0 * s[0], s[1]];
+            let sy = s[0] * y[0] + s[1] * y[1];
+            let gamma = match scaling {
+                InvHessianScaling::None => 1.0,
+                _ => sy / (y[0] * y[0] + y[1] * y[1]),
+            };
+            let rho = 1.0 / sy;
+            // `V = I - ρ s yᵀ`, `H_1 = γ V Vᵀ + ρ s sᵀ`
+            let v = |i: usize, j: usize| if i == j { 1.0 } else { 0.0 } - rho * s[i] * y[j];
+
+            let h = state.take_inv_hessian().unwrap();
+            for i in 0..2 {
+                for j in 0..2 {
+                    let expected =
+                        gamma * (v(i, 0) * v(j, 0) + v(i, 1) * v(j, 1)) + rho * s[i] * s[j];
+                    assert_relative_eq!(h[i][j], expected, epsilon = 1e-10);
+                }
+            }
+        }
+
+        for scaling in [
+            InvHessianScaling::None,
+            InvHessianScaling::Initial,
+            InvHessianScaling::SelfScaling,
+        ] {
+            let linesearch = MoreThuenteLineSearch::new();
+            let solver: BFGS<_, f64> = BFGS::new(linesearch).with_inv_hessian_scaling(scaling);
+            let res = Executor::new(Quadratic {}, solver)
+                .configure(|state| {
+                    state
+                        .param(vec![1.0, 1.0])
+                        .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+                        .max_iters(100)
+                })
+                .run()
+                .unwrap();
+            let param = res.state.get_best_param().unwrap();
+            assert_relative_eq!(param[0], 0.0, epsilon = 1e-6);
+            assert_relative_eq!(param[1], 0.0, epsilon = 1e-6);
+        }
+    }
 }
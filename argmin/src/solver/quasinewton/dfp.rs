@@ -5,11 +5,12 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use super::{InvHessianScaling, UpdatePolicy};
 use crate::core::{
     ArgminFloat, CostFunction, DeserializeOwnedAlias, Error, Executor, Gradient, IterState,
-    LineSearch, OptimizationResult, Problem, SerializeAlias, Solver, TerminationReason, KV,
+    LineSearch, OptimizationResult, Problem, SerializeAlias, Solver, State, TerminationReason, KV,
 };
-use argmin_math::{ArgminAdd, ArgminDot, ArgminMul, ArgminNorm, ArgminSub};
+use argmin_math::{ArgminAdd, ArgminDot, ArgminEye, ArgminMul, ArgminNorm, ArgminSub};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -31,6 +32,11 @@ use serde::{Deserialize, Serialize};
 /// [`with_tolerance_grad`](`DFP::with_tolerance_grad`): If the norm of the gradient is below
 /// said tolerance, the algorithm stops. It defaults to `sqrt(EPSILON)`.
 ///
+/// Powell's damping (see [`with_damping`](`DFP::with_damping`)) modifies `y` such that the
+/// curvature condition holds. Updates which remain unsafe are handled according to the
+/// [`UpdatePolicy`] set with [`with_update_policy`](`DFP::with_update_policy`) and the inverse
+/// Hessian can be scaled with [`with_inv_hessian_scaling`](`DFP::with_inv_hessian_scaling`).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
@@ -46,6 +52,12 @@ pub struct DFP<L, F> {
     linesearch: L,
     /// Tolerance for the stopping criterion based on the change of the norm on the gradient
     tol_grad: F,
+    /// Damping factor (disabled if `None`)
+    damping: Option<F>,
+    /// Scaling of the inverse Hessian
+    scaling: InvHessianScaling,
+    /// Handling of unsafe updates
+    update_policy: UpdatePolicy,
 }
 
 impl<L, F> DFP<L, F>
@@ -65,6 +77,9 @@ where
         DFP {
             linesearch,
             tol_grad: F::epsilon().sqrt(),
+            damping: None,
+            scaling: InvHessianScaling::None,
+            update_policy: UpdatePolicy::Always,
         }
     }
 
@@ -93,6 +108,73 @@ where
         self.tol_grad = tol_grad;
         Ok(self)
     }
+
+    /// Enable Powell's damped update.
+    ///
+    /// If `sᵀy < damping * sᵀBs`, where `B` is the inverse of the current inverse Hessian
+    /// approximation, `y` is replaced by `θy + (1 - θ)Bs` with `θ` chosen such that
+    /// `sᵀy = damping * sᵀBs`. This keeps the inverse Hessian approximation positive definite,
+    /// even if the line search does not satisfy the Wolfe conditions. The product `Bs` is obtained
+    /// from the search direction and does not require inverting the approximation.
+    ///
+    /// `damping` must be in `(0, 1)`; a common choice is `0.2`. Damping is disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::DFP;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let dfp: DFP<_, f64> = DFP::new(linesearch).with_damping(0.2)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_damping(mut self, damping: F) -> Result<Self, Error> {
+        if damping <= float!(0.0) || damping >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`DFP`: damping must be in (0, 1)."
+            ));
+        }
+        self.damping = Some(damping);
+        Ok(self)
+    }
+
+    /// Set the scaling strategy of the inverse Hessian approximation.
+    ///
+    /// Defaults to [`InvHessianScaling::None`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::{DFP, InvHessianScaling};
+    /// # let linesearch = ();
+    /// let dfp: DFP<_, f64> =
+    ///     DFP::new(linesearch).with_inv_hessian_scaling(InvHessianScaling::Initial);
+    /// ```
+    #[must_use]
+    pub fn with_inv_hessian_scaling(mut self, scaling: InvHessianScaling) -> Self {
+        self.scaling = scaling;
+        self
+    }
+
+    /// Set how updates which violate the curvature condition are handled.
+    ///
+    /// Defaults to [`UpdatePolicy::Always`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::{DFP, UpdatePolicy};
+    /// # let linesearch = ();
+    /// let dfp: DFP<_, f64> = DFP::new(linesearch).with_update_policy(UpdatePolicy::Skip);
+    /// ```
+    #[must_use]
+    pub fn with_update_policy(mut self, update_policy: UpdatePolicy) -> Self {
+        self.update_policy = update_policy;
+        self
+    }
 }
 
 impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, F>> for DFP<L, F>
@@ -104,20 +186,24 @@ where
         + ArgminSub<P, P>
         + ArgminDot<G, F>
         + ArgminDot<P, H>
-        + ArgminMul<F, P>,
+        + ArgminMul<F, P>
+        + ArgminNorm<F>,
     G: Clone
         + SerializeAlias
         + DeserializeOwnedAlias
         + ArgminSub<G, G>
+        + ArgminMul<F, G>
         + ArgminNorm<F>
-        + ArgminDot<P, F>,
+        + ArgminDot<P, F>
+        + ArgminDot<G, F>,
     H: Clone
         + SerializeAlias
         + DeserializeOwnedAlias
         + ArgminSub<H, H>
         + ArgminDot<G, P>
         + ArgminAdd<H, H>
-        + ArgminMul<F, H>,
+        + ArgminMul<F, H>
+        + ArgminEye,
     L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), F>>,
     F: ArgminFloat,
 {
@@ -188,13 +274,14 @@ where
         ))?;
 
         let p = inv_hessian.dot(&prev_grad).mul(&float!(-1.0));
+        let p_norm = p.norm();
 
         self.linesearch.search_direction(p);
 
         let OptimizationResult {
             problem: line_problem,
             state: mut linesearch_state,
-            ..
+            solver: line_solver,
         } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
             .configure(|config| {
                 config
@@ -205,6 +292,8 @@ where
             .ctrlc(false)
             .run()?;
 
+        self.linesearch.consume_line_search(line_solver);
+
         let xk1 = linesearch_state
             .take_param()
             .ok_or_else(argmin_error_closure!(
@@ -218,20 +307,70 @@ where
         problem.consume_problem(line_problem);
 
         let grad = problem.gradient(&xk1)?;
-        let yk = grad.sub(&prev_grad);
+        let mut yk = grad.sub(&prev_grad);
 
         let sk = xk1.sub(&param);
 
-        let yksk: F = yk.dot(&sk);
-
-        let sksk: H = sk.dot(&sk);
-
-        let tmp3: P = inv_hessian.dot(&yk);
-        let tmp4: F = tmp3.dot(&yk);
-        let tmp3: H = tmp3.dot(&tmp3);
-        let tmp3: H = tmp3.mul(&(float!(1.0) / tmp4));
+        let mut yksk: F = yk.dot(&sk);
+
+        let mut damped = false;
+        if let Some(damping) = self.damping {
+            // Since `p = -H g` and `s = alpha p`, the product of the Hessian approximation and
+            // the step is `B s = -alpha g`.
+            let alpha = sk.norm() / p_norm;
+            let sbs: F = -alpha * prev_grad.dot(&sk);
+            if yksk < damping * sbs {
+                let theta = (float!(1.0) - damping) * sbs / (sbs - yksk);
+                let bs: G = prev_grad.mul(&((float!(1.0) - theta) * alpha));
+                let tmp: G = yk.mul(&theta);
+                yk = tmp.sub(&bs);
+                yksk = yk.dot(&sk);
+                damped = true;
+            }
+        }
 
-        let inv_hessian = inv_hessian.sub(&tmp3).add(&sksk.mul(&(float!(1.0) / yksk)));
+        let ykyk: F = yk.dot(&yk);
+        let unsafe_update = yksk <= F::epsilon() * sk.norm() * ykyk.sqrt();
+
+        let (inv_hessian, update) = match (unsafe_update, self.update_policy) {
+            (true, UpdatePolicy::Skip) => (inv_hessian, "skipped"),
+            (true, UpdatePolicy::Reset) => {
+                let gamma = yksk / ykyk;
+                let gamma = if gamma > float!(0.0) && gamma.is_finite() {
+                    gamma
+                } else {
+                    float!(1.0)
+                };
+                (inv_hessian.eye_like().mul(&gamma), "reset")
+            }
+            _ => {
+                let inv_hessian = match self.scaling {
+                    InvHessianScaling::Initial if state.get_iter() == 0 && !unsafe_update => {
+                        inv_hessian.eye_like().mul(&(yksk / ykyk))
+                    }
+                    InvHessianScaling::SelfScaling if !unsafe_update => {
+                        let hy: P = inv_hessian.dot(&yk);
+                        let yhy: F = hy.dot(&yk);
+                        if yhy > float!(0.0) {
+                            inv_hessian.mul(&(yksk / yhy))
+                        } else {
+                            inv_hessian
+                        }
+                    }
+                    _ => inv_hessian,
+                };
+
+                let sksk: H = sk.dot(&sk);
+
+                let tmp3: P = inv_hessian.dot(&yk);
+                let tmp4: F = tmp3.dot(&yk);
+                let tmp3: H = tmp3.dot(&tmp3);
+                let tmp3: H = tmp3.mul(&(float!(1.0) / tmp4));
+
+                let inv_hessian = inv_hessian.sub(&tmp3).add(&sksk.mul(&(float!(1.0) / yksk)));
+                (inv_hessian, if damped { "damped" } else { "applied" })
+            }
+        };
 
         Ok((
             state
@@ -239,7 +378,7 @@ where
                 .cost(next_cost)
                 .grad(grad)
                 .inv_hessian(inv_hessian),
-            None,
+            Some(make_kv!["update" => update;]),
         ))
     }
 
@@ -255,8 +394,11 @@ where
 mod tests {
     use super::*;
     use crate::core::{test_utils::TestProblem, ArgminError, IterState, State};
-    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::linesearch::{
+        condition::ArmijoCondition, BacktrackingLineSearch, MoreThuenteLineSearch,
+    };
     use crate::test_trait_impl;
+    use approx::assert_relative_eq;
 
     test_trait_impl!(
         dfp,
@@ -272,10 +414,16 @@ mod tests {
         let DFP {
             linesearch,
             tol_grad,
+            damping,
+            scaling,
+            update_policy,
         } = dfp;
 
         assert_eq!(linesearch, MyFakeLineSearch {});
         assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(damping.is_none());
+        assert_eq!(scaling, InvHessianScaling::None);
+        assert_eq!(update_policy, UpdatePolicy::Always);
     }
 
     #[test]
@@ -305,6 +453,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_with_damping() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        // correct parameters
+        for damping in [f64::EPSILON, 0.2, 0.5, 1.0 - f64::EPSILON] {
+            let dfp: DFP<_, f64> = DFP::new(MyFakeLineSearch {});
+            let res = dfp.with_damping(damping);
+            assert!(res.is_ok());
+
+            let nm = res.unwrap();
+            assert_eq!(nm.damping.unwrap().to_ne_bytes(), damping.to_ne_bytes());
+        }
+
+        // incorrect parameters
+        for damping in [0.0, -1.0, 1.0, 42.0] {
+            let dfp: DFP<_, f64> = DFP::new(MyFakeLineSearch {});
+            let res = dfp.with_damping(damping);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`DFP`: damping must be in (0, 1).\""
+            );
+        }
+    }
+
     #[test]
     fn test_init() {
         let linesearch = MoreThuenteLineSearch::new().with_c(1e-4, 0.9).unwrap();
@@ -424,4 +599,60 @@ mod tests {
             assert_eq!(s.to_ne_bytes(), g.to_ne_bytes());
         }
     }
+
+    #[test]
+    fn test_next_iter_update_policy() {
+        /// Nonconvex problem `f(x) = sum(cos(x_i))`
+        struct Cosine {}
+
+        impl CostFunction for Cosine {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p.iter().map(|x| x.cos()).sum())
+            }
+        }
+
+        impl Gradient for Cosine {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(p.iter().map(|x| -x.sin()).collect())
+            }
+        }
+
+        // A unit step from `0.5` violates the curvature condition
+        let s = 0.5f64.sin();
+        let y = 0.5f64.sin() - (0.5 + s).sin();
+
+        for (damping, policy, expected_h, expected_update) in [
+            (None, UpdatePolicy::Always, s / y, "applied"),
+            (None, UpdatePolicy::Skip, 1.0, "skipped"),
+            (None, UpdatePolicy::Reset, 1.0, "reset"),
+            // With `sᵀBs = s²`, damping yields `sᵀy = 0.2 s²` and the one-dimensional update
+            // results in `s / y = 5`.
+            (Some(0.2), UpdatePolicy::Always, 5.0, "damped"),
+        ] {
+            let linesearch = BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+            let mut dfp: DFP<_, f64> = DFP::new(linesearch).with_update_policy(policy);
+            if let Some(damping) = damping {
+                dfp = dfp.with_damping(damping).unwrap();
+            }
+            let mut problem = Problem::new(Cosine {});
+            let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, f64> = IterState::new()
+                .param(vec![0.5])
+                .inv_hessian(vec![vec![1.0]]);
+            let (state, _) = dfp.init(&mut problem, state).unwrap();
+            let (mut state, kv) = dfp.next_iter(&mut problem, state).unwrap();
+
+            let kv = kv.unwrap();
+            assert_eq!(kv.kv[0].0, "update");
+            assert_eq!(format!("{}", kv.kv[0].1), expected_update);
+
+            let h = state.take_inv_hessian().unwrap()[0][0];
+            assert_relative_eq!(h, expected_h, epsilon = 1e-12);
+        }
+    }
 }
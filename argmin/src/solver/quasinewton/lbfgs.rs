@@ -240,7 +240,7 @@ where
         let OptimizationResult {
             problem: line_problem,
             state: mut linesearch_state,
-            ..
+            solver: line_solver,
         } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
             .configure(|config| {
                 config
@@ -251,6 +251,8 @@ where
             .ctrlc(false)
             .run()?;
 
+        self.linesearch.consume_line_search(line_solver);
+
         let xk1 = linesearch_state.take_param().unwrap();
         let next_cost = linesearch_state.get_cost();
 
@@ -0,0 +1,682 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, DeserializeOwnedAlias, Error, Executor, Gradient, IterState,
+    OptimizationResult, Problem, SerializeAlias, Solver, TerminationReason, TrustRegionRadius, KV,
+};
+use argmin_math::{ArgminAdd, ArgminDot, ArgminMul, ArgminNorm, ArgminSub, ArgminZeroLike};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Limited-memory SR1 approximation of the Hessian
+///
+/// Represents `B = gamma * I + sum_i u_i * u_i^T / (u_i^T s_i)` with `u_i = y_i - B_{i-1} s_i`,
+/// where `B_{i-1}` is the approximation built from the first `i - 1` stored pairs `(s_i, y_i)`.
+/// Only the vectors `u_i` and the denominators are stored, therefore the memory requirement is
+/// linear in the number of parameters.
+///
+/// It is used as the Hessian in the state of the trust region subproblem of [`LSR1TrustRegion`]:
+/// [`ArgminDot`] computes Hessian-vector products, which is all that subproblem solvers such as
+/// [`Steihaug`](`crate::solver::trustregion::Steihaug`) require.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LSR1Hessian<P, F> {
+    /// Scaling of the initial approximation `gamma * I`
+    gamma: F,
+    /// Rank one updates `u_i`
+    u: Vec<P>,
+    /// `1 / (u_i^T s_i)`
+    inv_denom: Vec<F>,
+}
+
+impl<P, F> LSR1Hessian<P, F>
+where
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminAdd<P, P>
+        + ArgminDot<P, F>
+        + ArgminMul<F, P>
+        + ArgminNorm<F>,
+    F: ArgminFloat,
+{
+    /// Build the approximation from the initial scaling and the stored pairs. Pairs which would
+    /// lead to a (nearly) vanishing denominator are skipped.
+    fn new<'a>(gamma: F, pairs: impl Iterator<Item = (&'a P, &'a P)>, denominator_factor: F) -> Self
+    where
+        P: 'a,
+    {
+        let mut hessian = LSR1Hessian {
+            gamma,
+            u: vec![],
+            inv_denom: vec![],
+        };
+        for (s, y) in pairs {
+            hessian.update(s, y, denominator_factor);
+        }
+        hessian
+    }
+
+    /// Apply the SR1 update for the pair `(s, y)` if the skipping rule allows it. Returns `true`
+    /// if the update was applied.
+    fn update(&mut self, s: &P, y: &P, denominator_factor: F) -> bool {
+        let u = y.sub(&self.dot(s));
+        let denom: F = u.dot(s);
+        if denom.abs() > denominator_factor * s.norm() * u.norm() {
+            self.u.push(u);
+            self.inv_denom.push(float!(1.0) / denom);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<P, F> ArgminDot<P, P> for LSR1Hessian<P, F>
+where
+    P: ArgminAdd<P, P> + ArgminDot<P, F> + ArgminMul<F, P>,
+    F: ArgminFloat,
+{
+    /// Hessian-vector product
+    fn dot(&self, v: &P) -> P {
+        self.u
+            .iter()
+            .zip(self.inv_denom.iter())
+            .fold(v.mul(&self.gamma), |acc, (u, &inv_denom)| {
+                let uv: F = u.dot(v);
+                acc.add(&u.mul(&(uv * inv_denom)))
+            })
+    }
+}
+
+/// # Limited-memory SR1 trust region method
+///
+/// A Quasi-Newton method which uses symmetric rank 1 (SR1) updating of the Hessian in a trust
+/// region framework. In contrast to [`SR1TrustRegion`](`crate::solver::quasinewton::SR1TrustRegion`),
+/// the Hessian approximation is never formed explicitly. Only the last `m` pairs of parameter
+/// vector differences `s` and gradient differences `y` are stored, from which an
+/// [`LSR1Hessian`] is built which provides Hessian-vector products to the
+/// [trust region subproblem](`crate::solver::trustregion`) (typically
+/// [`Steihaug`](`crate::solver::trustregion::Steihaug`)). Unlike [`LBFGS`](`crate::solver::quasinewton::LBFGS`),
+/// the SR1 approximation is not necessarily positive definite and can therefore capture negative
+/// curvature.
+///
+/// The initial approximation is `gamma * I`, where `gamma = y^T y / s^T y` of the most recent pair
+/// with `s^T y > 0` (`gamma = 1` before such a pair is available).
+///
+/// An initial parameter vector must be provided, initial cost and gradient are optional and will
+/// be computed if not provided. The algorithm stops if the norm of the gradient is below a
+/// tolerance (set via [`with_tolerance_grad`](`LSR1TrustRegion::with_tolerance_grad`)).
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
+///
+/// ## Reference
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+///
+/// Richard H. Byrd, Jorge Nocedal and Robert B. Schnabel (1994). Representations of quasi-Newton
+/// matrices and their use in limited memory methods. Mathematical Programming 63, 129–156.
+/// <https://doi.org/10.1007/BF01582063>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LSR1TrustRegion<R, P, F> {
+    /// subproblem
+    subproblem: R,
+    /// m
+    m: usize,
+    /// s_{k-1}
+    s: VecDeque<P>,
+    /// y_{k-1}
+    y: VecDeque<P>,
+    /// Scaling of the initial Hessian approximation
+    gamma: F,
+    /// parameter for skipping rule
+    denominator_factor: F,
+    /// Radius
+    radius: F,
+    /// eta \in (0, 10^-3)
+    eta: F,
+    /// Tolerance for the stopping criterion based on the change of the norm on the gradient
+    tol_grad: F,
+}
+
+impl<R, P, F> LSR1TrustRegion<R, P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`LSR1TrustRegion`]
+    ///
+    /// Takes the trust region subproblem and the number of stored pairs (history size `m`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LSR1TrustRegion;
+    /// let subproblem = argmin::solver::trustregion::Steihaug::new().with_max_iters(20);
+    /// # let subproblem: argmin::solver::trustregion::Steihaug<Vec<f64>, f64> = subproblem;
+    /// let lsr1: LSR1TrustRegion<_, Vec<f64>, f64> = LSR1TrustRegion::new(subproblem, 5);
+    /// ```
+    pub fn new(subproblem: R, m: usize) -> Self {
+        LSR1TrustRegion {
+            subproblem,
+            m,
+            s: VecDeque::with_capacity(m),
+            y: VecDeque::with_capacity(m),
+            gamma: float!(1.0),
+            denominator_factor: float!(1e-8),
+            radius: float!(1.0),
+            eta: float!(0.5 * 1e-3),
+            tol_grad: float!(1e-3),
+        }
+    }
+
+    /// Set denominator factor
+    ///
+    /// If the denominator of an update is below the `denominator_factor` (scaled with the norms
+    /// of the vectors involved), then the update is skipped.
+    ///
+    /// Must be in `(0, 1)` and defaults to `1e-8`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LSR1TrustRegion;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let subproblem = ();
+    /// let lsr1: LSR1TrustRegion<_, Vec<f64>, f64> =
+    ///     LSR1TrustRegion::new(subproblem, 5).with_denominator_factor(1e-7)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_denominator_factor(mut self, denominator_factor: F) -> Result<Self, Error> {
+        if denominator_factor <= float!(0.0) || denominator_factor >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`LSR1TrustRegion`: denominator_factor must be in (0, 1)."
+            ));
+        }
+        self.denominator_factor = denominator_factor;
+        Ok(self)
+    }
+
+    /// Set initial radius
+    ///
+    /// Defaults to 1.0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LSR1TrustRegion;
+    /// # let subproblem = ();
+    /// let lsr1: LSR1TrustRegion<_, Vec<f64>, f64> =
+    ///     LSR1TrustRegion::new(subproblem, 5).with_radius(2.0);
+    /// ```
+    #[must_use]
+    pub fn with_radius(mut self, radius: F) -> Self {
+        self.radius = radius.abs();
+        self
+    }
+
+    /// Set eta
+    ///
+    /// A step is taken if the actual reducation over the predicted reduction exceeds eta.
+    /// Must be in (0, 10^-3) and defaults to 0.5 * 10^-3.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LSR1TrustRegion;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let subproblem = ();
+    /// let lsr1: LSR1TrustRegion<_, Vec<f64>, f64> =
+    ///     LSR1TrustRegion::new(subproblem, 5).with_eta(1e-4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_eta(mut self, eta: F) -> Result<Self, Error> {
+        if eta >= float!(1e-3) || eta <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`LSR1TrustRegion`: eta must be in (0, 10^-3)."
+            ));
+        }
+        self.eta = eta;
+        Ok(self)
+    }
+
+    /// The algorithm stops if the norm of the gradient is below `tol_grad`.
+    ///
+    /// The provided value must be non-negative. Defaults to `10^-3`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LSR1TrustRegion;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let subproblem = ();
+    /// let lsr1: LSR1TrustRegion<_, Vec<f64>, f64> =
+    ///     LSR1TrustRegion::new(subproblem, 5).with_tolerance_grad(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_grad(mut self, tol_grad: F) -> Result<Self, Error> {
+        if tol_grad < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`LSR1TrustRegion`: gradient tolerance must be >= 0."
+            ));
+        }
+        self.tol_grad = tol_grad;
+        Ok(self)
+    }
+}
+
+impl<R, P, F> LSR1TrustRegion<R, P, F>
+where
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminAdd<P, P>
+        + ArgminDot<P, F>
+        + ArgminMul<F, P>
+        + ArgminNorm<F>,
+    F: ArgminFloat,
+{
+    /// Hessian approximation built from the stored pairs
+    fn hessian(&self) -> LSR1Hessian<P, F> {
+        LSR1Hessian::new(
+            self.gamma,
+            self.s.iter().zip(self.y.iter()),
+            self.denominator_factor,
+        )
+    }
+}
+
+impl<O, R, P, G, F> Solver<O, IterState<P, G, (), (), F>> for LSR1TrustRegion<R, P, F>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
+    P: Clone
+        + SerializeAlias
+        + DeserializeOwnedAlias
+        + ArgminSub<P, P>
+        + ArgminAdd<P, P>
+        + ArgminDot<P, F>
+        + ArgminMul<F, P>
+        + ArgminNorm<F>
+        + ArgminZeroLike,
+    G: Clone
+        + SerializeAlias
+        + DeserializeOwnedAlias
+        + ArgminNorm<F>
+        + ArgminDot<P, F>
+        + ArgminSub<G, P>,
+    R: Clone + TrustRegionRadius<F> + Solver<O, IterState<P, G, (), LSR1Hessian<P, F>, F>>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "L-SR1 trust region";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`LSR1TrustRegion` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let cost = state.get_cost();
+        let cost = if cost.is_infinite() {
+            problem.cost(&param)?
+        } else {
+            cost
+        };
+
+        let grad = state
+            .take_grad()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&param))?;
+
+        Ok((state.param(param).cost(cost).grad(grad), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), F>, Option<KV>), Error> {
+        let xk = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`LSR1TrustRegion`: Parameter vector in state not set."
+        ))?;
+
+        let cost = state.get_cost();
+
+        let prev_grad = state.take_grad().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`LSR1TrustRegion`: Gradient in state not set."
+        ))?;
+
+        let hessian = self.hessian();
+
+        self.subproblem.set_radius(self.radius);
+
+        let OptimizationResult {
+            problem: sub_problem,
+            state: mut sub_state,
+            ..
+        } = Executor::new(problem.take_problem().unwrap(), self.subproblem.clone())
+            .configure(|config| {
+                config
+                    .param(xk.zero_like())
+                    .hessian(hessian.clone())
+                    .grad(prev_grad.clone())
+                    .cost(cost)
+            })
+            .ctrlc(false)
+            .run()?;
+
+        let sk = sub_state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`LSR1TrustRegion`: No parameters returned by trust region subproblem."
+        ))?;
+
+        problem.consume_problem(sub_problem);
+
+        let xksk = xk.add(&sk);
+        let dfk1 = problem.gradient(&xksk)?;
+        let yk = dfk1.sub(&prev_grad);
+        let fk1 = problem.cost(&xksk)?;
+
+        let ared = cost - fk1;
+        let gs: F = prev_grad.dot(&sk);
+        let sbs: F = sk.dot(&hessian.dot(&sk));
+        let pred = -gs - float!(0.5) * sbs;
+        let ap = ared / pred;
+
+        let (xk1, fk1, dfk1) = if ap > self.eta {
+            (xksk, fk1, dfk1)
+        } else {
+            (xk, cost, prev_grad)
+        };
+
+        self.radius = if ap > float!(0.75) {
+            if sk.norm() <= float!(0.8) * self.radius {
+                self.radius
+            } else {
+                float!(2.0) * self.radius
+            }
+        } else if ap <= float!(0.75) && ap >= float!(0.1) {
+            self.radius
+        } else {
+            float!(0.5) * self.radius
+        };
+
+        // Store the new pair if it passes the skipping rule with respect to the current
+        // approximation. The pair is stored regardless of whether the step was accepted.
+        let hessian_update = hessian.clone().update(&sk, &yk, self.denominator_factor);
+        if hessian_update {
+            let sy: F = sk.dot(&yk);
+            if sy > float!(0.0) {
+                self.gamma = yk.dot(&yk) / sy;
+            }
+            if self.s.len() >= self.m {
+                self.s.pop_front();
+                self.y.pop_front();
+            }
+            self.s.push_back(sk);
+            self.y.push_back(yk);
+        }
+
+        Ok((
+            state.param(xk1).cost(fk1).grad(dfk1),
+            Some(make_kv!["ared" => ared;
+                         "pred" => pred;
+                         "ap" => ap;
+                         "radius" => self.radius;
+                         "hessian_update" => hessian_update;
+                         "memory" => self.s.len();]),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, G, (), (), F>) -> TerminationReason {
+        if state.get_grad().unwrap().norm() < self.tol_grad {
+            return TerminationReason::TargetPrecisionReached;
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, State};
+    use crate::solver::trustregion::{CauchyPoint, Steihaug};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(lsr1, LSR1TrustRegion<CauchyPoint<f64>, Vec<f64>, f64>);
+
+    test_trait_impl!(lsr1_hessian, LSR1Hessian<Vec<f64>, f64>);
+
+    #[test]
+    fn test_new() {
+        #[derive(Eq, PartialEq, Debug)]
+        struct MyFakeSubProblem {}
+
+        let lsr1: LSR1TrustRegion<_, Vec<f64>, f64> = LSR1TrustRegion::new(MyFakeSubProblem {}, 7);
+        let LSR1TrustRegion {
+            subproblem,
+            m,
+            s,
+            y,
+            gamma,
+            denominator_factor,
+            radius,
+            eta,
+            tol_grad,
+        } = lsr1;
+
+        assert_eq!(subproblem, MyFakeSubProblem {});
+        assert_eq!(m, 7);
+        assert!(s.is_empty());
+        assert!(y.is_empty());
+        assert_eq!(gamma.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(denominator_factor.to_ne_bytes(), 1e-8f64.to_ne_bytes());
+        assert_eq!(radius.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(eta.to_ne_bytes(), (0.5f64 * 1e-3f64).to_ne_bytes());
+        assert_eq!(tol_grad.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_with_denominator_factor() {
+        for tol in [f64::EPSILON, 1e-8, 1e-2, 1.0 - f64::EPSILON] {
+            let lsr1: LSR1TrustRegion<_, Vec<f64>, f64> = LSR1TrustRegion::new((), 5);
+            let lsr1 = lsr1.with_denominator_factor(tol).unwrap();
+            assert_eq!(lsr1.denominator_factor.to_ne_bytes(), tol.to_ne_bytes());
+        }
+
+        for tol in [-f64::EPSILON, 0.0, -1.0, 1.0] {
+            let lsr1: LSR1TrustRegion<_, Vec<f64>, f64> = LSR1TrustRegion::new((), 5);
+            assert_error!(
+                lsr1.with_denominator_factor(tol),
+                ArgminError,
+                "Invalid parameter: \"`LSR1TrustRegion`: denominator_factor must be in (0, 1).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_eta() {
+        for eta in [f64::EPSILON, 1e-4, 1e-3 - f64::EPSILON] {
+            let lsr1: LSR1TrustRegion<_, Vec<f64>, f64> = LSR1TrustRegion::new((), 5);
+            let lsr1 = lsr1.with_eta(eta).unwrap();
+            assert_eq!(lsr1.eta.to_ne_bytes(), eta.to_ne_bytes());
+        }
+
+        for eta in [0.0, -1.0, 1e-3, 2e-3, 10e-3] {
+            let lsr1: LSR1TrustRegion<_, Vec<f64>, f64> = LSR1TrustRegion::new((), 5);
+            assert_error!(
+                lsr1.with_eta(eta),
+                ArgminError,
+                "Invalid parameter: \"`LSR1TrustRegion`: eta must be in (0, 10^-3).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_tolerance_grad() {
+        let lsr1: LSR1TrustRegion<_, Vec<f64>, f64> = LSR1TrustRegion::new((), 5);
+        let lsr1 = lsr1.with_tolerance_grad(1e-6).unwrap();
+        assert_eq!(lsr1.tol_grad.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+
+        let lsr1: LSR1TrustRegion<_, Vec<f64>, f64> = LSR1TrustRegion::new((), 5);
+        assert_error!(
+            lsr1.with_tolerance_grad(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`LSR1TrustRegion`: gradient tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_hessian_matches_dense_sr1() {
+        let pairs = [
+            (vec![1.0, 0.0, 0.5], vec![2.0, 0.3, 0.1]),
+            (vec![0.0, 1.0, -0.5], vec![0.1, -1.0, 0.4]),
+            (vec![0.3, 0.2, 1.0], vec![0.5, 0.2, 3.0]),
+        ];
+        let gamma = 1.5;
+
+        // Dense SR1 updates starting from `gamma * I`
+        let mut b = vec![vec![0.0; 3]; 3];
+        for (i, row) in b.iter_mut().enumerate() {
+            row[i] = gamma;
+        }
+        for (s, y) in pairs.iter() {
+            let bs: Vec<f64> = b
+                .iter()
+                .map(|row| row.iter().zip(s.iter()).map(|(a, b)| a * b).sum())
+                .collect();
+            let u: Vec<f64> = y.sub(&bs);
+            let denom: f64 = u.dot(s);
+            for i in 0..3 {
+                for j in 0..3 {
+                    b[i][j] += u[i] * u[j] / denom;
+                }
+            }
+        }
+
+        let hessian = LSR1Hessian::new(gamma, pairs.iter().map(|(s, y)| (s, y)), 1e-8);
+        assert_eq!(hessian.u.len(), 3);
+
+        let v = vec![0.7, -1.2, 2.0];
+        let bv_dense: Vec<f64> = b
+            .iter()
+            .map(|row| row.iter().zip(v.iter()).map(|(a, b)| a * b).sum())
+            .collect();
+        let bv = hessian.dot(&v);
+        for i in 0..3 {
+            assert_relative_eq!(bv[i], bv_dense[i], epsilon = 1e-12);
+        }
+
+        // The secant condition holds for the most recent pair
+        let (s, y) = pairs.last().unwrap();
+        let bs = hessian.dot(s);
+        for i in 0..3 {
+            assert_relative_eq!(bs[i], y[i], epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_hessian_skips_update() {
+        // `y = gamma * s` leads to `u = 0`, therefore the update must be skipped.
+        let s = vec![1.0, 2.0];
+        let y = vec![2.0, 4.0];
+        let hessian = LSR1Hessian::new(2.0, std::iter::once((&s, &y)), 1e-8);
+        assert!(hessian.u.is_empty());
+        assert_eq!(hessian.dot(&vec![1.0, -1.0]), vec![2.0, -2.0]);
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut lsr1: LSR1TrustRegion<Steihaug<Vec<f64>, f64>, Vec<f64>, f64> =
+            LSR1TrustRegion::new(Steihaug::new(), 5);
+        let res = lsr1.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`LSR1TrustRegion` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    /// Extended Rosenbrock function
+    struct ExtendedRosenbrock {}
+
+    impl CostFunction for ExtendedRosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.chunks(2)
+                .map(|x| 100.0 * (x[1] - x[0].powi(2)).powi(2) + (1.0 - x[0]).powi(2))
+                .sum())
+        }
+    }
+
+    impl Gradient for ExtendedRosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(p.chunks(2)
+                .flat_map(|x| {
+                    let t = x[1] - x[0].powi(2);
+                    vec![-400.0 * x[0] * t - 2.0 * (1.0 - x[0]), 200.0 * t]
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_lsr1_steihaug() {
+        let n = 100;
+        let init_param: Vec<f64> = (0..n)
+            .map(|i| if i % 2 == 0 { -1.2 } else { 1.0 })
+            .collect();
+        let subproblem = Steihaug::new().with_max_iters(50);
+        let solver = LSR1TrustRegion::new(subproblem, 7)
+            .with_tolerance_grad(1e-6)
+            .unwrap();
+
+        let res = Executor::new(ExtendedRosenbrock {}, solver)
+            .configure(|state| state.param(init_param).max_iters(1000))
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            res.state.get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        for x in res.state.get_best_param().unwrap() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-5);
+        }
+        assert!(res.solver.s.len() <= 7);
+        assert!(res.state.get_func_counts()["gradient_count"] > 0);
+    }
+}
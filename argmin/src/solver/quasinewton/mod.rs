@@ -10,6 +10,7 @@
 //! * [`BFGS`]
 //! * [`DFP`]
 //! * [`LBFGS`]
+//! * [`LSR1TrustRegion`]
 //! * [`SR1`]
 //! * [`SR1TrustRegion`]
 //!
@@ -21,11 +22,15 @@
 mod bfgs;
 mod dfp;
 mod lbfgs;
+mod lsr1_trustregion;
 mod sr1;
 mod sr1_trustregion;
+mod update;
 
 pub use self::bfgs::BFGS;
 pub use self::dfp::DFP;
 pub use self::lbfgs::LBFGS;
+pub use self::lsr1_trustregion::{LSR1Hessian, LSR1TrustRegion};
 pub use self::sr1::SR1;
 pub use self::sr1_trustregion::SR1TrustRegion;
+pub use self::update::{InvHessianScaling, UpdatePolicy};
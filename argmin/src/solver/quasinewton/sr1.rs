@@ -5,11 +5,12 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use super::{InvHessianScaling, UpdatePolicy};
 use crate::core::{
     ArgminFloat, CostFunction, DeserializeOwnedAlias, Error, Executor, Gradient, IterState,
-    LineSearch, OptimizationResult, Problem, SerializeAlias, Solver, TerminationReason, KV,
+    LineSearch, OptimizationResult, Problem, SerializeAlias, Solver, State, TerminationReason, KV,
 };
-use argmin_math::{ArgminAdd, ArgminDot, ArgminMul, ArgminNorm, ArgminSub};
+use argmin_math::{ArgminAdd, ArgminDot, ArgminEye, ArgminMul, ArgminNorm, ArgminSub};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +18,18 @@ use serde::{Deserialize, Serialize};
 ///
 /// This method currently has problems: <https://github.com/argmin-rs/argmin/issues/221>.
 ///
+/// Updates with a too small denominator (see
+/// [`with_denominator_factor`](`SR1::with_denominator_factor`)) are skipped by default. This can
+/// be changed with [`with_update_policy`](`SR1::with_update_policy`). The inverse Hessian can be
+/// scaled with [`with_inv_hessian_scaling`](`SR1::with_inv_hessian_scaling`).
+///
+/// Unlike [`BFGS`](`crate::solver::quasinewton::BFGS`) and
+/// [`DFP`](`crate::solver::quasinewton::DFP`), `SR1` does not offer Powell's damping. Damping
+/// enforces `sᵀy > 0` in order to keep the approximation positive definite, whereas the SR1 update
+/// is deliberately allowed to produce indefinite approximations and does not require the
+/// curvature condition. Ill-conditioned SR1 updates are instead detected by the denominator
+/// safeguard.
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
@@ -36,6 +49,10 @@ pub struct SR1<L, F> {
     tol_grad: F,
     /// Tolerance for the stopping criterion based on the change of the cost stopping criterion
     tol_cost: F,
+    /// Scaling of the inverse Hessian
+    scaling: InvHessianScaling,
+    /// Handling of unsafe updates
+    update_policy: UpdatePolicy,
 }
 
 impl<L, F> SR1<L, F>
@@ -57,6 +74,8 @@ where
             linesearch,
             tol_grad: F::epsilon().sqrt(),
             tol_cost: F::epsilon(),
+            scaling: InvHessianScaling::None,
+            update_policy: UpdatePolicy::Skip,
         }
     }
 
@@ -64,7 +83,7 @@ where
     ///
     /// If the denominator of the update is below the `demoninator_factor` (scaled with other
     /// factors derived from the parameter vectors and the gradients), then the update of the
-    /// inverse Hessian will be skipped.
+    /// inverse Hessian is handled according to the [`UpdatePolicy`] (skipped by default).
     ///
     /// Must be in `(0, 1)` and defaults to `1e-8`.
     ///
@@ -142,6 +161,43 @@ where
         self.tol_cost = tol_cost;
         Ok(self)
     }
+
+    /// Set the scaling strategy of the inverse Hessian approximation.
+    ///
+    /// The scaling is only applied if `sᵀy > 0` and if the update is accepted, both with and
+    /// without scaling (see [`with_update_policy`](`SR1::with_update_policy`)). Defaults to
+    /// [`InvHessianScaling::None`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::{SR1, InvHessianScaling};
+    /// # let linesearch = ();
+    /// let sr1: SR1<_, f64> =
+    ///     SR1::new(linesearch).with_inv_hessian_scaling(InvHessianScaling::Initial);
+    /// ```
+    #[must_use]
+    pub fn with_inv_hessian_scaling(mut self, scaling: InvHessianScaling) -> Self {
+        self.scaling = scaling;
+        self
+    }
+
+    /// Set how updates with a too small denominator are handled.
+    ///
+    /// Defaults to [`UpdatePolicy::Skip`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::{SR1, UpdatePolicy};
+    /// # let linesearch = ();
+    /// let sr1: SR1<_, f64> = SR1::new(linesearch).with_update_policy(UpdatePolicy::Reset);
+    /// ```
+    #[must_use]
+    pub fn with_update_policy(mut self, update_policy: UpdatePolicy) -> Self {
+        self.update_policy = update_policy;
+        self
+    }
 }
 
 impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, F>> for SR1<L, F>
@@ -161,13 +217,15 @@ where
         + DeserializeOwnedAlias
         + ArgminSub<P, P>
         + ArgminNorm<F>
-        + ArgminSub<G, G>,
+        + ArgminSub<G, G>
+        + ArgminDot<G, F>,
     H: SerializeAlias
         + DeserializeOwnedAlias
         + ArgminDot<G, P>
         + ArgminDot<P, P>
         + ArgminAdd<H, H>
-        + ArgminMul<F, H>,
+        + ArgminMul<F, H>
+        + ArgminEye,
     L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), F>>,
     F: ArgminFloat,
 {
@@ -244,7 +302,7 @@ where
         let OptimizationResult {
             problem: line_problem,
             state: mut linesearch_state,
-            ..
+            solver: line_solver,
         } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
             .configure(|config| {
                 config
@@ -255,6 +313,8 @@ where
             .ctrlc(false)
             .run()?;
 
+        self.linesearch.consume_line_search(line_solver);
+
         let xk1 = linesearch_state.take_param().unwrap();
         let next_cost = linesearch_state.get_cost();
 
@@ -266,19 +326,67 @@ where
 
         let sk = xk1.sub(&param);
 
+        let yksk: F = sk.dot(&yk);
+
         // let skmhkyk: P = sk.sub(&inv_hessian.dot(&yk));
         // let a: H = skmhkyk.dot(&skmhkyk);
         // let b: F = skmhkyk.dot(&yk);
-        let ykmbksk: P = yk.sub(&inv_hessian.dot(&sk));
-        let a: H = ykmbksk.dot(&ykmbksk);
-        let b: F = ykmbksk.dot(&sk);
+        let mut ykmbksk: P = yk.sub(&inv_hessian.dot(&sk));
+        let mut b: F = ykmbksk.dot(&sk);
 
         // let hessian_update = b.abs() >= self.r * yk.norm() * skmhkyk.norm();
-        let hessian_update = b.abs() >= self.denominator_factor * sk.norm() * ykmbksk.norm();
-
-        if hessian_update {
-            inv_hessian = inv_hessian.add(&a.mul(&(float!(1.0) / b)));
-        }
+        let unsafe_update = b.abs() < self.denominator_factor * sk.norm() * ykmbksk.norm();
+
+        let update = match (unsafe_update, self.update_policy) {
+            (true, UpdatePolicy::Skip) => "skipped",
+            (true, UpdatePolicy::Reset) => {
+                let ykyk: F = yk.dot(&yk);
+                let gamma = yksk / ykyk;
+                let gamma = if gamma > float!(0.0) && gamma.is_finite() {
+                    gamma
+                } else {
+                    float!(1.0)
+                };
+                inv_hessian = inv_hessian.eye_like().mul(&gamma);
+                "reset"
+            }
+            _ => {
+                let scaled = match self.scaling {
+                    InvHessianScaling::Initial
+                        if state.get_iter() == 0 && yksk > float!(0.0) && !unsafe_update =>
+                    {
+                        let ykyk: F = yk.dot(&yk);
+                        Some(inv_hessian.eye_like().mul(&(yksk / ykyk)))
+                    }
+                    InvHessianScaling::SelfScaling if yksk > float!(0.0) && !unsafe_update => {
+                        let hy: P = inv_hessian.dot(&yk);
+                        let yhy: F = hy.dot(&yk);
+                        if yhy > float!(0.0) {
+                            Some(inv_hessian.mul(&(yksk / yhy)))
+                        } else {
+                            None
+                        }
+                    }
+                    _ => None,
+                };
+
+                // The scaled approximation is only used if its update is safe as well.
+                if let Some(scaled) = scaled {
+                    let scaled_ykmbksk: P = yk.sub(&scaled.dot(&sk));
+                    let scaled_b: F = scaled_ykmbksk.dot(&sk);
+                    if scaled_b.abs() >= self.denominator_factor * sk.norm() * scaled_ykmbksk.norm()
+                    {
+                        inv_hessian = scaled;
+                        ykmbksk = scaled_ykmbksk;
+                        b = scaled_b;
+                    }
+                }
+
+                let a: H = ykmbksk.dot(&ykmbksk);
+                inv_hessian = inv_hessian.add(&a.mul(&(float!(1.0) / b)));
+                "applied"
+            }
+        };
 
         Ok((
             state
@@ -286,7 +394,11 @@ where
                 .cost(next_cost)
                 .grad(grad)
                 .inv_hessian(inv_hessian),
-            Some(make_kv!["denominator" => b; "hessian_update" => hessian_update;]),
+            Some(make_kv![
+                "denominator" => b;
+                "hessian_update" => update != "skipped";
+                "update" => update;
+            ]),
         ))
     }
 
@@ -305,8 +417,11 @@ where
 mod tests {
     use super::*;
     use crate::core::{test_utils::TestProblem, ArgminError, IterState, State};
-    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::linesearch::{
+        condition::ArmijoCondition, BacktrackingLineSearch, MoreThuenteLineSearch,
+    };
     use crate::test_trait_impl;
+    use approx::assert_relative_eq;
 
     test_trait_impl!(
         sr1,
@@ -324,12 +439,16 @@ mod tests {
             linesearch,
             tol_grad,
             tol_cost,
+            scaling,
+            update_policy,
         } = sr1;
 
         assert_eq!(linesearch, MyFakeLineSearch {});
         assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
         assert_eq!(tol_cost.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
         assert_eq!(denominator_factor.to_ne_bytes(), 1e-8f64.to_ne_bytes());
+        assert_eq!(scaling, InvHessianScaling::None);
+        assert_eq!(update_policy, UpdatePolicy::Skip);
     }
 
     #[test]
@@ -532,4 +651,142 @@ mod tests {
             assert_eq!(s.to_ne_bytes(), g.to_ne_bytes());
         }
     }
+
+    #[test]
+    fn test_next_iter_update_policy() {
+        /// `f(x) = 0.5 xᵀAx` with `A = [[1, 1], [1, 3]]`
+        struct Quadratic {}
+
+        impl CostFunction for Quadratic {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(0.5 * p[0].powi(2) + p[0] * p[1] + 1.5 * p[1].powi(2))
+            }
+        }
+
+        impl Gradient for Quadratic {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![p[0] + p[1], p[0] + 3.0 * p[1]])
+            }
+        }
+
+        // The step `s = (1, 0)` leads to `y - Hs = (0, 1)`, which is orthogonal to `s`.
+        for (policy, expected_h, expected_update) in [
+            (UpdatePolicy::Skip, 1.0, "skipped"),
+            (UpdatePolicy::Reset, 0.5, "reset"),
+        ] {
+            let linesearch = BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+            let mut sr1: SR1<_, f64> = SR1::new(linesearch).with_update_policy(policy);
+            let mut problem = Problem::new(Quadratic {});
+            let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, f64> = IterState::new()
+                .param(vec![-1.5, 0.5])
+                .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+            let (state, _) = sr1.init(&mut problem, state).unwrap();
+            let (mut state, kv) = sr1.next_iter(&mut problem, state).unwrap();
+
+            let kv = kv.unwrap();
+            assert_eq!(kv.kv[1].0, "hessian_update");
+            assert_eq!(
+                format!("{}", kv.kv[1].1),
+                format!("{}", expected_update != "skipped")
+            );
+            assert_eq!(kv.kv[2].0, "update");
+            assert_eq!(format!("{}", kv.kv[2].1), expected_update);
+
+            let h = state.take_inv_hessian().unwrap();
+            assert_relative_eq!(h[0][0], expected_h, epsilon = f64::EPSILON);
+            assert_relative_eq!(h[0][1], 0.0, epsilon = f64::EPSILON);
+            assert_relative_eq!(h[1][0], 0.0, epsilon = f64::EPSILON);
+            assert_relative_eq!(h[1][1], expected_h, epsilon = f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_inv_hessian_scaling() {
+        /// `f(x) = 0.5 xᵀAx` with `A = [[1, 1], [1, 3]]`
+        struct Quadratic {}
+
+        impl CostFunction for Quadratic {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(0.5 * p[0].powi(2) + p[0] * p[1] + 1.5 * p[1].powi(2))
+            }
+        }
+
+        impl Gradient for Quadratic {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![p[0] + p[1], p[0] + 3.0 * p[1]])
+            }
+        }
+
+        // Starting from `(-1.5, 0.5)`, the step `s = (1, 0)` leads to `y = (1, 1)`. The unscaled
+        // update has a zero denominator and is skipped (see `test_next_iter_update_policy`),
+        // therefore no scaling is applied either.
+        for scaling in [
+            InvHessianScaling::None,
+            InvHessianScaling::Initial,
+            InvHessianScaling::SelfScaling,
+        ] {
+            let linesearch = BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+            let mut sr1: SR1<_, f64> = SR1::new(linesearch).with_inv_hessian_scaling(scaling);
+            let mut problem = Problem::new(Quadratic {});
+            let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, f64> = IterState::new()
+                .param(vec![-1.5, 0.5])
+                .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+            let (state, _) = sr1.init(&mut problem, state).unwrap();
+            let (mut state, kv) = sr1.next_iter(&mut problem, state).unwrap();
+
+            assert_eq!(format!("{}", kv.unwrap().kv[2].1), "skipped");
+            assert_eq!(
+                state.take_inv_hessian().unwrap(),
+                vec![vec![1.0, 0.0], vec![0.0, 1.0]]
+            );
+        }
+
+        // Starting from `(1, 1)`, the update is accepted. With `H = I`, both `Initial` and
+        // `SelfScaling` scale by `γ = sᵀy / yᵀy` before the update is applied.
+        for scaling in [
+            InvHessianScaling::None,
+            InvHessianScaling::Initial,
+            InvHessianScaling::SelfScaling,
+        ] {
+            let linesearch = BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+            let mut sr1: SR1<_, f64> = SR1::new(linesearch).with_inv_hessian_scaling(scaling);
+            let mut problem = Problem::new(Quadratic {});
+            let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, f64> = IterState::new()
+                .param(vec![1.0, 1.0])
+                .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+            let (state, _) = sr1.init(&mut problem, state).unwrap();
+            let (mut state, kv) = sr1.next_iter(&mut problem, state).unwrap();
+            assert_eq!(format!("{}", kv.unwrap().kv[2].1), "applied");
+
+            let x1 = state.get_param().unwrap();
+            let s = [x1[0] - 1.0, x1[1] - 1.0];
+            let y = [s[0] + s[1], s[0] + 3.0 * s[1]];
+            let gamma = match scaling {
+                InvHessianScaling::None => 1.0,
+                _ => (s[0] * y[0] + s[1] * y[1]) / (y[0] * y[0] + y[1] * y[1]),
+            };
+            let r = [y[0] - gamma * s[0], y[1] - gamma * s[1]];
+            let denominator = r[0] * s[0] + r[1] * s[1];
+
+            let h = state.take_inv_hessian().unwrap();
+            for i in 0..2 {
+                for j in 0..2 {
+                    let expected = if i == j { gamma } else { 0.0 } + r[i] * r[j] / denominator;
+                    assert_relative_eq!(h[i][j], expected, epsilon = 1e-12);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,50 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Scaling strategies for the inverse Hessian approximation of quasi-Newton methods
+///
+/// Both strategies use the scaling factor `γ = sᵀy / yᵀy` (or a variant thereof), where `s` is
+/// the step and `y` is the change of the gradient of the current iteration.
+///
+/// ## Reference
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum InvHessianScaling {
+    /// Use the provided initial inverse Hessian as is
+    #[default]
+    None,
+    /// Replace the initial inverse Hessian with `(sᵀy / yᵀy) I` before the first update
+    /// (Shanno and Phua)
+    Initial,
+    /// Scale the inverse Hessian `H` by `sᵀy / yᵀHy` before every update (Oren and Luenberger)
+    SelfScaling,
+}
+
+/// Policy for quasi-Newton updates which are considered unsafe
+///
+/// For [`BFGS`](`crate::solver::quasinewton::BFGS`) and
+/// [`DFP`](`crate::solver::quasinewton::DFP`) an update is unsafe if the curvature condition
+/// `sᵀy > EPSILON ‖s‖ ‖y‖` is violated. For [`SR1`](`crate::solver::quasinewton::SR1`) an update
+/// is unsafe if the denominator of the update is too small (see
+/// [`SR1::with_denominator_factor`](`crate::solver::quasinewton::SR1::with_denominator_factor`)).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum UpdatePolicy {
+    /// Always perform the update
+    Always,
+    /// Skip unsafe updates and keep the current inverse Hessian approximation
+    Skip,
+    /// Reset the inverse Hessian approximation to a scaled identity matrix `(sᵀy / yᵀy) I` on
+    /// unsafe updates. The identity is used if `sᵀy / yᵀy` is not positive.
+    Reset,
+}
@@ -0,0 +1,505 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::manifold::{retracted_line_search, RetractionProblem};
+use super::Manifold;
+use crate::core::{
+    ArgminFloat, CostFunction, DeserializeOwnedAlias, Error, Gradient, IterState, Problem,
+    SerializeAlias, Solver, State, TerminationReason, KV,
+};
+use crate::solver::linesearch::LineSearch;
+use argmin_math::{ArgminMul, ArgminScaledSub, ArgminSub, ArgminZeroLike};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Update formula for `beta` in [`RiemannianConjugateGradient`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum RiemannianBeta {
+    /// Fletcher-Reeves: `<g_{k+1}, g_{k+1}> / <g_k, g_k>`
+    FletcherReeves,
+    /// Polak-Ribière+: `max(0, <g_{k+1}, g_{k+1} - T(g_k)> / <g_k, g_k>)` (default)
+    #[default]
+    PolakRibierePlus,
+}
+
+/// # Riemannian conjugate gradient method
+///
+/// Nonlinear conjugate gradient method on a [`Manifold`]. The previous search direction and
+/// gradient are moved to the tangent space at the new iterate via the vector transport of the
+/// manifold. `beta` is computed according to [`RiemannianBeta`] (default: Polak-Ribière+). The
+/// line search is performed on the cost function pulled back to the tangent space via the
+/// retraction; any of the line searches in [`linesearch`](`crate::solver::linesearch`) can be
+/// used.
+///
+/// The method is restarted with the negative gradient if the search direction is not a descent
+/// direction or after the number of iterations set with
+/// [`with_restart_iters`](`RiemannianConjugateGradient::with_restart_iters`).
+///
+/// Terminates once the norm of the Riemannian gradient falls below the tolerance set with
+/// [`with_tolerance`](`RiemannianConjugateGradient::with_tolerance`).
+///
+/// Requires an initial parameter vector, which must be a point on the manifold.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
+///
+/// ## References
+///
+/// P.-A. Absil, R. Mahony and R. Sepulchre (2008). Optimization Algorithms on Matrix Manifolds.
+/// Princeton University Press. ISBN 978-0-691-13298-3.
+///
+/// Hiroyuki Sato (2021). Riemannian Optimization and Its Applications. Springer.
+/// ISBN 978-3-030-62389-0.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct RiemannianConjugateGradient<M, L, P, F> {
+    /// Manifold
+    manifold: M,
+    /// line search
+    linesearch: L,
+    /// beta update method
+    beta_method: RiemannianBeta,
+    /// Tolerance for the norm of the Riemannian gradient
+    tol: F,
+    /// Number of iterations after which a restart is performed
+    restart_iter: u64,
+    /// Search direction
+    p: Option<P>,
+    /// Euclidean gradient at the current iterate, projected onto the tangent space
+    egrad: Option<P>,
+    /// beta
+    beta: F,
+}
+
+impl<M, L, P, F> RiemannianConjugateGradient<M, L, P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`RiemannianConjugateGradient`]
+    ///
+    /// Requires a manifold and a line search.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::riemannian::{RiemannianConjugateGradient, Sphere};
+    /// # let linesearch = ();
+    /// let cg: RiemannianConjugateGradient<_, _, Vec<f64>, f64> =
+    ///     RiemannianConjugateGradient::new(Sphere::new(), linesearch);
+    /// ```
+    pub fn new(manifold: M, linesearch: L) -> Self {
+        RiemannianConjugateGradient {
+            manifold,
+            linesearch,
+            beta_method: RiemannianBeta::default(),
+            tol: F::epsilon().sqrt(),
+            restart_iter: u64::MAX,
+            p: None,
+            egrad: None,
+            beta: F::nan(),
+        }
+    }
+
+    /// Set the update formula for `beta`.
+    ///
+    /// Defaults to [`RiemannianBeta::PolakRibierePlus`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::riemannian::{RiemannianBeta, RiemannianConjugateGradient, Sphere};
+    /// # let linesearch = ();
+    /// let cg: RiemannianConjugateGradient<_, _, Vec<f64>, f64> =
+    ///     RiemannianConjugateGradient::new(Sphere::new(), linesearch)
+    ///         .with_beta(RiemannianBeta::FletcherReeves);
+    /// ```
+    #[must_use]
+    pub fn with_beta(mut self, beta_method: RiemannianBeta) -> Self {
+        self.beta_method = beta_method;
+        self
+    }
+
+    /// Specify the number of iterations after which a restart should be performed.
+    ///
+    /// Defaults to `u64::MAX` (no restarts).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::riemannian::{RiemannianConjugateGradient, Sphere};
+    /// # let linesearch = ();
+    /// let cg: RiemannianConjugateGradient<_, _, Vec<f64>, f64> =
+    ///     RiemannianConjugateGradient::new(Sphere::new(), linesearch).with_restart_iters(20);
+    /// ```
+    #[must_use]
+    pub fn with_restart_iters(mut self, iters: u64) -> Self {
+        self.restart_iter = iters;
+        self
+    }
+
+    /// Set tolerance for the norm of the Riemannian gradient.
+    ///
+    /// Tolerance must be larger than zero and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::riemannian::{RiemannianConjugateGradient, Sphere};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let cg: RiemannianConjugateGradient<_, _, Vec<f64>, f64> =
+    ///     RiemannianConjugateGradient::new(Sphere::new(), linesearch).with_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RiemannianConjugateGradient`: tol must be positive."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+}
+
+impl<O, M, L, P, F> Solver<O, IterState<P, P, (), (), F>>
+    for RiemannianConjugateGradient<M, L, P, F>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = P>,
+    M: Clone + Manifold<P, F>,
+    L: Clone + LineSearch<P, F> + Solver<RetractionProblem<O, M, P>, IterState<P, P, (), (), F>>,
+    P: Clone
+        + SerializeAlias
+        + DeserializeOwnedAlias
+        + ArgminMul<F, P>
+        + ArgminSub<P, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminZeroLike,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Riemannian Conjugate Gradient";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, P, (), (), F>,
+    ) -> Result<(IterState<P, P, (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`RiemannianConjugateGradient` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let cost = problem.cost(param)?;
+        let egrad = problem.gradient(param)?;
+        let rgrad = self
+            .manifold
+            .euclidean_to_riemannian_gradient(param, &egrad)?;
+        self.egrad = Some(self.manifold.project(param, &egrad)?);
+        self.p = Some(rgrad.mul(&(float!(-1.0))));
+        Ok((state.cost(cost).grad(rgrad), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, P, (), (), F>,
+    ) -> Result<(IterState<P, P, (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`RiemannianConjugateGradient`: No `param` in `state`"
+        ))?;
+        let rgrad = state.take_grad().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`RiemannianConjugateGradient`: No `grad` in `state`"
+        ))?;
+        let egrad = self.egrad.take().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`RiemannianConjugateGradient`: Field `egrad` not set"
+        ))?;
+        let p = self.p.take().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`RiemannianConjugateGradient`: Field `p` not set"
+        ))?;
+        let cost = state.get_cost();
+
+        // Fall back to steepest descent if `p` is not a descent direction
+        let restart_descent = self.manifold.inner(&param, &rgrad, &p)? >= float!(0.0);
+        let p = if restart_descent {
+            rgrad.mul(&(float!(-1.0)))
+        } else {
+            p
+        };
+
+        let (new_param, new_cost) = retracted_line_search(
+            problem,
+            &self.manifold,
+            &mut self.linesearch,
+            &param,
+            p.clone(),
+            egrad,
+            cost,
+        )?;
+
+        let egrad = problem.gradient(&new_param)?;
+        let new_rgrad = self
+            .manifold
+            .euclidean_to_riemannian_gradient(&new_param, &egrad)?;
+        self.egrad = Some(self.manifold.project(&new_param, &egrad)?);
+
+        // Update of beta
+        let restart_iter: bool =
+            state.get_iter().is_multiple_of(self.restart_iter) && state.get_iter() != 0;
+        let denom = self.manifold.inner(&param, &rgrad, &rgrad)?;
+        self.beta = if restart_iter || denom <= float!(0.0) {
+            float!(0.0)
+        } else {
+            match self.beta_method {
+                RiemannianBeta::FletcherReeves => {
+                    self.manifold.inner(&new_param, &new_rgrad, &new_rgrad)? / denom
+                }
+                RiemannianBeta::PolakRibierePlus => {
+                    let transported = self.manifold.transport(&param, &new_param, &rgrad)?;
+                    let diff = new_rgrad.sub(&transported);
+                    (self.manifold.inner(&new_param, &new_rgrad, &diff)? / denom).max(float!(0.0))
+                }
+            }
+        };
+
+        // Update of p: -g_{k+1} + beta T(p_k)
+        let transported_p = self.manifold.transport(&param, &new_param, &p)?;
+        self.p = Some(
+            transported_p
+                .mul(&self.beta)
+                .scaled_sub(&float!(1.0), &new_rgrad),
+        );
+
+        Ok((
+            state.param(new_param).cost(new_cost).grad(new_rgrad),
+            Some(make_kv!(
+                "beta" => self.beta;
+                "restart_iter" => restart_iter;
+                "restart_descent" => restart_descent;
+            )),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, P, (), (), F>) -> TerminationReason {
+        if let (Some(param), Some(grad)) = (state.get_param(), state.get_grad()) {
+            if matches!(self.manifold.norm(param, grad), Ok(norm) if norm <= self.tol) {
+                return TerminationReason::TargetPrecisionReached;
+            }
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::linesearch::{
+        condition::ArmijoCondition, BacktrackingLineSearch, MoreThuenteLineSearch,
+    };
+    use crate::solver::riemannian::{Sphere, Stiefel};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        riemannian_cg,
+        RiemannianConjugateGradient<Sphere, MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, Vec<f64>, f64>
+    );
+
+    /// Brockett cost function `tr(XᵀAXN)` on the Stiefel manifold `St(n, p)` with `A` symmetric
+    /// and `N = diag(p, ..., 1)`. Minimized by the eigenvectors of the `p` smallest eigenvalues of
+    /// `A` in increasing order.
+    struct Brockett {
+        a: Vec<f64>,
+        n: usize,
+        p: usize,
+    }
+
+    impl Brockett {
+        fn ax(&self, x: &[f64]) -> Vec<f64> {
+            let (n, p) = (self.n, self.p);
+            let mut ax = vec![0.0; n * p];
+            for i in 0..n {
+                for k in 0..n {
+                    for j in 0..p {
+                        ax[i * p + j] += self.a[i * n + k] * x[k * p + j];
+                    }
+                }
+            }
+            ax
+        }
+    }
+
+    impl CostFunction for Brockett {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+            let p = self.p;
+            Ok(self
+                .ax(x)
+                .iter()
+                .zip(x.iter())
+                .enumerate()
+                .map(|(idx, (ax, x))| (p - idx % p) as f64 * ax * x)
+                .sum())
+        }
+    }
+
+    impl Gradient for Brockett {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, x: &Self::Param) -> Result<Self::Gradient, Error> {
+            let p = self.p;
+            Ok(self
+                .ax(x)
+                .iter()
+                .enumerate()
+                .map(|(idx, ax)| 2.0 * (p - idx % p) as f64 * ax)
+                .collect())
+        }
+    }
+
+    fn brockett() -> Brockett {
+        // Eigenvalues 1, 2, 4, 7 with eigenvectors e_3, e_1, e_4, e_2
+        Brockett {
+            a: vec![
+                2.0, 0.0, 0.0, 0.0, //
+                0.0, 7.0, 0.0, 0.0, //
+                0.0, 0.0, 1.0, 0.0, //
+                0.0, 0.0, 0.0, 4.0,
+            ],
+            n: 4,
+            p: 2,
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let cg: RiemannianConjugateGradient<_, _, Vec<f64>, f64> =
+            RiemannianConjugateGradient::new(Sphere::new(), ());
+        let RiemannianConjugateGradient {
+            manifold,
+            beta_method,
+            tol,
+            restart_iter,
+            p,
+            egrad,
+            ..
+        } = cg;
+        assert_eq!(manifold, Sphere::new());
+        assert_eq!(beta_method, RiemannianBeta::PolakRibierePlus);
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(restart_iter, u64::MAX);
+        assert!(p.is_none());
+        assert!(egrad.is_none());
+    }
+
+    #[test]
+    fn test_builders() {
+        let cg: RiemannianConjugateGradient<_, _, Vec<f64>, f64> =
+            RiemannianConjugateGradient::new(Sphere::new(), ())
+                .with_beta(RiemannianBeta::FletcherReeves)
+                .with_restart_iters(10)
+                .with_tolerance(1e-4)
+                .unwrap();
+        assert_eq!(cg.beta_method, RiemannianBeta::FletcherReeves);
+        assert_eq!(cg.restart_iter, 10);
+        assert_eq!(cg.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        for tol in [0.0, -1.0] {
+            let res: Result<RiemannianConjugateGradient<_, _, Vec<f64>, f64>, _> =
+                RiemannianConjugateGradient::new(Sphere::new(), ()).with_tolerance(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`RiemannianConjugateGradient`: tol must be positive.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let mut cg = RiemannianConjugateGradient::new(Stiefel::new(4, 2).unwrap(), linesearch);
+        let res = cg.init(&mut Problem::new(brockett()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`RiemannianConjugateGradient` requires an initial parameter ",
+                "vector. Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_stiefel_brockett() {
+        // Orthonormal initial frame
+        let h = 0.5;
+        let init = vec![h, h, h, -h, h, h, h, -h];
+        for beta in [
+            RiemannianBeta::PolakRibierePlus,
+            RiemannianBeta::FletcherReeves,
+        ] {
+            let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+                MoreThuenteLineSearch::new();
+            let cg = RiemannianConjugateGradient::new(Stiefel::new(4, 2).unwrap(), linesearch)
+                .with_beta(beta)
+                .with_restart_iters(8)
+                .with_tolerance(1e-9)
+                .unwrap();
+            let res = Executor::new(brockett(), cg)
+                .configure(|state| state.param(init.clone()).max_iters(500))
+                .ctrlc(false)
+                .run()
+                .unwrap();
+
+            assert_eq!(
+                res.state().get_termination_reason(),
+                TerminationReason::TargetPrecisionReached
+            );
+            // 2 * 1 + 1 * 2
+            assert_relative_eq!(res.state().get_best_cost(), 4.0, epsilon = 1e-12);
+            let x = res.state().get_best_param().unwrap();
+            // First column is ±e_3, second column is ±e_1
+            assert_relative_eq!(x[4].abs(), 1.0, epsilon = 1e-8);
+            assert_relative_eq!(x[1].abs(), 1.0, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_restart_descent() {
+        // Search direction pointing uphill is replaced by the negative gradient
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+        let mut cg = RiemannianConjugateGradient::new(Stiefel::new(4, 2).unwrap(), linesearch);
+        let mut problem = Problem::new(brockett());
+        let h = 0.5;
+        let state = IterState::new().param(vec![h, h, h, -h, h, h, h, -h]);
+        let (state, _) = cg.init(&mut problem, state).unwrap();
+        cg.p = Some(cg.p.take().unwrap().mul(&(-1.0)));
+        let cost = state.get_cost();
+        let (state, kv) = cg.next_iter(&mut problem, state).unwrap();
+        let kv = kv.unwrap();
+        assert_eq!(kv.kv[2].0, "restart_descent");
+        assert_eq!(format!("{}", kv.kv[2].1), "true");
+        assert!(state.get_cost() < cost);
+    }
+}
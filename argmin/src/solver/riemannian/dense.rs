@@ -0,0 +1,48 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Matrices stored as flat vectors in row-major order.
+//!
+//! Linear algebra is done by converting them into the vector of rows representation of the
+//! `vec` backend of `argmin-math`.
+
+use crate::core::ArgminFloat;
+
+/// Rows of the matrix `a` with `n` columns
+pub(super) fn to_rows<F: ArgminFloat>(a: &[F], n: usize) -> Vec<Vec<F>> {
+    a.chunks(n).map(|row| row.to_vec()).collect()
+}
+
+/// Flat row-major representation of the matrix with the rows `a`
+pub(super) fn from_rows<F: ArgminFloat>(a: Vec<Vec<F>>) -> Vec<F> {
+    a.into_iter().flatten().collect()
+}
+
+/// Symmetric part `(A + Aᵀ) / 2` of the `n x n` matrix `A`
+pub(super) fn sym<F: ArgminFloat>(a: &[F], n: usize) -> Vec<F> {
+    let mut s = vec![float!(0.0); n * n];
+    for i in 0..n {
+        for j in 0..n {
+            s[i * n + j] = float!(0.5) * (a[i * n + j] + a[j * n + i]);
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows() {
+        let a = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let rows = to_rows(&a, 3);
+        assert_eq!(rows, vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        assert_eq!(from_rows(rows), a);
+        assert_eq!(sym(&[1.0, 2.0, 4.0, 3.0], 2), vec![1.0, 3.0, 3.0, 3.0]);
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, DeserializeOwnedAlias, Error, Executor, Gradient, IterState,
+    OptimizationResult, Problem, SerializeAlias, Solver,
+};
+use crate::solver::linesearch::LineSearch;
+use argmin_math::ArgminZeroLike;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Riemannian manifold embedded in a Euclidean space
+///
+/// Points and tangent vectors are both represented as elements `P` of the embedding space. The
+/// cost function and its gradient are defined on the embedding space; the Euclidean gradient is
+/// turned into the Riemannian gradient by
+/// [`euclidean_to_riemannian_gradient`](`Manifold::euclidean_to_riemannian_gradient`).
+///
+/// All methods return an error if the dimensions of the arguments do not match the manifold.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::Error;
+/// use argmin::solver::riemannian::Manifold;
+/// #[cfg(feature = "serde1")]
+/// use serde::{Deserialize, Serialize};
+///
+/// /// Euclidean space (no constraints)
+/// #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+/// struct Euclidean {}
+///
+/// impl Manifold<Vec<f64>, f64> for Euclidean {
+///     fn inner(&self, _x: &Vec<f64>, u: &Vec<f64>, v: &Vec<f64>) -> Result<f64, Error> {
+///         Ok(u.iter().zip(v.iter()).map(|(u, v)| u * v).sum())
+///     }
+///
+///     fn project(&self, _x: &Vec<f64>, v: &Vec<f64>) -> Result<Vec<f64>, Error> {
+///         Ok(v.clone())
+///     }
+///
+///     fn retract(&self, x: &Vec<f64>, v: &Vec<f64>) -> Result<Vec<f64>, Error> {
+///         Ok(x.iter().zip(v.iter()).map(|(x, v)| x + v).collect())
+///     }
+///
+///     fn transport(&self, _x: &Vec<f64>, _y: &Vec<f64>, v: &Vec<f64>) -> Result<Vec<f64>, Error> {
+///         Ok(v.clone())
+///     }
+/// }
+/// # let x = vec![1.0, 2.0];
+/// # assert_eq!(Euclidean {}.retract(&x, &vec![0.5, -1.0]).unwrap(), vec![1.5, 1.0]);
+/// # assert_eq!(Euclidean {}.norm(&x, &vec![3.0, 4.0]).unwrap(), 5.0);
+/// ```
+pub trait Manifold<P, F>: SerializeAlias {
+    /// Riemannian metric `<u, v>_x` of the tangent vectors `u` and `v` at `x`
+    fn inner(&self, x: &P, u: &P, v: &P) -> Result<F, Error>;
+
+    /// Orthogonal projection of a vector `v` of the embedding space onto the tangent space at `x`
+    fn project(&self, x: &P, v: &P) -> Result<P, Error>;
+
+    /// Retraction: maps the tangent vector `v` at `x` to a point on the manifold
+    fn retract(&self, x: &P, v: &P) -> Result<P, Error>;
+
+    /// Vector transport of the tangent vector `v` at `x` to the tangent space at `y`
+    fn transport(&self, x: &P, y: &P, v: &P) -> Result<P, Error>;
+
+    /// Riemannian gradient at `x` from the Euclidean gradient `egrad`
+    ///
+    /// Defaults to the projection onto the tangent space, which is correct for manifolds with
+    /// the metric inherited from the embedding space.
+    fn euclidean_to_riemannian_gradient(&self, x: &P, egrad: &P) -> Result<P, Error> {
+        self.project(x, egrad)
+    }
+
+    /// Norm of the tangent vector `v` at `x` induced by the Riemannian metric
+    fn norm(&self, x: &P, v: &P) -> Result<F, Error>
+    where
+        F: ArgminFloat,
+    {
+        Ok(self.inner(x, v, v)?.sqrt())
+    }
+}
+
+/// Cost function `ξ -> f(R_x(ξ))` on the tangent space at `x`, pulled back via the retraction.
+///
+/// The gradient is the Euclidean gradient of `f` at `R_x(ξ)` projected onto the tangent space at
+/// `x`. For `ξ = 0` its inner product with a tangent vector is the exact directional derivative,
+/// which allows to reuse line searches written for Euclidean spaces.
+#[doc(hidden)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub(super) struct RetractionProblem<O, M, P> {
+    /// User defined problem
+    pub(super) problem: O,
+    /// Manifold
+    manifold: M,
+    /// Base point `x`
+    base: P,
+}
+
+impl<O, M, P> RetractionProblem<O, M, P> {
+    /// Construct a new [`RetractionProblem`]
+    pub(super) fn new(problem: O, manifold: M, base: P) -> Self {
+        RetractionProblem {
+            problem,
+            manifold,
+            base,
+        }
+    }
+}
+
+impl<O, M, P, F> CostFunction for RetractionProblem<O, M, P>
+where
+    O: CostFunction<Param = P, Output = F>,
+    M: Manifold<P, F>,
+    P: Clone + SerializeAlias + DeserializeOwnedAlias,
+{
+    type Param = P;
+    type Output = F;
+
+    fn cost(&self, xi: &Self::Param) -> Result<Self::Output, Error> {
+        self.problem.cost(&self.manifold.retract(&self.base, xi)?)
+    }
+}
+
+impl<O, M, P, F> Gradient for RetractionProblem<O, M, P>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = P>,
+    M: Manifold<P, F>,
+    P: Clone + SerializeAlias + DeserializeOwnedAlias,
+{
+    type Param = P;
+    type Gradient = P;
+
+    fn gradient(&self, xi: &Self::Param) -> Result<Self::Gradient, Error> {
+        let egrad = self
+            .problem
+            .gradient(&self.manifold.retract(&self.base, xi)?)?;
+        self.manifold.project(&self.base, &egrad)
+    }
+}
+
+/// Runs the line search along the tangent vector `direction` at `x` on the pulled back cost
+/// function and returns the retracted point together with its cost.
+///
+/// `egrad` is the Euclidean gradient at `x` projected onto the tangent space and `cost` the cost
+/// function value at `x`.
+pub(super) fn retracted_line_search<O, M, L, P, F>(
+    problem: &mut Problem<O>,
+    manifold: &M,
+    linesearch: &mut L,
+    x: &P,
+    direction: P,
+    egrad: P,
+    cost: F,
+) -> Result<(P, F), Error>
+where
+    M: Clone + Manifold<P, F>,
+    L: Clone + LineSearch<P, F> + Solver<RetractionProblem<O, M, P>, IterState<P, P, (), (), F>>,
+    P: Clone + SerializeAlias + DeserializeOwnedAlias + ArgminZeroLike,
+    F: ArgminFloat,
+{
+    linesearch.search_direction(direction);
+
+    let OptimizationResult {
+        problem: mut line_problem,
+        state: mut line_state,
+        solver: line_solver,
+    } = Executor::new(
+        RetractionProblem::new(
+            problem.take_problem().ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "Riemannian line search: Failed to take `problem` for line search"
+            ))?,
+            manifold.clone(),
+            x.clone(),
+        ),
+        linesearch.clone(),
+    )
+    .configure(|state| state.param(x.zero_like()).grad(egrad).cost(cost))
+    .ctrlc(false)
+    .run()?;
+
+    linesearch.consume_line_search(line_solver);
+
+    // The user defined problem is hidden inside a `RetractionProblem`, therefore it has to be
+    // moved back and the function evaluation counts have to be merged separately.
+    problem.problem = Some(
+        line_problem
+            .take_problem()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "Riemannian line search: Failed to take `problem` from line search"
+            ))?
+            .problem,
+    );
+    problem.consume_func_counts(line_problem);
+
+    let xi = line_state.take_param().ok_or_else(argmin_error_closure!(
+        PotentialBug,
+        "Riemannian line search: No `param` returned by line search"
+    ))?;
+    Ok((manifold.retract(x, &xi)?, line_state.get_cost()))
+}
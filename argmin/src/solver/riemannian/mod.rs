@@ -0,0 +1,46 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Riemannian optimization
+//!
+//! Gradient based methods for problems whose parameters are constrained to a smooth manifold,
+//! such as unit vectors, orthonormal frames or covariance matrices. The manifold is described by
+//! the [`Manifold`] trait (metric, projection onto the tangent space, retraction and vector
+//! transport). The cost function and its Euclidean gradient are defined on the embedding space.
+//!
+//! * [Riemannian steepest descent](`RiemannianSteepestDescent`)
+//! * [Riemannian conjugate gradient method](`RiemannianConjugateGradient`)
+//!
+//! Both solvers reuse the line searches in [`linesearch`](`crate::solver::linesearch`).
+//!
+//! ## Available manifolds
+//!
+//! * [`Sphere`]: unit vectors (any vector type of the `argmin-math` backends)
+//! * [`Stiefel`]: `n x p` matrices with orthonormal columns
+//! * [`SymmetricPositiveDefinite`]: symmetric positive definite matrices
+//!
+//! Matrices are stored as `Vec`s in row-major order.
+//!
+//! ## Reference
+//!
+//! P.-A. Absil, R. Mahony and R. Sepulchre (2008). Optimization Algorithms on Matrix Manifolds.
+//! Princeton University Press. ISBN 978-0-691-13298-3.
+
+mod cg;
+mod dense;
+mod manifold;
+mod spd;
+mod sphere;
+mod steepestdescent;
+mod stiefel;
+
+pub use self::cg::{RiemannianBeta, RiemannianConjugateGradient};
+pub use self::manifold::Manifold;
+pub use self::spd::SymmetricPositiveDefinite;
+pub use self::sphere::Sphere;
+pub use self::steepestdescent::RiemannianSteepestDescent;
+pub use self::stiefel::Stiefel;
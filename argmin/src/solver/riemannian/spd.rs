@@ -0,0 +1,228 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::dense::{from_rows, sym, to_rows};
+use super::Manifold;
+use crate::core::{ArgminFloat, Error};
+use argmin_math::{ArgminCholeskySolve, ArgminDot};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Manifold of symmetric positive definite `n x n` matrices with the affine-invariant metric
+///
+/// Points and tangent vectors are `n x n` matrices stored as `Vec`s in row-major order; tangent
+/// vectors are symmetric matrices. The metric is `<U, V>_X = tr(X⁻¹ U X⁻¹ V)`, therefore the
+/// Riemannian gradient is `X sym(G) X` for a Euclidean gradient `G`. The retraction
+/// `R_X(V) = X + V + V X⁻¹ V / 2` is a second order approximation of the exponential map which
+/// always yields a positive definite matrix. Tangent vectors are transported by identity.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::riemannian::{Manifold, SymmetricPositiveDefinite};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let spd = SymmetricPositiveDefinite::new(2)?;
+/// let x = vec![2.0f64, 0.0, 0.0, 1.0];
+/// let y = spd.retract(&x, &vec![-1.5, 0.0, 0.0, 0.0])?;
+/// # assert!((y[0] - 1.0625f64).abs() < 1e-15);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SymmetricPositiveDefinite {
+    /// Number of rows and columns
+    n: usize,
+}
+
+impl SymmetricPositiveDefinite {
+    /// Construct a new instance of [`SymmetricPositiveDefinite`] for `n x n` matrices.
+    ///
+    /// Requires `n >= 1`.
+    pub fn new(n: usize) -> Result<Self, Error> {
+        if n < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SymmetricPositiveDefinite`: dimension must be at least 1."
+            ));
+        }
+        Ok(SymmetricPositiveDefinite { n })
+    }
+
+    /// Returns an error if any of the matrices `a` does not have `n * n` elements
+    fn check_dims<F>(&self, a: &[&Vec<F>]) -> Result<(), Error> {
+        if a.iter().any(|a| a.len() != self.n * self.n) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SymmetricPositiveDefinite`: matrices must have `n * n` elements."
+            ));
+        }
+        Ok(())
+    }
+
+    /// Solves `XZ = V`, fails if `X` is not positive definite
+    fn solve<F>(&self, x: &Vec<F>, v: &Vec<F>) -> Result<Vec<F>, Error>
+    where
+        F: ArgminFloat,
+        Vec<Vec<F>>: ArgminCholeskySolve<Vec<Vec<F>>, Vec<Vec<F>>>,
+    {
+        self.check_dims(&[x, v])?;
+        let z: Vec<Vec<F>> = to_rows(x, self.n)
+            .cholesky_solve(&to_rows(v, self.n))
+            .map_err(|_| -> Error {
+                argmin_error!(
+                    ConditionViolated,
+                    "`SymmetricPositiveDefinite`: point is not positive definite."
+                )
+            })?;
+        Ok(from_rows(z))
+    }
+}
+
+impl<F> Manifold<Vec<F>, F> for SymmetricPositiveDefinite
+where
+    F: ArgminFloat,
+    Vec<Vec<F>>:
+        ArgminCholeskySolve<Vec<Vec<F>>, Vec<Vec<F>>> + ArgminDot<Vec<Vec<F>>, Vec<Vec<F>>>,
+{
+    fn inner(&self, x: &Vec<F>, u: &Vec<F>, v: &Vec<F>) -> Result<F, Error> {
+        // tr(X⁻¹U X⁻¹V)
+        let a = self.solve(x, u)?;
+        let b = self.solve(x, v)?;
+        let n = self.n;
+        Ok((0..n).fold(float!(0.0), |acc, i| {
+            (0..n).fold(acc, |acc, j| acc + a[i * n + j] * b[j * n + i])
+        }))
+    }
+
+    fn project(&self, x: &Vec<F>, v: &Vec<F>) -> Result<Vec<F>, Error> {
+        self.check_dims(&[x, v])?;
+        Ok(sym(v, self.n))
+    }
+
+    fn retract(&self, x: &Vec<F>, v: &Vec<F>) -> Result<Vec<F>, Error> {
+        self.check_dims(&[x, v])?;
+        let n = self.n;
+        let v = sym(v, n);
+        let xinv_v = self.solve(x, &v)?;
+        let vxv = from_rows(to_rows(&v, n).dot(&to_rows(&xinv_v, n)));
+        let y: Vec<F> = x
+            .iter()
+            .zip(v.iter())
+            .zip(vxv.iter())
+            .map(|((x, v), vxv)| *x + *v + float!(0.5) * *vxv)
+            .collect();
+        Ok(sym(&y, n))
+    }
+
+    fn transport(&self, x: &Vec<F>, y: &Vec<F>, v: &Vec<F>) -> Result<Vec<F>, Error> {
+        self.check_dims(&[x, y, v])?;
+        Ok(sym(v, self.n))
+    }
+
+    fn euclidean_to_riemannian_gradient(
+        &self,
+        x: &Vec<F>,
+        egrad: &Vec<F>,
+    ) -> Result<Vec<F>, Error> {
+        self.check_dims(&[x, egrad])?;
+        let n = self.n;
+        let x = to_rows(x, n);
+        let g = to_rows(&sym(egrad, n), n);
+        Ok(sym(&from_rows(x.dot(&g).dot(&x)), n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+    use argmin_math::ArgminCholesky;
+
+    test_trait_impl!(spd, SymmetricPositiveDefinite);
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            SymmetricPositiveDefinite::new(3).unwrap(),
+            SymmetricPositiveDefinite { n: 3 }
+        );
+        assert_error!(
+            SymmetricPositiveDefinite::new(0),
+            ArgminError,
+            "Invalid parameter: \"`SymmetricPositiveDefinite`: dimension must be at least 1.\""
+        );
+    }
+
+    #[test]
+    fn test_inner_and_gradient() {
+        let spd = SymmetricPositiveDefinite::new(2).unwrap();
+        let x = vec![2.0f64, 1.0, 1.0, 3.0];
+        let u = vec![1.0, 0.5, 0.5, -1.0];
+        let g = vec![0.3, -0.2, 0.4, 1.0];
+        // Riemannian gradient satisfies <rgrad, u>_X = <G, u>_F
+        let rgrad = spd.euclidean_to_riemannian_gradient(&x, &g).unwrap();
+        let euclidean: f64 = g.iter().zip(u.iter()).map(|(g, u)| g * u).sum();
+        assert_relative_eq!(
+            spd.inner(&x, &rgrad, &u).unwrap(),
+            euclidean,
+            epsilon = 1e-14
+        );
+        // Metric is the Frobenius inner product at the identity
+        let eye = vec![1.0, 0.0, 0.0, 1.0];
+        assert_relative_eq!(spd.inner(&eye, &u, &u).unwrap(), 2.5, epsilon = 1e-15);
+        assert_error!(
+            spd.inner(&vec![1.0, 2.0, 2.0, 1.0], &u, &u),
+            ArgminError,
+            "Condition violated: \"`SymmetricPositiveDefinite`: point is not positive definite.\""
+        );
+    }
+
+    #[test]
+    fn test_dimension_errors() {
+        let spd = SymmetricPositiveDefinite::new(2).unwrap();
+        let x = vec![2.0f64, 1.0, 1.0, 3.0];
+        let v = vec![1.0f64; 3];
+        for res in [
+            spd.project(&x, &v),
+            spd.retract(&x, &v),
+            spd.transport(&x, &x, &v),
+            spd.euclidean_to_riemannian_gradient(&v, &x),
+        ] {
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`SymmetricPositiveDefinite`: matrices must have `n * n` elements.\""
+            );
+        }
+        assert_error!(
+            spd.inner(&x, &x, &v),
+            ArgminError,
+            "Invalid parameter: \"`SymmetricPositiveDefinite`: matrices must have `n * n` elements.\""
+        );
+    }
+
+    #[test]
+    fn test_retract() {
+        let spd = SymmetricPositiveDefinite::new(2).unwrap();
+        let x = vec![2.0f64, 1.0, 1.0, 3.0];
+        // Large step towards the boundary of the cone still yields a positive definite matrix
+        let y = spd.retract(&x, &vec![-10.0, 0.0, 0.0, -10.0]).unwrap();
+        assert!(to_rows(&y, 2).cholesky().is_ok());
+        assert_relative_eq!(y[1], y[2], epsilon = 1e-15);
+
+        let res = spd.retract(&vec![1.0, 2.0, 2.0, 1.0], &x);
+        assert_error!(
+            res,
+            ArgminError,
+            "Condition violated: \"`SymmetricPositiveDefinite`: point is not positive definite.\""
+        );
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::Manifold;
+use crate::core::{ArgminFloat, Error};
+use argmin_math::{ArgminAdd, ArgminDot, ArgminMul, ArgminNorm, ArgminScaledSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Unit sphere `{x : ‖x‖ = 1}`
+///
+/// Works with any vector type of the `argmin-math` backends. The metric is inherited from the
+/// embedding space, the retraction is the normalization `R_x(v) = (x + v) / ‖x + v‖` and vectors
+/// are transported by projection onto the tangent space `{v : xᵀv = 0}`.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::riemannian::{Manifold, Sphere};
+/// let sphere = Sphere::new();
+/// let x = sphere.retract(&vec![1.0f64, 0.0], &vec![0.0, 1.0]).unwrap();
+/// # assert!((x[0] - 0.5f64.sqrt()).abs() < 1e-15 && (x[1] - 0.5f64.sqrt()).abs() < 1e-15);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Sphere {}
+
+impl Sphere {
+    /// Construct a new instance of [`Sphere`]
+    pub fn new() -> Self {
+        Sphere {}
+    }
+}
+
+impl<P, F> Manifold<P, F> for Sphere
+where
+    P: ArgminDot<P, F>
+        + ArgminScaledSub<P, F, P>
+        + ArgminAdd<P, P>
+        + ArgminMul<F, P>
+        + ArgminNorm<F>,
+    F: ArgminFloat,
+{
+    fn inner(&self, _x: &P, u: &P, v: &P) -> Result<F, Error> {
+        Ok(u.dot(v))
+    }
+
+    fn project(&self, x: &P, v: &P) -> Result<P, Error> {
+        Ok(v.scaled_sub(&x.dot(v), x))
+    }
+
+    fn retract(&self, x: &P, v: &P) -> Result<P, Error> {
+        let y = x.add(v);
+        let norm = y.norm();
+        if !norm.is_finite() || norm <= float!(0.0) {
+            return Err(argmin_error!(
+                ConditionViolated,
+                "`Sphere`: retraction is not defined for `x + v = 0`."
+            ));
+        }
+        Ok(y.mul(&(float!(1.0) / norm)))
+    }
+
+    fn transport(&self, _x: &P, y: &P, v: &P) -> Result<P, Error> {
+        self.project(y, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(sphere, Sphere);
+
+    #[test]
+    fn test_project() {
+        let x = vec![0.6f64, 0.8, 0.0];
+        let v = Sphere::new().project(&x, &vec![1.0, 2.0, 3.0]).unwrap();
+        assert_relative_eq!(
+            Sphere::new().inner(&x, &v, &x).unwrap(),
+            0.0,
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(v[0], 1.0 - 0.6 * 2.2, epsilon = 1e-15);
+        assert_relative_eq!(v[1], 2.0 - 0.8 * 2.2, epsilon = 1e-15);
+        assert_relative_eq!(v[2], 3.0, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_retract_and_transport() {
+        let sphere = Sphere::new();
+        let x = vec![1.0f64, 0.0, 0.0];
+        let y = sphere.retract(&x, &vec![0.0, 3.0, 4.0]).unwrap();
+        assert_relative_eq!(ArgminNorm::<f64>::norm(&y), 1.0, epsilon = 1e-15);
+        let v = sphere.transport(&x, &y, &vec![0.0, 1.0, 0.0]).unwrap();
+        assert_relative_eq!(sphere.inner(&y, &v, &y).unwrap(), 0.0, epsilon = 1e-15);
+
+        let res = Manifold::<Vec<f64>, f64>::retract(&sphere, &x, &vec![-1.0, 0.0, 0.0]);
+        assert_error!(
+            res,
+            ArgminError,
+            "Condition violated: \"`Sphere`: retraction is not defined for `x + v = 0`.\""
+        );
+    }
+}
@@ -0,0 +1,390 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::manifold::{retracted_line_search, RetractionProblem};
+use super::Manifold;
+use crate::core::{
+    ArgminFloat, CostFunction, DeserializeOwnedAlias, Error, Gradient, IterState, Problem,
+    SerializeAlias, Solver, State, TerminationReason, KV,
+};
+use crate::solver::linesearch::LineSearch;
+use argmin_math::{ArgminMul, ArgminZeroLike};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Riemannian steepest descent
+///
+/// Steepest descent on a [`Manifold`]. In each iteration, a line search is performed along the
+/// negative Riemannian gradient on the cost function pulled back to the tangent space via the
+/// retraction, and the resulting tangent vector is retracted onto the manifold. Any of the line
+/// searches in [`linesearch`](`crate::solver::linesearch`) can be used.
+///
+/// The cost function and its Euclidean gradient are defined on the embedding space of the
+/// manifold. The Riemannian gradient is stored in the state.
+///
+/// Terminates once the norm of the Riemannian gradient falls below the tolerance set with
+/// [`with_tolerance`](`RiemannianSteepestDescent::with_tolerance`).
+///
+/// Requires an initial parameter vector, which must be a point on the manifold.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
+///
+/// ## Reference
+///
+/// P.-A. Absil, R. Mahony and R. Sepulchre (2008). Optimization Algorithms on Matrix Manifolds.
+/// Princeton University Press. ISBN 978-0-691-13298-3.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct RiemannianSteepestDescent<M, L, P, F> {
+    /// Manifold
+    manifold: M,
+    /// line search
+    linesearch: L,
+    /// Tolerance for the norm of the Riemannian gradient
+    tol: F,
+    /// Euclidean gradient at the current iterate, projected onto the tangent space
+    egrad: Option<P>,
+}
+
+impl<M, L, P, F> RiemannianSteepestDescent<M, L, P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`RiemannianSteepestDescent`]
+    ///
+    /// Requires a manifold and a line search.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::riemannian::{RiemannianSteepestDescent, Sphere};
+    /// # let linesearch = ();
+    /// let sd: RiemannianSteepestDescent<_, _, Vec<f64>, f64> =
+    ///     RiemannianSteepestDescent::new(Sphere::new(), linesearch);
+    /// ```
+    pub fn new(manifold: M, linesearch: L) -> Self {
+        RiemannianSteepestDescent {
+            manifold,
+            linesearch,
+            tol: F::epsilon().sqrt(),
+            egrad: None,
+        }
+    }
+
+    /// Set tolerance for the norm of the Riemannian gradient.
+    ///
+    /// Tolerance must be larger than zero and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::riemannian::{RiemannianSteepestDescent, Sphere};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let sd: RiemannianSteepestDescent<_, _, Vec<f64>, f64> =
+    ///     RiemannianSteepestDescent::new(Sphere::new(), linesearch).with_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RiemannianSteepestDescent`: tol must be positive."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+}
+
+impl<O, M, L, P, F> Solver<O, IterState<P, P, (), (), F>> for RiemannianSteepestDescent<M, L, P, F>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = P>,
+    M: Clone + Manifold<P, F>,
+    L: Clone + LineSearch<P, F> + Solver<RetractionProblem<O, M, P>, IterState<P, P, (), (), F>>,
+    P: Clone + SerializeAlias + DeserializeOwnedAlias + ArgminMul<F, P> + ArgminZeroLike,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Riemannian Steepest Descent";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, P, (), (), F>,
+    ) -> Result<(IterState<P, P, (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`RiemannianSteepestDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let cost = problem.cost(param)?;
+        let egrad = problem.gradient(param)?;
+        let rgrad = self
+            .manifold
+            .euclidean_to_riemannian_gradient(param, &egrad)?;
+        self.egrad = Some(self.manifold.project(param, &egrad)?);
+        Ok((state.cost(cost).grad(rgrad), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, P, (), (), F>,
+    ) -> Result<(IterState<P, P, (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`RiemannianSteepestDescent`: No `param` in `state`"
+        ))?;
+        let rgrad = state.take_grad().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`RiemannianSteepestDescent`: No `grad` in `state`"
+        ))?;
+        let egrad = self.egrad.take().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`RiemannianSteepestDescent`: Field `egrad` not set"
+        ))?;
+        let cost = state.get_cost();
+
+        let (new_param, new_cost) = retracted_line_search(
+            problem,
+            &self.manifold,
+            &mut self.linesearch,
+            &param,
+            rgrad.mul(&(float!(-1.0))),
+            egrad,
+            cost,
+        )?;
+
+        let egrad = problem.gradient(&new_param)?;
+        let rgrad = self
+            .manifold
+            .euclidean_to_riemannian_gradient(&new_param, &egrad)?;
+        self.egrad = Some(self.manifold.project(&new_param, &egrad)?);
+        let grad_norm = self.manifold.norm(&new_param, &rgrad)?;
+
+        Ok((
+            state.param(new_param).cost(new_cost).grad(rgrad),
+            Some(make_kv!("grad_norm" => grad_norm;)),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, P, (), (), F>) -> TerminationReason {
+        if let (Some(param), Some(grad)) = (state.get_param(), state.get_grad()) {
+            if matches!(self.manifold.norm(param, grad), Ok(norm) if norm <= self.tol) {
+                return TerminationReason::TargetPrecisionReached;
+            }
+        }
+        TerminationReason::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::linesearch::{
+        condition::ArmijoCondition, BacktrackingLineSearch, MoreThuenteLineSearch,
+    };
+    use crate::solver::riemannian::{Sphere, SymmetricPositiveDefinite};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        riemannian_steepest_descent,
+        RiemannianSteepestDescent<Sphere, MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, Vec<f64>, f64>
+    );
+
+    /// Rayleigh quotient `xᵀAx` of a symmetric matrix, minimized on the sphere by the eigenvector
+    /// of the smallest eigenvalue
+    struct Rayleigh {
+        a: Vec<Vec<f64>>,
+    }
+
+    impl CostFunction for Rayleigh {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self
+                .a
+                .iter()
+                .zip(x.iter())
+                .map(|(a_i, x_i)| x_i * a_i.iter().zip(x.iter()).map(|(a, x)| a * x).sum::<f64>())
+                .sum())
+        }
+    }
+
+    impl Gradient for Rayleigh {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, x: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(self
+                .a
+                .iter()
+                .map(|a_i| 2.0 * a_i.iter().zip(x.iter()).map(|(a, x)| a * x).sum::<f64>())
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
+        let RiemannianSteepestDescent {
+            manifold,
+            linesearch: ls,
+            tol,
+            egrad,
+        }: RiemannianSteepestDescent<_, _, Vec<f64>, f64> =
+            RiemannianSteepestDescent::new(Sphere::new(), linesearch.clone());
+        assert_eq!(manifold, Sphere::new());
+        assert_eq!(ls, linesearch);
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(egrad.is_none());
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let sd: RiemannianSteepestDescent<_, _, Vec<f64>, f64> =
+            RiemannianSteepestDescent::new(Sphere::new(), ())
+                .with_tolerance(1e-4)
+                .unwrap();
+        assert_eq!(sd.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        for tol in [0.0, -1.0] {
+            let res: Result<RiemannianSteepestDescent<_, _, Vec<f64>, f64>, _> =
+                RiemannianSteepestDescent::new(Sphere::new(), ()).with_tolerance(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`RiemannianSteepestDescent`: tol must be positive.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let mut sd = RiemannianSteepestDescent::new(Sphere::new(), linesearch);
+        let problem = Rayleigh {
+            a: vec![vec![1.0, 0.0], vec![0.0, 2.0]],
+        };
+        let res = sd.init(&mut Problem::new(problem), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`RiemannianSteepestDescent` requires an initial parameter ",
+                "vector. Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_sphere_rayleigh_quotient() {
+        let problem = Rayleigh {
+            a: vec![
+                vec![2.0, -1.0, 0.0],
+                vec![-1.0, 2.0, -1.0],
+                vec![0.0, -1.0, 2.0],
+            ],
+        };
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let sd = RiemannianSteepestDescent::new(Sphere::new(), linesearch)
+            .with_tolerance(1e-10)
+            .unwrap();
+        let res = Executor::new(problem, sd)
+            .configure(|state| state.param(vec![1.0, 0.0, 0.0]).max_iters(200))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            res.state().get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        // Smallest eigenvalue 2 - sqrt(2) with eigenvector (1, sqrt(2), 1) / 2
+        assert_relative_eq!(
+            res.state().get_best_cost(),
+            2.0 - 2.0f64.sqrt(),
+            epsilon = 1e-12
+        );
+        let x = res.state().get_best_param().unwrap();
+        assert_relative_eq!(x[0], 0.5, epsilon = 1e-8);
+        assert_relative_eq!(x[1], 0.5f64.sqrt(), epsilon = 1e-8);
+        assert_relative_eq!(x[2], 0.5, epsilon = 1e-8);
+        assert!(res.state().get_func_counts()["cost_count"] > 1);
+    }
+
+    #[test]
+    fn test_spd_log_det() {
+        // f(X) = tr(SX) - log det X is minimized by X = S⁻¹
+        struct LogDet {
+            s: Vec<f64>,
+        }
+
+        impl CostFunction for LogDet {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+                let det = x[0] * x[3] - x[1] * x[2];
+                if det <= 0.0 {
+                    return Ok(f64::INFINITY);
+                }
+                let tr: f64 = self.s.iter().zip(x.iter()).map(|(s, x)| s * x).sum();
+                Ok(tr - det.ln())
+            }
+        }
+
+        impl Gradient for LogDet {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, x: &Self::Param) -> Result<Self::Gradient, Error> {
+                // S - X⁻¹
+                let det = x[0] * x[3] - x[1] * x[2];
+                let inv = [x[3] / det, -x[1] / det, -x[2] / det, x[0] / det];
+                Ok(self.s.iter().zip(inv.iter()).map(|(s, i)| s - i).collect())
+            }
+        }
+
+        let problem = LogDet {
+            s: vec![2.0, 1.0, 1.0, 2.0],
+        };
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+        let sd =
+            RiemannianSteepestDescent::new(SymmetricPositiveDefinite::new(2).unwrap(), linesearch)
+                .with_tolerance(1e-10)
+                .unwrap();
+        let res = Executor::new(problem, sd)
+            .configure(|state| state.param(vec![1.0, 0.0, 0.0, 1.0]).max_iters(100))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            res.state().get_termination_reason(),
+            TerminationReason::TargetPrecisionReached
+        );
+        let x = res.state().get_best_param().unwrap();
+        let expected = [2.0 / 3.0, -1.0 / 3.0, -1.0 / 3.0, 2.0 / 3.0];
+        for (x, e) in x.iter().zip(expected.iter()) {
+            assert_relative_eq!(x, e, epsilon = 1e-8);
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::dense::{from_rows, sym, to_rows};
+use super::Manifold;
+use crate::core::{ArgminFloat, Error};
+use argmin_math::{ArgminDot, ArgminTranspose};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Stiefel manifold `{X ∈ R^(n x p) : XᵀX = I}` of orthonormal `p`-frames in `R^n`
+///
+/// Points and tangent vectors are `n x p` matrices stored as `Vec`s in row-major order. The metric
+/// is inherited from the embedding space, the retraction is the Q factor of the QR decomposition
+/// of `X + V` (with positive diagonal of R) and vectors are transported by projection onto the
+/// tangent space `{V : XᵀV + VᵀX = 0}`.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::riemannian::{Manifold, Stiefel};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// // Orthonormal 2-frames in R^3
+/// let stiefel = Stiefel::new(3, 2)?;
+/// let x = vec![1.0f64, 0.0, 0.0, 1.0, 0.0, 0.0];
+/// let y = stiefel.retract(&x, &vec![0.0, 0.0, 0.0, 0.0, 0.5, 0.5])?;
+/// # assert!((y.iter().map(|y| y * y).sum::<f64>() - 2.0).abs() < 1e-14);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Stiefel {
+    /// Number of rows
+    n: usize,
+    /// Number of columns
+    p: usize,
+}
+
+impl Stiefel {
+    /// Construct a new instance of [`Stiefel`] for `n x p` matrices.
+    ///
+    /// Requires `1 <= p <= n`.
+    pub fn new(n: usize, p: usize) -> Result<Self, Error> {
+        if p < 1 || p > n {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Stiefel`: dimensions must satisfy `1 <= p <= n`."
+            ));
+        }
+        Ok(Stiefel { n, p })
+    }
+
+    /// Returns an error if any of the matrices `a` does not have `n * p` elements
+    fn check_dims<F>(&self, a: &[&Vec<F>]) -> Result<(), Error> {
+        if a.iter().any(|a| a.len() != self.n * self.p) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Stiefel`: point and tangent vector must have `n * p` elements."
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<F> Manifold<Vec<F>, F> for Stiefel
+where
+    F: ArgminFloat,
+    Vec<Vec<F>>: ArgminTranspose<Vec<Vec<F>>> + ArgminDot<Vec<Vec<F>>, Vec<Vec<F>>>,
+{
+    fn inner(&self, x: &Vec<F>, u: &Vec<F>, v: &Vec<F>) -> Result<F, Error> {
+        self.check_dims(&[x, u, v])?;
+        Ok(u.iter()
+            .zip(v.iter())
+            .fold(float!(0.0), |acc, (u, v)| acc + *u * *v))
+    }
+
+    fn project(&self, x: &Vec<F>, v: &Vec<F>) -> Result<Vec<F>, Error> {
+        self.check_dims(&[x, v])?;
+        // V - X sym(XᵀV)
+        let x = to_rows(x, self.p);
+        let xtv = from_rows(x.clone().t().dot(&to_rows(v, self.p)));
+        let xs = from_rows(x.dot(&to_rows(&sym(&xtv, self.p), self.p)));
+        Ok(v.iter().zip(xs.iter()).map(|(v, xs)| *v - *xs).collect())
+    }
+
+    fn retract(&self, x: &Vec<F>, v: &Vec<F>) -> Result<Vec<F>, Error> {
+        self.check_dims(&[x, v])?;
+        let (n, p) = (self.n, self.p);
+        let mut q: Vec<F> = x.iter().zip(v.iter()).map(|(x, v)| *x + *v).collect();
+        let scale = q.iter().fold(float!(0.0), |acc: F, q| acc.max(q.abs()));
+        // Modified Gram-Schmidt on the columns
+        for j in 0..p {
+            for k in 0..j {
+                let r = (0..n).fold(float!(0.0), |acc, i| acc + q[i * p + k] * q[i * p + j]);
+                for i in 0..n {
+                    q[i * p + j] = q[i * p + j] - r * q[i * p + k];
+                }
+            }
+            let norm = (0..n)
+                .fold(float!(0.0), |acc, i| acc + q[i * p + j] * q[i * p + j])
+                .sqrt();
+            if !norm.is_finite() || norm <= F::epsilon() * scale {
+                return Err(argmin_error!(
+                    ConditionViolated,
+                    "`Stiefel`: retraction is not defined for rank deficient `X + V`."
+                ));
+            }
+            for i in 0..n {
+                q[i * p + j] = q[i * p + j] / norm;
+            }
+        }
+        Ok(q)
+    }
+
+    fn transport(&self, x: &Vec<F>, y: &Vec<F>, v: &Vec<F>) -> Result<Vec<F>, Error> {
+        self.check_dims(&[x])?;
+        self.project(y, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(stiefel, Stiefel);
+
+    /// `XᵀV` for `n x p` matrices `X` and `V`
+    fn transpose_matmul(x: &[f64], v: &[f64], p: usize) -> Vec<f64> {
+        from_rows(to_rows(x, p).t().dot(&to_rows(v, p)))
+    }
+
+    fn assert_orthonormal(x: &[f64], n: usize, p: usize) {
+        assert_eq!(x.len(), n * p);
+        let xtx = transpose_matmul(x, x, p);
+        for i in 0..p {
+            for j in 0..p {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert_relative_eq!(xtx[i * p + j], expected, epsilon = 1e-14);
+            }
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(Stiefel::new(3, 2).unwrap(), Stiefel { n: 3, p: 2 });
+        for (n, p) in [(3, 0), (2, 3)] {
+            assert_error!(
+                Stiefel::new(n, p),
+                ArgminError,
+                "Invalid parameter: \"`Stiefel`: dimensions must satisfy `1 <= p <= n`.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_project() {
+        let stiefel = Stiefel::new(3, 2).unwrap();
+        let x = vec![1.0f64, 0.0, 0.0, 1.0, 0.0, 0.0];
+        let v = stiefel
+            .project(&x, &vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+            .unwrap();
+        // XᵀV is skew-symmetric
+        let xtv = transpose_matmul(&x, &v, 2);
+        assert_relative_eq!(xtv[0], 0.0, epsilon = 1e-15);
+        assert_relative_eq!(xtv[3], 0.0, epsilon = 1e-15);
+        assert_relative_eq!(xtv[1], -xtv[2], epsilon = 1e-15);
+        // Projection is idempotent
+        let w = stiefel.project(&x, &v).unwrap();
+        for (v, w) in v.iter().zip(w.iter()) {
+            assert_relative_eq!(v, w, epsilon = 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_dimension_errors() {
+        let stiefel = Stiefel::new(3, 2).unwrap();
+        let x = vec![1.0f64, 0.0, 0.0, 1.0, 0.0, 0.0];
+        let v = vec![0.0f64; 4];
+        for res in [
+            stiefel.project(&x, &v),
+            stiefel.project(&v, &x),
+            stiefel.transport(&x, &x, &v),
+            stiefel.transport(&v, &x, &x),
+            stiefel.euclidean_to_riemannian_gradient(&x, &v),
+        ] {
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`Stiefel`: point and tangent vector must have `n * p` elements.\""
+            );
+        }
+        for res in [stiefel.inner(&x, &x, &v), stiefel.norm(&x, &v)] {
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`Stiefel`: point and tangent vector must have `n * p` elements.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_retract() {
+        let stiefel = Stiefel::new(3, 2).unwrap();
+        let x = vec![1.0f64, 0.0, 0.0, 1.0, 0.0, 0.0];
+        let v = stiefel
+            .project(&x, &vec![0.3, -0.2, 0.1, 0.4, 0.5, 0.6])
+            .unwrap();
+        let y = stiefel.retract(&x, &v).unwrap();
+        assert_orthonormal(&y, 3, 2);
+        let w = stiefel.transport(&x, &y, &v).unwrap();
+        let ytw = transpose_matmul(&y, &w, 2);
+        assert_relative_eq!(ytw[1], -ytw[2], epsilon = 1e-14);
+
+        let res = stiefel.retract(&x, &vec![-1.0, 0.0, 0.0, -1.0, 0.0, 0.0]);
+        assert_error!(
+            res,
+            ArgminError,
+            "Condition violated: \"`Stiefel`: retraction is not defined for rank deficient `X + V`.\""
+        );
+        let res = stiefel.retract(&x, &vec![0.0; 4]);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Stiefel`: point and tangent vector must have `n * p` elements.\""
+        );
+    }
+}
@@ -0,0 +1,790 @@
+// Copyright 2018-2022 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Simultaneous perturbation stochastic approximation (SPSA)
+//!
+//! Gradient-free optimization of noisy cost functions, for instance Monte Carlo estimates.
+//! See [`SPSA`] for details.
+//!
+//! ## References
+//!
+//! James C. Spall (1992). Multivariate stochastic approximation using a simultaneous perturbation
+//! gradient approximation. IEEE Transactions on Automatic Control 37(3), 332–341.
+//!
+//! James C. Spall (1998). Implementation of the simultaneous perturbation algorithm for
+//! stochastic optimization. IEEE Transactions on Aerospace and Electronic Systems 34(3),
+//! 817–823.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, SerializeAlias, Solver, State, SyncAlias,
+    TerminationReason, KV,
+};
+use argmin_math::{ArgminMap, ArgminScaledAdd, ArgminScaledSub, ArgminZeroLike};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+/// Noise-aware termination criterion for noisy cost functions
+///
+/// Every `interval` iterations, the cost function is evaluated `num_evaluations` times at the
+/// current parameter vector. The sample mean and its standard error are compared to those of the
+/// previous check: if the mean did not decrease by more than `confidence` times the standard
+/// error of the difference, the improvement is considered insignificant. The solver terminates
+/// with [`TerminationReason::NoChangeInCost`] after `patience` consecutive insignificant checks.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::spsa::NoiseAwareTermination;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let termination: NoiseAwareTermination<f64> = NoiseAwareTermination::new(20)?
+///     .with_interval(50)?
+///     .with_confidence(3.0)?
+///     .with_patience(2)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NoiseAwareTermination<F> {
+    /// Number of repeated evaluations per check
+    num_evaluations: usize,
+    /// Number of iterations between checks
+    interval: u64,
+    /// Number of standard errors an improvement needs to exceed to be significant
+    confidence: F,
+    /// Number of consecutive insignificant checks before termination
+    patience: u64,
+}
+
+impl<F> NoiseAwareTermination<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`NoiseAwareTermination`]
+    ///
+    /// Takes the number of repeated evaluations per check as input, which must be at least 2.
+    /// Defaults to a check every 10 iterations, a confidence of 2 standard errors and a patience
+    /// of 3 checks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::spsa::NoiseAwareTermination;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let termination: NoiseAwareTermination<f64> = NoiseAwareTermination::new(20)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(num_evaluations: usize) -> Result<Self, Error> {
+        if num_evaluations < 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NoiseAwareTermination`: number of evaluations must be at least 2."
+            ));
+        }
+        Ok(NoiseAwareTermination {
+            num_evaluations,
+            interval: 10,
+            confidence: float!(2.0),
+            patience: 3,
+        })
+    }
+
+    /// Set the number of iterations between two checks.
+    ///
+    /// Must be > 0 and defaults to `10`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::spsa::NoiseAwareTermination;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let termination: NoiseAwareTermination<f64> =
+    ///     NoiseAwareTermination::new(20)?.with_interval(50)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_interval(mut self, interval: u64) -> Result<Self, Error> {
+        if interval == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NoiseAwareTermination`: interval must be > 0."
+            ));
+        }
+        self.interval = interval;
+        Ok(self)
+    }
+
+    /// Set the number of standard errors an improvement needs to exceed to be considered
+    /// significant.
+    ///
+    /// Must be > 0 and defaults to `2`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::spsa::NoiseAwareTermination;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let termination: NoiseAwareTermination<f64> =
+    ///     NoiseAwareTermination::new(20)?.with_confidence(3.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_confidence(mut self, confidence: F) -> Result<Self, Error> {
+        if confidence <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NoiseAwareTermination`: confidence must be > 0."
+            ));
+        }
+        self.confidence = confidence;
+        Ok(self)
+    }
+
+    /// Set the number of consecutive insignificant checks after which the solver terminates.
+    ///
+    /// Must be > 0 and defaults to `3`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::spsa::NoiseAwareTermination;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let termination: NoiseAwareTermination<f64> =
+    ///     NoiseAwareTermination::new(20)?.with_patience(5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_patience(mut self, patience: u64) -> Result<Self, Error> {
+        if patience == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NoiseAwareTermination`: patience must be > 0."
+            ));
+        }
+        self.patience = patience;
+        Ok(self)
+    }
+
+    /// Sample mean and standard error of repeated cost function evaluations at `param`
+    fn average_cost<O, P>(&self, problem: &mut Problem<O>, param: &P) -> Result<(F, F), Error>
+    where
+        O: CostFunction<Param = P, Output = F> + SyncAlias,
+        P: Clone + SyncAlias,
+    {
+        let costs = problem.bulk_cost(&vec![param.clone(); self.num_evaluations])?;
+        let n = F::from_usize(self.num_evaluations).unwrap();
+        let mean = costs.iter().fold(float!(0.0), |acc, &c| acc + c) / n;
+        let variance = costs
+            .iter()
+            .fold(float!(0.0), |acc, &c| acc + (c - mean) * (c - mean))
+            / (n - float!(1.0));
+        Ok((mean, (variance / n).sqrt()))
+    }
+}
+
+/// # Simultaneous perturbation stochastic approximation (SPSA)
+///
+/// Minimizes noisy cost functions without gradient information. In iteration `k` (starting at
+/// `0`), the gradient is estimated from two cost function evaluations along a random direction
+/// `Δ_k` with independent `±1` entries:
+///
+/// `g_k = (f(x_k + c_k Δ_k) - f(x_k - c_k Δ_k)) / (2 c_k) * Δ_k`
+///
+/// and the parameter vector is updated according to `x_{k+1} = x_k - a_k g_k`. The standard gain
+/// sequences are
+///
+/// `a_k = a / (k + 1 + A)^alpha` and `c_k = c / (k + 1)^gamma`
+///
+/// with `alpha = 0.602` and `gamma = 0.101` by default (see
+/// [`with_exponents`](`SPSA::with_exponents`)) and a stability constant `A = 0` (see
+/// [`with_stability_constant`](`SPSA::with_stability_constant`)). Spall recommends choosing `c`
+/// approximately as the standard deviation of the noise, `A` as about 10% of the expected number
+/// of iterations and `a` such that `a / (1 + A)^alpha * |g_0|` is the desired change of the
+/// parameters in early iterations.
+///
+/// The cost of each iteration is independent of the dimension of the problem. Optionally, the
+/// gradient estimate can be averaged over several random directions (see
+/// [`with_gradient_averaging`](`SPSA::with_gradient_averaging`)). The perturbed parameter
+/// vectors are evaluated via [`bulk_cost`](`crate::core::Problem::bulk_cost`) and are therefore
+/// computed in parallel if the `rayon` feature is enabled.
+///
+/// As the cost function is only evaluated at perturbed parameter vectors, no cost function value
+/// is stored in the state by default and the best parameter vector is the current iterate. With a
+/// [`NoiseAwareTermination`] (see
+/// [`with_noise_aware_termination`](`SPSA::with_noise_aware_termination`)), the state holds the
+/// averaged cost function value of the most recent check, which is also used to determine the
+/// best parameter vector.
+///
+/// The random number generator is part of the solver, hence seeded runs are reproducible and can
+/// be checkpointed.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## References
+///
+/// James C. Spall (1992). Multivariate stochastic approximation using a simultaneous perturbation
+/// gradient approximation. IEEE Transactions on Automatic Control 37(3), 332–341.
+///
+/// James C. Spall (1998). Implementation of the simultaneous perturbation algorithm for
+/// stochastic optimization. IEEE Transactions on Aerospace and Electronic Systems 34(3),
+/// 817–823.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SPSA<F, R> {
+    /// Step size gain `a`
+    a: F,
+    /// Perturbation gain `c`
+    c: F,
+    /// Stability constant `A`
+    stability: F,
+    /// Decay exponent `alpha` of the step size
+    alpha: F,
+    /// Decay exponent `gamma` of the perturbation size
+    gamma: F,
+    /// Number of gradient estimates averaged per iteration
+    gradient_averaging: usize,
+    /// Noise-aware termination
+    termination: Option<NoiseAwareTermination<F>>,
+    /// Mean and standard error of the cost function at the most recent check
+    last_check: Option<(F, F)>,
+    /// Number of consecutive checks without significant improvement
+    stalled_checks: u64,
+    /// Random number generator
+    rng: R,
+}
+
+impl<F> SPSA<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`SPSA`]
+    ///
+    /// Takes the gains `a` and `c` of the step size and perturbation sequences as input, which
+    /// must both be > 0.
+    ///
+    /// Uses the `Xoshiro256PlusPlus` RNG internally. For use of another RNG or a seeded RNG,
+    /// consider using [`SPSA::new_with_rng`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::spsa::SPSA;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let spsa: SPSA<f64, _> = SPSA::new(0.1, 0.05)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(a: F, c: F) -> Result<Self, Error> {
+        SPSA::new_with_rng(a, c, Xoshiro256PlusPlus::from_entropy())
+    }
+}
+
+impl<F, R> SPSA<F, R>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`SPSA`]
+    ///
+    /// Takes the gains `a` and `c` of the step size and perturbation sequences as input, which
+    /// must both be > 0.
+    /// Requires a RNG which must implement `rand::Rng` (and `serde::Serialize` if the `serde1`
+    /// feature is enabled).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::spsa::SPSA;
+    /// # use argmin::core::Error;
+    /// use rand::SeedableRng;
+    /// use rand_xoshiro::Xoshiro256PlusPlus;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let rng = Xoshiro256PlusPlus::seed_from_u64(42);
+    /// let spsa: SPSA<f64, _> = SPSA::new_with_rng(0.1, 0.05, rng)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_rng(a: F, c: F, rng: R) -> Result<Self, Error> {
+        if a <= float!(0.0) || c <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SPSA`: gains `a` and `c` must be > 0."
+            ));
+        }
+        Ok(SPSA {
+            a,
+            c,
+            stability: float!(0.0),
+            alpha: float!(0.602),
+            gamma: float!(0.101),
+            gradient_averaging: 1,
+            termination: None,
+            last_check: None,
+            stalled_checks: 0,
+            rng,
+        })
+    }
+
+    /// Set the stability constant `A` of the step size sequence.
+    ///
+    /// Must be non-negative and defaults to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::spsa::SPSA;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let spsa: SPSA<f64, _> = SPSA::new(0.1, 0.05)?.with_stability_constant(100.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_stability_constant(mut self, stability: F) -> Result<Self, Error> {
+        if stability < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SPSA`: stability constant must be non-negative."
+            ));
+        }
+        self.stability = stability;
+        Ok(self)
+    }
+
+    /// Set the decay exponents `alpha` and `gamma` of the step size and perturbation sequences.
+    ///
+    /// `alpha` must be in `(0, 1]` and `gamma` must be > 0. Defaults to the practical values
+    /// `alpha = 0.602` and `gamma = 0.101`. The asymptotically optimal values are `alpha = 1` and
+    /// `gamma = 1/6`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::spsa::SPSA;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let spsa: SPSA<f64, _> = SPSA::new(0.1, 0.05)?.with_exponents(1.0, 1.0 / 6.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_exponents(mut self, alpha: F, gamma: F) -> Result<Self, Error> {
+        if alpha <= float!(0.0) || alpha > float!(1.0) || gamma <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SPSA`: exponents must satisfy 0 < alpha <= 1 and gamma > 0."
+            ));
+        }
+        self.alpha = alpha;
+        self.gamma = gamma;
+        Ok(self)
+    }
+
+    /// Set the number of gradient estimates (each from a new random direction) which are averaged
+    /// per iteration.
+    ///
+    /// Must be > 0 and defaults to `1`. Each gradient estimate requires two cost function
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::spsa::SPSA;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let spsa: SPSA<f64, _> = SPSA::new(0.1, 0.05)?.with_gradient_averaging(4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_gradient_averaging(mut self, num_estimates: usize) -> Result<Self, Error> {
+        if num_estimates == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SPSA`: number of averaged gradient estimates must be > 0."
+            ));
+        }
+        self.gradient_averaging = num_estimates;
+        Ok(self)
+    }
+
+    /// Terminate once averaged cost function values stop decreasing significantly.
+    ///
+    /// See [`NoiseAwareTermination`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::spsa::{NoiseAwareTermination, SPSA};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let spsa: SPSA<f64, _> = SPSA::new(0.1, 0.05)?
+    ///     .with_noise_aware_termination(NoiseAwareTermination::new(20)?.with_interval(50)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_noise_aware_termination(mut self, termination: NoiseAwareTermination<F>) -> Self {
+        self.termination = Some(termination);
+        self
+    }
+}
+
+/// Random direction with independent `±1` entries of the same shape as `param`
+fn rademacher<P, F, R>(param: &P, rng: &mut R) -> P
+where
+    P: ArgminMap<F>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    let rng = RefCell::new(rng);
+    param.map(|_| {
+        if rng.borrow_mut().gen::<bool>() {
+            float!(1.0)
+        } else {
+            float!(-1.0)
+        }
+    })
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), F>> for SPSA<F, R>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone
+        + SyncAlias
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminMap<F>
+        + ArgminZeroLike,
+    F: ArgminFloat,
+    R: Rng + SerializeAlias,
+{
+    const NAME: &'static str = "SPSA";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`SPSA` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        self.last_check = None;
+        self.stalled_checks = 0;
+        if let Some(termination) = self.termination {
+            let (mean, std_error) = termination.average_cost(problem, param)?;
+            self.last_check = Some((mean, std_error));
+            return Ok((
+                state.cost(mean),
+                Some(make_kv!("cost_std_error" => std_error;)),
+            ));
+        }
+        Ok((state, None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`SPSA` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let k = F::from_u64(state.get_iter()).unwrap();
+        let a_k = self.a / (k + float!(1.0) + self.stability).powf(self.alpha);
+        let c_k = self.c / (k + float!(1.0)).powf(self.gamma);
+        let num_estimates = F::from_usize(self.gradient_averaging).unwrap();
+
+        let mut grad = param.zero_like();
+        for _ in 0..self.gradient_averaging {
+            let delta = rademacher(&param, &mut self.rng);
+            let costs = problem.bulk_cost(&vec![
+                param.scaled_add(&c_k, &delta),
+                param.scaled_sub(&c_k, &delta),
+            ])?;
+            let scale = (costs[0] - costs[1]) / (float!(2.0) * c_k * num_estimates);
+            grad = grad.scaled_add(&scale, &delta);
+        }
+        let new_param = param.scaled_sub(&a_k, &grad);
+
+        let mut kv = make_kv!("a_k" => a_k; "c_k" => c_k;);
+        if let Some(termination) = self.termination {
+            if state.get_iter() % termination.interval == termination.interval - 1 {
+                let (mean, std_error) = termination.average_cost(problem, &new_param)?;
+                if let Some((last_mean, last_std_error)) = self.last_check {
+                    let threshold = termination.confidence
+                        * (last_std_error * last_std_error + std_error * std_error).sqrt();
+                    if last_mean - mean <= threshold {
+                        self.stalled_checks += 1;
+                    } else {
+                        self.stalled_checks = 0;
+                    }
+                }
+                self.last_check = Some((mean, std_error));
+                state = state.cost(mean);
+                kv = kv.merge(make_kv!(
+                    "cost_std_error" => std_error;
+                    "stalled_checks" => self.stalled_checks;
+                ));
+            }
+        }
+        Ok((state.param(new_param), Some(kv)))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, (), (), (), F>) -> TerminationReason {
+        match self.termination {
+            Some(termination) if self.stalled_checks >= termination.patience => {
+                TerminationReason::NoChangeInCost
+            }
+            _ => TerminationReason::NotTerminated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::test_trait_impl;
+    use std::sync::Mutex;
+
+    test_trait_impl!(spsa, SPSA<f64, Xoshiro256PlusPlus>);
+
+    /// Sphere function with additive uniform noise in `[-noise, noise]`
+    struct NoisySphere {
+        noise: f64,
+        rng: Mutex<Xoshiro256PlusPlus>,
+    }
+
+    impl NoisySphere {
+        fn new(noise: f64) -> Self {
+            NoisySphere {
+                noise,
+                rng: Mutex::new(Xoshiro256PlusPlus::seed_from_u64(0)),
+            }
+        }
+    }
+
+    impl CostFunction for NoisySphere {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Vec<f64>) -> Result<f64, Error> {
+            let noise = self.rng.lock().unwrap().gen_range(-1.0..=1.0) * self.noise;
+            Ok(p.iter().map(|x| x * x).sum::<f64>() + noise)
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let SPSA {
+            a,
+            c,
+            stability,
+            alpha,
+            gamma,
+            gradient_averaging,
+            termination,
+            last_check,
+            stalled_checks,
+            rng: _,
+        }: SPSA<f64, _> = SPSA::new(0.1, 0.05).unwrap();
+        assert_eq!(a.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(c.to_ne_bytes(), 0.05f64.to_ne_bytes());
+        assert_eq!(stability.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(alpha.to_ne_bytes(), 0.602f64.to_ne_bytes());
+        assert_eq!(gamma.to_ne_bytes(), 0.101f64.to_ne_bytes());
+        assert_eq!(gradient_averaging, 1);
+        assert!(termination.is_none());
+        assert!(last_check.is_none());
+        assert_eq!(stalled_checks, 0);
+
+        for (a, c) in [(0.0, 0.1), (0.1, 0.0), (-1.0, 0.1)] {
+            let res: Result<SPSA<f64, _>, _> = SPSA::new(a, c);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`SPSA`: gains `a` and `c` must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_builders() {
+        let spsa: SPSA<f64, _> = SPSA::new(0.1, 0.05)
+            .unwrap()
+            .with_stability_constant(10.0)
+            .unwrap()
+            .with_exponents(1.0, 0.2)
+            .unwrap()
+            .with_gradient_averaging(3)
+            .unwrap();
+        assert_eq!(spsa.stability.to_ne_bytes(), 10.0f64.to_ne_bytes());
+        assert_eq!(spsa.alpha.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(spsa.gamma.to_ne_bytes(), 0.2f64.to_ne_bytes());
+        assert_eq!(spsa.gradient_averaging, 3);
+
+        let spsa: SPSA<f64, _> = SPSA::new(0.1, 0.05).unwrap();
+        assert_error!(
+            spsa.clone().with_stability_constant(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`SPSA`: stability constant must be non-negative.\""
+        );
+        for (alpha, gamma) in [(0.0, 0.1), (1.5, 0.1), (0.6, 0.0)] {
+            assert_error!(
+                spsa.clone().with_exponents(alpha, gamma),
+                ArgminError,
+                "Invalid parameter: \"`SPSA`: exponents must satisfy 0 < alpha <= 1 and gamma > 0.\""
+            );
+        }
+        assert_error!(
+            spsa.with_gradient_averaging(0),
+            ArgminError,
+            "Invalid parameter: \"`SPSA`: number of averaged gradient estimates must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_noise_aware_termination_builders() {
+        let termination: NoiseAwareTermination<f64> = NoiseAwareTermination::new(5).unwrap();
+        assert_eq!(
+            termination,
+            NoiseAwareTermination {
+                num_evaluations: 5,
+                interval: 10,
+                confidence: 2.0,
+                patience: 3,
+            }
+        );
+        assert_error!(
+            NoiseAwareTermination::<f64>::new(1),
+            ArgminError,
+            "Invalid parameter: \"`NoiseAwareTermination`: number of evaluations must be at least 2.\""
+        );
+        assert_error!(
+            termination.with_interval(0),
+            ArgminError,
+            "Invalid parameter: \"`NoiseAwareTermination`: interval must be > 0.\""
+        );
+        assert_error!(
+            termination.with_confidence(0.0),
+            ArgminError,
+            "Invalid parameter: \"`NoiseAwareTermination`: confidence must be > 0.\""
+        );
+        assert_error!(
+            termination.with_patience(0),
+            ArgminError,
+            "Invalid parameter: \"`NoiseAwareTermination`: patience must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut spsa: SPSA<f64, _> = SPSA::new(0.1, 0.05).unwrap();
+        let res = spsa.init(&mut Problem::new(NoisySphere::new(0.0)), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`SPSA` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_rademacher() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+        let delta: Vec<f64> = rademacher(&vec![0.0; 100], &mut rng);
+        assert!(delta.iter().all(|&d| (d.abs() - 1.0).abs() < f64::EPSILON));
+        assert!(delta.iter().any(|&d| d > 0.0));
+        assert!(delta.iter().any(|&d| d < 0.0));
+    }
+
+    #[test]
+    fn test_noisy_sphere() {
+        let spsa = SPSA::new_with_rng(0.2, 0.1, Xoshiro256PlusPlus::seed_from_u64(42))
+            .unwrap()
+            .with_stability_constant(10.0)
+            .unwrap();
+        let res = Executor::new(NoisySphere::new(0.01), spsa)
+            .configure(|state| state.param(vec![1.0, -0.5, 0.8]).max_iters(1000))
+            .run()
+            .unwrap();
+        let param = res.state().get_best_param().unwrap();
+        assert!(param.iter().all(|x| x.abs() < 0.05));
+        assert_eq!(res.problem().counts["cost_count"], 2000);
+    }
+
+    #[test]
+    fn test_seeded_runs_reproducible() {
+        let run = |seed: u64| {
+            let spsa = SPSA::new_with_rng(0.2, 0.1, Xoshiro256PlusPlus::seed_from_u64(seed))
+                .unwrap()
+                .with_gradient_averaging(2)
+                .unwrap();
+            Executor::new(NoisySphere::new(0.01), spsa)
+                .configure(|state| state.param(vec![1.0, -0.5, 0.8]).max_iters(10))
+                .run()
+                .unwrap()
+                .state()
+                .get_param()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(run(1), run(1));
+        assert_ne!(run(1), run(2));
+    }
+
+    #[test]
+    fn test_noise_aware_termination() {
+        let spsa = SPSA::new_with_rng(0.2, 0.1, Xoshiro256PlusPlus::seed_from_u64(42))
+            .unwrap()
+            .with_stability_constant(10.0)
+            .unwrap()
+            .with_noise_aware_termination(
+                NoiseAwareTermination::new(10)
+                    .unwrap()
+                    .with_interval(20)
+                    .unwrap(),
+            );
+        let res = Executor::new(NoisySphere::new(0.1), spsa)
+            .configure(|state| state.param(vec![1.0, -0.5, 0.8]).max_iters(10000))
+            .run()
+            .unwrap();
+        let state = res.state();
+        assert_eq!(
+            state.get_termination_reason(),
+            TerminationReason::NoChangeInCost
+        );
+        assert!(state.get_iter() < 10000);
+        assert_eq!(state.get_iter() % 20, 0);
+        assert!(state.get_best_cost() < 0.1);
+        let checks = state.get_iter() / 20 + 1;
+        assert_eq!(
+            res.problem().counts["cost_count"],
+            2 * state.get_iter() + 10 * checks
+        );
+    }
+}
@@ -9,26 +9,36 @@ use crate::core::{
     ArgminFloat, Error, IterState, Problem, SerializeAlias, Solver, State, TerminationReason,
     TrustRegionRadius, KV,
 };
-use argmin_math::{ArgminAdd, ArgminDot, ArgminMul, ArgminNorm, ArgminWeightedDot, ArgminZeroLike};
+use crate::solver::newton::{FullHessian, HessianProduct, MatrixFreeHessian};
+use argmin_math::{ArgminAdd, ArgminDot, ArgminMul, ArgminNorm, ArgminSub, ArgminZeroLike};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
 /// # Steihaug method
 ///
 /// The Steihaug method is a conjugate gradients based approach for finding an approximate solution
 /// to the second order approximation of the cost function within the trust region.
 ///
+/// The Hessian (or an approximation thereof) is only used via Hessian-vector products. By default,
+/// the Hessian provided in the state is multiplied with vectors directly. Via
+/// [`Steihaug::matrix_free`], the products are instead computed by the optimization problem via
+/// [`HessianVectorProduct`](`crate::core::HessianVectorProduct`) at the parameter vector provided
+/// to the solver. In this case, the Hessian in the state is `()`.
+///
 /// ## Reference
 ///
 /// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 /// Springer. ISBN 0-387-30303-0.
 #[derive(Clone, Default)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct Steihaug<P, F> {
+pub struct Steihaug<P, F, M = FullHessian> {
     /// Radius
     radius: F,
     /// epsilon
     epsilon: F,
+    /// point at which the Hessian is evaluated
+    x: Option<P>,
     /// p
     p: Option<P>,
     /// residual
@@ -41,6 +51,8 @@ pub struct Steihaug<P, F> {
     d: Option<P>,
     /// max iters
     max_iters: u64,
+    /// Access to the Hessian
+    mode: PhantomData<M>,
 }
 
 impl<P, F> Steihaug<P, F>
@@ -60,15 +72,51 @@ where
         Steihaug {
             radius: F::nan(),
             epsilon: float!(10e-10),
+            x: None,
             p: None,
             r: None,
             rtr: F::nan(),
             r_0_norm: F::nan(),
             d: None,
             max_iters: std::u64::MAX,
+            mode: PhantomData,
         }
     }
 
+    /// Compute Hessian-vector products via the optimization problem
+    ///
+    /// The optimization problem is then required to implement
+    /// [`HessianVectorProduct`](`crate::core::HessianVectorProduct`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newton::MatrixFreeHessian;
+    /// # use argmin::solver::trustregion::Steihaug;
+    /// let sh: Steihaug<Vec<f64>, f64, MatrixFreeHessian> = Steihaug::new().matrix_free();
+    /// ```
+    #[must_use]
+    pub fn matrix_free(self) -> Steihaug<P, F, MatrixFreeHessian> {
+        Steihaug {
+            radius: self.radius,
+            epsilon: self.epsilon,
+            x: self.x,
+            p: self.p,
+            r: self.r,
+            rtr: self.rtr,
+            r_0_norm: self.r_0_norm,
+            d: self.d,
+            max_iters: self.max_iters,
+            mode: PhantomData,
+        }
+    }
+}
+
+impl<P, F, M> Steihaug<P, F, M>
+where
+    P: ArgminMul<F, P> + ArgminDot<P, F> + ArgminAdd<P, P>,
+    F: ArgminFloat,
+{
     /// Set epsilon
     ///
     /// The algorithm stops when the residual is smaller than `epsilon`.
@@ -115,20 +163,21 @@ where
         self
     }
 
-    /// evaluate m(p) (without considering f_init because it is not available)
-    fn eval_m<H>(&self, p: &P, g: &P, h: &H) -> F
-    where
-        P: ArgminWeightedDot<P, F, H>,
-    {
-        g.dot(p) + float!(0.5) * p.weighted_dot(h, p)
+    /// evaluate m(p) (without considering f_init because it is not available), where `hp` is the
+    /// product of the Hessian and `p`
+    fn eval_m(&self, p: &P, g: &P, hp: &P) -> F {
+        g.dot(p) + float!(0.5) * p.dot(hp)
     }
 
     /// calculate all possible step lengths
+    ///
+    /// `hd` is the product of the Hessian and the current direction `d`. Since the residual
+    /// satisfies `r = g + Hp`, no further Hessian-vector products are needed to evaluate m.
     #[allow(clippy::many_single_char_names)]
-    fn tau<G, H>(&self, filter_func: G, eval: bool, g: &P, h: &H) -> F
+    fn tau<G>(&self, filter_func: G, eval: bool, g: &P, hd: &P) -> F
     where
         G: Fn(F) -> bool,
-        P: ArgminWeightedDot<P, F, H>,
+        P: ArgminSub<P, P>,
     {
         let p = self.p.as_ref().unwrap();
         let d = self.d.as_ref().unwrap();
@@ -148,6 +197,7 @@ where
         let v = if eval {
             // remove NAN taus and calculate m (without f_init) for all taus, then sort them based
             // on their result and return the tau which corresponds to the lowest m
+            let hp = self.r.as_ref().unwrap().sub(g);
             let mut v = t
                 .iter()
                 .cloned()
@@ -155,7 +205,8 @@ where
                 .filter(|(_, tau)| (!tau.is_nan() || !tau.is_infinite()) && filter_func(*tau))
                 .map(|(i, tau)| {
                     let p_local = p.add(&d.mul(&tau));
-                    (i, self.eval_m(&p_local, g, h))
+                    let hp_local = hp.add(&hd.mul(&tau));
+                    (i, self.eval_m(&p_local, g, &hp_local))
                 })
                 .filter(|(_, m)| !m.is_nan() || !m.is_infinite())
                 .collect::<Vec<(usize, F)>>();
@@ -176,7 +227,7 @@ where
     }
 }
 
-impl<P, O, F, H> Solver<O, IterState<P, P, (), H, F>> for Steihaug<P, F>
+impl<P, O, F, H, M> Solver<O, IterState<P, P, (), H, F>> for Steihaug<P, F, M>
 where
     P: Clone
         + SerializeAlias
@@ -184,16 +235,17 @@ where
         + ArgminNorm<F>
         + ArgminDot<P, F>
         + ArgminAdd<P, P>
+        + ArgminSub<P, P>
         + ArgminZeroLike,
-    H: ArgminDot<P, P>,
     F: ArgminFloat,
+    M: HessianProduct<O, P, H>,
 {
     const NAME: &'static str = "Steihaug";
 
     fn init(
         &mut self,
         _problem: &mut Problem<O>,
-        state: IterState<P, P, (), H, F>,
+        mut state: IterState<P, P, (), H, F>,
     ) -> Result<(IterState<P, P, (), H, F>, Option<KV>), Error> {
         let r = state
             .get_grad()
@@ -216,6 +268,7 @@ where
             ));
         }
 
+        self.x = state.take_param();
         self.r_0_norm = r.norm();
         self.rtr = r.dot(&r);
         self.d = Some(r.mul(&float!(-1.0)));
@@ -229,7 +282,7 @@ where
 
     fn next_iter(
         &mut self,
-        _problem: &mut Problem<O>,
+        problem: &mut Problem<O>,
         mut state: IterState<P, P, (), H, F>,
     ) -> Result<(IterState<P, P, (), H, F>, Option<KV>), Error> {
         let grad = state.take_grad().ok_or_else(argmin_error_closure!(
@@ -243,12 +296,13 @@ where
         ))?;
 
         let d = self.d.as_ref().unwrap();
-        let dhd = d.weighted_dot(&h, d);
+        let hd = M::hessian_vector_product(problem, self.x.as_ref(), &h, d)?;
+        let dhd = d.dot(&hd);
 
         // Current search direction d is a direction of zero curvature or negative curvature
         let p = self.p.as_ref().unwrap();
         if dhd <= float!(0.0) {
-            let tau = self.tau(|_| true, true, &grad, &h);
+            let tau = self.tau(|_| true, true, &grad, &hd);
             return Ok((
                 state
                     .param(p.add(&d.mul(&tau)))
@@ -262,7 +316,7 @@ where
 
         // new p violates trust region bound
         if p_n.norm() >= self.radius {
-            let tau = self.tau(|x| x >= float!(0.0), false, &grad, &h);
+            let tau = self.tau(|x| x >= float!(0.0), false, &grad, &hd);
             return Ok((
                 state
                     .param(p.add(&d.mul(&tau)))
@@ -272,7 +326,7 @@ where
         }
 
         let r = self.r.as_ref().unwrap();
-        let r_n = r.add(&h.dot(d).mul(&alpha));
+        let r_n = r.add(&hd.mul(&alpha));
 
         if r_n.norm() < self.epsilon * self.r_0_norm {
             return Ok((
@@ -304,7 +358,7 @@ where
     }
 }
 
-impl<P, F: ArgminFloat> TrustRegionRadius<F> for Steihaug<P, F> {
+impl<P, F: ArgminFloat, M> TrustRegionRadius<F> for Steihaug<P, F, M> {
     /// Set current radius.
     ///
     /// Needed by [`TrustRegion`](`crate::solver::trustregion::TrustRegion`).
@@ -338,16 +392,19 @@ mod tests {
         let Steihaug {
             radius,
             epsilon,
+            x,
             p,
             r,
             rtr,
             r_0_norm,
             d,
             max_iters,
+            mode: _,
         } = sh;
 
         assert_eq!(radius.to_ne_bytes(), f64::NAN.to_ne_bytes());
         assert_eq!(epsilon.to_ne_bytes(), 10e-10f64.to_ne_bytes());
+        assert!(x.is_none());
         assert!(p.is_none());
         assert!(r.is_none());
         assert_eq!(rtr.to_ne_bytes(), f64::NAN.to_ne_bytes());
@@ -441,16 +498,19 @@ mod tests {
         let Steihaug {
             radius,
             epsilon,
+            x,
             p,
             r,
             rtr,
             r_0_norm,
             d,
             max_iters,
+            mode: _,
         } = sh;
 
         assert_eq!(radius.to_ne_bytes(), 1.0f64.to_ne_bytes());
         assert_eq!(epsilon.to_ne_bytes(), 10e-10f64.to_ne_bytes());
+        assert!(x.is_none());
         assert_relative_eq!(p.as_ref().unwrap()[0], 0.0f64, epsilon = f64::EPSILON);
         assert_relative_eq!(p.as_ref().unwrap()[1], 0.0f64, epsilon = f64::EPSILON);
         assert_relative_eq!(r.as_ref().unwrap()[0], grad[0], epsilon = f64::EPSILON);
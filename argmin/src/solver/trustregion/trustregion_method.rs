@@ -6,14 +6,15 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, DeserializeOwnedAlias, Error, Executor, Gradient, Hessian,
-    IterState, OptimizationResult, Problem, SerializeAlias, Solver, TerminationReason,
-    TrustRegionRadius, KV,
+    ArgminFloat, CostFunction, DeserializeOwnedAlias, Error, Executor, Gradient, IterState,
+    OptimizationResult, Problem, SerializeAlias, Solver, TerminationReason, TrustRegionRadius, KV,
 };
+use crate::solver::newton::{FullHessian, HessianSource, MatrixFreeHessian};
 use crate::solver::trustregion::reduction_ratio;
-use argmin_math::{ArgminAdd, ArgminDot, ArgminNorm, ArgminWeightedDot};
+use argmin_math::{ArgminAdd, ArgminDot, ArgminNorm};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
 /// # Trust region method
 ///
@@ -31,7 +32,13 @@ use serde::{Deserialize, Serialize};
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`], [`Gradient`] and
-/// [`Hessian`].
+/// [`Hessian`](`crate::core::Hessian`).
+///
+/// If the full Hessian is not available, [`TrustRegion::matrix_free`] turns the method into a
+/// matrix-free one which instead requires the problem to implement
+/// [`HessianVectorProduct`](`crate::core::HessianVectorProduct`). This needs a subproblem solver
+/// which only relies on Hessian-vector products, such as
+/// [`Steihaug::matrix_free`](`crate::solver::trustregion::Steihaug::matrix_free`).
 ///
 /// ## Reference
 ///
@@ -39,7 +46,7 @@ use serde::{Deserialize, Serialize};
 /// Springer. ISBN 0-387-30303-0.
 #[derive(Clone)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct TrustRegion<R, F> {
+pub struct TrustRegion<R, F, M = FullHessian> {
     /// Radius
     radius: F,
     /// Maximum radius
@@ -52,6 +59,8 @@ pub struct TrustRegion<R, F> {
     fxk: F,
     /// mk(0)
     mk0: F,
+    /// Access to the Hessian
+    mode: PhantomData<M>,
 }
 
 impl<R, F> TrustRegion<R, F>
@@ -75,9 +84,42 @@ where
             subproblem,
             fxk: F::nan(),
             mk0: F::nan(),
+            mode: PhantomData,
+        }
+    }
+
+    /// Use Hessian-vector products instead of the full Hessian
+    ///
+    /// The optimization problem is then required to implement
+    /// [`HessianVectorProduct`](`crate::core::HessianVectorProduct`) and the subproblem solver
+    /// must be matrix-free as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::solver::newton::MatrixFreeHessian;
+    /// use argmin::solver::trustregion::{Steihaug, TrustRegion};
+    /// let sh: Steihaug<Vec<f64>, f64, MatrixFreeHessian> = Steihaug::new().matrix_free();
+    /// let tr: TrustRegion<_, f64, MatrixFreeHessian> = TrustRegion::new(sh).matrix_free();
+    /// ```
+    #[must_use]
+    pub fn matrix_free(self) -> TrustRegion<R, F, MatrixFreeHessian> {
+        TrustRegion {
+            radius: self.radius,
+            max_radius: self.max_radius,
+            eta: self.eta,
+            subproblem: self.subproblem,
+            fxk: self.fxk,
+            mk0: self.mk0,
+            mode: PhantomData,
         }
     }
+}
 
+impl<R, F, M> TrustRegion<R, F, M>
+where
+    F: ArgminFloat,
+{
     /// Set radius
     ///
     /// Defaults to `1.0`.
@@ -157,11 +199,9 @@ where
     }
 }
 
-impl<O, R, F, P, G, H> Solver<O, IterState<P, G, (), H, F>> for TrustRegion<R, F>
+impl<O, R, F, P, G, H, M> Solver<O, IterState<P, G, (), H, F>> for TrustRegion<R, F, M>
 where
-    O: CostFunction<Param = P, Output = F>
-        + Gradient<Param = P, Gradient = G>
-        + Hessian<Param = P, Hessian = H>,
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
     P: Clone
         + SerializeAlias
         + DeserializeOwnedAlias
@@ -170,9 +210,10 @@ where
         + ArgminDot<G, F>
         + ArgminAdd<P, P>,
     G: Clone + SerializeAlias + DeserializeOwnedAlias,
-    H: Clone + SerializeAlias + DeserializeOwnedAlias + ArgminDot<P, P>,
+    H: Clone + SerializeAlias + DeserializeOwnedAlias,
     R: Clone + TrustRegionRadius<F> + Solver<O, IterState<P, G, (), H, F>>,
     F: ArgminFloat,
+    M: HessianSource<O, P, H>,
 {
     const NAME: &'static str = "Trust region";
 
@@ -197,7 +238,7 @@ where
         let hessian = state
             .take_hessian()
             .map(Result::Ok)
-            .unwrap_or_else(|| problem.hessian(&param))?;
+            .unwrap_or_else(|| M::hessian(problem, &param))?;
 
         let cost = state.get_cost();
         self.fxk = if cost.is_infinite() && cost.is_sign_positive() {
@@ -260,7 +301,8 @@ where
 
         let new_param = pk.add(&param);
         let fxkpk = problem.cost(&new_param)?;
-        let mkpk = self.fxk + pk.dot(&grad) + float!(0.5) * pk.weighted_dot(&hessian, &pk);
+        let hpk = M::hessian_vector_product(problem, Some(&param), &hessian, &pk)?;
+        let mkpk = self.fxk + pk.dot(&grad) + float!(0.5) * pk.dot(&hpk);
 
         let rho = reduction_ratio(self.fxk, fxkpk, self.mk0, mkpk);
 
@@ -282,7 +324,7 @@ where
                 self.fxk = fxkpk;
                 self.mk0 = fxkpk;
                 let grad = problem.gradient(&new_param)?;
-                let hessian = problem.hessian(&new_param)?;
+                let hessian = M::hessian(problem, &new_param)?;
                 state
                     .param(new_param)
                     .cost(fxkpk)
@@ -326,6 +368,7 @@ mod tests {
             subproblem: _,
             fxk,
             mk0,
+            mode: _,
         } = tr;
 
         assert_eq!(radius.to_ne_bytes(), 1.0f64.to_ne_bytes());
@@ -433,6 +476,7 @@ mod tests {
             subproblem: _,
             fxk,
             mk0,
+            mode: _,
         } = tr;
 
         assert_eq!(radius.to_ne_bytes(), 1.0f64.to_ne_bytes());
@@ -441,4 +485,75 @@ mod tests {
         assert_eq!(fxk.to_ne_bytes(), 1.0f64.sqrt().to_ne_bytes());
         assert_eq!(mk0.to_ne_bytes(), 1.0f64.to_ne_bytes());
     }
+
+    #[test]
+    fn test_matrix_free_rosenbrock() {
+        use crate::core::{CostFunction, Executor, Hessian, HessianVectorProduct};
+        use crate::solver::newton::MatrixFreeHessian;
+        use argmin_testfunctions::{
+            rosenbrock_2d, rosenbrock_2d_derivative, rosenbrock_2d_hessian,
+        };
+
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(rosenbrock_2d(p, 1.0, 100.0))
+            }
+        }
+
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(rosenbrock_2d_derivative(p, 1.0, 100.0))
+            }
+        }
+
+        impl Hessian for Rosenbrock {
+            type Param = Vec<f64>;
+            type Hessian = Vec<Vec<f64>>;
+
+            fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+                let h = rosenbrock_2d_hessian(p, 1.0, 100.0);
+                Ok(vec![vec![h[0], h[1]], vec![h[2], h[3]]])
+            }
+        }
+
+        impl HessianVectorProduct for Rosenbrock {
+            type Param = Vec<f64>;
+
+            fn hessian_vector_product(
+                &self,
+                p: &Self::Param,
+                v: &Self::Param,
+            ) -> Result<Self::Param, Error> {
+                let h = rosenbrock_2d_hessian(p, 1.0, 100.0);
+                Ok(vec![h[0] * v[0] + h[1] * v[1], h[2] * v[0] + h[3] * v[1]])
+            }
+        }
+
+        let full = Executor::new(Rosenbrock {}, TrustRegion::new(Steihaug::new()))
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+            .run()
+            .unwrap();
+
+        let steihaug: Steihaug<Vec<f64>, f64, MatrixFreeHessian> = Steihaug::new().matrix_free();
+        let solver = TrustRegion::new(steihaug).matrix_free();
+        let res = Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+            .run()
+            .unwrap();
+
+        let param = res.state.get_best_param().unwrap();
+        assert!((param[0] - 1.0).abs() < 1e-6);
+        assert!((param[1] - 1.0).abs() < 1e-6);
+        assert!((res.state.get_best_cost() - full.state.get_best_cost()).abs() < 1e-10);
+        assert!(!res.problem.counts.contains_key("hessian_count"));
+        assert!(res.problem.counts["hessian_vector_product_count"] > 0);
+    }
 }